@@ -0,0 +1,28 @@
+-- Notes, projects and users, the part of the schema that can live in Postgres
+
+CREATE TABLE notes (
+    id VARCHAR(16) PRIMARY KEY,
+    slug VARCHAR(128) NOT NULL,
+    title VARCHAR(128) NOT NULL,
+    description VARCHAR(256) NOT NULL,
+    body VARCHAR(32768) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE
+);
+CREATE INDEX notes_created_at_id_idx ON notes (created_at, id);
+CREATE INDEX notes_slug_idx ON notes (slug);
+
+CREATE TABLE projects (
+    id VARCHAR(16) PRIMARY KEY,
+    title VARCHAR(128) NOT NULL,
+    description VARCHAR(256) NOT NULL,
+    url VARCHAR(2048),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX projects_created_at_id_idx ON projects (created_at, id);
+
+CREATE TABLE users (
+    id VARCHAR(16) PRIMARY KEY,
+    username VARCHAR(128) NOT NULL UNIQUE,
+    password_hash BYTEA NOT NULL CHECK (octet_length(password_hash) = 32)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE notes ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE projects ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
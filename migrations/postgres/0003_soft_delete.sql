@@ -0,0 +1,5 @@
+ALTER TABLE notes ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+CREATE INDEX notes_deleted_at_idx ON notes (deleted_at);
+
+ALTER TABLE projects ADD COLUMN deleted_at TIMESTAMP WITH TIME ZONE;
+CREATE INDEX projects_deleted_at_idx ON projects (deleted_at);
@@ -0,0 +1,228 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use serde_json::Value;
+use url::{Position, Url};
+
+use crate::adapters::public_http::{check_url, public_only};
+use crate::adapters::activitypub::signature::{check_request, parse_signature_header, sign_request, verify_signature};
+use crate::application::common::federation_client::{FederationClient, SignedRequest};
+use crate::domain::models::actor::{ActorKey, RemoteActor};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const ACTIVITY_JSON: &str = "application/activity+json";
+
+
+pub struct HttpFederationClient {
+    http: reqwest::Client,
+    key: ActorKey,
+    key_id: String,
+    /// Off only in tests, which talk to a server on localhost
+    public_only: bool,
+}
+
+impl HttpFederationClient {
+    /// Key ids and inboxes come from strangers, only public addresses are reached
+    pub fn new(key: ActorKey, key_id: String) -> Self {
+        let http = public_only(Self::builder(), 5).build().unwrap();
+        Self { http, key, key_id, public_only: true }
+    }
+
+    #[cfg(test)]
+    fn local(key: ActorKey, key_id: String) -> Self {
+        let http = Self::builder().build().unwrap();
+        Self { http, key, key_id, public_only: false }
+    }
+
+    fn builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .user_agent(concat!("jkearnsl/", env!("CARGO_PKG_VERSION"), " (ActivityPub)"))
+            .timeout(TIMEOUT)
+    }
+
+    /// Signed request, servers in secure mode refuse anonymous fetches
+    async fn send(&self, method: reqwest::Method, url: &str, body: Option<&str>) -> Result<reqwest::Response, String> {
+        let parsed = Url::parse(url).map_err(|e| e.to_string())?;
+        if self.public_only {
+            check_url(&parsed)?;
+        }
+        let headers = sign_request(
+            &self.key.private_key_pem,
+            &self.key_id,
+            method.as_str(),
+            &parsed[Position::BeforeHost..Position::BeforePath],
+            &parsed[Position::BeforePath..Position::AfterQuery],
+            body.map(str::as_bytes),
+            Utc::now()
+        )?;
+
+        let mut request = self.http.request(method, parsed).header(ACCEPT, ACTIVITY_JSON);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = body {
+            request = request.header(CONTENT_TYPE, ACTIVITY_JSON).body(body.to_string());
+        }
+
+        request.send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Value, String> {
+        self.send(reqwest::Method::GET, url, None).await?
+            .json().await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Resolves the key to its owner, the key id is usually a fragment of the actor IRI.
+    /// Every document must be the one its id names, and the owner must live on the
+    /// origin of the key, or any server could claim the key of another
+    async fn fetch_key_owner(&self, key_id: &str) -> Result<(RemoteActor, String), String> {
+        let key_url = Url::parse(key_id).map_err(|e| e.to_string())?;
+        let mut url = key_url.clone();
+        url.set_fragment(None);
+        let mut document = self.fetch(url.as_str()).await?;
+        let id = document["id"].as_str().ok_or("Document has no id")?;
+        if id != url.as_str() && id != key_id {
+            return Err(format!("Document fetched from {} claims to be {}", url, id));
+        }
+
+        // Some servers serve the key as a separate document
+        if document.get("inbox").is_none() {
+            let owner = document["owner"].as_str().ok_or("Key has no owner")?.to_string();
+            let owner_url = Url::parse(&owner).map_err(|e| e.to_string())?;
+            if owner_url.origin() != key_url.origin() {
+                return Err(format!("Key {} is owned by another origin", key_id));
+            }
+            document = self.fetch(&owner).await?;
+            if document["id"] != owner.as_str() {
+                return Err(format!("Document fetched from {} claims another id", owner));
+            }
+        }
+
+        let public_key = &document["publicKey"];
+        let id = document["id"].as_str().ok_or("Actor has no id")?;
+        if public_key["id"] != key_id || public_key["owner"] != id {
+            return Err("Key does not belong to the actor".to_string());
+        }
+
+        let actor = RemoteActor {
+            id: id.to_string(),
+            inbox: document["inbox"].as_str().ok_or("Actor has no inbox")?.to_string(),
+            shared_inbox: document["endpoints"]["sharedInbox"].as_str().map(str::to_string),
+        };
+        let public_key_pem = public_key["publicKeyPem"].as_str().ok_or("Actor has no key")?.to_string();
+        Ok((actor, public_key_pem))
+    }
+}
+
+#[async_trait]
+impl FederationClient for HttpFederationClient {
+    async fn verify(&self, request: &SignedRequest) -> Result<RemoteActor, String> {
+        let params = parse_signature_header(request.headers.get("signature").ok_or("Missing signature")?)?;
+        // Cheap checks first, the key is fetched over the network
+        check_request(request, &params, Utc::now())?;
+
+        let (actor, public_key_pem) = self.fetch_key_owner(&params.key_id).await?;
+        verify_signature(request, &params, &public_key_pem)?;
+        Ok(actor)
+    }
+
+    async fn deliver(&self, inbox: &str, activity: &str) -> Result<(), String> {
+        self.send(reqwest::Method::POST, inbox, Some(activity)).await.map(|_| ())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::adapters::activitypub::keys::generate_actor_key;
+    use crate::adapters::test_server::{TestResponse, TestServer};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_deliver_and_verify() {
+        let remote_key = generate_actor_key(1024);
+        let server = TestServer::start(vec![("/inbox", TestResponse::status(202))]).await;
+        let remote_id = server.url("/users/jane");
+        server.route("/users/jane", TestResponse::json(&json!({
+            "id": remote_id,
+            "type": "Person",
+            "inbox": server.url("/users/jane/inbox"),
+            "endpoints": { "sharedInbox": server.url("/inbox") },
+            "publicKey": {
+                "id": format!("{}#main-key", remote_id),
+                "owner": remote_id,
+                "publicKeyPem": remote_key.public_key_pem
+            }
+        }).to_string(), ACTIVITY_JSON)).await;
+
+        // Jane's server delivers to the inbox, signed with her key
+        let jane = HttpFederationClient::local(remote_key, format!("{}#main-key", remote_id));
+        jane.deliver(&server.url("/inbox"), "{\"type\":\"Follow\"}").await.unwrap();
+
+        let received = server.requests().await.into_iter()
+            .find(|request| request.method == "POST")
+            .unwrap();
+        let request = SignedRequest {
+            method: received.method,
+            path: received.path,
+            headers: received.headers,
+            body: received.body.into_bytes()
+        };
+
+        let us = HttpFederationClient::local(generate_actor_key(1024), "https://jkearnsl.su/actor#main-key".to_string());
+        let actor = us.verify(&request).await.unwrap();
+        assert_eq!(actor.id, remote_id);
+        assert_eq!(actor.shared_inbox, Some(server.url("/inbox")));
+
+        let mut tampered = request.clone();
+        tampered.body = b"{\"type\":\"Delete\"}".to_vec();
+        assert!(us.verify(&tampered).await.is_err());
+
+        let mut unsigned = request;
+        unsigned.headers.remove("signature");
+        assert!(us.verify(&unsigned).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_key_owner_rejects_impostors() {
+        let key = generate_actor_key(1024);
+        let server = TestServer::start(vec![]).await;
+        let victim = "https://mastodon.example/users/alice";
+        // Claims to be someone else
+        server.route("/users/mallory", TestResponse::json(&json!({
+            "id": victim,
+            "inbox": format!("{}/inbox", victim),
+            "publicKey": {
+                "id": server.url("/users/mallory#main-key"),
+                "owner": victim,
+                "publicKeyPem": key.public_key_pem
+            }
+        }).to_string(), ACTIVITY_JSON)).await;
+        // Hands the key to an actor on another server
+        server.route("/keys/mallory", TestResponse::json(&json!({
+            "id": server.url("/keys/mallory"),
+            "owner": victim,
+            "publicKeyPem": key.public_key_pem
+        }).to_string(), ACTIVITY_JSON)).await;
+
+        let us = HttpFederationClient::local(generate_actor_key(1024), "https://jkearnsl.su/actor#main-key".to_string());
+        assert!(us.fetch_key_owner(&server.url("/users/mallory#main-key")).await.is_err());
+        assert!(us.fetch_key_owner(&server.url("/keys/mallory")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refuses_private_addresses() {
+        let server = TestServer::start(vec![("/inbox", TestResponse::status(202))]).await;
+
+        let us = HttpFederationClient::new(generate_actor_key(1024), "https://jkearnsl.su/actor#main-key".to_string());
+        assert!(us.deliver(&server.url("/inbox"), "{}").await.is_err());
+        assert!(us.fetch_key_owner(&server.url("/users/jane#main-key")).await.is_err());
+        assert!(server.requests().await.is_empty());
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::Utc;
+use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+
+use crate::application::common::actor_key_gateway::{ActorKeyGateway, ActorKeyReader, ActorKeyWriter};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::actor::{ActorKey, ACTOR_KEY_ID_SIZE};
+
+/// What Mastodon and most other servers expect
+const KEY_BITS: usize = 2048;
+
+
+pub fn generate_actor_key(bits: usize) -> ActorKey {
+    let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), bits).unwrap();
+    let public_key = RsaPublicKey::from(&private_key);
+
+    ActorKey {
+        id: generate_id(ACTOR_KEY_ID_SIZE),
+        public_key_pem: public_key.to_public_key_pem(LineEnding::LF).unwrap(),
+        private_key_pem: private_key.to_pkcs8_pem(LineEnding::LF).unwrap().to_string(),
+        created_at: Utc::now(),
+    }
+}
+
+/// The key is created once, on the first start. Replacing it would break
+/// verification of our requests by servers that cached the old one
+pub async fn load_or_create_actor_key(gateway: &dyn ActorKeyGateway) -> ActorKey {
+    if let Some(key) = gateway.get_current().await {
+        return key;
+    }
+
+    let key = tokio::task::spawn_blocking(|| generate_actor_key(KEY_BITS)).await.unwrap();
+    gateway.save(&key).await;
+    key
+}
+
+/// For administrative commands, which never sign requests: the stored key,
+/// or a throwaway one so that the first start of the server still creates it
+pub async fn load_actor_key(gateway: &dyn ActorKeyGateway) -> ActorKey {
+    match gateway.get_current().await {
+        Some(key) => key,
+        None => tokio::task::spawn_blocking(|| generate_actor_key(KEY_BITS)).await.unwrap()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod keys;
+pub mod signature;
+pub mod http_client;
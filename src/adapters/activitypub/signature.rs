@@ -0,0 +1,247 @@
+//! HTTP signatures as used across the fediverse
+//! (draft-cavage-http-signatures with `rsa-sha256`)
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use rsa::sha2::{Digest, Sha256};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+
+use crate::application::common::federation_client::SignedRequest;
+
+/// Requests older than that are treated as replays
+const MAX_AGE_HOURS: i64 = 12;
+/// Tolerated clock drift of the sender
+const MAX_SKEW_HOURS: i64 = 1;
+
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SignatureParams {
+    pub key_id: String,
+    /// Covered headers in signing order, `(request-target)` included
+    pub headers: Vec<String>,
+    pub signature: Vec<u8>,
+}
+
+pub fn parse_signature_header(value: &str) -> Result<SignatureParams, String> {
+    let mut params = HashMap::new();
+    for part in value.split(',') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        params.insert(key.trim().to_ascii_lowercase(), value.trim().trim_matches('"').to_string());
+    }
+
+    if let Some(algorithm) = params.get("algorithm") {
+        if !matches!(algorithm.to_ascii_lowercase().as_str(), "rsa-sha256" | "hs2019") {
+            return Err(format!("Unsupported algorithm {}", algorithm));
+        }
+    }
+
+    let key_id = params.remove("keyid").ok_or("Missing keyId")?;
+    let headers = params.get("headers")
+        .map(|headers| headers.split_whitespace().map(str::to_ascii_lowercase).collect())
+        // The draft defaults to the date header only
+        .unwrap_or_else(|| vec!["date".to_string()]);
+    let signature = params.get("signature")
+        .and_then(|signature| BASE64.decode(signature).ok())
+        .ok_or("Missing signature")?;
+
+    Ok(SignatureParams { key_id, headers, signature })
+}
+
+pub fn signing_string(
+    method: &str,
+    path: &str,
+    headers: &HashMap<String, String>,
+    covered: &[String]
+) -> Result<String, String> {
+    covered.iter()
+        .map(|name| match name.as_str() {
+            "(request-target)" => Ok(format!("(request-target): {} {}", method.to_ascii_lowercase(), path)),
+            name => headers.get(name)
+                .map(|value| format!("{}: {}", name, value))
+                .ok_or_else(|| format!("Signed header {} is missing", name))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|lines| lines.join("\n"))
+}
+
+pub fn digest(body: &[u8]) -> String {
+    format!("SHA-256={}", BASE64.encode(Sha256::digest(body)))
+}
+
+pub fn http_date(time: DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// Headers to add to an outgoing request. `host` must be exactly
+/// what the HTTP client sends in the `Host` header
+pub fn sign_request(
+    private_key_pem: &str,
+    key_id: &str,
+    method: &str,
+    host: &str,
+    path: &str,
+    body: Option<&[u8]>,
+    now: DateTime<Utc>
+) -> Result<Vec<(String, String)>, String> {
+    let private_key = RsaPrivateKey::from_pkcs8_pem(private_key_pem).map_err(|e| e.to_string())?;
+
+    let mut headers = HashMap::from([
+        ("host".to_string(), host.to_string()),
+        ("date".to_string(), http_date(now)),
+    ]);
+    let mut covered = vec!["(request-target)".to_string(), "host".to_string(), "date".to_string()];
+    if let Some(body) = body {
+        headers.insert("digest".to_string(), digest(body));
+        covered.push("digest".to_string());
+    }
+
+    let signed = signing_string(method, path, &headers, &covered)?;
+    let signature = SigningKey::<Sha256>::new(private_key).sign(signed.as_bytes());
+
+    headers.insert("signature".to_string(), format!(
+        "keyId=\"{}\",algorithm=\"rsa-sha256\",headers=\"{}\",signature=\"{}\"",
+        key_id,
+        covered.join(" "),
+        BASE64.encode(signature.to_bytes())
+    ));
+    headers.remove("host");
+
+    let mut headers = headers.into_iter().collect::<Vec<_>>();
+    headers.sort();
+    Ok(headers)
+}
+
+/// Everything that can be checked without the sender's key: the covered
+/// headers, the age of the request and the body digest
+pub fn check_request(request: &SignedRequest, params: &SignatureParams, now: DateTime<Utc>) -> Result<(), String> {
+    for required in ["(request-target)", "host", "date"] {
+        if !params.headers.iter().any(|h| h == required) {
+            return Err(format!("Signature does not cover {}", required));
+        }
+    }
+
+    let date = request.headers.get("date")
+        .and_then(|date| DateTime::parse_from_rfc2822(date).ok())
+        .ok_or("Missing or invalid date")?
+        .with_timezone(&Utc);
+    if date < now - Duration::hours(MAX_AGE_HOURS) || date > now + Duration::hours(MAX_SKEW_HOURS) {
+        return Err("Request is expired".to_string());
+    }
+
+    if request.method.eq_ignore_ascii_case("POST") {
+        if !params.headers.iter().any(|h| h == "digest") {
+            return Err("Signature does not cover digest".to_string());
+        }
+        let expected = BASE64.encode(Sha256::digest(&request.body));
+        let matches = request.headers.get("digest").is_some_and(|value| {
+            value.split(',')
+                .filter_map(|d| d.trim().split_once('='))
+                .any(|(algorithm, value)| algorithm.eq_ignore_ascii_case("SHA-256") && value == expected)
+        });
+        if !matches {
+            return Err("Digest does not match the body".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn verify_signature(request: &SignedRequest, params: &SignatureParams, public_key_pem: &str) -> Result<(), String> {
+    let public_key = RsaPublicKey::from_public_key_pem(public_key_pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(public_key_pem))
+        .map_err(|e| e.to_string())?;
+    let signature = Signature::try_from(params.signature.as_slice()).map_err(|e| e.to_string())?;
+
+    let signed = signing_string(&request.method, &request.path, &request.headers, &params.headers)?;
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(signed.as_bytes(), &signature)
+        .map_err(|_| "Signature is not valid".to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::sync::OnceLock;
+    use crate::adapters::activitypub::keys::generate_actor_key;
+    use crate::domain::models::actor::ActorKey;
+    use super::*;
+
+    const KEY_ID: &str = "https://jkearnsl.su/actor#main-key";
+
+    fn key() -> &'static ActorKey {
+        static KEY: OnceLock<ActorKey> = OnceLock::new();
+        KEY.get_or_init(|| generate_actor_key(1024))
+    }
+
+    fn signed(body: &[u8], now: DateTime<Utc>) -> SignedRequest {
+        let mut headers = sign_request(
+            &key().private_key_pem,
+            KEY_ID,
+            "POST",
+            "mastodon.example",
+            "/inbox",
+            Some(body),
+            now
+        ).unwrap().into_iter().collect::<HashMap<_, _>>();
+        headers.insert("host".to_string(), "mastodon.example".to_string());
+
+        SignedRequest {
+            method: "POST".to_string(),
+            path: "/inbox".to_string(),
+            headers,
+            body: body.to_vec()
+        }
+    }
+
+    fn verify(request: &SignedRequest, now: DateTime<Utc>) -> Result<(), String> {
+        let params = parse_signature_header(request.headers.get("signature").unwrap())?;
+        assert_eq!(params.key_id, KEY_ID);
+        check_request(request, &params, now)?;
+        verify_signature(request, &params, &key().public_key_pem)
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let now = Utc::now();
+        let request = signed(b"{}", now);
+
+        assert_eq!(verify(&request, now), Ok(()));
+    }
+
+    #[test]
+    fn test_tampered_request() {
+        let now = Utc::now();
+
+        let mut request = signed(b"{}", now);
+        request.body = b"{\"type\":\"Delete\"}".to_vec();
+        assert!(verify(&request, now).is_err());
+
+        let mut request = signed(b"{}", now);
+        request.path = "/other".to_string();
+        assert!(verify(&request, now).is_err());
+
+        let request = signed(b"{}", now - Duration::days(1));
+        assert!(verify(&request, now).is_err());
+    }
+
+    #[test]
+    fn test_parse_signature_header() {
+        let params = parse_signature_header(
+            "keyId=\"https://a.example/u#key\",algorithm=\"hs2019\",headers=\"(request-target) Host date\",signature=\"AQID\""
+        ).unwrap();
+        assert_eq!(params.key_id, "https://a.example/u#key");
+        assert_eq!(params.headers, vec!["(request-target)", "host", "date"]);
+        assert_eq!(params.signature, vec![1, 2, 3]);
+
+        assert!(parse_signature_header("keyId=\"k\",algorithm=\"hmac-sha256\",signature=\"AQID\"").is_err());
+    }
+}
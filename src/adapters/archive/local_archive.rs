@@ -0,0 +1,148 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read};
+use std::path::{Component, Path};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::domain::models::archive::ArchiveEntry;
+
+
+/// Paths ending in `.tar.gz` or `.tgz` are tarballs, anything else a directory
+fn is_tarball(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Entries come from the archive, a path must not leave its root
+fn safe_path(path: &str) -> Result<&Path, String> {
+    let relative = Path::new(path);
+    match relative.components().all(|component| matches!(component, Component::Normal(_))) {
+        true => Ok(relative),
+        false => Err(format!("Unsafe path in the archive: {}", path))
+    }
+}
+
+fn to_entry_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Refuses to write into a directory that is not empty, a stale file
+/// from an older export would be restored along with the new ones
+pub fn write_archive(path: &Path, entries: &[ArchiveEntry]) -> Result<(), String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+
+    if path.exists() {
+        let not_empty = !path.is_dir() || path.read_dir().map_err(error)?.next().is_some();
+        if not_empty {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    if is_tarball(path) {
+        let output = BufWriter::new(File::create(path).map_err(error)?);
+        let mut builder = tar::Builder::new(GzEncoder::new(output, Compression::default()));
+        for entry in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(entry.content.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(chrono::Utc::now().timestamp() as u64);
+            builder.append_data(&mut header, safe_path(&entry.path)?, entry.content.as_slice())
+                .map_err(error)?;
+        }
+        builder.into_inner()
+            .and_then(|encoder| encoder.finish())
+            .and_then(|output| output.into_inner().map_err(|e| e.into_error()))
+            .map_err(error)?;
+        return Ok(());
+    }
+
+    for entry in entries {
+        let target = path.join(safe_path(&entry.path)?);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(error)?;
+        }
+        std::fs::write(&target, &entry.content).map_err(error)?;
+    }
+    Ok(())
+}
+
+/// Every file of the archive, directories are walked recursively
+pub fn read_archive(path: &Path) -> Result<Vec<ArchiveEntry>, String> {
+    let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+    let mut entries = Vec::new();
+
+    if !path.is_dir() {
+        let input = BufReader::new(File::open(path).map_err(error)?);
+        let mut archive = tar::Archive::new(GzDecoder::new(input));
+        for entry in archive.entries().map_err(error)? {
+            let mut entry = entry.map_err(error)?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let entry_path = entry.path().map_err(error)?;
+            let entry_path = to_entry_path(safe_path(&entry_path.to_string_lossy())?);
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).map_err(error)?;
+            entries.push(ArchiveEntry { path: entry_path, content });
+        }
+        return Ok(entries);
+    }
+
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for item in std::fs::read_dir(&current).map_err(error)? {
+            let item = item.map_err(error)?.path();
+            if item.is_dir() {
+                pending.push(item);
+                continue;
+            }
+            entries.push(ArchiveEntry {
+                path: to_entry_path(item.strip_prefix(path).unwrap()),
+                content: std::fs::read(&item).map_err(error)?
+            });
+        }
+    }
+    Ok(entries)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<ArchiveEntry> {
+        vec![
+            ArchiveEntry { path: "manifest.json".to_string(), content: b"{}".to_vec() },
+            ArchiveEntry { path: "notes/hello.md".to_string(), content: b"---\n---\nHello".to_vec() },
+        ]
+    }
+
+    fn sorted(mut entries: Vec<ArchiveEntry>) -> Vec<ArchiveEntry> {
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = std::env::temp_dir().join(format!("archive-test-{}", std::process::id()));
+        for path in [dir.join("export"), dir.join("export.tar.gz")] {
+            write_archive(&path, &entries()).unwrap();
+            assert_eq!(sorted(read_archive(&path).unwrap()), entries());
+            assert!(write_archive(&path, &entries()).is_err());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unsafe_path() {
+        let dir = std::env::temp_dir().join(format!("archive-unsafe-{}", std::process::id()));
+        let entries = vec![ArchiveEntry { path: "../outside".to_string(), content: vec![] }];
+        assert!(write_archive(&dir, &entries).is_err());
+        assert!(!dir.join("../outside").exists());
+    }
+}
@@ -76,4 +76,12 @@ impl TokenProcessor {
             None => Err("Token not valid".to_string())
         }
     }
+
+    /// Drops every token of the user, returns how many there were
+    pub fn revoke_user_sessions(&self, username: &str) -> usize {
+        let mut data = self.data.write().unwrap();
+        let before = data.len();
+        data.retain(|_, session_username| session_username != username);
+        before - data.len()
+    }
 }
@@ -0,0 +1,188 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection};
+
+use crate::adapters::database::pool::DbPool;
+use crate::application::common::backup_storage::BackupStorage;
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::backup::{backup_created_at, backup_name, Backup};
+
+
+/// Snapshots are made with `VACUUM INTO` and gzipped into `<dir>/<name>`
+pub struct SqliteBackupStorage {
+    /// A read connection is enough, and with WAL it sees a consistent
+    /// database without holding up the writer
+    db: DbPool,
+    dir: PathBuf,
+}
+
+impl SqliteBackupStorage {
+    pub fn new(db: DbPool, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            db,
+            dir: dir.into(),
+        }
+    }
+
+    async fn verify(path: &Path) -> Result<(), String> {
+        let mut connection = SqliteConnectOptions::new()
+            .filename(path)
+            .read_only(true)
+            .connect()
+            .await
+            .map_err(|e| e.to_string())?;
+        let result: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_all(&mut connection)
+            .await
+            .map_err(|e| e.to_string())?;
+        connection.close().await.map_err(|e| e.to_string())?;
+
+        match result.as_slice() {
+            [ok] if ok == "ok" => Ok(()),
+            problems => Err(format!("Integrity check failed: {}", problems.join("; ")))
+        }
+    }
+
+    fn compress(from: &Path, to: &Path) -> Result<(), String> {
+        let mut input = BufReader::new(File::open(from).map_err(|e| e.to_string())?);
+        let output = BufWriter::new(File::create(to).map_err(|e| e.to_string())?);
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        std::io::copy(&mut input, &mut encoder).map_err(|e| e.to_string())?;
+        encoder.finish()
+            .and_then(|output| output.into_inner().map_err(|e| e.into_error()))
+            .and_then(|file| file.sync_all())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn snapshot(&self, snapshot: &Path, verify: bool, name: &str) -> Result<u64, String> {
+        sqlx::query("VACUUM INTO $1")
+            .bind(snapshot.to_string_lossy().to_string())
+            .execute(&self.db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if verify {
+            Self::verify(snapshot).await?;
+        }
+
+        // Compress aside and rename, so a listed backup is always complete
+        let compressed = self.dir.join(format!(".{}.tmp", name));
+        let path = self.dir.join(name);
+        let (from, to) = (snapshot.to_path_buf(), compressed.clone());
+        tokio::task::spawn_blocking(move || Self::compress(&from, &to))
+            .await
+            .map_err(|e| e.to_string())??;
+        tokio::fs::rename(&compressed, &path).await.map_err(|e| e.to_string())?;
+
+        let metadata = tokio::fs::metadata(&path).await.map_err(|e| e.to_string())?;
+        Ok(metadata.len())
+    }
+}
+
+#[async_trait]
+impl BackupStorage for SqliteBackupStorage {
+    async fn create(&self, verify: bool) -> Result<Backup, String> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| e.to_string())?;
+
+        let created_at = Utc::now();
+        let name = backup_name(created_at);
+        let snapshot = self.dir.join(format!(".{}.sqlite", generate_id(8)));
+
+        let result = self.snapshot(&snapshot, verify, &name).await;
+        // Leftovers of a failed attempt are not worth an error of their own
+        let _ = tokio::fs::remove_file(&snapshot).await;
+        let _ = tokio::fs::remove_file(self.dir.join(format!(".{}.tmp", name))).await;
+
+        Ok(Backup {
+            name,
+            // Second precision, as in the name
+            created_at: backup_created_at(&backup_name(created_at)).unwrap(),
+            size: result?
+        })
+    }
+
+    async fn list(&self) -> Result<Vec<Backup>, String> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.to_string())
+        };
+
+        let mut backups = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(created_at) = backup_created_at(&name) {
+                let size = entry.metadata().await.map_err(|e| e.to_string())?.len();
+                backups.push(Backup { name, created_at, size });
+            }
+        }
+        Ok(backups)
+    }
+
+    async fn get(&self, name: &str) -> Option<Vec<u8>> {
+        backup_created_at(name)?;
+        tokio::fs::read(self.dir.join(name)).await.ok()
+    }
+
+    async fn remove(&self, name: &str) -> Result<(), String> {
+        if backup_created_at(name).is_none() {
+            return Err(format!("{:?} is not a backup", name));
+        }
+        match tokio::fs::remove_file(self.dir.join(name)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::time::Duration;
+    use flate2::read::GzDecoder;
+    use crate::adapters::database::pool::{connect, DbOptions};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_list_remove() {
+        let root = std::env::temp_dir().join(format!("backup-{}", generate_id(8)));
+        let url = format!("sqlite://{}", root.join("database").display());
+        tokio::fs::create_dir_all(&root).await.unwrap();
+        let pools = connect(&DbOptions {
+            url: &url,
+            writers: 1,
+            readers: 1,
+            busy_timeout: Duration::from_secs(1),
+        }).await.unwrap();
+        sqlx::query("CREATE TABLE t (id INTEGER)").execute(&pools.writer).await.unwrap();
+        sqlx::query("INSERT INTO t VALUES (42)").execute(&pools.writer).await.unwrap();
+
+        let storage = SqliteBackupStorage::new(pools.reader.clone(), root.join("backups"));
+        let backup = storage.create(true).await.unwrap();
+        assert_eq!(storage.list().await.unwrap(), vec![backup.clone()]);
+
+        // The snapshot is a complete database
+        let compressed = storage.get(&backup.name).await.unwrap();
+        let mut database = Vec::new();
+        GzDecoder::new(compressed.as_slice()).read_to_end(&mut database).unwrap();
+        let restored = root.join("restored");
+        tokio::fs::write(&restored, database).await.unwrap();
+        let mut connection = SqliteConnectOptions::new().filename(&restored).connect().await.unwrap();
+        let id: i64 = sqlx::query_scalar("SELECT id FROM t").fetch_one(&mut connection).await.unwrap();
+        assert_eq!(id, 42);
+
+        assert!(storage.get("../database").await.is_none());
+        storage.remove(&backup.name).await.unwrap();
+        assert!(storage.list().await.unwrap().is_empty());
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::Path;
+
+use base64::Engine;
+use resvg::usvg;
+
+use crate::application::common::counter_renderer::CounterRenderer;
+
+/// Formats a digit image may have, in the order they are looked up
+const DIGIT_FORMATS: [(&str, &str); 3] = [
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+];
+
+
+struct Digit {
+    /// `data:` URI of the image
+    href: String,
+    width: u32,
+    height: u32,
+}
+
+/// Themes are directories under `assets/counter` with one image per digit,
+/// `0.svg` to `9.svg` (or `.png`, `.gif`). Every theme is loaded once at start
+pub struct AssetCounterRenderer {
+    themes: HashMap<String, Vec<Digit>>,
+}
+
+impl AssetCounterRenderer {
+    pub fn load(dir: &str) -> Self {
+        let mut themes = HashMap::new();
+
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(error) => {
+                log::warn!("Failed to read counter themes from {}: {}", dir, error.to_string());
+                return Self { themes };
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            match load_theme(&path) {
+                Ok(digits) => {
+                    themes.insert(name, digits);
+                }
+                Err(error) => log::warn!("Skipping counter theme {}: {}", name, error)
+            }
+        }
+
+        Self { themes }
+    }
+}
+
+fn load_theme(dir: &Path) -> Result<Vec<Digit>, String> {
+    (0..10).map(|digit| {
+        let (path, mime) = DIGIT_FORMATS.iter()
+            .map(|(ext, mime)| (dir.join(format!("{}.{}", digit, ext)), *mime))
+            .find(|(path, _)| path.is_file())
+            .ok_or_else(|| format!("no image for digit {}", digit))?;
+        let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+        let (width, height) = dimensions(&data, mime)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Ok(Digit {
+            href: format!(
+                "data:{};base64,{}",
+                mime,
+                base64::engine::general_purpose::STANDARD.encode(&data)
+            ),
+            width,
+            height,
+        })
+    }).collect()
+}
+
+fn dimensions(data: &[u8], mime: &str) -> Result<(u32, u32), String> {
+    match mime {
+        "image/svg+xml" => {
+            let tree = usvg::Tree::from_data(data, &usvg::Options::default()).map_err(|e| e.to_string())?;
+            Ok((tree.size().width().ceil() as u32, tree.size().height().ceil() as u32))
+        }
+        _ => image::ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .map_err(|e| e.to_string())?
+            .into_dimensions()
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl CounterRenderer for AssetCounterRenderer {
+    fn has_theme(&self, theme: &str) -> bool {
+        self.themes.contains_key(theme)
+    }
+
+    fn render(&self, theme: &str, digits: &str) -> Option<String> {
+        let theme = self.themes.get(theme)?;
+
+        let mut x = 0;
+        let mut height = 0;
+        let mut images = String::new();
+        for digit in digits.chars().filter_map(|c| c.to_digit(10)) {
+            let digit = &theme[digit as usize];
+            images.push_str(&format!(
+                "<image x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" href=\"{}\"/>",
+                x, digit.width, digit.height, digit.href
+            ));
+            x += digit.width;
+            height = height.max(digit.height);
+        }
+
+        Some(format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\
+                <title>{digits}</title>\
+                <g style=\"image-rendering: pixelated\">{images}</g>\
+            </svg>",
+            w = x,
+            h = height,
+            digits = digits,
+            images = images,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_themes() {
+        let renderer = AssetCounterRenderer::load("assets/counter");
+
+        for theme in ["default", "amber", "light"] {
+            assert!(renderer.has_theme(theme), "{}", theme);
+        }
+        assert!(!renderer.has_theme("missing"));
+
+        let svg = renderer.render("default", "0042").unwrap();
+        assert_eq!(svg.matches("<image").count(), 4);
+        assert!(svg.contains("width=\"160\" height=\"72\""));
+        assert!(svg.contains("<title>0042</title>"));
+        usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+    }
+}
@@ -0,0 +1 @@
+pub mod asset_renderer;
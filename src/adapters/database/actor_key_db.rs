@@ -0,0 +1,63 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::actor_key_gateway::{
+    ActorKeyGateway as ActorKeyGatewayTrait,
+    ActorKeyReader,
+    ActorKeyWriter
+};
+use crate::domain::models::actor::ActorKey as ActorKeyDomain;
+use crate::adapters::database::models::actor_keys::{ActorKey, ACTOR_KEY_TABLE};
+
+
+pub struct ActorKeyGateway{
+    db: DbPools,
+}
+
+impl ActorKeyGateway {
+    pub fn new(db: DbPools) -> Self {
+        ActorKeyGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl ActorKeyReader for ActorKeyGateway {
+    async fn get_current(&self) -> Option<ActorKeyDomain> {
+        let row: Option<ActorKey> = sqlx::query_as(
+            format!("SELECT * FROM {} ORDER BY created_at DESC LIMIT 1", ACTOR_KEY_TABLE).as_str()
+        )
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_actor_key_model_to_domain)
+    }
+}
+
+#[async_trait]
+impl ActorKeyWriter for ActorKeyGateway {
+    async fn save(&self, key: &ActorKeyDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, public_key_pem, private_key_pem, created_at) VALUES ($1, $2, $3, $4)",
+            ACTOR_KEY_TABLE
+        ).as_str())
+            .bind(&key.id)
+            .bind(&key.public_key_pem)
+            .bind(&key.private_key_pem)
+            .bind(&key.created_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_actor_key_model_to_domain(key: ActorKey) -> ActorKeyDomain {
+    ActorKeyDomain {
+        id: key.id,
+        public_key_pem: key.public_key_pem,
+        private_key_pem: key.private_key_pem,
+        created_at: key.created_at
+    }
+}
+
+impl ActorKeyGatewayTrait for ActorKeyGateway {}
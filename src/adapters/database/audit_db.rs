@@ -0,0 +1,205 @@
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::adapters::database::session::DbSession;
+use crate::application::common::audit_gateway::{
+    AuditFilter,
+    AuditGateway as AuditGatewayTrait,
+    AuditReader,
+    AuditWriter
+};
+use crate::application::common::pagination::{Cursor, Direction};
+use crate::domain::models::audit::{AuditAction, AuditEntry as AuditEntryDomain, AuditTarget};
+use crate::adapters::database::models::audit_log::{AuditEntry, AUDIT_TABLE};
+
+
+/// Kept in SQLite with either backend
+pub struct AuditGateway{
+    db: DbSession,
+}
+
+impl AuditGateway {
+    pub fn new(db: DbPools) -> Self {
+        AuditGateway {
+            db: DbSession::Pools(db),
+        }
+    }
+
+    /// Gateway of a unit of work, see [`SqliteUnitOfWork`](crate::adapters::database::unit_of_work::SqliteUnitOfWork)
+    pub fn with_session(db: DbSession) -> Self {
+        AuditGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditReader for AuditGateway {
+    async fn list(&self, filter: &AuditFilter, cursor: Option<&Cursor>, limit: &u64) -> Vec<AuditEntryDomain> {
+        let mut index = 0;
+        let mut param = || {
+            index += 1;
+            format!("${}", index)
+        };
+
+        let mut conditions = Vec::new();
+        if filter.actor.is_some() {
+            conditions.push(format!("actor = {}", param()));
+        }
+        if filter.action.is_some() {
+            conditions.push(format!("action = {}", param()));
+        }
+        if filter.target.is_some() {
+            conditions.push(format!("target = {}", param()));
+        }
+        if filter.target_id.is_some() {
+            conditions.push(format!("target_id = {}", param()));
+        }
+        if filter.since.is_some() {
+            conditions.push(format!("created_at >= {}", param()));
+        }
+        if filter.until.is_some() {
+            conditions.push(format!("created_at < {}", param()));
+        }
+        let order = match cursor {
+            Some(cursor) => {
+                let (comparison, order) = match cursor.direction {
+                    Direction::After => ("<", "DESC"),
+                    Direction::Before => (">", "ASC"),
+                };
+                conditions.push(format!("(created_at, id) {} ({}, {})", comparison, param(), param()));
+                order
+            }
+            None => "DESC"
+        };
+        let limit_param = param();
+
+        let sql = format!(
+            "SELECT * FROM {table} {conditions} ORDER BY created_at {order}, id {order} LIMIT {limit_param}",
+            table = AUDIT_TABLE,
+            conditions = match conditions.is_empty() {
+                true => String::new(),
+                false => format!("WHERE {}", conditions.join(" AND "))
+            },
+        );
+
+        // Bound in the order the parameters were numbered above
+        let mut query = sqlx::query_as::<_, AuditEntry>(sql.as_str());
+        if let Some(actor) = &filter.actor {
+            query = query.bind(actor);
+        }
+        if let Some(action) = filter.action {
+            query = query.bind(action.as_str());
+        }
+        if let Some(target) = filter.target {
+            query = query.bind(target.as_str());
+        }
+        if let Some(target_id) = &filter.target_id {
+            query = query.bind(target_id);
+        }
+        if let Some(since) = filter.since {
+            query = query.bind(since);
+        }
+        if let Some(until) = filter.until {
+            query = query.bind(until);
+        }
+        if let Some(cursor) = cursor {
+            query = query.bind(cursor.created_at).bind(&cursor.id);
+        }
+        let rows = query
+            .bind(*limit as i64)
+            .fetch_all(&mut *self.db.reader().await).await.unwrap();
+
+        rows.into_iter().map(map_audit_entry_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl AuditWriter for AuditGateway {
+    async fn save(&self, entry: &AuditEntryDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, actor, action, target, target_id, before, after, ip, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            AUDIT_TABLE
+        ).as_str())
+            .bind(&entry.id)
+            .bind(&entry.actor)
+            .bind(entry.action.as_str())
+            .bind(entry.target.as_str())
+            .bind(&entry.target_id)
+            .bind(entry.before.as_ref().map(|summary| summary.to_string()))
+            .bind(entry.after.as_ref().map(|summary| summary.to_string()))
+            .bind(&entry.ip)
+            .bind(entry.created_at)
+            .execute(&mut *self.db.writer().await).await.unwrap();
+    }
+}
+
+fn map_audit_entry_model_to_domain(entry: AuditEntry) -> AuditEntryDomain {
+    AuditEntryDomain {
+        id: entry.id,
+        actor: entry.actor,
+        // Only known actions, targets and valid JSON are ever written to the table
+        action: AuditAction::parse(&entry.action).unwrap(),
+        target: AuditTarget::parse(&entry.target).unwrap(),
+        target_id: entry.target_id,
+        before: entry.before.map(|summary| serde_json::from_str(&summary).unwrap()),
+        after: entry.after.map(|summary| serde_json::from_str(&summary).unwrap()),
+        ip: entry.ip,
+        created_at: entry.created_at
+    }
+}
+
+impl AuditGatewayTrait for AuditGateway {}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use crate::domain::models::audit::note_summary;
+    use crate::domain::models::note::Note;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_audit_gateway() {
+        let gateway = AuditGateway::new(gateway_tests::sqlite().await);
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let entry = |actor: &str, action: AuditAction| AuditEntryDomain::create(
+            Some(actor.to_string()),
+            action,
+            AuditTarget::Note,
+            note.id.clone(),
+            None,
+            Some(note_summary(&note)),
+            Some("127.0.0.1".to_string())
+        );
+        let entries = [
+            entry("admin", AuditAction::Create),
+            entry("bot", AuditAction::Update),
+            entry("bot", AuditAction::Delete)
+        ];
+        for entry in &entries {
+            gateway.save(entry).await;
+        }
+
+        let all = gateway.list(&AuditFilter::default(), None, &10).await;
+        assert_eq!(all.len(), 3);
+        assert_eq!(all.iter().find(|e| e.id == entries[0].id).unwrap(), &entries[0]);
+
+        let filter = AuditFilter {
+            actor: Some("bot".to_string()),
+            action: Some(AuditAction::Delete),
+            target_id: Some(note.id.clone()),
+            ..AuditFilter::default()
+        };
+        let found = gateway.list(&filter, None, &10).await;
+        assert_eq!(found.iter().map(|e| e.id.clone()).collect::<Vec<_>>(), vec![entries[2].id.clone()]);
+
+        let first = &all[0];
+        let rest = gateway.list(
+            &AuditFilter::default(),
+            Some(&Cursor::after(first.created_at, first.id.clone())),
+            &10
+        ).await;
+        assert_eq!(rest.len(), 2);
+    }
+}
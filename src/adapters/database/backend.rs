@@ -0,0 +1,76 @@
+//! Where notes, projects and users are kept. The rest of the data
+//! stays in SQLite until its gateways are ported. Comments and webmentions
+//! only hold the id of their note, no foreign key crosses the databases
+
+#[cfg(feature = "postgres")]
+use sqlx::postgres::PgPool;
+
+use crate::adapters::database::note_db::NoteGateway as SqliteNoteGateway;
+use crate::adapters::database::pool::DbPools;
+use crate::adapters::database::project_db::ProjectGateway as SqliteProjectGateway;
+use crate::adapters::database::unit_of_work::SqliteUnitOfWork;
+use crate::adapters::database::user_db::UserGateway as SqliteUserGateway;
+#[cfg(feature = "postgres")]
+use crate::adapters::database::postgres;
+use crate::application::common::note_gateway::NoteGateway;
+use crate::application::common::project_gateway::ProjectGateway;
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::UserGateway;
+
+
+pub enum Backend {
+    Sqlite(DbPools),
+    /// With the SQLite pools, which keep the audit log of its units of work
+    #[cfg(feature = "postgres")]
+    Postgres(PgPool, DbPools),
+}
+
+impl Backend {
+    /// Postgres when `postgres_url` is given, its schema is migrated first.
+    /// Otherwise the SQLite database everything else lives in
+    pub async fn connect(sqlite: &DbPools, postgres_url: Option<&str>) -> Result<Self, String> {
+        match postgres_url {
+            None => Ok(Backend::Sqlite(sqlite.clone())),
+            #[cfg(feature = "postgres")]
+            Some(url) => {
+                let db = postgres::connect(url).await.map_err(|e| e.to_string())?;
+                postgres::migrate(&db).await.map_err(|e| e.to_string())?;
+                Ok(Backend::Postgres(db, sqlite.clone()))
+            }
+            #[cfg(not(feature = "postgres"))]
+            Some(_) => Err("Postgres support is not built in, enable the postgres feature".to_string())
+        }
+    }
+
+    pub fn note_gateway(&self) -> Box<dyn NoteGateway> {
+        match self {
+            Backend::Sqlite(db) => Box::new(SqliteNoteGateway::new(db.clone())),
+            #[cfg(feature = "postgres")]
+            Backend::Postgres(db, _) => Box::new(postgres::note_db::NoteGateway::new(db.clone())),
+        }
+    }
+
+    pub fn project_gateway(&self) -> Box<dyn ProjectGateway> {
+        match self {
+            Backend::Sqlite(db) => Box::new(SqliteProjectGateway::new(db.clone())),
+            #[cfg(feature = "postgres")]
+            Backend::Postgres(db, _) => Box::new(postgres::project_db::ProjectGateway::new(db.clone())),
+        }
+    }
+
+    pub fn user_gateway(&self) -> Box<dyn UserGateway> {
+        match self {
+            Backend::Sqlite(db) => Box::new(SqliteUserGateway::new(db.clone())),
+            #[cfg(feature = "postgres")]
+            Backend::Postgres(db, _) => Box::new(postgres::user_db::UserGateway::new(db.clone())),
+        }
+    }
+
+    pub fn unit_of_work(&self) -> Box<dyn UnitOfWork> {
+        match self {
+            Backend::Sqlite(db) => Box::new(SqliteUnitOfWork::new(db.clone())),
+            #[cfg(feature = "postgres")]
+            Backend::Postgres(db, sqlite) => Box::new(postgres::unit_of_work::PgUnitOfWork::new(db.clone(), sqlite.clone())),
+        }
+    }
+}
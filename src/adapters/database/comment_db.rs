@@ -0,0 +1,196 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::comment_gateway::{
+    CommentGateway as CommentGatewayTrait,
+    CommentReader,
+    CommentRemover,
+    CommentWriter
+};
+use crate::domain::models::comment::{Comment as CommentDomain, CommentId, CommentStatus};
+use crate::domain::models::note::NoteId;
+use crate::adapters::database::models::comments::{Comment, COMMENT_TABLE};
+
+
+pub struct CommentGateway{
+    db: DbPools,
+}
+
+impl CommentGateway {
+    pub fn new(db: DbPools) -> Self {
+        CommentGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl CommentReader for CommentGateway {
+    async fn get_by_id(&self, id: &CommentId) -> Option<CommentDomain> {
+        let row: Option<Comment> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE id = $1", COMMENT_TABLE).as_str()
+        )
+            .bind(id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_comment_model_to_domain)
+    }
+
+    async fn list_by_note(&self, note_id: &NoteId, status: CommentStatus) -> Vec<CommentDomain> {
+        let rows: Vec<Comment> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE note_id = $1 AND status = $2 ORDER BY created_at, id",
+            COMMENT_TABLE
+        ).as_str())
+            .bind(note_id)
+            .bind(status.as_str())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_comment_model_to_domain).collect()
+    }
+
+    async fn list_by_status(&self, status: CommentStatus) -> Vec<CommentDomain> {
+        let rows: Vec<Comment> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE status = $1 ORDER BY created_at, id",
+            COMMENT_TABLE
+        ).as_str())
+            .bind(status.as_str())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_comment_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl CommentWriter for CommentGateway {
+    async fn save(&self, comment: &CommentDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, note_id, parent_id, author, body, status, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (id) DO UPDATE SET \
+             author = $4, body = $5, status = $6",
+            COMMENT_TABLE
+        ).as_str())
+            .bind(&comment.id)
+            .bind(&comment.note_id)
+            .bind(&comment.parent_id)
+            .bind(&comment.author)
+            .bind(&comment.body)
+            .bind(comment.status.as_str())
+            .bind(&comment.created_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl CommentRemover for CommentGateway {
+    async fn remove(&self, comment_id: &CommentId) {
+        // Foreign keys may be disabled on the connection, so replies are removed explicitly
+        sqlx::query(format!(
+            "WITH RECURSIVE thread(id) AS ( \
+                SELECT $1 \
+                UNION ALL \
+                SELECT c.id FROM {table} c JOIN thread t ON c.parent_id = t.id \
+             ) \
+             DELETE FROM {table} WHERE id IN (SELECT id FROM thread)",
+            table = COMMENT_TABLE
+        ).as_str())
+            .bind(comment_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+
+    async fn remove_by_note(&self, note_id: &NoteId) {
+        sqlx::query(format!("DELETE FROM {} WHERE note_id = $1", COMMENT_TABLE).as_str())
+            .bind(note_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_comment_model_to_domain(comment: Comment) -> CommentDomain {
+    CommentDomain {
+        id: comment.id,
+        note_id: comment.note_id,
+        parent_id: comment.parent_id,
+        author: comment.author,
+        body: comment.body,
+        // Only known statuses are ever written to the table
+        status: CommentStatus::parse(&comment.status).unwrap(),
+        created_at: comment.created_at
+    }
+}
+
+impl CommentGatewayTrait for CommentGateway {}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use crate::adapters::database::initial::initial_models;
+    use crate::adapters::database::note_db::NoteGateway;
+    use crate::application::common::note_gateway::NoteWriter;
+    use crate::domain::models::note::Note;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_comments_of_a_note_elsewhere() {
+        // Nothing ties a comment to the SQLite notes table
+        let db = gateway_tests::sqlite().await;
+        let gateway = CommentGateway::new(db);
+        let comment = CommentDomain::create("elsewhere".to_string(), None, "Reader".to_string(), "Nice".to_string()).unwrap();
+        gateway.save(&comment).await;
+
+        let listed = gateway.list_by_note(&"elsewhere".to_string(), comment.status).await;
+        assert_eq!(listed.len(), 1);
+        gateway.remove_by_note(&"elsewhere".to_string()).await;
+        assert!(gateway.get_by_id(&comment.id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_note_reference_dropped() {
+        let db = gateway_tests::sqlite().await;
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let comment = CommentDomain::create(note.id.clone(), None, "Reader".to_string(), "Nice".to_string()).unwrap();
+        // As older versions created it
+        sqlx::query(format!(
+            "DROP TABLE {table};
+            CREATE TABLE {table} (
+                id CHAR(16) PRIMARY KEY,
+                note_id CHAR(16) NOT NULL REFERENCES notes (id) ON DELETE CASCADE,
+                parent_id CHAR(16) REFERENCES {table} (id) ON DELETE CASCADE,
+                author VARCHAR(64) NOT NULL,
+                body VARCHAR(4096) NOT NULL,
+                status VARCHAR(16) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );",
+            table = COMMENT_TABLE
+        ).as_str()).execute(&db.writer).await.unwrap();
+        NoteGateway::new(db.clone()).save(&note).await;
+        CommentGateway::new(db.clone()).save(&comment).await;
+
+        initial_models(&db.writer).await.unwrap();
+
+        let references: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pragma_foreign_key_list($1) WHERE \"table\" = 'notes'")
+            .bind(COMMENT_TABLE)
+            .fetch_one(&db.writer).await.unwrap();
+        assert_eq!(references, 0);
+        assert!(CommentGateway::new(db).get_by_id(&comment.id).await.is_some());
+    }
+
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    async fn test_comment_on_postgres_note() {
+        use crate::adapters::database::backend::Backend;
+
+        let sqlite = gateway_tests::sqlite().await;
+        let backend = Backend::Postgres(gateway_tests::postgres().await, sqlite.clone());
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        backend.note_gateway().save(&note).await;
+
+        let gateway = CommentGateway::new(sqlite);
+        let comment = CommentDomain::create(note.id.clone(), None, "Reader".to_string(), "Nice".to_string()).unwrap();
+        gateway.save(&comment).await;
+
+        let listed = gateway.list_by_note(&note.id, comment.status).await;
+        assert_eq!(listed.iter().map(|c| &c.id).collect::<Vec<_>>(), vec![&comment.id]);
+    }
+}
@@ -0,0 +1,147 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::contact_gateway::{
+    ContactMessageGateway as ContactMessageGatewayTrait,
+    ContactMessageReader,
+    ContactMessageWriter
+};
+use crate::domain::models::contact_message::{
+    ContactMessage as ContactMessageDomain,
+    ContactMessageId,
+    ContactMessageStatus
+};
+use crate::adapters::database::models::contact_messages::{
+    ContactMessage,
+    CONTACT_FORM_TOKEN_TABLE,
+    CONTACT_MESSAGE_TABLE
+};
+
+
+pub struct ContactMessageGateway{
+    db: DbPools,
+}
+
+impl ContactMessageGateway {
+    pub fn new(db: DbPools) -> Self {
+        ContactMessageGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl ContactMessageReader for ContactMessageGateway {
+    async fn get_by_id(&self, id: &ContactMessageId) -> Option<ContactMessageDomain> {
+        let row: Option<ContactMessage> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE id = $1", CONTACT_MESSAGE_TABLE).as_str()
+        )
+            .bind(id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_contact_message_model_to_domain)
+    }
+
+    async fn list(&self, limit: &u64, offset: &u64) -> Vec<ContactMessageDomain> {
+        let rows: Vec<ContactMessage> = sqlx::query_as(format!(
+            "SELECT * FROM {} ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+            CONTACT_MESSAGE_TABLE
+        ).as_str())
+            .bind(*limit as i64)
+            .bind(*offset as i64)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_contact_message_model_to_domain).collect()
+    }
+
+    async fn list_by_status(&self, status: ContactMessageStatus) -> Vec<ContactMessageDomain> {
+        let rows: Vec<ContactMessage> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE status = $1 ORDER BY created_at, id",
+            CONTACT_MESSAGE_TABLE
+        ).as_str())
+            .bind(status.as_str())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_contact_message_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl ContactMessageWriter for ContactMessageGateway {
+    async fn save(&self, message: &ContactMessageDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, name, email, subject, body, ip, status, created_at, delivered_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (id) DO UPDATE SET \
+             status = $7, delivered_at = $9",
+            CONTACT_MESSAGE_TABLE
+        ).as_str())
+            .bind(&message.id)
+            .bind(&message.name)
+            .bind(&message.email)
+            .bind(&message.subject)
+            .bind(&message.body)
+            .bind(&message.ip)
+            .bind(message.status.as_str())
+            .bind(&message.created_at)
+            .bind(&message.delivered_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+
+    async fn use_token(&self, token_hash: &str, expires_at: DateTime<Utc>) -> bool {
+        sqlx::query(format!("DELETE FROM {} WHERE expires_at < $1", CONTACT_FORM_TOKEN_TABLE).as_str())
+            .bind(Utc::now())
+            .execute(&self.db.writer).await.unwrap();
+
+        // The primary key settles concurrent submits of the same token
+        let result = sqlx::query(format!(
+            "INSERT INTO {} (hash, expires_at) VALUES ($1, $2) ON CONFLICT (hash) DO NOTHING",
+            CONTACT_FORM_TOKEN_TABLE
+        ).as_str())
+            .bind(token_hash)
+            .bind(expires_at)
+            .execute(&self.db.writer).await.unwrap();
+        result.rows_affected() == 1
+    }
+}
+
+fn map_contact_message_model_to_domain(message: ContactMessage) -> ContactMessageDomain {
+    ContactMessageDomain {
+        id: message.id,
+        name: message.name,
+        email: message.email,
+        subject: message.subject,
+        body: message.body,
+        ip: message.ip,
+        // Only known statuses are ever written to the table
+        status: ContactMessageStatus::parse(&message.status).unwrap(),
+        created_at: message.created_at,
+        delivered_at: message.delivered_at
+    }
+}
+
+impl ContactMessageGatewayTrait for ContactMessageGateway {}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_use_token() {
+        let gateway = ContactMessageGateway::new(gateway_tests::sqlite().await);
+        let expires_at = Utc::now() + Duration::hours(1);
+
+        assert!(gateway.use_token("a", expires_at).await);
+        assert!(!gateway.use_token("a", expires_at).await);
+        assert!(gateway.use_token("b", expires_at).await);
+
+        // Expired entries are dropped, the token itself is refused by then anyway
+        assert!(gateway.use_token("c", Utc::now() - Duration::hours(1)).await);
+        assert!(gateway.use_token("c", expires_at).await);
+    }
+}
@@ -0,0 +1,67 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::counter_gateway::{
+    CounterGateway as CounterGatewayTrait,
+    CounterReader,
+    CounterWriter
+};
+use crate::domain::models::counter::Counter as CounterDomain;
+use crate::adapters::database::models::counters::{Counter, COUNTER_TABLE};
+
+
+pub struct CounterGateway{
+    db: DbPools,
+}
+
+impl CounterGateway {
+    pub fn new(db: DbPools) -> Self {
+        CounterGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl CounterReader for CounterGateway {
+    async fn get_by_name(&self, name: &str) -> Option<CounterDomain> {
+        let row: Option<Counter> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE name = $1", COUNTER_TABLE).as_str()
+        )
+            .bind(name)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_counter_model_to_domain)
+    }
+}
+
+#[async_trait]
+impl CounterWriter for CounterGateway {
+    async fn increment(&self, name: &str) -> u64 {
+        // A single statement, so concurrent hits are never lost
+        let count: i64 = sqlx::query_scalar(format!(
+            "INSERT INTO {} (name, count, created_at, updated_at) VALUES ($1, 1, $2, $2) \
+             ON CONFLICT (name) DO UPDATE SET count = count + 1, updated_at = $2 \
+             RETURNING count",
+            COUNTER_TABLE
+        ).as_str())
+            .bind(name)
+            .bind(Utc::now())
+            .fetch_one(&self.db.writer).await.unwrap();
+        count as u64
+    }
+}
+
+fn map_counter_model_to_domain(counter: Counter) -> CounterDomain {
+    CounterDomain {
+        name: counter.name,
+        count: counter.count as u64,
+        created_at: counter.created_at,
+        updated_at: counter.updated_at
+    }
+}
+
+impl CounterGatewayTrait for CounterGateway {}
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::delivery_gateway::{
+    DeliveryGateway as DeliveryGatewayTrait,
+    DeliveryReader,
+    DeliveryRemover,
+    DeliveryWriter
+};
+use crate::domain::models::delivery::{Delivery as DeliveryDomain, DeliveryId};
+use crate::adapters::database::models::deliveries::{Delivery, DELIVERY_TABLE};
+
+
+pub struct DeliveryGateway{
+    db: DbPools,
+}
+
+impl DeliveryGateway {
+    pub fn new(db: DbPools) -> Self {
+        DeliveryGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl DeliveryReader for DeliveryGateway {
+    async fn list_due(&self, now: DateTime<Utc>, limit: &u64) -> Vec<DeliveryDomain> {
+        let rows: Vec<Delivery> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE next_attempt_at <= $1 ORDER BY next_attempt_at, id LIMIT $2",
+            DELIVERY_TABLE
+        ).as_str())
+            .bind(now)
+            .bind(limit.clone() as i64)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_delivery_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl DeliveryWriter for DeliveryGateway {
+    async fn save(&self, delivery: &DeliveryDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, inbox, activity, attempts, next_attempt_at, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (id) DO UPDATE SET \
+             attempts = $4, next_attempt_at = $5",
+            DELIVERY_TABLE
+        ).as_str())
+            .bind(&delivery.id)
+            .bind(&delivery.inbox)
+            .bind(&delivery.activity)
+            .bind(delivery.attempts as i64)
+            .bind(&delivery.next_attempt_at)
+            .bind(&delivery.created_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl DeliveryRemover for DeliveryGateway {
+    async fn remove(&self, delivery_id: &DeliveryId) {
+        sqlx::query(format!("DELETE FROM {} WHERE id = $1", DELIVERY_TABLE).as_str())
+            .bind(delivery_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_delivery_model_to_domain(delivery: Delivery) -> DeliveryDomain {
+    DeliveryDomain {
+        id: delivery.id,
+        inbox: delivery.inbox,
+        activity: delivery.activity,
+        attempts: delivery.attempts as u32,
+        next_attempt_at: delivery.next_attempt_at,
+        created_at: delivery.created_at
+    }
+}
+
+impl DeliveryGatewayTrait for DeliveryGateway {}
@@ -0,0 +1,86 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::follower_gateway::{
+    FollowerGateway as FollowerGatewayTrait,
+    FollowerReader,
+    FollowerRemover,
+    FollowerWriter
+};
+use crate::domain::models::follower::{Follower as FollowerDomain, FollowerId};
+use crate::adapters::database::models::followers::{Follower, FOLLOWER_TABLE};
+
+
+pub struct FollowerGateway{
+    db: DbPools,
+}
+
+impl FollowerGateway {
+    pub fn new(db: DbPools) -> Self {
+        FollowerGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl FollowerReader for FollowerGateway {
+    async fn get_by_actor(&self, actor: &str) -> Option<FollowerDomain> {
+        let row: Option<Follower> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE actor = $1", FOLLOWER_TABLE).as_str()
+        )
+            .bind(actor)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_follower_model_to_domain)
+    }
+
+    async fn inboxes(&self) -> Vec<String> {
+        sqlx::query_scalar(format!("SELECT DISTINCT inbox FROM {} ORDER BY inbox", FOLLOWER_TABLE).as_str())
+            .fetch_all(&self.db.reader).await.unwrap()
+    }
+
+    async fn count(&self) -> u64 {
+        let count: i64 = sqlx::query_scalar(format!("SELECT COUNT(*) FROM {}", FOLLOWER_TABLE).as_str())
+            .fetch_one(&self.db.reader).await.unwrap();
+        count as u64
+    }
+}
+
+#[async_trait]
+impl FollowerWriter for FollowerGateway {
+    async fn save(&self, follower: &FollowerDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, actor, inbox, created_at) VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (id) DO UPDATE SET inbox = $3",
+            FOLLOWER_TABLE
+        ).as_str())
+            .bind(&follower.id)
+            .bind(&follower.actor)
+            .bind(&follower.inbox)
+            .bind(&follower.created_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl FollowerRemover for FollowerGateway {
+    async fn remove(&self, follower_id: &FollowerId) {
+        sqlx::query(format!("DELETE FROM {} WHERE id = $1", FOLLOWER_TABLE).as_str())
+            .bind(follower_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_follower_model_to_domain(follower: Follower) -> FollowerDomain {
+    FollowerDomain {
+        id: follower.id,
+        actor: follower.actor,
+        inbox: follower.inbox,
+        created_at: follower.created_at
+    }
+}
+
+impl FollowerGatewayTrait for FollowerGateway {}
@@ -0,0 +1,253 @@
+//! Cases shared by the SQLite and Postgres gateways, so both backends
+//! are held to the same contract. The Postgres tests need a server,
+//! `scripts/test-postgres.sh` starts one in a container
+
+use std::time::Duration;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::adapters::database::initial::initial_models;
+use crate::adapters::database::pool::{connect, DbOptions, DbPools};
+use crate::application::common::audit_gateway::{AuditFilter, AuditReader};
+use crate::application::common::note_gateway::{NoteGateway, NoteReader, NoteRemover, NoteWriter};
+use crate::application::common::pagination::Cursor;
+use crate::application::common::project_gateway::{ProjectGateway, ProjectReader, ProjectRemover, ProjectWriter};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::{UserGateway, UserReader, UserRemover, UserWriter};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::audit::{note_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::hash::Hash;
+use crate::domain::models::note::{Note, NoteListItem};
+use crate::domain::models::project::Project;
+use crate::domain::models::user::User;
+
+/// Fresh database in a temporary file
+pub async fn sqlite() -> DbPools {
+    let path = std::env::temp_dir().join(format!("database-{}", generate_id(8)));
+    let url = format!("sqlite://{}", path.display());
+    let db = connect(&DbOptions {
+        url: &url,
+        writers: 1,
+        readers: 2,
+        busy_timeout: Duration::from_secs(1),
+    }).await.unwrap();
+    initial_models(&db.writer).await.unwrap();
+    db
+}
+
+/// Fresh schema in the database of `JKEARNSL_TEST_POSTGRES_URL`
+#[cfg(feature = "postgres")]
+pub async fn postgres() -> sqlx::postgres::PgPool {
+    use std::str::FromStr;
+    use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+    let url = std::env::var("JKEARNSL_TEST_POSTGRES_URL")
+        .expect("JKEARNSL_TEST_POSTGRES_URL is not set, run scripts/test-postgres.sh");
+    let schema = format!("test_{}", generate_id(12).to_lowercase());
+
+    let admin = PgPoolOptions::new().max_connections(1).connect(&url).await.unwrap();
+    sqlx::query(&format!("CREATE SCHEMA {}", schema)).execute(&admin).await.unwrap();
+    admin.close().await;
+
+    let options = PgConnectOptions::from_str(&url).unwrap().options([("search_path", schema.as_str())]);
+    let db = PgPoolOptions::new().max_connections(2).connect_with(options).await.unwrap();
+    crate::adapters::database::postgres::migrate(&db).await.unwrap();
+    db
+}
+
+fn at(day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap()
+}
+
+fn note(day: u32) -> Note {
+    Note {
+        id: generate_id(16),
+        slug: format!("note-{}", day),
+        title: format!("Note {}", day),
+        description: "Description".to_string(),
+        body: "Body".to_string(),
+        created_at: at(day),
+        updated_at: None,
+        version: 1
+    }
+}
+
+pub async fn note_gateway(gateway: &dyn NoteGateway) {
+    let notes = [note(1), note(2), note(3)];
+    for note in &notes {
+        gateway.save(note).await;
+    }
+    assert_eq!(gateway.count().await, 3);
+
+    let found = gateway.get_by_slug("note-2").await.unwrap();
+    assert_eq!((found.id.as_str(), found.created_at), (notes[1].id.as_str(), at(2)));
+    assert!(gateway.get_by_slug("missing").await.is_none());
+
+    let ids = |items: Vec<NoteListItem>| {
+        items.into_iter().map(|item| item.id).collect::<Vec<_>>()
+    };
+    assert_eq!(ids(gateway.range(&2, &0).await), vec![notes[2].id.clone(), notes[1].id.clone()]);
+    assert_eq!(ids(gateway.range(&2, &2).await), vec![notes[0].id.clone()]);
+    assert_eq!(
+        ids(gateway.range_by_cursor(Some(&Cursor::after(at(3), notes[2].id.clone())), &10).await),
+        vec![notes[1].id.clone(), notes[0].id.clone()]
+    );
+    assert_eq!(
+        ids(gateway.range_by_cursor(Some(&Cursor::before(at(1), notes[0].id.clone())), &10).await),
+        vec![notes[1].id.clone(), notes[2].id.clone()]
+    );
+
+    let mut updated = notes[0].clone();
+    updated.title = "Updated".to_string();
+    updated.updated_at = Some(at(4));
+    gateway.save(&updated).await;
+    let found = gateway.get_by_id(&updated.id).await.unwrap();
+    assert_eq!((found.title.as_str(), found.updated_at), ("Updated", Some(at(4))));
+    assert_eq!(gateway.count().await, 3);
+
+    let mut edited = updated.clone();
+    edited.update("Edited".to_string(), "Edited body".to_string()).unwrap();
+    assert!(gateway.update(&edited, updated.version).await);
+    assert!(!gateway.update(&edited, updated.version).await);
+    let found = gateway.get_by_id(&edited.id).await.unwrap();
+    assert_eq!((found.title.as_str(), found.version), ("Edited", 2));
+
+    gateway.remove(&updated.id).await;
+    assert!(gateway.get_by_id(&updated.id).await.is_none());
+    assert!(gateway.get_by_slug(&edited.slug).await.is_none());
+    assert_eq!(gateway.count().await, 2);
+    assert!(!gateway.update(&edited, edited.version).await);
+    let trash = gateway.get_deleted().await;
+    assert_eq!((trash.len(), trash[0].id.as_str(), trash[0].title.as_str()), (1, updated.id.as_str(), "Edited"));
+
+    assert!(!gateway.purge(&notes[1].id).await);
+    assert!(gateway.restore(&updated.id).await);
+    assert!(!gateway.restore(&updated.id).await);
+    assert_eq!(gateway.count().await, 3);
+
+    gateway.remove(&updated.id).await;
+    assert!(gateway.purge(&updated.id).await);
+    assert!(gateway.get_deleted().await.is_empty());
+
+    gateway.remove(&notes[1].id).await;
+    assert!(gateway.purge_deleted_before(&(Utc::now() - chrono::Duration::hours(1))).await.is_empty());
+    assert_eq!(gateway.purge_deleted_before(&(Utc::now() + chrono::Duration::hours(1))).await, vec![notes[1].id.clone()]);
+    assert!(gateway.get_deleted().await.is_empty());
+    assert_eq!(gateway.count().await, 1);
+}
+
+pub async fn project_gateway(gateway: &dyn ProjectGateway) {
+    let project = |day: u32, url: Option<&str>| Project {
+        id: generate_id(16),
+        title: format!("Project {}", day),
+        description: "Description".to_string(),
+        url: url.map(str::to_string),
+        created_at: at(day),
+        version: 1
+    };
+    let projects = [project(1, Some("https://github.com/JKearnsl")), project(2, None)];
+    for project in &projects {
+        gateway.save_project(project).await;
+    }
+
+    let found = gateway.get_project(&projects[0].id).await.unwrap();
+    assert_eq!(found.url.as_deref(), Some("https://github.com/JKearnsl"));
+    assert_eq!(gateway.get_project(&projects[1].id).await.unwrap().url, None);
+
+    let range = gateway.get_projects_range(&10, &0).await;
+    assert_eq!(range.iter().map(|p| p.id.clone()).collect::<Vec<_>>(), vec![
+        projects[1].id.clone(),
+        projects[0].id.clone()
+    ]);
+    let page = gateway.get_projects_by_cursor(Some(&Cursor::after(at(2), projects[1].id.clone())), &10).await;
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].id, projects[0].id);
+
+    let mut edited = projects[0].clone();
+    edited.update("Edited".to_string(), "Description".to_string(), None).unwrap();
+    assert!(gateway.update_project(&edited, 1).await);
+    assert!(!gateway.update_project(&edited, 1).await);
+    let found = gateway.get_project(&edited.id).await.unwrap();
+    assert_eq!((found.title.as_str(), found.url, found.version), ("Edited", None, 2));
+
+    gateway.remove_project(&projects[0].id).await;
+    assert!(gateway.get_project(&projects[0].id).await.is_none());
+    assert_eq!(gateway.get_projects_range(&10, &0).await.len(), 1);
+    assert!(!gateway.update_project(&edited, edited.version).await);
+    let trash = gateway.get_deleted_projects().await;
+    assert_eq!((trash.len(), trash[0].id.as_str()), (1, projects[0].id.as_str()));
+
+    assert!(gateway.restore_project(&projects[0].id).await);
+    assert!(gateway.get_project(&projects[0].id).await.is_some());
+    assert!(!gateway.purge_project(&projects[0].id).await);
+
+    gateway.remove_project(&projects[0].id).await;
+    assert!(gateway.purge_project(&projects[0].id).await);
+    gateway.remove_project(&projects[1].id).await;
+    assert_eq!(gateway.purge_deleted_projects_before(&(Utc::now() + chrono::Duration::hours(1))).await, 1);
+    assert!(gateway.get_deleted_projects().await.is_empty());
+}
+
+pub async fn user_gateway(gateway: &dyn UserGateway) {
+    let user = User::create("admin".to_string(), Hash::sha256(b"password")).unwrap();
+    gateway.save(&user).await;
+    gateway.save(&User::create("editor".to_string(), Hash::sha256(b"secret")).unwrap()).await;
+
+    let found = gateway.get_by_username("admin").await.unwrap();
+    assert_eq!((found.id.as_str(), &found.password_hash), (user.id.as_str(), &user.password_hash));
+    assert!(gateway.get_by_username("nobody").await.is_none());
+
+    let mut changed = user.clone();
+    changed.password_hash = Hash::sha256(b"changed");
+    gateway.save(&changed).await;
+    assert_eq!(gateway.get_by_username("admin").await.unwrap().password_hash, changed.password_hash);
+
+    let names = gateway.get_all().await.into_iter().map(|u| u.username).collect::<Vec<_>>();
+    assert_eq!(names, vec!["admin", "editor"]);
+
+    assert!(!gateway.insert(&User::create("editor".to_string(), Hash::sha256(b"other")).unwrap()).await);
+    assert!(gateway.insert(&User::create("writer".to_string(), Hash::sha256(b"other")).unwrap()).await);
+    assert_eq!(gateway.get_all().await.len(), 3);
+
+    gateway.remove(&user.id).await;
+    assert!(gateway.get_by_username("admin").await.is_none());
+}
+
+/// `outside` is a gateway on the pools, it must only see committed writes
+pub async fn unit_of_work(unit_of_work: &dyn UnitOfWork, outside: &dyn NoteGateway, audit: &dyn AuditReader) {
+    let entry = |note: &Note| AuditEntry::create(
+        Some("admin".to_string()),
+        AuditAction::Create,
+        AuditTarget::Note,
+        note.id.clone(),
+        None,
+        Some(note_summary(note)),
+        None
+    );
+
+    let rolled_back = note(1);
+    let unit = unit_of_work.begin().await.unwrap();
+    unit.note_gateway().save(&rolled_back).await;
+    unit.audit_writer().save(&entry(&rolled_back)).await;
+    assert!(unit.note_gateway().get_by_id(&rolled_back.id).await.is_some());
+    unit.rollback().await.unwrap();
+    assert!(outside.get_by_id(&rolled_back.id).await.is_none());
+    assert!(audit.list(&AuditFilter::default(), None, &10).await.is_empty());
+
+    let dropped = note(2);
+    let unit = unit_of_work.begin().await.unwrap();
+    unit.note_gateway().save(&dropped).await;
+    drop(unit);
+    assert!(outside.get_by_id(&dropped.id).await.is_none());
+
+    let committed = note(3);
+    let unit = unit_of_work.begin().await.unwrap();
+    unit.note_gateway().save(&committed).await;
+    unit.user_gateway().save(&User::create("admin".to_string(), Hash::sha256(b"password")).unwrap()).await;
+    unit.audit_writer().save(&entry(&committed)).await;
+    unit.commit().await.unwrap();
+    assert!(outside.get_by_id(&committed.id).await.is_some());
+    assert_eq!(outside.count().await, 1);
+    let entries = audit.list(&AuditFilter::default(), None, &10).await;
+    assert_eq!(entries.iter().map(|e| &e.target_id).collect::<Vec<_>>(), vec![&committed.id]);
+}
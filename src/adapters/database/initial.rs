@@ -1,10 +1,42 @@
+use crate::adapters::database::models::actor_keys::ActorKey;
+use crate::adapters::database::models::audit_log::AuditEntry;
+use crate::adapters::database::models::comments::Comment;
+use crate::adapters::database::models::contact_messages::{ContactFormToken, ContactMessage};
+use crate::adapters::database::models::counters::Counter;
+use crate::adapters::database::models::deliveries::Delivery;
+use crate::adapters::database::models::followers::Follower;
+use crate::adapters::database::models::jobs::{Job, JobSchedule};
+use crate::adapters::database::models::media::Media;
 use crate::adapters::database::models::notes::Note;
+use crate::adapters::database::models::page_views::{DailyStat, PageView};
 use crate::adapters::database::models::projects::Project;
+use crate::adapters::database::models::subscribers::Subscriber;
+use crate::adapters::database::models::users::User;
+use crate::adapters::database::models::webhooks::{Webhook, WebhookDelivery};
+use crate::adapters::database::models::webmentions::Webmention;
 use crate::adapters::database::models::CreateIFNotExists;
 use crate::adapters::database::pool::DbPool;
 
 pub async fn initial_models(db: &DbPool) -> Result<(), sqlx::Error> {
     Note::create_if_not_exists(db).await?;
     Project::create_if_not_exists(db).await?;
+    Media::create_if_not_exists(db).await?;
+    Comment::create_if_not_exists(db).await?;
+    Webmention::create_if_not_exists(db).await?;
+    Follower::create_if_not_exists(db).await?;
+    ActorKey::create_if_not_exists(db).await?;
+    Delivery::create_if_not_exists(db).await?;
+    Subscriber::create_if_not_exists(db).await?;
+    ContactMessage::create_if_not_exists(db).await?;
+    ContactFormToken::create_if_not_exists(db).await?;
+    PageView::create_if_not_exists(db).await?;
+    DailyStat::create_if_not_exists(db).await?;
+    Counter::create_if_not_exists(db).await?;
+    User::create_if_not_exists(db).await?;
+    AuditEntry::create_if_not_exists(db).await?;
+    Webhook::create_if_not_exists(db).await?;
+    WebhookDelivery::create_if_not_exists(db).await?;
+    Job::create_if_not_exists(db).await?;
+    JobSchedule::create_if_not_exists(db).await?;
     Ok(())
 }
@@ -0,0 +1,256 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::job_gateway::{
+    JobFilter,
+    JobGateway as JobGatewayTrait,
+    JobReader,
+    JobWriter
+};
+use crate::application::common::pagination::{Cursor, Direction};
+use crate::domain::models::job::{
+    Job as JobDomain,
+    JobId,
+    JobSchedule as JobScheduleDomain,
+    JobStatus
+};
+use crate::adapters::database::models::jobs::{Job, JobSchedule, JOB_SCHEDULE_TABLE, JOB_TABLE};
+
+
+/// Kept in SQLite with either backend
+pub struct JobGateway{
+    db: DbPools,
+}
+
+impl JobGateway {
+    pub fn new(db: DbPools) -> Self {
+        JobGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl JobReader for JobGateway {
+    async fn list_jobs(&self, filter: &JobFilter, cursor: Option<&Cursor>, limit: &u64) -> Vec<JobDomain> {
+        let mut index = 0;
+        let mut param = || {
+            index += 1;
+            format!("${}", index)
+        };
+
+        let mut conditions = Vec::new();
+        if filter.status.is_some() {
+            conditions.push(format!("status = {}", param()));
+        }
+        if filter.kind.is_some() {
+            conditions.push(format!("kind = {}", param()));
+        }
+        let order = match cursor {
+            Some(cursor) => {
+                let (comparison, order) = match cursor.direction {
+                    Direction::After => ("<", "DESC"),
+                    Direction::Before => (">", "ASC"),
+                };
+                conditions.push(format!("(created_at, id) {} ({}, {})", comparison, param(), param()));
+                order
+            }
+            None => "DESC"
+        };
+        let limit_param = param();
+
+        let sql = format!(
+            "SELECT * FROM {table} {conditions} ORDER BY created_at {order}, id {order} LIMIT {limit_param}",
+            table = JOB_TABLE,
+            conditions = match conditions.is_empty() {
+                true => String::new(),
+                false => format!("WHERE {}", conditions.join(" AND "))
+            },
+        );
+
+        // Bound in the order the parameters were numbered above
+        let mut query = sqlx::query_as::<_, Job>(sql.as_str());
+        if let Some(status) = filter.status {
+            query = query.bind(status.as_str());
+        }
+        if let Some(kind) = &filter.kind {
+            query = query.bind(kind);
+        }
+        if let Some(cursor) = cursor {
+            query = query.bind(cursor.created_at).bind(&cursor.id);
+        }
+        let rows = query
+            .bind(*limit as i64)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_job_model_to_domain).collect()
+    }
+
+    async fn get_job(&self, job_id: &JobId) -> Option<JobDomain> {
+        let row: Option<Job> = sqlx::query_as(format!("SELECT * FROM {} WHERE id = $1", JOB_TABLE).as_str())
+            .bind(job_id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_job_model_to_domain)
+    }
+
+    async fn has_pending(&self, kind: &str) -> bool {
+        sqlx::query_scalar(format!(
+            "SELECT EXISTS (SELECT 1 FROM {} WHERE kind = $1 AND status IN ($2, $3))",
+            JOB_TABLE
+        ).as_str())
+            .bind(kind)
+            .bind(JobStatus::Queued.as_str())
+            .bind(JobStatus::Running.as_str())
+            .fetch_one(&self.db.reader).await.unwrap()
+    }
+
+    async fn get_schedule(&self, kind: &str) -> Option<JobScheduleDomain> {
+        let row: Option<JobSchedule> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE kind = $1",
+            JOB_SCHEDULE_TABLE
+        ).as_str())
+            .bind(kind)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(|schedule| JobScheduleDomain {
+            kind: schedule.kind,
+            expression: schedule.expression,
+            next_run_at: schedule.next_run_at
+        })
+    }
+}
+
+#[async_trait]
+impl JobWriter for JobGateway {
+    async fn save_job(&self, job: &JobDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, kind, payload, status, attempts, max_attempts, run_at, last_error, \
+             created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT (id) DO UPDATE SET \
+             status = $4, attempts = $5, max_attempts = $6, run_at = $7, last_error = $8, updated_at = $10",
+            JOB_TABLE
+        ).as_str())
+            .bind(&job.id)
+            .bind(&job.kind)
+            .bind(job.payload.to_string())
+            .bind(job.status.as_str())
+            .bind(job.attempts as i64)
+            .bind(job.max_attempts as i64)
+            .bind(job.run_at)
+            .bind(&job.last_error)
+            .bind(job.created_at)
+            .bind(job.updated_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+
+    async fn claim_next(&self, now: DateTime<Utc>) -> Option<JobDomain> {
+        // A single statement, so two workers cannot claim the same row
+        let row: Option<Job> = sqlx::query_as(format!(
+            "UPDATE {table} SET status = $1, updated_at = $2 WHERE id = (\
+                SELECT id FROM {table} WHERE status = $3 AND run_at <= $2 ORDER BY run_at, id LIMIT 1\
+             ) RETURNING *",
+            table = JOB_TABLE
+        ).as_str())
+            .bind(JobStatus::Running.as_str())
+            .bind(now)
+            .bind(JobStatus::Queued.as_str())
+            .fetch_optional(&self.db.writer).await.unwrap();
+
+        row.map(map_job_model_to_domain)
+    }
+
+    async fn requeue_running(&self, now: DateTime<Utc>) -> u64 {
+        sqlx::query(format!("UPDATE {} SET status = $1, updated_at = $2 WHERE status = $3", JOB_TABLE).as_str())
+            .bind(JobStatus::Queued.as_str())
+            .bind(now)
+            .bind(JobStatus::Running.as_str())
+            .execute(&self.db.writer).await.unwrap()
+            .rows_affected()
+    }
+
+    async fn remove_done_before(&self, before: DateTime<Utc>) -> u64 {
+        sqlx::query(format!("DELETE FROM {} WHERE status = $1 AND updated_at < $2", JOB_TABLE).as_str())
+            .bind(JobStatus::Done.as_str())
+            .bind(before)
+            .execute(&self.db.writer).await.unwrap()
+            .rows_affected()
+    }
+
+    async fn save_schedule(&self, schedule: &JobScheduleDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (kind, expression, next_run_at) VALUES ($1, $2, $3) \
+             ON CONFLICT (kind) DO UPDATE SET expression = $2, next_run_at = $3",
+            JOB_SCHEDULE_TABLE
+        ).as_str())
+            .bind(&schedule.kind)
+            .bind(&schedule.expression)
+            .bind(schedule.next_run_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_job_model_to_domain(job: Job) -> JobDomain {
+    JobDomain {
+        id: job.id,
+        kind: job.kind,
+        // Only ever written from a `Value`
+        payload: serde_json::from_str(&job.payload).unwrap(),
+        status: JobStatus::parse(&job.status).unwrap(),
+        attempts: job.attempts as u32,
+        max_attempts: job.max_attempts as u32,
+        run_at: job.run_at,
+        last_error: job.last_error,
+        created_at: job.created_at,
+        updated_at: job.updated_at
+    }
+}
+
+impl JobGatewayTrait for JobGateway {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_job_gateway() {
+        let gateway = JobGateway::new(gateway_tests::sqlite().await);
+        let now = Utc::now();
+        let job = JobDomain::create("backup", json!({"full": true}), now - Duration::seconds(1));
+        let later = JobDomain::create("backup", json!({}), now + Duration::hours(1));
+        gateway.save_job(&job).await;
+        gateway.save_job(&later).await;
+        assert!(gateway.has_pending("backup").await);
+
+        let claimed = gateway.claim_next(now).await.unwrap();
+        assert_eq!((claimed.id.as_str(), claimed.status), (job.id.as_str(), JobStatus::Running));
+        assert_eq!(claimed.payload, json!({"full": true}));
+        assert!(gateway.claim_next(now).await.is_none());
+
+        assert_eq!(gateway.requeue_running(now).await, 1);
+        let mut claimed_again = gateway.claim_next(now).await.unwrap();
+        assert_eq!(claimed_again.id, job.id);
+
+        claimed_again.complete(now - Duration::days(2));
+        gateway.save_job(&claimed_again).await;
+        assert_eq!(gateway.get_job(&job.id).await.unwrap().status, JobStatus::Done);
+
+        let filter = JobFilter { status: Some(JobStatus::Done), kind: None };
+        assert_eq!(gateway.list_jobs(&filter, None, &10).await.len(), 1);
+        assert_eq!(gateway.remove_done_before(now - Duration::days(1)).await, 1);
+        assert_eq!(gateway.list_jobs(&JobFilter::default(), None, &10).await, vec![later]);
+
+        let schedule = JobScheduleDomain {
+            kind: "backup".to_string(),
+            expression: "@daily".to_string(),
+            next_run_at: now
+        };
+        gateway.save_schedule(&schedule).await;
+        gateway.save_schedule(&schedule).await;
+        assert_eq!(gateway.get_schedule("backup").await, Some(schedule));
+    }
+}
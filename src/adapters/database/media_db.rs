@@ -0,0 +1,106 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::media_gateway::{
+    MediaGateway as MediaGatewayTrait,
+    MediaReader,
+    MediaRemover,
+    MediaWriter
+};
+use crate::domain::models::hash::Hash;
+use crate::domain::models::media::Media as MediaDomain;
+use crate::adapters::database::models::media::{Media, MEDIA_TABLE};
+
+
+pub struct MediaGateway{
+    db: DbPools,
+}
+
+impl MediaGateway {
+    pub fn new(db: DbPools) -> Self {
+        MediaGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl MediaReader for MediaGateway {
+    async fn get_by_hash(&self, hash: &Hash) -> Option<MediaDomain> {
+        let row: Option<Media> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE hash = $1", MEDIA_TABLE).as_str()
+        )
+            .bind(hash.to_string())
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_media_model_to_domain)
+    }
+
+    async fn get_variants(&self, source: &Hash) -> Vec<MediaDomain> {
+        let rows: Vec<Media> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE source = $1 ORDER BY width", MEDIA_TABLE).as_str()
+        )
+            .bind(source.to_string())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_media_model_to_domain).collect()
+    }
+
+    async fn get_all(&self) -> Vec<MediaDomain> {
+        let rows: Vec<Media> = sqlx::query_as(
+            format!("SELECT * FROM {} ORDER BY created_at, hash", MEDIA_TABLE).as_str()
+        )
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_media_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl MediaWriter for MediaGateway {
+    async fn save(&self, media: &MediaDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (hash, mime, size, name, uploaded_by, width, height, source, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT (hash) DO NOTHING",
+            MEDIA_TABLE
+        ).as_str())
+            .bind(media.hash.to_string())
+            .bind(&media.mime)
+            .bind(media.size as i64)
+            .bind(&media.name)
+            .bind(&media.uploaded_by)
+            .bind(media.width.map(i64::from))
+            .bind(media.height.map(i64::from))
+            .bind(media.source.as_ref().map(Hash::to_string))
+            .bind(&media.created_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl MediaRemover for MediaGateway {
+    async fn remove(&self, hash: &Hash) {
+        sqlx::query(format!("DELETE FROM {} WHERE hash = $1", MEDIA_TABLE).as_str())
+            .bind(hash.to_string())
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_media_model_to_domain(media: Media) -> MediaDomain {
+    MediaDomain {
+        // Only valid hashes are ever written to the table
+        hash: Hash::from_hex(&media.hash).unwrap(),
+        mime: media.mime,
+        size: media.size as u64,
+        name: media.name,
+        uploaded_by: media.uploaded_by,
+        width: media.width.map(|width| width as u32),
+        height: media.height.map(|height| height as u32),
+        source: media.source.map(|source| Hash::from_hex(&source).unwrap()),
+        created_at: media.created_at
+    }
+}
+
+impl MediaGatewayTrait for MediaGateway {}
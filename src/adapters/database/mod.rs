@@ -1,5 +1,26 @@
 pub mod models;
 pub mod pool;
+pub mod session;
+pub mod unit_of_work;
 pub mod note_db;
 pub mod project_db;
-pub mod initial;
\ No newline at end of file
+pub mod media_db;
+pub mod comment_db;
+pub mod webmention_db;
+pub mod follower_db;
+pub mod actor_key_db;
+pub mod delivery_db;
+pub mod subscriber_db;
+pub mod contact_db;
+pub mod page_view_db;
+pub mod counter_db;
+pub mod user_db;
+pub mod audit_db;
+pub mod webhook_db;
+pub mod job_db;
+pub mod backend;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod initial;
+#[cfg(test)]
+pub mod gateway_tests;
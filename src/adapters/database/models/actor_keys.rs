@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::actor::ACTOR_KEY_ID_SIZE;
+
+pub const ACTOR_KEY_TABLE: &str = "actor_keys";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct ActorKey {
+    pub id: String,
+    pub public_key_pem: String,
+    pub private_key_pem: String,
+    pub created_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for ActorKey {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                public_key_pem TEXT NOT NULL,
+                private_key_pem TEXT NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );",
+            table = ACTOR_KEY_TABLE,
+            id_size = ACTOR_KEY_ID_SIZE
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::audit::{AUDIT_ENTRY_ID_SIZE, AuditEntryId};
+
+pub const AUDIT_TABLE: &str = "audit_log";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    pub actor: Option<String>,
+    pub action: String,
+    pub target: String,
+    pub target_id: String,
+    /// JSON summaries
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for AuditEntry {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                actor VARCHAR(128),
+                action VARCHAR(16) NOT NULL,
+                target VARCHAR(16) NOT NULL,
+                target_id VARCHAR(128) NOT NULL,
+                before TEXT,
+                after TEXT,
+                ip VARCHAR(64),
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS {table}_created_at_id_idx ON {table} (created_at, id);
+            CREATE INDEX IF NOT EXISTS {table}_target_idx ON {table} (target, target_id);
+            CREATE INDEX IF NOT EXISTS {table}_actor_idx ON {table} (actor);",
+            table = AUDIT_TABLE,
+            id_size = AUDIT_ENTRY_ID_SIZE
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
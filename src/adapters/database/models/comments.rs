@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::{drop_reference_if_exists, CreateIFNotExists};
+use crate::adapters::database::models::notes::NOTE_TABLE;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::comment::{
+    COMMENT_ID_SIZE,
+    COMMENT_AUTHOR_MAX,
+    COMMENT_BODY_MAX,
+    CommentId
+};
+use crate::domain::models::note::{NOTE_ID_SIZE, NoteId};
+
+pub const COMMENT_TABLE: &str = "comments";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Comment {
+    pub id: CommentId,
+    pub note_id: NoteId,
+    pub parent_id: Option<CommentId>,
+    pub author: String,
+    pub body: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>
+}
+
+/// Notes may be kept in Postgres, so `note_id` references nothing
+/// and the comments of a purged note are removed by the application
+fn columns() -> String {
+    format!(
+        "id CHAR({id_size}) PRIMARY KEY,
+        note_id CHAR({note_id_size}) NOT NULL,
+        parent_id CHAR({id_size}) REFERENCES {table} (id) ON DELETE CASCADE,
+        author VARCHAR({author_max}) NOT NULL,
+        body VARCHAR({body_max}) NOT NULL,
+        status VARCHAR(16) NOT NULL,
+        created_at TIMESTAMP WITH TIME ZONE NOT NULL",
+        table = COMMENT_TABLE,
+        id_size = COMMENT_ID_SIZE,
+        note_id_size = NOTE_ID_SIZE,
+        author_max = COMMENT_AUTHOR_MAX,
+        body_max = COMMENT_BODY_MAX
+    )
+}
+
+impl CreateIFNotExists for Comment {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        drop_reference_if_exists(db_pool, COMMENT_TABLE, NOTE_TABLE, &columns()).await?;
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} ({columns});
+            CREATE INDEX IF NOT EXISTS {table}_note_id_status_idx ON {table} (note_id, status);
+            CREATE INDEX IF NOT EXISTS {table}_status_idx ON {table} (status);",
+            table = COMMENT_TABLE,
+            columns = columns()
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::contact_message::{
+    CONTACT_MESSAGE_ID_SIZE,
+    CONTACT_NAME_MAX,
+    CONTACT_EMAIL_MAX,
+    CONTACT_SUBJECT_MAX,
+    CONTACT_BODY_MAX,
+    ContactMessageId
+};
+
+pub const CONTACT_MESSAGE_TABLE: &str = "contact_messages";
+pub const CONTACT_FORM_TOKEN_TABLE: &str = "contact_form_tokens";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct ContactMessage {
+    pub id: ContactMessageId,
+    pub name: String,
+    pub email: String,
+    pub subject: String,
+    pub body: String,
+    pub ip: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>
+}
+
+impl CreateIFNotExists for ContactMessage {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                name VARCHAR({name_max}) NOT NULL,
+                email VARCHAR({email_max}) NOT NULL,
+                subject VARCHAR({subject_max}) NOT NULL,
+                body VARCHAR({body_max}) NOT NULL,
+                ip VARCHAR(45) NOT NULL,
+                status VARCHAR(16) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                delivered_at TIMESTAMP WITH TIME ZONE
+            );
+            CREATE INDEX IF NOT EXISTS {table}_created_at_idx ON {table} (created_at DESC, id DESC);
+            CREATE INDEX IF NOT EXISTS {table}_status_idx ON {table} (status);",
+            table = CONTACT_MESSAGE_TABLE,
+            id_size = CONTACT_MESSAGE_ID_SIZE,
+            name_max = CONTACT_NAME_MAX,
+            email_max = CONTACT_EMAIL_MAX,
+            subject_max = CONTACT_SUBJECT_MAX,
+            body_max = CONTACT_BODY_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Used form tokens, kept until they expire
+pub struct ContactFormToken;
+
+impl CreateIFNotExists for ContactFormToken {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                hash CHAR(64) PRIMARY KEY,
+                expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS {table}_expires_at_idx ON {table} (expires_at);",
+            table = CONTACT_FORM_TOKEN_TABLE
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
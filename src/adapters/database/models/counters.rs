@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::counter::COUNTER_NAME_MAX;
+
+pub const COUNTER_TABLE: &str = "counters";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Counter {
+    pub name: String,
+    pub count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for Counter {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                name VARCHAR({name_max}) PRIMARY KEY,
+                count INTEGER NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );",
+            table = COUNTER_TABLE,
+            name_max = COUNTER_NAME_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
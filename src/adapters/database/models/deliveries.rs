@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::delivery::{DELIVERY_ID_SIZE, DeliveryId};
+use crate::domain::models::follower::FOLLOWER_URL_MAX;
+
+pub const DELIVERY_TABLE: &str = "deliveries";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Delivery {
+    pub id: DeliveryId,
+    pub inbox: String,
+    pub activity: String,
+    pub attempts: i64,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for Delivery {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                inbox VARCHAR({url_max}) NOT NULL,
+                activity TEXT NOT NULL,
+                attempts INTEGER NOT NULL,
+                next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS {table}_next_attempt_at_idx ON {table} (next_attempt_at);",
+            table = DELIVERY_TABLE,
+            id_size = DELIVERY_ID_SIZE,
+            url_max = FOLLOWER_URL_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
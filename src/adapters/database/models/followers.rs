@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::follower::{FOLLOWER_ID_SIZE, FOLLOWER_URL_MAX, FollowerId};
+
+pub const FOLLOWER_TABLE: &str = "followers";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Follower {
+    pub id: FollowerId,
+    pub actor: String,
+    pub inbox: String,
+    pub created_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for Follower {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                actor VARCHAR({url_max}) NOT NULL UNIQUE,
+                inbox VARCHAR({url_max}) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );",
+            table = FOLLOWER_TABLE,
+            id_size = FOLLOWER_ID_SIZE,
+            url_max = FOLLOWER_URL_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::job::{JobId, JOB_ID_SIZE, JOB_KIND_MAX};
+
+pub const JOB_TABLE: &str = "jobs";
+pub const JOB_SCHEDULE_TABLE: &str = "job_schedules";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Job {
+    pub id: JobId,
+    pub kind: String,
+    /// JSON
+    pub payload: String,
+    pub status: String,
+    pub attempts: i64,
+    pub max_attempts: i64,
+    pub run_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>
+}
+
+impl CreateIFNotExists for Job {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                kind VARCHAR({kind_max}) NOT NULL,
+                payload TEXT NOT NULL,
+                status VARCHAR(16) NOT NULL,
+                attempts INTEGER NOT NULL,
+                max_attempts INTEGER NOT NULL,
+                run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                last_error TEXT,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                updated_at TIMESTAMP WITH TIME ZONE
+            );
+            CREATE INDEX IF NOT EXISTS {table}_status_run_at_idx ON {table} (status, run_at);
+            CREATE INDEX IF NOT EXISTS {table}_created_at_idx ON {table} (created_at, id);",
+            table = JOB_TABLE,
+            id_size = JOB_ID_SIZE,
+            kind_max = JOB_KIND_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct JobSchedule {
+    pub kind: String,
+    pub expression: String,
+    pub next_run_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for JobSchedule {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                kind VARCHAR({kind_max}) PRIMARY KEY,
+                expression TEXT NOT NULL,
+                next_run_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );",
+            table = JOB_SCHEDULE_TABLE,
+            kind_max = JOB_KIND_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::media::MEDIA_NAME_MAX;
+
+pub const MEDIA_TABLE: &str = "media";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Media {
+    /// Hex encoded SHA-256 of the content
+    pub hash: String,
+    pub mime: String,
+    pub size: i64,
+    pub name: String,
+    pub uploaded_by: String,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+    pub source: Option<String>,
+    pub created_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for Media {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                hash CHAR({hash_size}) PRIMARY KEY,
+                mime VARCHAR(64) NOT NULL,
+                size INTEGER NOT NULL,
+                name VARCHAR({name_max}) NOT NULL,
+                uploaded_by VARCHAR(128) NOT NULL,
+                width INTEGER,
+                height INTEGER,
+                source CHAR({hash_size}) REFERENCES {table} (hash) ON DELETE CASCADE,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS {table}_source_idx ON {table} (source);",
+            table = MEDIA_TABLE,
+            hash_size = Hash::SIZE * 2,
+            name_max = MEDIA_NAME_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,8 +1,96 @@
 pub mod notes;
 pub mod projects;
+pub mod media;
+pub mod comments;
+pub mod webmentions;
+pub mod followers;
+pub mod actor_keys;
+pub mod deliveries;
+pub mod subscribers;
+pub mod contact_messages;
+pub mod page_views;
+pub mod counters;
+pub mod users;
+pub mod audit_log;
+pub mod webhooks;
+pub mod jobs;
+
+use sqlx::{Connection, SqliteConnection};
 
 use crate::adapters::database::pool::DbPool;
 
 pub trait CreateIFNotExists {
     async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error>;
 }
+
+/// `CREATE TABLE IF NOT EXISTS` leaves tables of older databases as they were,
+/// columns added later are brought in here
+pub(crate) async fn add_column_if_not_exists(
+    db_pool: &DbPool,
+    table: &str,
+    column: &str,
+    definition: &str
+) -> Result<(), sqlx::Error> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM pragma_table_info($1) WHERE name = $2)"
+    )
+        .bind(table)
+        .bind(column)
+        .fetch_one(db_pool)
+        .await?;
+
+    if !exists {
+        sqlx::query(format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition).as_str())
+            .execute(db_pool)
+            .await?;
+    }
+    Ok(())
+}
+
+/// SQLite cannot drop a constraint. A table of an older database that still
+/// references `referenced` is copied into a new one made of `columns`
+pub(crate) async fn drop_reference_if_exists(
+    db_pool: &DbPool,
+    table: &str,
+    referenced: &str,
+    columns: &str
+) -> Result<(), sqlx::Error> {
+    let references: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM pragma_foreign_key_list($1) WHERE \"table\" = $2)"
+    )
+        .bind(table)
+        .bind(referenced)
+        .fetch_one(db_pool)
+        .await?;
+    if !references {
+        return Ok(());
+    }
+
+    // Foreign keys are switched per connection and only outside a transaction
+    let mut connection = db_pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *connection).await?;
+    let result = rebuild_table(&mut connection, table, columns).await;
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *connection).await?;
+    result
+}
+
+async fn rebuild_table(connection: &mut SqliteConnection, table: &str, columns: &str) -> Result<(), sqlx::Error> {
+    let copied: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info($1)")
+        .bind(table)
+        .fetch_all(&mut *connection)
+        .await?;
+
+    let mut transaction = connection.begin().await?;
+    sqlx::query(format!(
+        "CREATE TABLE {table}_rebuilt ({columns});
+        INSERT INTO {table}_rebuilt ({copied}) SELECT {copied} FROM {table};
+        DROP TABLE {table};
+        ALTER TABLE {table}_rebuilt RENAME TO {table};",
+        table = table,
+        columns = columns,
+        copied = copied.join(", ")
+    ).as_str())
+        .execute(&mut *transaction)
+        .await?;
+    transaction.commit().await
+}
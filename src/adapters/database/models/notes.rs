@@ -1,11 +1,11 @@
 use chrono::{DateTime, Utc};
-use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::models::{add_column_if_not_exists, CreateIFNotExists};
 use crate::adapters::database::pool::DbPool;
 use crate::domain::models::note::{
-    NOTE_ID_SIZE, 
-    NOTE_TITLE_LENGTH, 
-    NOTE_DESCRIPTION_LENGTH, 
-    NOTE_BODY_LENGTH, 
+    NOTE_ID_SIZE,
+    NOTE_TITLE_MAX,
+    NOTE_DESCRIPTION_MAX,
+    NOTE_BODY_MAX,
     NoteId
 };
 
@@ -14,29 +14,61 @@ pub const NOTE_TABLE: &str = "notes";
 #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
 pub struct Note {
     pub id: NoteId,
+    pub slug: String,
     pub title: String,
     pub description: String,
     pub body: String,
     pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub version: i64
+}
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct NoteListItem {
+    pub id: NoteId,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>
 }
 
+/// Row of the trash listing
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct DeletedNote {
+    pub id: NoteId,
+    pub title: String,
+    pub deleted_at: DateTime<Utc>
+}
+
 impl CreateIFNotExists for Note {
     async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
         sqlx::query(format!(
             "CREATE TABLE IF NOT EXISTS {table} (
                 id CHAR({id_size}) PRIMARY KEY,
+                slug VARCHAR({title_max}) NOT NULL,
                 title VARCHAR({title_max}) NOT NULL,
                 description VARCHAR({description_max}) NOT NULL,
                 body VARCHAR({body_max}) NOT NULL,
                 created_at TIMESTAMP WITH TIME ZONE NOT NULL,
-                updated_at TIMESTAMP WITH TIME ZONE
-            );",
+                updated_at TIMESTAMP WITH TIME ZONE,
+                version INTEGER NOT NULL DEFAULT 1,
+                deleted_at TIMESTAMP WITH TIME ZONE
+            );
+            CREATE INDEX IF NOT EXISTS {table}_created_at_id_idx ON {table} (created_at, id);",
             table = NOTE_TABLE,
             id_size = NOTE_ID_SIZE,
-            title_max = NOTE_TITLE_LENGTH.1,
-            description_max = NOTE_DESCRIPTION_LENGTH.1,
-            body_max = NOTE_BODY_LENGTH.1
+            title_max = NOTE_TITLE_MAX,
+            description_max = NOTE_DESCRIPTION_MAX,
+            body_max = NOTE_BODY_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        add_column_if_not_exists(db_pool, NOTE_TABLE, "version", "INTEGER NOT NULL DEFAULT 1").await?;
+        add_column_if_not_exists(db_pool, NOTE_TABLE, "deleted_at", "TIMESTAMP WITH TIME ZONE").await?;
+        sqlx::query(format!(
+            "CREATE INDEX IF NOT EXISTS {table}_deleted_at_idx ON {table} (deleted_at)",
+            table = NOTE_TABLE
         ).as_str())
             .execute(db_pool)
             .await?;
@@ -0,0 +1,70 @@
+use chrono::NaiveDate;
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::page_view::{PAGE_VIEW_PATH_MAX, PAGE_VIEW_REFERRER_MAX, VISITOR_HASH_SIZE};
+
+pub const PAGE_VIEW_TABLE: &str = "page_views";
+pub const DAILY_STAT_TABLE: &str = "daily_stats";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct PageView {
+    pub day: NaiveDate,
+    pub path: String,
+    pub referrer: Option<String>,
+    pub user_agent: String,
+    pub visitor: String
+}
+
+impl CreateIFNotExists for PageView {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                day DATE NOT NULL,
+                path VARCHAR({path_max}) NOT NULL,
+                referrer VARCHAR({referrer_max}),
+                user_agent VARCHAR(16) NOT NULL,
+                visitor CHAR({visitor_size}) NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS {table}_day_idx ON {table} (day);",
+            table = PAGE_VIEW_TABLE,
+            path_max = PAGE_VIEW_PATH_MAX,
+            referrer_max = PAGE_VIEW_REFERRER_MAX,
+            visitor_size = VISITOR_HASH_SIZE
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct DailyStat {
+    pub day: NaiveDate,
+    pub path: String,
+    pub referrer: Option<String>,
+    pub user_agent: String,
+    pub views: i64,
+    pub visitors: i64
+}
+
+impl CreateIFNotExists for DailyStat {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                day DATE NOT NULL,
+                path VARCHAR({path_max}) NOT NULL,
+                referrer VARCHAR({referrer_max}),
+                user_agent VARCHAR(16) NOT NULL,
+                views INTEGER NOT NULL,
+                visitors INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS {table}_day_idx ON {table} (day);",
+            table = DAILY_STAT_TABLE,
+            path_max = PAGE_VIEW_PATH_MAX,
+            referrer_max = PAGE_VIEW_REFERRER_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
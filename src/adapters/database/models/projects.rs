@@ -1,11 +1,11 @@
 use chrono::{DateTime, Utc};
-use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::models::{add_column_if_not_exists, CreateIFNotExists};
 use crate::adapters::database::pool::DbPool;
 use crate::domain::models::project::{
-    PROJECT_ID_SIZE, 
-    PROJECT_TITLE_LENGTH, 
-    PROJECT_DESCRIPTION_LENGTH, 
-    PROJECT_URL_LENGTH_MAX, 
+    PROJECT_ID_SIZE,
+    PROJECT_TITLE_MAX,
+    PROJECT_DESCRIPTION_MAX,
+    PROJECT_URL_MAX,
     ProjectId
 };
 
@@ -18,6 +18,15 @@ pub struct Project {
     pub description: String,
     pub url: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub version: i64
+}
+
+/// Row of the trash listing
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct DeletedProject {
+    pub id: ProjectId,
+    pub title: String,
+    pub deleted_at: DateTime<Utc>
 }
 
 impl CreateIFNotExists for Project {
@@ -27,14 +36,25 @@ impl CreateIFNotExists for Project {
                 id CHAR({id_size}) PRIMARY KEY,
                 title VARCHAR({title_max}) NOT NULL,
                 description VARCHAR({description_max}) NOT NULL,
-                url VARCHAR({url_max}) NOT NULL,
-                created_at TIMESTAMP WITH TIME ZONE NOT NULL
-            );",
+                url VARCHAR({url_max}),
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                version INTEGER NOT NULL DEFAULT 1,
+                deleted_at TIMESTAMP WITH TIME ZONE
+            );
+            CREATE INDEX IF NOT EXISTS {table}_created_at_id_idx ON {table} (created_at, id);",
             table = PROJECT_TABLE,
             id_size = PROJECT_ID_SIZE,
-            title_max = PROJECT_TITLE_LENGTH.1,
-            description_max = PROJECT_DESCRIPTION_LENGTH.1,
-            url_max = PROJECT_URL_LENGTH_MAX
+            title_max = PROJECT_TITLE_MAX,
+            description_max = PROJECT_DESCRIPTION_MAX,
+            url_max = PROJECT_URL_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        add_column_if_not_exists(db_pool, PROJECT_TABLE, "version", "INTEGER NOT NULL DEFAULT 1").await?;
+        add_column_if_not_exists(db_pool, PROJECT_TABLE, "deleted_at", "TIMESTAMP WITH TIME ZONE").await?;
+        sqlx::query(format!(
+            "CREATE INDEX IF NOT EXISTS {table}_deleted_at_idx ON {table} (deleted_at)",
+            table = PROJECT_TABLE
         ).as_str())
             .execute(db_pool)
             .await?;
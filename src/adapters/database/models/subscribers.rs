@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::subscriber::{SUBSCRIBER_ID_SIZE, SUBSCRIBER_EMAIL_MAX, SubscriberId};
+
+pub const SUBSCRIBER_TABLE: &str = "subscribers";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Subscriber {
+    pub id: SubscriberId,
+    pub email: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub digest_sent_at: Option<DateTime<Utc>>
+}
+
+impl CreateIFNotExists for Subscriber {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                email VARCHAR({email_max}) NOT NULL UNIQUE,
+                status VARCHAR(16) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                confirmed_at TIMESTAMP WITH TIME ZONE,
+                digest_sent_at TIMESTAMP WITH TIME ZONE
+            );
+            CREATE INDEX IF NOT EXISTS {table}_status_idx ON {table} (status);",
+            table = SUBSCRIBER_TABLE,
+            id_size = SUBSCRIBER_ID_SIZE,
+            email_max = SUBSCRIBER_EMAIL_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::user::{USERNAME_MAX, USER_ID_SIZE, UserId};
+
+pub const USER_TABLE: &str = "users";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct User {
+    pub id: UserId,
+    pub username: String,
+    pub password_hash: Vec<u8>
+}
+
+impl CreateIFNotExists for User {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                username VARCHAR({username_max}) NOT NULL UNIQUE,
+                password_hash BLOB NOT NULL CHECK (length(password_hash) = {hash_size})
+            );",
+            table = USER_TABLE,
+            id_size = USER_ID_SIZE,
+            username_max = USERNAME_MAX,
+            hash_size = Hash::SIZE
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
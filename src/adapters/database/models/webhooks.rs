@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::CreateIFNotExists;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::webhook::{
+    WebhookDeliveryId,
+    WebhookId,
+    WEBHOOK_DELIVERY_ID_SIZE,
+    WEBHOOK_ID_SIZE,
+    WEBHOOK_SECRET_MAX,
+    WEBHOOK_URL_MAX
+};
+
+pub const WEBHOOK_TABLE: &str = "webhooks";
+pub const WEBHOOK_DELIVERY_TABLE: &str = "webhook_deliveries";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub url: String,
+    /// Comma separated event kinds
+    pub events: String,
+    pub secret: String,
+    pub created_at: DateTime<Utc>
+}
+
+impl CreateIFNotExists for Webhook {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                url VARCHAR({url_max}) NOT NULL,
+                events TEXT NOT NULL,
+                secret VARCHAR({secret_max}) NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL
+            );",
+            table = WEBHOOK_TABLE,
+            id_size = WEBHOOK_ID_SIZE,
+            url_max = WEBHOOK_URL_MAX,
+            secret_max = WEBHOOK_SECRET_MAX
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub webhook_id: WebhookId,
+    pub event: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i64,
+    pub response_code: Option<i64>,
+    pub error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>
+}
+
+impl CreateIFNotExists for WebhookDelivery {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                id CHAR({id_size}) PRIMARY KEY,
+                webhook_id CHAR({webhook_id_size}) NOT NULL,
+                event VARCHAR(32) NOT NULL,
+                payload TEXT NOT NULL,
+                status VARCHAR(16) NOT NULL,
+                attempts INTEGER NOT NULL,
+                response_code INTEGER,
+                error TEXT,
+                next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+                updated_at TIMESTAMP WITH TIME ZONE
+            );
+            CREATE INDEX IF NOT EXISTS {table}_status_next_attempt_at_idx ON {table} (status, next_attempt_at);
+            CREATE INDEX IF NOT EXISTS {table}_webhook_id_created_at_idx ON {table} (webhook_id, created_at);",
+            table = WEBHOOK_DELIVERY_TABLE,
+            id_size = WEBHOOK_DELIVERY_ID_SIZE,
+            webhook_id_size = WEBHOOK_ID_SIZE
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
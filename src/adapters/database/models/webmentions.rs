@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use crate::adapters::database::models::{drop_reference_if_exists, CreateIFNotExists};
+use crate::adapters::database::models::notes::NOTE_TABLE;
+use crate::adapters::database::pool::DbPool;
+use crate::domain::models::note::{NOTE_ID_SIZE, NoteId};
+use crate::domain::models::webmention::{
+    WEBMENTION_ID_SIZE,
+    WEBMENTION_URL_MAX,
+    WEBMENTION_AUTHOR_NAME_MAX,
+    WebmentionId
+};
+
+pub const WEBMENTION_TABLE: &str = "webmentions";
+
+#[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+pub struct Webmention {
+    pub id: WebmentionId,
+    pub source: String,
+    pub target: String,
+    pub note_id: NoteId,
+    pub status: String,
+    pub author_name: Option<String>,
+    pub author_url: Option<String>,
+    pub author_photo: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>
+}
+
+/// Notes may be kept in Postgres, so `note_id` references nothing
+/// and the mentions of a purged note are removed by the application
+fn columns() -> String {
+    format!(
+        "id CHAR({id_size}) PRIMARY KEY,
+        source VARCHAR({url_max}) NOT NULL,
+        target VARCHAR({url_max}) NOT NULL,
+        note_id CHAR({note_id_size}) NOT NULL,
+        status VARCHAR(16) NOT NULL,
+        author_name VARCHAR({author_name_max}),
+        author_url VARCHAR({url_max}),
+        author_photo VARCHAR({url_max}),
+        created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+        verified_at TIMESTAMP WITH TIME ZONE,
+        UNIQUE (source, target)",
+        id_size = WEBMENTION_ID_SIZE,
+        note_id_size = NOTE_ID_SIZE,
+        url_max = WEBMENTION_URL_MAX,
+        author_name_max = WEBMENTION_AUTHOR_NAME_MAX
+    )
+}
+
+impl CreateIFNotExists for Webmention {
+    async fn create_if_not_exists(db_pool: &DbPool) -> Result<(), sqlx::Error> {
+        drop_reference_if_exists(db_pool, WEBMENTION_TABLE, NOTE_TABLE, &columns()).await?;
+        sqlx::query(format!(
+            "CREATE TABLE IF NOT EXISTS {table} ({columns});
+            CREATE INDEX IF NOT EXISTS {table}_note_id_status_idx ON {table} (note_id, status);",
+            table = WEBMENTION_TABLE,
+            columns = columns()
+        ).as_str())
+            .execute(db_pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::page_view_gateway::{
+    DailyStatGateway as DailyStatGatewayTrait,
+    DailyStatReader,
+    DailyStatWriter,
+    PageViewGateway as PageViewGatewayTrait,
+    PageViewReader,
+    PageViewRemover,
+    PageViewWriter
+};
+use crate::domain::models::page_view::{
+    DailyStat as DailyStatDomain,
+    PageView as PageViewDomain,
+    UserAgentClass
+};
+use crate::adapters::database::models::page_views::{DailyStat, PageView, DAILY_STAT_TABLE, PAGE_VIEW_TABLE};
+
+
+pub struct PageViewGateway{
+    db: DbPools,
+}
+
+impl PageViewGateway {
+    pub fn new(db: DbPools) -> Self {
+        PageViewGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl PageViewReader for PageViewGateway {
+    async fn days(&self) -> Vec<NaiveDate> {
+        sqlx::query_scalar(
+            format!("SELECT DISTINCT day FROM {} ORDER BY day", PAGE_VIEW_TABLE).as_str()
+        )
+            .fetch_all(&self.db.reader).await.unwrap()
+    }
+
+    async fn list_by_day(&self, day: NaiveDate) -> Vec<PageViewDomain> {
+        let rows: Vec<PageView> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE day = $1", PAGE_VIEW_TABLE).as_str()
+        )
+            .bind(day)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_page_view_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl PageViewWriter for PageViewGateway {
+    async fn save(&self, view: &PageViewDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (day, path, referrer, user_agent, visitor) VALUES ($1, $2, $3, $4, $5)",
+            PAGE_VIEW_TABLE
+        ).as_str())
+            .bind(view.day)
+            .bind(&view.path)
+            .bind(&view.referrer)
+            .bind(view.user_agent.as_str())
+            .bind(&view.visitor)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl PageViewRemover for PageViewGateway {
+    async fn remove_before(&self, day: NaiveDate) {
+        sqlx::query(format!("DELETE FROM {} WHERE day < $1", PAGE_VIEW_TABLE).as_str())
+            .bind(day)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_page_view_model_to_domain(view: PageView) -> PageViewDomain {
+    PageViewDomain {
+        day: view.day,
+        path: view.path,
+        referrer: view.referrer,
+        // Only known classes are ever written to the table
+        user_agent: UserAgentClass::parse(&view.user_agent).unwrap(),
+        visitor: view.visitor
+    }
+}
+
+impl PageViewGatewayTrait for PageViewGateway {}
+
+
+pub struct DailyStatGateway{
+    db: DbPools,
+}
+
+impl DailyStatGateway {
+    pub fn new(db: DbPools) -> Self {
+        DailyStatGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl DailyStatReader for DailyStatGateway {
+    async fn range(&self, from: NaiveDate, to: NaiveDate) -> Vec<DailyStatDomain> {
+        let rows: Vec<DailyStat> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE day >= $1 AND day <= $2", DAILY_STAT_TABLE).as_str()
+        )
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_daily_stat_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl DailyStatWriter for DailyStatGateway {
+    async fn replace_day(&self, day: NaiveDate, stats: &[DailyStatDomain]) {
+        let mut tx = self.db.writer.begin().await.unwrap();
+
+        sqlx::query(format!("DELETE FROM {} WHERE day = $1", DAILY_STAT_TABLE).as_str())
+            .bind(day)
+            .execute(&mut *tx).await.unwrap();
+
+        for stat in stats {
+            sqlx::query(format!(
+                "INSERT INTO {} (day, path, referrer, user_agent, views, visitors) \
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                DAILY_STAT_TABLE
+            ).as_str())
+                .bind(stat.day)
+                .bind(&stat.path)
+                .bind(&stat.referrer)
+                .bind(stat.user_agent.as_str())
+                .bind(stat.views as i64)
+                .bind(stat.visitors as i64)
+                .execute(&mut *tx).await.unwrap();
+        }
+
+        tx.commit().await.unwrap();
+    }
+}
+
+fn map_daily_stat_model_to_domain(stat: DailyStat) -> DailyStatDomain {
+    DailyStatDomain {
+        day: stat.day,
+        path: stat.path,
+        referrer: stat.referrer,
+        // Only known classes are ever written to the table
+        user_agent: UserAgentClass::parse(&stat.user_agent).unwrap(),
+        views: stat.views as u64,
+        visitors: stat.visitors as u64
+    }
+}
+
+impl DailyStatGatewayTrait for DailyStatGateway {}
@@ -1,3 +1,78 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use sqlx::{Pool, Sqlite};
 
-pub type DbPool = Pool<Sqlite>;
\ No newline at end of file
+pub type DbPool = Pool<Sqlite>;
+
+/// SQLite allows a single writer at a time, so writes go through their own
+/// small pool and never hold up the readers. With WAL readers see every
+/// committed write and are not blocked by the writer
+#[derive(Clone)]
+pub struct DbPools {
+    pub writer: DbPool,
+    pub reader: DbPool,
+}
+
+pub struct DbOptions<'a> {
+    pub url: &'a str,
+    pub writers: u32,
+    pub readers: u32,
+    pub busy_timeout: Duration,
+}
+
+pub async fn connect(options: &DbOptions<'_>) -> Result<DbPools, sqlx::Error> {
+    let connect_options = SqliteConnectOptions::from_str(options.url)?
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .synchronous(SqliteSynchronous::Normal)
+        .busy_timeout(options.busy_timeout)
+        .foreign_keys(true);
+
+    // The writer goes first: it creates the file and switches it to WAL
+    let writer = SqlitePoolOptions::new()
+        .max_connections(options.writers)
+        .connect_with(connect_options.clone())
+        .await?;
+    let reader = SqlitePoolOptions::new()
+        .max_connections(options.readers)
+        .connect_with(connect_options.read_only(true))
+        .await?;
+
+    Ok(DbPools { writer, reader })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::id_generator::generate_id;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connect() {
+        let path = std::env::temp_dir().join(format!("database-{}", generate_id(8)));
+        let url = format!("sqlite://{}", path.display());
+        let pools = connect(&DbOptions {
+            url: &url,
+            writers: 1,
+            readers: 2,
+            busy_timeout: Duration::from_secs(1),
+        }).await.unwrap();
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&pools.reader).await.unwrap();
+        assert_eq!(journal_mode, "wal");
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&pools.writer).await.unwrap();
+        assert_eq!(foreign_keys, 1);
+
+        sqlx::query("CREATE TABLE t (id INTEGER)").execute(&pools.writer).await.unwrap();
+        assert!(sqlx::query("INSERT INTO t VALUES (1)").execute(&pools.reader).await.is_err());
+
+        pools.writer.close().await;
+        pools.reader.close().await;
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+}
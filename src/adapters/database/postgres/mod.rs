@@ -0,0 +1,36 @@
+//! Postgres implementations of the gateways that have been ported so far.
+//! Unlike SQLite the schema is versioned, see `migrations/postgres`
+
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::Postgres;
+
+use crate::adapters::database::session::{DbConnection, SharedTransaction};
+
+pub mod note_db;
+pub mod project_db;
+pub mod user_db;
+pub mod unit_of_work;
+
+pub async fn connect(url: &str) -> Result<PgPool, sqlx::Error> {
+    PgPoolOptions::new().connect(url).await
+}
+
+pub async fn migrate(db: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("migrations/postgres").run(db).await
+}
+
+/// Postgres counterpart of [`DbSession`](crate::adapters::database::session::DbSession)
+#[derive(Clone)]
+pub enum PgSession {
+    Pool(PgPool),
+    Transaction(SharedTransaction<Postgres>),
+}
+
+impl PgSession {
+    pub async fn connection(&self) -> DbConnection<'_, Postgres> {
+        match self {
+            PgSession::Pool(db) => DbConnection::Pooled(db.acquire().await.unwrap()),
+            PgSession::Transaction(transaction) => DbConnection::Transaction(transaction.lock().await)
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+use crate::adapters::database::postgres::PgSession;
+use crate::adapters::database::note_db::{
+    map_deleted_note_model_to_domain,
+    map_note_list_item_model_to_domain,
+    map_note_model_to_domain
+};
+use crate::application::common::note_gateway::{
+    NoteGateway as NoteGatewayTrait,
+    NoteReader,
+    NoteRemover,
+    NoteWriter
+};
+use crate::application::common::pagination::{Cursor, Direction};
+use crate::domain::models::note::{Note as NoteDomain, NoteId, NoteListItem as NoteListItemDomain};
+use crate::domain::models::trash::TrashItem;
+use crate::adapters::database::models::notes::{DeletedNote, Note, NoteListItem, NOTE_TABLE};
+
+
+/// Same queries as the SQLite gateway, Postgres has no separate writer
+pub struct NoteGateway{
+    db: PgSession,
+}
+
+impl NoteGateway {
+    pub fn new(db: PgPool) -> Self {
+        NoteGateway {
+            db: PgSession::Pool(db),
+        }
+    }
+
+    pub fn with_session(db: PgSession) -> Self {
+        NoteGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl NoteReader for NoteGateway {
+    async fn get_by_id(&self, note_id: &NoteId) -> Option<NoteDomain> {
+        let row: Option<Note> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE id = $1 AND deleted_at IS NULL", NOTE_TABLE).as_str()
+        )
+            .bind(note_id)
+            .fetch_optional(&mut *self.db.connection().await).await.unwrap();
+
+        row.map(map_note_model_to_domain)
+    }
+
+    async fn get_by_slug(&self, slug: &str) -> Option<NoteDomain> {
+        let row: Option<Note> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE slug = $1 AND deleted_at IS NULL", NOTE_TABLE).as_str()
+        )
+            .bind(slug)
+            .fetch_optional(&mut *self.db.connection().await).await.unwrap();
+
+        row.map(map_note_model_to_domain)
+    }
+
+    async fn range(&self, limit: &u64, offset: &u64) -> Vec<NoteListItemDomain> {
+        let rows: Vec<NoteListItem> = sqlx::query_as(format!(
+            "SELECT id, slug, title, description, created_at, updated_at FROM {} \
+             WHERE deleted_at IS NULL ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+            NOTE_TABLE
+        ).as_str())
+            .bind(*limit as i64)
+            .bind(*offset as i64)
+            .fetch_all(&mut *self.db.connection().await).await.unwrap();
+
+        rows.into_iter().map(map_note_list_item_model_to_domain).collect()
+    }
+
+    async fn range_by_cursor(&self, cursor: Option<&Cursor>, limit: &u64) -> Vec<NoteListItemDomain> {
+        let rows: Vec<NoteListItem> = match cursor {
+            None => sqlx::query_as(format!(
+                "SELECT id, slug, title, description, created_at, updated_at FROM {} \
+                 WHERE deleted_at IS NULL ORDER BY created_at DESC, id DESC LIMIT $1",
+                NOTE_TABLE
+            ).as_str())
+                .bind(*limit as i64)
+                .fetch_all(&mut *self.db.connection().await).await.unwrap(),
+            Some(cursor) => {
+                let (comparison, order) = match cursor.direction {
+                    Direction::After => ("<", "DESC"),
+                    Direction::Before => (">", "ASC"),
+                };
+                sqlx::query_as(format!(
+                    "SELECT id, slug, title, description, created_at, updated_at FROM {table} \
+                     WHERE deleted_at IS NULL AND (created_at, id) {comparison} ($1, $2) \
+                     ORDER BY created_at {order}, id {order} LIMIT $3",
+                    table = NOTE_TABLE,
+                ).as_str())
+                    .bind(cursor.created_at)
+                    .bind(&cursor.id)
+                    .bind(*limit as i64)
+                    .fetch_all(&mut *self.db.connection().await).await.unwrap()
+            }
+        };
+
+        rows.into_iter().map(map_note_list_item_model_to_domain).collect()
+    }
+
+    async fn count(&self) -> u64 {
+        let count: i64 = sqlx::query_scalar(
+            format!("SELECT COUNT(*) FROM {} WHERE deleted_at IS NULL", NOTE_TABLE).as_str()
+        )
+            .fetch_one(&mut *self.db.connection().await).await.unwrap();
+        count as u64
+    }
+
+    async fn get_deleted(&self) -> Vec<TrashItem> {
+        let rows: Vec<DeletedNote> = sqlx::query_as(format!(
+            "SELECT id, title, deleted_at FROM {} WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+            NOTE_TABLE
+        ).as_str())
+            .fetch_all(&mut *self.db.connection().await).await.unwrap();
+
+        rows.into_iter().map(map_deleted_note_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl NoteWriter for NoteGateway {
+    async fn save(&self, note: &NoteDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, slug, title, description, body, created_at, updated_at, version) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (id) DO UPDATE SET \
+             slug = $2, title = $3, description = $4, body = $5, created_at = $6, updated_at = $7, version = $8",
+            NOTE_TABLE
+        ).as_str())
+            .bind(&note.id)
+            .bind(&note.slug)
+            .bind(&note.title)
+            .bind(&note.description)
+            .bind(&note.body)
+            .bind(note.created_at)
+            .bind(note.updated_at)
+            .bind(note.version)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+    }
+
+    async fn update(&self, note: &NoteDomain, expected_version: i64) -> bool {
+        let result = sqlx::query(format!(
+            "UPDATE {} SET slug = $2, title = $3, description = $4, body = $5, updated_at = $6, version = $7, \
+             created_at = $9 WHERE id = $1 AND version = $8 AND deleted_at IS NULL",
+            NOTE_TABLE
+        ).as_str())
+            .bind(&note.id)
+            .bind(&note.slug)
+            .bind(&note.title)
+            .bind(&note.description)
+            .bind(&note.body)
+            .bind(note.updated_at)
+            .bind(note.version)
+            .bind(expected_version)
+            .bind(note.created_at)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+}
+
+#[async_trait]
+impl NoteRemover for NoteGateway {
+    async fn remove(&self, note_id: &NoteId) {
+        sqlx::query(format!(
+            "UPDATE {} SET deleted_at = $2 WHERE id = $1 AND deleted_at IS NULL",
+            NOTE_TABLE
+        ).as_str())
+            .bind(note_id)
+            .bind(Utc::now())
+            .execute(&mut *self.db.connection().await).await.unwrap();
+    }
+
+    async fn restore(&self, note_id: &NoteId) -> bool {
+        let result = sqlx::query(format!(
+            "UPDATE {} SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            NOTE_TABLE
+        ).as_str())
+            .bind(note_id)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+
+    async fn purge(&self, note_id: &NoteId) -> bool {
+        let result = sqlx::query(format!(
+            "DELETE FROM {} WHERE id = $1 AND deleted_at IS NOT NULL",
+            NOTE_TABLE
+        ).as_str())
+            .bind(note_id)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+
+    async fn purge_deleted_before(&self, before: &DateTime<Utc>) -> Vec<NoteId> {
+        sqlx::query_scalar(format!("DELETE FROM {} WHERE deleted_at < $1 RETURNING id", NOTE_TABLE).as_str())
+            .bind(before)
+            .fetch_all(&mut *self.db.connection().await).await.unwrap()
+    }
+}
+
+impl NoteGatewayTrait for NoteGateway {}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_note_gateway() {
+        let db = gateway_tests::postgres().await;
+        gateway_tests::note_gateway(&NoteGateway::new(db)).await;
+    }
+}
@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+use crate::adapters::database::postgres::PgSession;
+use crate::adapters::database::project_db::{map_deleted_project_model_to_domain, map_project_model_to_domain};
+use crate::application::common::project_gateway::{
+    ProjectGateway as ProjectGatewayTrait,
+    ProjectReader,
+    ProjectRemover,
+    ProjectWriter
+};
+use crate::application::common::pagination::{Cursor, Direction};
+use crate::domain::models::project::{Project as ProjectDomain, ProjectId};
+use crate::domain::models::trash::TrashItem;
+use crate::adapters::database::models::projects::{DeletedProject, Project, PROJECT_TABLE};
+
+
+pub struct ProjectGateway{
+    db: PgSession,
+}
+
+impl ProjectGateway {
+    pub fn new(db: PgPool) -> Self {
+        ProjectGateway {
+            db: PgSession::Pool(db),
+        }
+    }
+
+    pub fn with_session(db: PgSession) -> Self {
+        ProjectGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl ProjectReader for ProjectGateway {
+    async fn get_project(&self, project_id: &ProjectId) -> Option<ProjectDomain> {
+        let row: Option<Project> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE id = $1 AND deleted_at IS NULL", PROJECT_TABLE).as_str()
+        )
+            .bind(project_id)
+            .fetch_optional(&mut *self.db.connection().await).await.unwrap();
+
+        row.map(map_project_model_to_domain)
+    }
+
+    async fn get_projects_range(&self, limit: &u64, offset: &u64) -> Vec<ProjectDomain> {
+        let rows: Vec<Project> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE deleted_at IS NULL ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+            PROJECT_TABLE
+        ).as_str())
+            .bind(*limit as i64)
+            .bind(*offset as i64)
+            .fetch_all(&mut *self.db.connection().await).await.unwrap();
+
+        rows.into_iter().map(map_project_model_to_domain).collect()
+    }
+
+    async fn get_projects_by_cursor(&self, cursor: Option<&Cursor>, limit: &u64) -> Vec<ProjectDomain> {
+        let rows: Vec<Project> = match cursor {
+            None => sqlx::query_as(format!(
+                "SELECT * FROM {} WHERE deleted_at IS NULL ORDER BY created_at DESC, id DESC LIMIT $1",
+                PROJECT_TABLE
+            ).as_str())
+                .bind(*limit as i64)
+                .fetch_all(&mut *self.db.connection().await).await.unwrap(),
+            Some(cursor) => {
+                let (comparison, order) = match cursor.direction {
+                    Direction::After => ("<", "DESC"),
+                    Direction::Before => (">", "ASC"),
+                };
+                sqlx::query_as(format!(
+                    "SELECT * FROM {table} WHERE deleted_at IS NULL AND (created_at, id) {comparison} ($1, $2) \
+                     ORDER BY created_at {order}, id {order} LIMIT $3",
+                    table = PROJECT_TABLE,
+                ).as_str())
+                    .bind(cursor.created_at)
+                    .bind(&cursor.id)
+                    .bind(*limit as i64)
+                    .fetch_all(&mut *self.db.connection().await).await.unwrap()
+            }
+        };
+
+        rows.into_iter().map(map_project_model_to_domain).collect()
+    }
+
+    async fn get_deleted_projects(&self) -> Vec<TrashItem> {
+        let rows: Vec<DeletedProject> = sqlx::query_as(format!(
+            "SELECT id, title, deleted_at FROM {} WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+            PROJECT_TABLE
+        ).as_str())
+            .fetch_all(&mut *self.db.connection().await).await.unwrap();
+
+        rows.into_iter().map(map_deleted_project_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl ProjectWriter for ProjectGateway {
+    async fn save_project(&self, project: &ProjectDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, title, description, url, created_at, version) \
+             VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (id) DO UPDATE SET \
+             title = $2, description = $3, url = $4, created_at = $5, version = $6",
+            PROJECT_TABLE
+        ).as_str())
+            .bind(&project.id)
+            .bind(&project.title)
+            .bind(&project.description)
+            .bind(&project.url)
+            .bind(project.created_at)
+            .bind(project.version)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+    }
+
+    async fn update_project(&self, project: &ProjectDomain, expected_version: i64) -> bool {
+        let result = sqlx::query(format!(
+            "UPDATE {} SET title = $2, description = $3, url = $4, version = $5 \
+             WHERE id = $1 AND version = $6 AND deleted_at IS NULL",
+            PROJECT_TABLE
+        ).as_str())
+            .bind(&project.id)
+            .bind(&project.title)
+            .bind(&project.description)
+            .bind(&project.url)
+            .bind(project.version)
+            .bind(expected_version)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+}
+
+#[async_trait]
+impl ProjectRemover for ProjectGateway {
+    async fn remove_project(&self, project_id: &ProjectId) {
+        sqlx::query(format!(
+            "UPDATE {} SET deleted_at = $2 WHERE id = $1 AND deleted_at IS NULL",
+            PROJECT_TABLE
+        ).as_str())
+            .bind(project_id)
+            .bind(Utc::now())
+            .execute(&mut *self.db.connection().await).await.unwrap();
+    }
+
+    async fn restore_project(&self, project_id: &ProjectId) -> bool {
+        let result = sqlx::query(format!(
+            "UPDATE {} SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL",
+            PROJECT_TABLE
+        ).as_str())
+            .bind(project_id)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+
+    async fn purge_project(&self, project_id: &ProjectId) -> bool {
+        let result = sqlx::query(format!(
+            "DELETE FROM {} WHERE id = $1 AND deleted_at IS NOT NULL",
+            PROJECT_TABLE
+        ).as_str())
+            .bind(project_id)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+
+    async fn purge_deleted_projects_before(&self, before: &DateTime<Utc>) -> u64 {
+        let result = sqlx::query(format!("DELETE FROM {} WHERE deleted_at < $1", PROJECT_TABLE).as_str())
+            .bind(before)
+            .execute(&mut *self.db.connection().await).await.unwrap();
+        result.rows_affected()
+    }
+}
+
+impl ProjectGatewayTrait for ProjectGateway {}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_project_gateway() {
+        let db = gateway_tests::postgres().await;
+        gateway_tests::project_gateway(&ProjectGateway::new(db)).await;
+    }
+}
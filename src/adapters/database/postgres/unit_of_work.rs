@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Sqlite};
+
+use crate::adapters::database::audit_db::AuditGateway;
+use crate::adapters::database::pool::DbPools;
+use crate::adapters::database::postgres::note_db::NoteGateway;
+use crate::adapters::database::postgres::project_db::ProjectGateway;
+use crate::adapters::database::postgres::user_db::UserGateway;
+use crate::adapters::database::postgres::PgSession;
+use crate::adapters::database::session::{share, DbSession, SharedTransaction};
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::note_gateway::NoteGateway as NoteGatewayTrait;
+use crate::application::common::project_gateway::ProjectGateway as ProjectGatewayTrait;
+use crate::application::common::unit_of_work::{Unit, UnitOfWork};
+use crate::application::common::user_gateway::UserGateway as UserGatewayTrait;
+
+
+/// The audit log stays in SQLite, a unit has a transaction in each database.
+/// The SQLite one is committed right after the Postgres one
+pub struct PgUnitOfWork {
+    db: PgPool,
+    sqlite: DbPools,
+}
+
+impl PgUnitOfWork {
+    pub fn new(db: PgPool, sqlite: DbPools) -> Self {
+        PgUnitOfWork {
+            db,
+            sqlite,
+        }
+    }
+}
+
+pub struct PgUnit {
+    transaction: SharedTransaction<Postgres>,
+    audit_transaction: SharedTransaction<Sqlite>,
+    note_gateway: NoteGateway,
+    project_gateway: ProjectGateway,
+    user_gateway: UserGateway,
+    audit_gateway: AuditGateway,
+}
+
+#[async_trait]
+impl UnitOfWork for PgUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn Unit + '_>, String> {
+        let transaction = share(self.db.begin().await.map_err(|e| e.to_string())?);
+        let audit_transaction = share(self.sqlite.writer.begin().await.map_err(|e| e.to_string())?);
+        let session = PgSession::Transaction(transaction.clone());
+        Ok(Box::new(PgUnit {
+            transaction,
+            audit_transaction: audit_transaction.clone(),
+            note_gateway: NoteGateway::with_session(session.clone()),
+            project_gateway: ProjectGateway::with_session(session.clone()),
+            user_gateway: UserGateway::with_session(session),
+            audit_gateway: AuditGateway::with_session(DbSession::Transaction(audit_transaction)),
+        }))
+    }
+}
+
+#[async_trait]
+impl Unit for PgUnit {
+    fn note_gateway(&self) -> &dyn NoteGatewayTrait {
+        &self.note_gateway
+    }
+
+    fn project_gateway(&self) -> &dyn ProjectGatewayTrait {
+        &self.project_gateway
+    }
+
+    fn user_gateway(&self) -> &dyn UserGatewayTrait {
+        &self.user_gateway
+    }
+
+    fn audit_writer(&self) -> &dyn AuditWriter {
+        &self.audit_gateway
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), String> {
+        if let Some(transaction) = self.transaction.lock().await.take() {
+            transaction.commit().await.map_err(|e| e.to_string())?;
+        }
+        match self.audit_transaction.lock().await.take() {
+            Some(transaction) => transaction.commit().await.map_err(|e| e.to_string()),
+            None => Ok(())
+        }
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), String> {
+        if let Some(transaction) = self.audit_transaction.lock().await.take() {
+            transaction.rollback().await.map_err(|e| e.to_string())?;
+        }
+        match self.transaction.lock().await.take() {
+            Some(transaction) => transaction.rollback().await.map_err(|e| e.to_string()),
+            None => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unit_of_work() {
+        let db = gateway_tests::postgres().await;
+        let sqlite = gateway_tests::sqlite().await;
+        gateway_tests::unit_of_work(
+            &PgUnitOfWork::new(db.clone(), sqlite.clone()),
+            &NoteGateway::new(db),
+            &AuditGateway::new(sqlite)
+        ).await;
+    }
+}
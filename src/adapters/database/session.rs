@@ -0,0 +1,66 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use sqlx::pool::PoolConnection;
+use sqlx::{Database, Sqlite, Transaction};
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::adapters::database::pool::DbPools;
+
+/// Transaction of a unit of work, shared by the gateways of the unit.
+/// Empty once the unit is committed or rolled back
+pub type SharedTransaction<DB> = Arc<Mutex<Option<Transaction<'static, DB>>>>;
+
+pub fn share<DB: Database>(transaction: Transaction<'static, DB>) -> SharedTransaction<DB> {
+    Arc::new(Mutex::new(Some(transaction)))
+}
+
+/// Connection a single query runs on
+pub enum DbConnection<'a, DB: Database> {
+    Pooled(PoolConnection<DB>),
+    Transaction(MutexGuard<'a, Option<Transaction<'static, DB>>>),
+}
+
+impl<DB: Database> Deref for DbConnection<'_, DB> {
+    type Target = DB::Connection;
+
+    fn deref(&self) -> &DB::Connection {
+        match self {
+            DbConnection::Pooled(connection) => connection,
+            DbConnection::Transaction(transaction) => (**transaction).as_ref().expect("unit of work is finished")
+        }
+    }
+}
+
+impl<DB: Database> DerefMut for DbConnection<'_, DB> {
+    fn deref_mut(&mut self) -> &mut DB::Connection {
+        match self {
+            DbConnection::Pooled(connection) => connection,
+            DbConnection::Transaction(transaction) => (**transaction).as_mut().expect("unit of work is finished")
+        }
+    }
+}
+
+/// Where a gateway takes its connections from: the pools, or the
+/// transaction of the unit of work it belongs to
+#[derive(Clone)]
+pub enum DbSession {
+    Pools(DbPools),
+    Transaction(SharedTransaction<Sqlite>),
+}
+
+impl DbSession {
+    pub async fn reader(&self) -> DbConnection<'_, Sqlite> {
+        match self {
+            DbSession::Pools(db) => DbConnection::Pooled(db.reader.acquire().await.unwrap()),
+            DbSession::Transaction(transaction) => DbConnection::Transaction(transaction.lock().await)
+        }
+    }
+
+    pub async fn writer(&self) -> DbConnection<'_, Sqlite> {
+        match self {
+            DbSession::Pools(db) => DbConnection::Pooled(db.writer.acquire().await.unwrap()),
+            DbSession::Transaction(transaction) => DbConnection::Transaction(transaction.lock().await)
+        }
+    }
+}
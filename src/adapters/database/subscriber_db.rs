@@ -0,0 +1,102 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::subscriber_gateway::{
+    SubscriberGateway as SubscriberGatewayTrait,
+    SubscriberReader,
+    SubscriberRemover,
+    SubscriberWriter
+};
+use crate::domain::models::subscriber::{Subscriber as SubscriberDomain, SubscriberId, SubscriberStatus};
+use crate::adapters::database::models::subscribers::{Subscriber, SUBSCRIBER_TABLE};
+
+
+pub struct SubscriberGateway{
+    db: DbPools,
+}
+
+impl SubscriberGateway {
+    pub fn new(db: DbPools) -> Self {
+        SubscriberGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl SubscriberReader for SubscriberGateway {
+    async fn get_by_id(&self, id: &SubscriberId) -> Option<SubscriberDomain> {
+        let row: Option<Subscriber> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE id = $1", SUBSCRIBER_TABLE).as_str()
+        )
+            .bind(id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_subscriber_model_to_domain)
+    }
+
+    async fn get_by_email(&self, email: &str) -> Option<SubscriberDomain> {
+        let row: Option<Subscriber> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE email = $1", SUBSCRIBER_TABLE).as_str()
+        )
+            .bind(email)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_subscriber_model_to_domain)
+    }
+
+    async fn list_by_status(&self, status: SubscriberStatus) -> Vec<SubscriberDomain> {
+        let rows: Vec<Subscriber> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE status = $1 ORDER BY created_at, id",
+            SUBSCRIBER_TABLE
+        ).as_str())
+            .bind(status.as_str())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_subscriber_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl SubscriberWriter for SubscriberGateway {
+    async fn save(&self, subscriber: &SubscriberDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, email, status, created_at, confirmed_at, digest_sent_at) \
+             VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (id) DO UPDATE SET \
+             status = $3, confirmed_at = $5, digest_sent_at = $6",
+            SUBSCRIBER_TABLE
+        ).as_str())
+            .bind(&subscriber.id)
+            .bind(&subscriber.email)
+            .bind(subscriber.status.as_str())
+            .bind(&subscriber.created_at)
+            .bind(&subscriber.confirmed_at)
+            .bind(&subscriber.digest_sent_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl SubscriberRemover for SubscriberGateway {
+    async fn remove(&self, subscriber_id: &SubscriberId) {
+        sqlx::query(format!("DELETE FROM {} WHERE id = $1", SUBSCRIBER_TABLE).as_str())
+            .bind(subscriber_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_subscriber_model_to_domain(subscriber: Subscriber) -> SubscriberDomain {
+    SubscriberDomain {
+        id: subscriber.id,
+        email: subscriber.email,
+        // Only known statuses are ever written to the table
+        status: SubscriberStatus::parse(&subscriber.status).unwrap(),
+        created_at: subscriber.created_at,
+        confirmed_at: subscriber.confirmed_at,
+        digest_sent_at: subscriber.digest_sent_at
+    }
+}
+
+impl SubscriberGatewayTrait for SubscriberGateway {}
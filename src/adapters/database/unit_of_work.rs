@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use sqlx::Sqlite;
+
+use crate::adapters::database::audit_db::AuditGateway;
+use crate::adapters::database::note_db::NoteGateway;
+use crate::adapters::database::pool::DbPools;
+use crate::adapters::database::project_db::ProjectGateway;
+use crate::adapters::database::session::{share, DbSession, SharedTransaction};
+use crate::adapters::database::user_db::UserGateway;
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::note_gateway::NoteGateway as NoteGatewayTrait;
+use crate::application::common::project_gateway::ProjectGateway as ProjectGatewayTrait;
+use crate::application::common::unit_of_work::{Unit, UnitOfWork};
+use crate::application::common::user_gateway::UserGateway as UserGatewayTrait;
+
+
+/// A unit holds the writer connection until it is finished, keep units short
+pub struct SqliteUnitOfWork {
+    db: DbPools,
+}
+
+impl SqliteUnitOfWork {
+    pub fn new(db: DbPools) -> Self {
+        SqliteUnitOfWork {
+            db,
+        }
+    }
+}
+
+pub struct SqliteUnit {
+    transaction: SharedTransaction<Sqlite>,
+    note_gateway: NoteGateway,
+    project_gateway: ProjectGateway,
+    user_gateway: UserGateway,
+    audit_gateway: AuditGateway,
+}
+
+#[async_trait]
+impl UnitOfWork for SqliteUnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn Unit + '_>, String> {
+        let transaction = share(self.db.writer.begin().await.map_err(|e| e.to_string())?);
+        let session = DbSession::Transaction(transaction.clone());
+        Ok(Box::new(SqliteUnit {
+            transaction,
+            note_gateway: NoteGateway::with_session(session.clone()),
+            project_gateway: ProjectGateway::with_session(session.clone()),
+            user_gateway: UserGateway::with_session(session.clone()),
+            audit_gateway: AuditGateway::with_session(session),
+        }))
+    }
+}
+
+#[async_trait]
+impl Unit for SqliteUnit {
+    fn note_gateway(&self) -> &dyn NoteGatewayTrait {
+        &self.note_gateway
+    }
+
+    fn project_gateway(&self) -> &dyn ProjectGatewayTrait {
+        &self.project_gateway
+    }
+
+    fn user_gateway(&self) -> &dyn UserGatewayTrait {
+        &self.user_gateway
+    }
+
+    fn audit_writer(&self) -> &dyn AuditWriter {
+        &self.audit_gateway
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), String> {
+        match self.transaction.lock().await.take() {
+            Some(transaction) => transaction.commit().await.map_err(|e| e.to_string()),
+            None => Ok(())
+        }
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), String> {
+        match self.transaction.lock().await.take() {
+            Some(transaction) => transaction.rollback().await.map_err(|e| e.to_string()),
+            None => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unit_of_work() {
+        let db = gateway_tests::sqlite().await;
+        gateway_tests::unit_of_work(
+            &SqliteUnitOfWork::new(db.clone()),
+            &NoteGateway::new(db.clone()),
+            &AuditGateway::new(db)
+        ).await;
+    }
+}
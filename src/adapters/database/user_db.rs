@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::adapters::database::session::DbSession;
+use crate::application::common::user_gateway::{
+    UserGateway as UserGatewayTrait,
+    UserReader,
+    UserRemover,
+    UserWriter
+};
+use crate::domain::models::hash::Hash;
+use crate::domain::models::user::{User as UserDomain, UserId};
+use crate::adapters::database::models::users::{User, USER_TABLE};
+
+
+pub struct UserGateway{
+    db: DbSession,
+}
+
+impl UserGateway {
+    pub fn new(db: DbPools) -> Self {
+        UserGateway {
+            db: DbSession::Pools(db),
+        }
+    }
+
+    /// Gateway of a unit of work, see [`SqliteUnitOfWork`](crate::adapters::database::unit_of_work::SqliteUnitOfWork)
+    pub fn with_session(db: DbSession) -> Self {
+        UserGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl UserReader for UserGateway {
+    async fn get_by_username(&self, username: &str) -> Option<UserDomain> {
+        let row: Option<User> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE username = $1", USER_TABLE).as_str()
+        )
+            .bind(username)
+            .fetch_optional(&mut *self.db.reader().await).await.unwrap();
+
+        row.map(map_user_model_to_domain)
+    }
+
+    async fn get_all(&self) -> Vec<UserDomain> {
+        let rows: Vec<User> = sqlx::query_as(
+            format!("SELECT * FROM {} ORDER BY username", USER_TABLE).as_str()
+        )
+            .fetch_all(&mut *self.db.reader().await).await.unwrap();
+
+        rows.into_iter().map(map_user_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl UserWriter for UserGateway {
+    async fn save(&self, user: &UserDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, username, password_hash) VALUES ($1, $2, $3) \
+             ON CONFLICT (id) DO UPDATE SET username = $2, password_hash = $3",
+            USER_TABLE
+        ).as_str())
+            .bind(&user.id)
+            .bind(&user.username)
+            .bind(user.password_hash.0.as_slice())
+            .execute(&mut *self.db.writer().await).await.unwrap();
+    }
+
+    async fn insert(&self, user: &UserDomain) -> bool {
+        let result = sqlx::query(format!(
+            "INSERT INTO {} (id, username, password_hash) VALUES ($1, $2, $3) \
+             ON CONFLICT (username) DO NOTHING",
+            USER_TABLE
+        ).as_str())
+            .bind(&user.id)
+            .bind(&user.username)
+            .bind(user.password_hash.0.as_slice())
+            .execute(&mut *self.db.writer().await).await.unwrap();
+        result.rows_affected() == 1
+    }
+}
+
+#[async_trait]
+impl UserRemover for UserGateway {
+    async fn remove(&self, user_id: &UserId) {
+        sqlx::query(format!("DELETE FROM {} WHERE id = $1", USER_TABLE).as_str())
+            .bind(user_id)
+            .execute(&mut *self.db.writer().await).await.unwrap();
+    }
+}
+
+/// The length is checked by the table, a row that fails it is a corrupted database
+pub(crate) fn map_user_model_to_domain(user: User) -> UserDomain {
+    UserDomain {
+        id: user.id,
+        username: user.username,
+        password_hash: Hash(user.password_hash.try_into().expect("password hash of a wrong size")),
+    }
+}
+
+impl UserGatewayTrait for UserGateway {}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_user_gateway() {
+        let db = gateway_tests::sqlite().await;
+        gateway_tests::user_gateway(&UserGateway::new(db)).await;
+    }
+}
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::webhook_gateway::{
+    WebhookDeliveryReader,
+    WebhookDeliveryWriter,
+    WebhookGateway as WebhookGatewayTrait,
+    WebhookReader,
+    WebhookRemover,
+    WebhookWriter
+};
+use crate::domain::models::event::EventKind;
+use crate::domain::models::webhook::{
+    Webhook as WebhookDomain,
+    WebhookDelivery as WebhookDeliveryDomain,
+    WebhookDeliveryId,
+    WebhookDeliveryStatus,
+    WebhookId
+};
+use crate::adapters::database::models::webhooks::{Webhook, WebhookDelivery, WEBHOOK_DELIVERY_TABLE, WEBHOOK_TABLE};
+
+
+/// Kept in SQLite with either backend
+pub struct WebhookGateway{
+    db: DbPools,
+}
+
+impl WebhookGateway {
+    pub fn new(db: DbPools) -> Self {
+        WebhookGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookReader for WebhookGateway {
+    async fn list_webhooks(&self) -> Vec<WebhookDomain> {
+        let rows: Vec<Webhook> = sqlx::query_as(format!(
+            "SELECT * FROM {} ORDER BY created_at, id",
+            WEBHOOK_TABLE
+        ).as_str())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_webhook_model_to_domain).collect()
+    }
+
+    async fn get_webhook(&self, webhook_id: &WebhookId) -> Option<WebhookDomain> {
+        let row: Option<Webhook> = sqlx::query_as(format!("SELECT * FROM {} WHERE id = $1", WEBHOOK_TABLE).as_str())
+            .bind(webhook_id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_webhook_model_to_domain)
+    }
+
+    async fn list_subscribed(&self, kind: EventKind) -> Vec<WebhookDomain> {
+        // There are a handful of webhooks at most, not worth a join table
+        self.list_webhooks().await
+            .into_iter()
+            .filter(|webhook| webhook.wants(kind))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl WebhookWriter for WebhookGateway {
+    async fn save_webhook(&self, webhook: &WebhookDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, url, events, secret, created_at) VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (id) DO UPDATE SET url = $2, events = $3, secret = $4",
+            WEBHOOK_TABLE
+        ).as_str())
+            .bind(&webhook.id)
+            .bind(&webhook.url)
+            .bind(webhook.events.iter().map(EventKind::as_str).collect::<Vec<_>>().join(","))
+            .bind(&webhook.secret)
+            .bind(webhook.created_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl WebhookRemover for WebhookGateway {
+    async fn remove_webhook(&self, webhook_id: &WebhookId) -> bool {
+        let mut tx = self.db.writer.begin().await.unwrap();
+        sqlx::query(format!("DELETE FROM {} WHERE webhook_id = $1", WEBHOOK_DELIVERY_TABLE).as_str())
+            .bind(webhook_id)
+            .execute(&mut *tx).await.unwrap();
+        let removed = sqlx::query(format!("DELETE FROM {} WHERE id = $1", WEBHOOK_TABLE).as_str())
+            .bind(webhook_id)
+            .execute(&mut *tx).await.unwrap()
+            .rows_affected();
+        tx.commit().await.unwrap();
+        removed == 1
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryReader for WebhookGateway {
+    async fn list_due_deliveries(&self, now: DateTime<Utc>, limit: &u64) -> Vec<WebhookDeliveryDomain> {
+        let rows: Vec<WebhookDelivery> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE status = $1 AND next_attempt_at <= $2 ORDER BY next_attempt_at, id LIMIT $3",
+            WEBHOOK_DELIVERY_TABLE
+        ).as_str())
+            .bind(WebhookDeliveryStatus::Pending.as_str())
+            .bind(now)
+            .bind(*limit as i64)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_webhook_delivery_model_to_domain).collect()
+    }
+
+    async fn get_delivery(&self, delivery_id: &WebhookDeliveryId) -> Option<WebhookDeliveryDomain> {
+        let row: Option<WebhookDelivery> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE id = $1",
+            WEBHOOK_DELIVERY_TABLE
+        ).as_str())
+            .bind(delivery_id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_webhook_delivery_model_to_domain)
+    }
+
+    async fn list_deliveries(&self, webhook_id: &WebhookId, limit: &u64) -> Vec<WebhookDeliveryDomain> {
+        let rows: Vec<WebhookDelivery> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE webhook_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2",
+            WEBHOOK_DELIVERY_TABLE
+        ).as_str())
+            .bind(webhook_id)
+            .bind(*limit as i64)
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_webhook_delivery_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryWriter for WebhookGateway {
+    async fn save_delivery(&self, delivery: &WebhookDeliveryDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, webhook_id, event, payload, status, attempts, response_code, error, \
+             next_attempt_at, created_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) ON CONFLICT (id) DO UPDATE SET \
+             status = $5, attempts = $6, response_code = $7, error = $8, next_attempt_at = $9, updated_at = $11",
+            WEBHOOK_DELIVERY_TABLE
+        ).as_str())
+            .bind(&delivery.id)
+            .bind(&delivery.webhook_id)
+            .bind(delivery.event.as_str())
+            .bind(&delivery.payload)
+            .bind(delivery.status.as_str())
+            .bind(delivery.attempts as i64)
+            .bind(delivery.response_code.map(|code| code as i64))
+            .bind(&delivery.error)
+            .bind(delivery.next_attempt_at)
+            .bind(delivery.created_at)
+            .bind(delivery.updated_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_webhook_model_to_domain(webhook: Webhook) -> WebhookDomain {
+    WebhookDomain {
+        id: webhook.id,
+        url: webhook.url,
+        // Kinds that are no longer known are dropped
+        events: webhook.events.split(',').filter_map(EventKind::parse).collect(),
+        secret: webhook.secret,
+        created_at: webhook.created_at
+    }
+}
+
+fn map_webhook_delivery_model_to_domain(delivery: WebhookDelivery) -> WebhookDeliveryDomain {
+    WebhookDeliveryDomain {
+        id: delivery.id,
+        webhook_id: delivery.webhook_id,
+        // Only known kinds and statuses are ever written to the table
+        event: EventKind::parse(&delivery.event).unwrap(),
+        payload: delivery.payload,
+        status: WebhookDeliveryStatus::parse(&delivery.status).unwrap(),
+        attempts: delivery.attempts as u32,
+        response_code: delivery.response_code.map(|code| code as u16),
+        error: delivery.error,
+        next_attempt_at: delivery.next_attempt_at,
+        created_at: delivery.created_at,
+        updated_at: delivery.updated_at
+    }
+}
+
+impl WebhookGatewayTrait for WebhookGateway {}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::database::gateway_tests;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_webhook_gateway() {
+        let gateway = WebhookGateway::new(gateway_tests::sqlite().await);
+        let webhook = WebhookDomain::create(
+            "https://chat.example/hook".to_string(),
+            vec![EventKind::NotePublished, EventKind::ProjectUpdated],
+            None
+        ).unwrap();
+        gateway.save_webhook(&webhook).await;
+
+        assert_eq!(gateway.get_webhook(&webhook.id).await, Some(webhook.clone()));
+        assert_eq!(gateway.list_subscribed(EventKind::ProjectUpdated).await.len(), 1);
+        assert!(gateway.list_subscribed(EventKind::UserLoggedIn).await.is_empty());
+
+        let mut delivery = WebhookDeliveryDomain::create(webhook.id.clone(), EventKind::NotePublished, "{}".to_string());
+        gateway.save_delivery(&delivery).await;
+        assert_eq!(gateway.list_due_deliveries(Utc::now(), &10).await, vec![delivery.clone()]);
+
+        delivery.succeed(Utc::now(), 204);
+        gateway.save_delivery(&delivery).await;
+        assert!(gateway.list_due_deliveries(Utc::now(), &10).await.is_empty());
+        assert_eq!(gateway.get_delivery(&delivery.id).await, Some(delivery.clone()));
+        assert_eq!(gateway.list_deliveries(&webhook.id, &10).await.len(), 1);
+
+        assert!(gateway.remove_webhook(&webhook.id).await);
+        assert!(gateway.get_delivery(&delivery.id).await.is_none());
+        assert!(!gateway.remove_webhook(&webhook.id).await);
+    }
+}
@@ -0,0 +1,127 @@
+use core::option::Option;
+
+use async_trait::async_trait;
+
+use crate::adapters::database::pool::DbPools;
+use crate::application::common::webmention_gateway::{
+    WebmentionGateway as WebmentionGatewayTrait,
+    WebmentionReader,
+    WebmentionRemover,
+    WebmentionWriter
+};
+use crate::domain::models::note::NoteId;
+use crate::domain::models::webmention::{
+    MentionAuthor,
+    Webmention as WebmentionDomain,
+    WebmentionId,
+    WebmentionStatus
+};
+use crate::adapters::database::models::webmentions::{Webmention, WEBMENTION_TABLE};
+
+
+pub struct WebmentionGateway{
+    db: DbPools,
+}
+
+impl WebmentionGateway {
+    pub fn new(db: DbPools) -> Self {
+        WebmentionGateway {
+            db,
+        }
+    }
+}
+
+#[async_trait]
+impl WebmentionReader for WebmentionGateway {
+    async fn get_by_id(&self, id: &WebmentionId) -> Option<WebmentionDomain> {
+        let row: Option<Webmention> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE id = $1", WEBMENTION_TABLE).as_str()
+        )
+            .bind(id)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_webmention_model_to_domain)
+    }
+
+    async fn get_by_source_target(&self, source: &str, target: &str) -> Option<WebmentionDomain> {
+        let row: Option<Webmention> = sqlx::query_as(
+            format!("SELECT * FROM {} WHERE source = $1 AND target = $2", WEBMENTION_TABLE).as_str()
+        )
+            .bind(source)
+            .bind(target)
+            .fetch_optional(&self.db.reader).await.unwrap();
+
+        row.map(map_webmention_model_to_domain)
+    }
+
+    async fn list_by_note(&self, note_id: &NoteId, status: WebmentionStatus) -> Vec<WebmentionDomain> {
+        let rows: Vec<Webmention> = sqlx::query_as(format!(
+            "SELECT * FROM {} WHERE note_id = $1 AND status = $2 ORDER BY created_at, id",
+            WEBMENTION_TABLE
+        ).as_str())
+            .bind(note_id)
+            .bind(status.as_str())
+            .fetch_all(&self.db.reader).await.unwrap();
+
+        rows.into_iter().map(map_webmention_model_to_domain).collect()
+    }
+}
+
+#[async_trait]
+impl WebmentionWriter for WebmentionGateway {
+    async fn save(&self, webmention: &WebmentionDomain) {
+        sqlx::query(format!(
+            "INSERT INTO {} (id, source, target, note_id, status, author_name, author_url, author_photo, \
+             created_at, verified_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+             ON CONFLICT (id) DO UPDATE SET \
+             status = $5, author_name = $6, author_url = $7, author_photo = $8, verified_at = $10",
+            WEBMENTION_TABLE
+        ).as_str())
+            .bind(&webmention.id)
+            .bind(&webmention.source)
+            .bind(&webmention.target)
+            .bind(&webmention.note_id)
+            .bind(webmention.status.as_str())
+            .bind(&webmention.author.name)
+            .bind(&webmention.author.url)
+            .bind(&webmention.author.photo)
+            .bind(&webmention.created_at)
+            .bind(&webmention.verified_at)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+#[async_trait]
+impl WebmentionRemover for WebmentionGateway {
+    async fn remove(&self, webmention_id: &WebmentionId) {
+        sqlx::query(format!("DELETE FROM {} WHERE id = $1", WEBMENTION_TABLE).as_str())
+            .bind(webmention_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+
+    async fn remove_by_note(&self, note_id: &NoteId) {
+        sqlx::query(format!("DELETE FROM {} WHERE note_id = $1", WEBMENTION_TABLE).as_str())
+            .bind(note_id)
+            .execute(&self.db.writer).await.unwrap();
+    }
+}
+
+fn map_webmention_model_to_domain(webmention: Webmention) -> WebmentionDomain {
+    WebmentionDomain {
+        id: webmention.id,
+        source: webmention.source,
+        target: webmention.target,
+        note_id: webmention.note_id,
+        // Only known statuses are ever written to the table
+        status: WebmentionStatus::parse(&webmention.status).unwrap(),
+        author: MentionAuthor {
+            name: webmention.author_name,
+            url: webmention.author_url,
+            photo: webmention.author_photo
+        },
+        created_at: webmention.created_at,
+        verified_at: webmention.verified_at
+    }
+}
+
+impl WebmentionGatewayTrait for WebmentionGateway {}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::application::common::event_bus::{EventPublisher, EventSubscriber};
+use crate::domain::models::event::DomainEvent;
+
+
+/// Hands events to the subscribers one at a time from a background task,
+/// so every subscriber sees them in the order they were published.
+/// Each handler runs in a task of its own, a panic in one is logged
+/// and the bus carries on.
+///
+/// Must be created inside a Tokio runtime
+pub struct InProcessEventBus {
+    sender: mpsc::UnboundedSender<Message>,
+}
+
+enum Message {
+    Event(DomainEvent),
+    /// Answered once everything queued before it is dispatched
+    Flush(oneshot::Sender<()>),
+}
+
+impl InProcessEventBus {
+    pub fn new(subscribers: Vec<Arc<dyn EventSubscriber>>) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Message>();
+
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                match message {
+                    Message::Event(event) => dispatch(&subscribers, &event).await,
+                    Message::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Waits until the events published so far are handled,
+    /// short-lived processes call it before they exit
+    pub async fn flush(&self) {
+        let (done, wait) = oneshot::channel();
+        if self.sender.send(Message::Flush(done)).is_ok() {
+            let _ = wait.await;
+        }
+    }
+}
+
+impl EventPublisher for InProcessEventBus {
+    fn publish(&self, event: DomainEvent) {
+        if self.sender.send(Message::Event(event)).is_err() {
+            log::error!("Event bus is closed, an event was dropped");
+        }
+    }
+}
+
+async fn dispatch(subscribers: &[Arc<dyn EventSubscriber>], event: &DomainEvent) {
+    let kind = event.kind();
+    for subscriber in subscribers.iter().filter(|s| s.kinds().contains(&kind)) {
+        let handler = subscriber.clone();
+        let owned = event.clone();
+        let result = tokio::spawn(async move { handler.handle(&owned).await }).await;
+
+        let error = match result {
+            Ok(Ok(())) => continue,
+            Ok(Err(error)) => error,
+            Err(error) => error.to_string()
+        };
+        log::error!(
+            "Event subscriber {} failed on {}: {}",
+            subscriber.name(), kind.as_str(), error
+        );
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use crate::domain::models::event::EventKind;
+    use super::*;
+
+    struct Recorder {
+        kinds: Vec<EventKind>,
+        sender: mpsc::UnboundedSender<DomainEvent>,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for Recorder {
+        fn name(&self) -> &'static str {
+            "recorder"
+        }
+
+        fn kinds(&self) -> &[EventKind] {
+            &self.kinds
+        }
+
+        async fn handle(&self, event: &DomainEvent) -> Result<(), String> {
+            self.sender.send(event.clone()).unwrap();
+            Ok(())
+        }
+    }
+
+    struct Failing;
+
+    #[async_trait]
+    impl EventSubscriber for Failing {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        fn kinds(&self) -> &[EventKind] {
+            &EventKind::ALL
+        }
+
+        async fn handle(&self, _: &DomainEvent) -> Result<(), String> {
+            Err("boom".to_string())
+        }
+    }
+
+    struct Panicking;
+
+    #[async_trait]
+    impl EventSubscriber for Panicking {
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn kinds(&self) -> &[EventKind] {
+            &EventKind::ALL
+        }
+
+        async fn handle(&self, _: &DomainEvent) -> Result<(), String> {
+            panic!("boom")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_process_bus() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let bus = InProcessEventBus::new(vec![
+            Arc::new(Failing),
+            Arc::new(Panicking),
+            Arc::new(Recorder { kinds: vec![EventKind::NoteDeleted], sender })
+        ]);
+
+        bus.publish(DomainEvent::UserLoggedIn { username: "test".to_string() });
+        bus.publish(DomainEvent::NoteDeleted { id: "1".to_string() });
+        bus.publish(DomainEvent::NoteDeleted { id: "2".to_string() });
+
+        for id in ["1", "2"] {
+            let event = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event, DomainEvent::NoteDeleted { id: id.to_string() });
+        }
+        assert!(receiver.try_recv().is_err());
+
+        bus.publish(DomainEvent::NoteDeleted { id: "3".to_string() });
+        bus.flush().await;
+        assert_eq!(receiver.try_recv().unwrap(), DomainEvent::NoteDeleted { id: "3".to_string() });
+    }
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+use crate::application::common::event_bus::EventSubscriber;
+use crate::domain::models::event::{DomainEvent, EventKind};
+
+
+/// Writes every event to the log at debug level
+pub struct LogSubscriber;
+
+#[async_trait]
+impl EventSubscriber for LogSubscriber {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn kinds(&self) -> &[EventKind] {
+        &EventKind::ALL
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), String> {
+        log::debug!("{}: {}", event.kind().as_str(), serde_json::to_string(event).map_err(|e| e.to_string())?);
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod in_process_bus;
+pub mod log_subscriber;
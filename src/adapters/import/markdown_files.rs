@@ -0,0 +1,162 @@
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use crate::application::note::import::{ImportFile, IMPORT_FILES_MAX};
+use crate::domain::models::note::NOTE_BODY_MAX;
+
+
+/// Front matter and markup on top of the largest body
+const FILE_SIZE_MAX: u64 = NOTE_BODY_MAX as u64 * 2;
+/// Uploaded archives, compressed
+pub const ZIP_SIZE_MAX: usize = 16 * 1024 * 1024;
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension.to_ascii_lowercase().as_str(), "md" | "markdown"))
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.components().any(|component| component.as_os_str().to_string_lossy().starts_with('.'))
+}
+
+/// Markdown files under `dir`, with paths relative to it. Hidden files and
+/// directories are left out, such as `.git`, and so are symbolic links:
+/// they could point outside `dir` or back to one of its parents
+pub fn read_directory(dir: &Path) -> Result<Vec<ImportFile>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let entries = std::fs::read_dir(&current)
+            .map_err(|e| format!("{}: {}", current.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let path = entry.path();
+            let relative = path.strip_prefix(dir).unwrap();
+            // Not followed, unlike `Path::is_dir`
+            let file_type = entry.file_type().map_err(|e| format!("{}: {}", path.display(), e))?;
+            if is_hidden(relative) || file_type.is_symlink() {
+                continue;
+            }
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if !is_markdown(&path) {
+                continue;
+            }
+            if files.len() == IMPORT_FILES_MAX {
+                return Err(format!("More than {} Markdown files", IMPORT_FILES_MAX));
+            }
+            files.push(ImportFile {
+                path: relative.components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                content: std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?
+            });
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Markdown files in a zip archive. Entries are read up to a size limit
+/// rather than trusting the sizes the archive declares
+pub fn read_zip(bytes: Vec<u8>) -> Result<Vec<ImportFile>, String> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Not a zip archive: {}", e))?;
+    let mut files = Vec::new();
+
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        let Some(path) = entry.enclosed_name() else {
+            return Err(format!("Unsafe path in the archive: {}", entry.name()));
+        };
+        if entry.is_dir() || is_hidden(&path) || !is_markdown(&path) {
+            continue;
+        }
+        if files.len() == IMPORT_FILES_MAX {
+            return Err(format!("More than {} Markdown files", IMPORT_FILES_MAX));
+        }
+
+        let mut content = Vec::new();
+        entry.take(FILE_SIZE_MAX + 1).read_to_end(&mut content).map_err(|e| e.to_string())?;
+        if content.len() as u64 > FILE_SIZE_MAX {
+            return Err(format!("{} is larger than {} bytes", path.display(), FILE_SIZE_MAX));
+        }
+        files.push(ImportFile {
+            path: path.components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+            content
+        });
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+    use crate::domain::id_generator::generate_id;
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_directory() {
+        let dir = std::env::temp_dir().join(format!("import-{}", generate_id(8)));
+        let outside = std::env::temp_dir().join(format!("outside-{}", generate_id(8)));
+        std::fs::create_dir_all(dir.join("posts")).unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(dir.join("posts/a.md"), "A").unwrap();
+        std::fs::write(dir.join("posts/image.png"), "").unwrap();
+        std::fs::write(dir.join(".git/b.md"), "B").unwrap();
+        std::fs::write(outside.join("secret.md"), "S").unwrap();
+        std::os::unix::fs::symlink(outside.join("secret.md"), dir.join("secret.md")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.join("linked")).unwrap();
+        std::os::unix::fs::symlink(&dir, dir.join("posts/loop")).unwrap();
+
+        let files = read_directory(&dir).unwrap();
+        assert_eq!(
+            files.iter().map(|file| file.path.as_str()).collect::<Vec<_>>(),
+            vec!["posts/a.md"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn test_read_zip() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in [
+            ("posts/b.md", "B"),
+            ("posts/a.markdown", "A"),
+            ("posts/image.png", ""),
+            (".hidden/c.md", "C"),
+        ] {
+            writer.start_file(name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let files = read_zip(bytes).unwrap();
+        assert_eq!(
+            files.iter().map(|file| file.path.as_str()).collect::<Vec<_>>(),
+            vec!["posts/a.markdown", "posts/b.md"]
+        );
+        assert_eq!(files[1].content, b"B");
+        assert!(read_zip(b"not a zip".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::Mailbox;
+
+use crate::adapters::mailer::message::build_message;
+use crate::application::common::mailer::Mailer;
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::mail::Mail;
+
+
+/// Writes every message to `<dir>/<time>-<id>.eml` instead of sending it.
+/// For development and for servers without SMTP
+pub struct FileMailer {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>, from: Mailbox) -> Self {
+        Self {
+            dir: dir.into(),
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), String> {
+        let message = build_message(&self.from, mail)?;
+
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| e.to_string())?;
+        let path = self.dir.join(format!("{}-{}.eml", Utc::now().format("%Y%m%dT%H%M%S"), generate_id(8)));
+        tokio::fs::write(&path, message.formatted()).await.map_err(|e| e.to_string())?;
+
+        log::info!("Mail \"{}\" to {} written to {}", mail.subject, mail.to, path.display());
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_mail() {
+        let dir = std::env::temp_dir().join(format!("mail-{}", generate_id(8)));
+        let mailer = FileMailer::new(&dir, "Blog <noreply@jkearnsl.su>".parse().unwrap());
+
+        mailer.send(&Mail {
+            to: "reader@example.com".to_string(),
+            subject: "New notes".to_string(),
+            text: "Plain".to_string(),
+            html: Some("<p>Rich</p>".to_string()),
+            headers: vec![("List-Unsubscribe-Post".to_string(), "List-Unsubscribe=One-Click".to_string())]
+        }).await.unwrap();
+
+        let mut entries = tokio::fs::read_dir(&dir).await.unwrap();
+        let path = entries.next_entry().await.unwrap().unwrap().path();
+        let written = String::from_utf8(tokio::fs::read(path).await.unwrap()).unwrap();
+        assert!(written.contains("To: reader@example.com"));
+        assert!(written.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+        assert!(written.contains("multipart/alternative"));
+
+        let result = mailer.send(&Mail {
+            to: "not an address".to_string(),
+            subject: "x".to_string(),
+            text: "x".to_string(),
+            html: None,
+            headers: vec![]
+        }).await;
+        assert!(result.is_err());
+
+        tokio::fs::remove_dir_all(dir).await.unwrap();
+    }
+}
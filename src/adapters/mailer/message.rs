@@ -0,0 +1,24 @@
+use lettre::message::header::{HeaderName, HeaderValue};
+use lettre::message::{Mailbox, MultiPart, SinglePart};
+use lettre::Message;
+
+use crate::domain::models::mail::Mail;
+
+
+pub fn build_message(from: &Mailbox, mail: &Mail) -> Result<Message, String> {
+    let to: Mailbox = mail.to.parse().map_err(|e: lettre::address::AddressError| e.to_string())?;
+
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(&mail.subject);
+    for (name, value) in &mail.headers {
+        let name = HeaderName::new_from_ascii(name.clone()).map_err(|e| e.to_string())?;
+        builder = builder.raw_header(HeaderValue::new(name, value.clone()));
+    }
+
+    match &mail.html {
+        Some(html) => builder.multipart(MultiPart::alternative_plain_html(mail.text.clone(), html.clone())),
+        None => builder.singlepart(SinglePart::plain(mail.text.clone()))
+    }.map_err(|e| e.to_string())
+}
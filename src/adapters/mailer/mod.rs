@@ -0,0 +1,3 @@
+pub mod message;
+pub mod smtp;
+pub mod file;
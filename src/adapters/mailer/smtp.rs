@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+
+use crate::adapters::mailer::message::build_message;
+use crate::application::common::mailer::Mailer;
+use crate::domain::models::mail::Mail;
+
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// Connections are made lazily, a wrong host shows up on the first mail
+    pub fn new(host: &str, port: u16, username: &str, password: &str, from: Mailbox) -> Result<Self, String> {
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+            .map_err(|e| e.to_string())?
+            .port(port);
+        if !username.is_empty() {
+            builder = builder.credentials(Credentials::new(username.to_string(), password.to_string()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, mail: &Mail) -> Result<(), String> {
+        let message = build_message(&self.from, mail)?;
+        self.transport.send(message).await.map(|_| ()).map_err(|e| e.to_string())
+    }
+}
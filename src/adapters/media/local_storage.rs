@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+
+use crate::application::common::media_storage::MediaStorage;
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::hash::Hash;
+
+
+/// Stores blobs as `<root>/ab/cd/abcd...`, so a single directory
+/// never grows too large
+pub struct LocalMediaStorage {
+    root: PathBuf,
+}
+
+impl LocalMediaStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+        }
+    }
+
+    fn path(&self, hash: &Hash) -> PathBuf {
+        let name = hash.to_string();
+        self.root.join(&name[0..2]).join(&name[2..4]).join(name)
+    }
+}
+
+#[async_trait]
+impl MediaStorage for LocalMediaStorage {
+    async fn put(&self, hash: &Hash, data: &[u8]) -> Result<(), String> {
+        let path = self.path(hash);
+        if tokio::fs::try_exists(&path).await.map_err(|e| e.to_string())? {
+            return Ok(());
+        }
+
+        let dir = path.parent().unwrap();
+        tokio::fs::create_dir_all(dir).await.map_err(|e| e.to_string())?;
+
+        // Write aside and rename, so readers never see a partial file
+        let tmp = dir.join(format!(".{}.tmp", generate_id(8)));
+        tokio::fs::write(&tmp, data).await.map_err(|e| e.to_string())?;
+        tokio::fs::rename(&tmp, &path).await.map_err(|e| e.to_string())
+    }
+
+    async fn get(&self, hash: &Hash) -> Option<Vec<u8>> {
+        tokio::fs::read(self.path(hash)).await.ok()
+    }
+
+    async fn remove(&self, hash: &Hash) -> Result<(), String> {
+        match tokio::fs::remove_file(self.path(hash)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_get_remove() {
+        let root = std::env::temp_dir().join(format!("media-{}", generate_id(8)));
+        let storage = LocalMediaStorage::new(&root);
+        let data = b"content".to_vec();
+        let hash = Hash::sha256(&data);
+
+        storage.put(&hash, &data).await.unwrap();
+        storage.put(&hash, &data).await.unwrap();
+        assert_eq!(storage.get(&hash).await, Some(data));
+
+        storage.remove(&hash).await.unwrap();
+        assert_eq!(storage.get(&hash).await, None);
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+}
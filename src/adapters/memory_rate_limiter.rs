@@ -0,0 +1,97 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use crate::application::common::rate_limiter::RateLimiter;
+
+
+/// Sliding window limiter. State is per process and is lost on restart,
+/// which is fine for spam protection
+pub struct MemoryRateLimiter {
+    limit: usize,
+    window: Duration,
+    state: Mutex<State>,
+}
+
+struct State {
+    hits: HashMap<String, VecDeque<Instant>>,
+    swept_at: Instant,
+}
+
+impl MemoryRateLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            state: Mutex::new(State {
+                hits: HashMap::new(),
+                swept_at: Instant::now(),
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for MemoryRateLimiter {
+    async fn hit(&self, key: &str) -> bool {
+        let now = Instant::now();
+        let mut state = self.state.lock().await;
+
+        // Forget keys that went quiet, so the map does not grow forever.
+        // Swept once a window, a quiet key lives two windows at most
+        if now.duration_since(state.swept_at) >= self.window {
+            state.hits.retain(|_, times| times.back().is_some_and(|t| now.duration_since(*t) < self.window));
+            state.swept_at = now;
+        }
+
+        let times = state.hits.entry(key.to_string()).or_default();
+        while times.front().is_some_and(|t| now.duration_since(*t) >= self.window) {
+            times.pop_front();
+        }
+
+        if times.len() >= self.limit {
+            return false;
+        }
+        times.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_limit() {
+        let limiter = MemoryRateLimiter::new(2, Duration::from_secs(60));
+
+        assert!(limiter.hit("a").await);
+        assert!(limiter.hit("a").await);
+        assert!(!limiter.hit("a").await);
+        assert!(limiter.hit("b").await);
+    }
+
+    #[tokio::test]
+    async fn test_window() {
+        let limiter = MemoryRateLimiter::new(1, Duration::from_millis(20));
+
+        assert!(limiter.hit("a").await);
+        assert!(!limiter.hit("a").await);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(limiter.hit("a").await);
+    }
+
+    #[tokio::test]
+    async fn test_sweep() {
+        let limiter = MemoryRateLimiter::new(1, Duration::from_millis(20));
+
+        assert!(limiter.hit("a").await);
+        assert!(limiter.hit("b").await);
+        assert_eq!(limiter.state.lock().await.hits.len(), 2);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(limiter.hit("c").await);
+        assert_eq!(limiter.state.lock().await.hits.keys().collect::<Vec<_>>(), vec!["c"]);
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use tokio::sync::Mutex;
+
+use crate::application::common::visitor_salt::VisitorSalt;
+
+
+/// Keeps only the salt of the current day and never writes it anywhere.
+/// A restart starts a new salt, so a returning visitor counts twice that day
+pub struct MemoryVisitorSalt {
+    current: Mutex<Option<(NaiveDate, Vec<u8>)>>,
+}
+
+impl MemoryVisitorSalt {
+    pub fn new() -> Self {
+        Self {
+            current: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl VisitorSalt for MemoryVisitorSalt {
+    async fn salt(&self, day: NaiveDate) -> Vec<u8> {
+        let mut current = self.current.lock().await;
+        match current.as_ref() {
+            Some((current_day, salt)) if *current_day == day => salt.clone(),
+            _ => {
+                let salt = rand::random::<[u8; 32]>().to_vec();
+                *current = Some((day, salt.clone()));
+                salt
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rotation() {
+        let salt = MemoryVisitorSalt::new();
+        let today = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let tomorrow = today.succ_opt().unwrap();
+
+        let first = salt.salt(today).await;
+        assert_eq!(first, salt.salt(today).await);
+        assert_ne!(first, salt.salt(tomorrow).await);
+        // Yesterday's salt is gone for good
+        assert_ne!(first, salt.salt(today).await);
+    }
+}
@@ -1,3 +1,20 @@
 pub mod auth;
 pub mod argon2_password_hasher;
-pub mod database;
\ No newline at end of file
+pub mod database;
+pub mod media;
+pub mod raster_image_processor;
+pub mod social_card;
+pub mod memory_rate_limiter;
+pub mod memory_visitor_salt;
+pub mod webmention;
+pub mod activitypub;
+pub mod mailer;
+pub mod counter;
+pub mod backup;
+pub mod events;
+pub mod webhook;
+pub mod import;
+pub mod archive;
+pub mod public_http;
+#[cfg(test)]
+pub mod test_server;
@@ -0,0 +1,260 @@
+use std::io::Cursor;
+
+use async_trait::async_trait;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader, Limits};
+
+use crate::application::common::image_processor::ImageProcessor;
+
+
+/// The largest side a WebP can hold
+const MAX_DIMENSION: u32 = 16_383;
+/// Decoding stops before a crafted header makes it allocate more than this
+const MAX_ALLOC: u64 = 512 * 1024 * 1024;
+/// Quality of the lossy WebP encoder, from 0 to 100
+const WEBP_QUALITY: f32 = 80.0;
+
+/// Metadata is removed at the container level, so originals are never re-encoded.
+/// Derivatives are lossy WebP made by libwebp, the `image` crate only writes lossless
+/// WebP, which comes out larger than the photos it is made from
+pub struct RasterImageProcessor;
+
+impl RasterImageProcessor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ImageProcessor for RasterImageProcessor {
+    async fn strip_metadata(&self, mime: &str, data: Vec<u8>) -> Result<Vec<u8>, String> {
+        match mime {
+            "image/jpeg" => strip_jpeg(&data),
+            "image/png" => strip_png(&data),
+            "image/webp" => strip_webp(&data),
+            // GIF has no place for EXIF
+            "image/gif" => Ok(data),
+            _ => Err(format!("metadata can not be removed from {}", mime))
+        }
+    }
+
+    async fn dimensions(&self, data: &[u8]) -> Option<(u32, u32)> {
+        ImageReader::new(Cursor::new(data))
+            .with_guessed_format().ok()?
+            .into_dimensions().ok()
+    }
+
+    async fn resize_to_webp(&self, data: &[u8], widths: &[u32]) -> Result<Vec<(Vec<u8>, u32)>, String> {
+        let data = data.to_vec();
+        let widths = widths.to_vec();
+        tokio::task::spawn_blocking(move || {
+            let image = decode(&data)?;
+            widths.into_iter().map(|width| {
+                let height = (image.height() as u64 * width as u64 / image.width().max(1) as u64).max(1) as u32;
+                let resized = (width < image.width())
+                    .then(|| image.resize_exact(width, height, FilterType::Lanczos3));
+                let resized = resized.as_ref().unwrap_or(&image);
+                Ok((encode_webp(resized)?, resized.height()))
+            }).collect()
+        }).await.map_err(|e| e.to_string())?
+    }
+}
+
+fn decode(data: &[u8]) -> Result<DynamicImage, String> {
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    limits.max_alloc = Some(MAX_ALLOC);
+    reader.limits(limits);
+    reader.decode().map_err(|e| e.to_string())
+}
+
+fn encode_webp(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    let rgba = image.to_rgba8();
+    webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height())
+        .encode_simple(false, WEBP_QUALITY)
+        .map(|encoded| encoded.to_vec())
+        .map_err(|e| format!("WebP encoding failed: {:?}", e))
+}
+
+/// Drops APP1 (EXIF, XMP), APP13 (IPTC) and comment segments
+fn strip_jpeg(data: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "JPEG is malformed".to_string();
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err(invalid());
+    }
+
+    let mut result = data[..2].to_vec();
+    let mut pos = 2;
+    loop {
+        if pos + 4 > data.len() || data[pos] != 0xFF {
+            return Err(invalid());
+        }
+        let marker = data[pos + 1];
+        if marker == 0xFF {
+            // Fill byte
+            pos += 1;
+            continue;
+        }
+        if marker == 0xDA {
+            // Start of scan, the rest is entropy-coded data
+            result.extend_from_slice(&data[pos..]);
+            return Ok(result);
+        }
+
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > data.len() {
+            return Err(invalid());
+        }
+        if !matches!(marker, 0xE1 | 0xED | 0xFE) {
+            result.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+}
+
+/// Drops `eXIf` and textual chunks
+fn strip_png(data: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "PNG is malformed".to_string();
+    if data.len() < 8 {
+        return Err(invalid());
+    }
+
+    let mut result = data[..8].to_vec();
+    let mut pos = 8;
+    while pos < data.len() {
+        if pos + 12 > data.len() {
+            return Err(invalid());
+        }
+        let length = u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let end = pos + 12 + length;
+        if end > data.len() {
+            return Err(invalid());
+        }
+        if !matches!(kind, b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt" | b"tIME") {
+            result.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    Ok(result)
+}
+
+/// Drops `EXIF` and `XMP ` chunks and clears their flags in `VP8X`
+fn strip_webp(data: &[u8]) -> Result<Vec<u8>, String> {
+    let invalid = || "WebP is malformed".to_string();
+    if data.len() < 12 {
+        return Err(invalid());
+    }
+
+    let mut result = data[..12].to_vec();
+    let mut pos = 12;
+    while pos < data.len() {
+        if pos + 8 > data.len() {
+            return Err(invalid());
+        }
+        let kind = &data[pos..pos + 4];
+        let length = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().unwrap()) as usize;
+        // Chunks are padded to an even size
+        let end = (pos + 8 + length + (length & 1)).min(data.len());
+        if pos + 8 + length > data.len() {
+            return Err(invalid());
+        }
+        match kind {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" if length > 0 => {
+                let flags = result.len() + 8;
+                result.extend_from_slice(&data[pos..end]);
+                result[flags] &= !(0x08 | 0x04);
+            }
+            _ => result.extend_from_slice(&data[pos..end])
+        }
+        pos = end;
+    }
+
+    let riff_size = (result.len() - 8) as u32;
+    result[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use image::ImageFormat;
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut result = Cursor::new(Vec::new());
+        DynamicImage::new_rgb8(width, height).write_to(&mut result, ImageFormat::Png).unwrap();
+        result.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_resize_to_webp() {
+        let processor = RasterImageProcessor::new();
+        let resized = processor.resize_to_webp(&png(40, 20), &[10, 40, 80]).await.unwrap();
+
+        let heights: Vec<u32> = resized.iter().map(|(_, height)| *height).collect();
+        assert_eq!(heights, vec![5, 20, 20]);
+        for (data, _) in &resized {
+            assert!(data.starts_with(b"RIFF") && &data[8..12] == b"WEBP");
+            // Lossy WebP keeps its pixels in a VP8 chunk, lossless ones in VP8L
+            assert!(data.windows(4).any(|chunk| chunk == b"VP8 "));
+        }
+
+        let too_wide = processor.resize_to_webp(&png(MAX_DIMENSION + 1, 1), &[10]).await;
+        assert!(too_wide.is_err());
+    }
+
+    fn png_chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[0; 4]);
+        chunk
+    }
+
+    #[test]
+    fn test_strip_jpeg() {
+        let mut jpeg = vec![0xFF, 0xD8];
+        jpeg.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46]);
+        jpeg.extend_from_slice(&[0xFF, 0xE1, 0x00, 0x06, b'E', b'x', b'i', b'f']);
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x01, 0x02, 0xFF, 0xD9]);
+
+        let stripped = strip_jpeg(&jpeg).unwrap();
+        assert_eq!(
+            stripped,
+            vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x4A, 0x46, 0xFF, 0xDA, 0x01, 0x02, 0xFF, 0xD9]
+        );
+        assert!(strip_jpeg(b"not a jpeg").is_err());
+    }
+
+    #[test]
+    fn test_strip_png() {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(png_chunk(b"IHDR", &[1; 13]));
+        png.extend(png_chunk(b"eXIf", b"GPS"));
+        png.extend(png_chunk(b"IEND", &[]));
+
+        let mut expected = b"\x89PNG\r\n\x1a\n".to_vec();
+        expected.extend(png_chunk(b"IHDR", &[1; 13]));
+        expected.extend(png_chunk(b"IEND", &[]));
+
+        assert_eq!(strip_png(&png).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_strip_webp() {
+        let mut webp = b"RIFF\0\0\0\0WEBP".to_vec();
+        webp.extend_from_slice(b"VP8X\x0a\0\0\0\x0c\0\0\0\0\0\0\0\0\0");
+        webp.extend_from_slice(b"EXIF\x03\0\0\0GPS\0");
+
+        let stripped = strip_webp(&webp).unwrap();
+        assert_eq!(stripped.len(), 12 + 18);
+        assert_eq!(stripped[20], 0);
+        assert_eq!(u32::from_le_bytes(stripped[4..8].try_into().unwrap()), 22);
+    }
+}
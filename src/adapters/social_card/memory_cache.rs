@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::application::common::social_card::SocialCardCache;
+use crate::domain::models::note::NoteId;
+
+
+/// Keeps only the latest revision of every note, so the cache
+/// is bounded by the number of notes
+pub struct MemorySocialCardCache {
+    cards: RwLock<HashMap<NoteId, (i64, Vec<u8>)>>,
+}
+
+impl MemorySocialCardCache {
+    pub fn new() -> Self {
+        Self {
+            cards: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl SocialCardCache for MemorySocialCardCache {
+    async fn get(&self, note_id: &NoteId, revision: i64) -> Option<Vec<u8>> {
+        match self.cards.read().await.get(note_id) {
+            Some((cached, card)) if *cached == revision => Some(card.clone()),
+            _ => None
+        }
+    }
+
+    async fn put(&self, note_id: &NoteId, revision: i64, card: Vec<u8>) {
+        self.cards.write().await.insert(note_id.clone(), (revision, card));
+    }
+}
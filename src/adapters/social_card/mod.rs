@@ -0,0 +1,2 @@
+pub mod resvg_renderer;
+pub mod memory_cache;
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::Engine;
+use resvg::{tiny_skia, usvg};
+
+use crate::application::common::social_card::SocialCardRenderer;
+
+/// Size recommended by both Open Graph and Twitter cards
+const CARD_WIDTH: u32 = 1200;
+const CARD_HEIGHT: u32 = 630;
+
+const TITLE_LINE_CHARS: usize = 28;
+const TITLE_LINES_MAX: usize = 3;
+
+
+/// Draws the card as SVG and rasterizes it with `resvg`, using the system fonts
+pub struct ResvgCardRenderer {
+    options: Arc<usvg::Options<'static>>,
+    /// `data:` URI of the site logo
+    logo: String,
+}
+
+impl ResvgCardRenderer {
+    /// `logo` is the content of `assets/images/logo/overlord.webp`
+    pub fn new(logo: &[u8]) -> Self {
+        let mut options = usvg::Options::default();
+        options.fontdb_mut().load_system_fonts();
+
+        Self {
+            options: Arc::new(options),
+            logo: format!(
+                "data:image/webp;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(logo)
+            ),
+        }
+    }
+
+    fn svg(&self, title: &str) -> String {
+        let lines = wrap_title(title)
+            .iter()
+            .enumerate()
+            .map(|(i, line)| format!(
+                "<tspan x=\"80\" dy=\"{}\">{}</tspan>",
+                if i == 0 { 0 } else { 84 },
+                escape(line)
+            ))
+            .collect::<String>();
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+                width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\
+                <rect width=\"100%\" height=\"100%\" fill=\"#f9f9f9\"/>\
+                <rect y=\"{bar}\" width=\"100%\" height=\"16\" fill=\"#982c61\"/>\
+                <image x=\"{logo_x}\" y=\"80\" width=\"300\" height=\"300\" xlink:href=\"{logo}\"/>\
+                <text x=\"80\" y=\"200\" font-family=\"sans-serif\" font-size=\"68\" \
+                    font-weight=\"bold\" fill=\"#4a4a4a\">{lines}</text>\
+                <text x=\"80\" y=\"{site_y}\" font-family=\"sans-serif\" font-size=\"36\" \
+                    fill=\"#982c61\">jkearnsl.su</text>\
+            </svg>",
+            w = CARD_WIDTH,
+            h = CARD_HEIGHT,
+            bar = CARD_HEIGHT - 16,
+            logo_x = CARD_WIDTH - 380,
+            site_y = CARD_HEIGHT - 70,
+            logo = self.logo,
+            lines = lines,
+        )
+    }
+}
+
+#[async_trait]
+impl SocialCardRenderer for ResvgCardRenderer {
+    async fn render(&self, title: &str) -> Result<Vec<u8>, String> {
+        let svg = self.svg(title);
+        let options = self.options.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let tree = usvg::Tree::from_str(&svg, &options).map_err(|e| e.to_string())?;
+            let mut pixmap = tiny_skia::Pixmap::new(CARD_WIDTH, CARD_HEIGHT)
+                .ok_or("Failed to allocate pixmap")?;
+            resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+            pixmap.encode_png().map_err(|e| e.to_string())
+        }).await.map_err(|e| e.to_string())?
+    }
+}
+
+/// SVG text does not wrap by itself
+fn wrap_title(title: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in title.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= TITLE_LINE_CHARS => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.chars().take(TITLE_LINE_CHARS).collect())
+        }
+    }
+
+    if lines.len() > TITLE_LINES_MAX {
+        lines.truncate(TITLE_LINES_MAX);
+        let last = &mut lines[TITLE_LINES_MAX - 1];
+        let kept: String = last.chars().take(TITLE_LINE_CHARS - 1).collect();
+        *last = format!("{}…", kept);
+    }
+    lines
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_title() {
+        assert_eq!(wrap_title("Short"), vec!["Short"]);
+        assert_eq!(
+            wrap_title("Cursor-based pagination for note and project listings"),
+            vec!["Cursor-based pagination for", "note and project listings"]
+        );
+
+        let long = "word ".repeat(40);
+        let lines = wrap_title(&long);
+        assert_eq!(lines.len(), TITLE_LINES_MAX);
+        assert!(lines[TITLE_LINES_MAX - 1].ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn test_render_png() {
+        let renderer = ResvgCardRenderer::new(&[]);
+        let card = renderer.render("<Title> & more").await.unwrap();
+        assert!(card.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+}
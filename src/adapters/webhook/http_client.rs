@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+
+use crate::application::common::webhook_client::{WebhookClient, WebhookResponse};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+
+pub struct HttpWebhookClient {
+    http: reqwest::Client,
+}
+
+impl HttpWebhookClient {
+    pub fn new() -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("jkearnsl/", env!("CARGO_PKG_VERSION"), " (Webhook)"))
+            .timeout(TIMEOUT)
+            // A redirect would resend the signed payload to wherever it points
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        Self { http }
+    }
+}
+
+#[async_trait]
+impl WebhookClient for HttpWebhookClient {
+    async fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> WebhookResponse {
+        let mut request = self.http.post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+
+        match request.send().await {
+            Ok(response) => WebhookResponse::Status(response.status().as_u16()),
+            Err(error) => WebhookResponse::Unreachable(error.to_string())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::test_server::{TestResponse, TestServer};
+    use crate::domain::services::webhook::{webhook_signature, SIGNATURE_HEADER};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_post_webhook() {
+        let server = TestServer::start(vec![
+            ("/hook", TestResponse::status(204)),
+            ("/broken", TestResponse::status(500))
+        ]).await;
+        let client = HttpWebhookClient::new();
+        let body = "{\"event\":\"note.published\"}";
+        let headers = [(SIGNATURE_HEADER, webhook_signature("secret", body))];
+
+        assert_eq!(client.post(&server.url("/hook"), &headers, body).await, WebhookResponse::Status(204));
+        assert_eq!(client.post(&server.url("/broken"), &headers, body).await, WebhookResponse::Status(500));
+
+        let request = server.requests().await.remove(0);
+        assert_eq!((request.method.as_str(), request.body.as_str()), ("POST", body));
+        assert_eq!(request.headers["content-type"], "application/json");
+        assert_eq!(
+            request.headers[&SIGNATURE_HEADER.to_ascii_lowercase()],
+            webhook_signature("secret", &request.body)
+        );
+
+        let unreachable = client.post("http://127.0.0.1:1/hook", &headers, body).await;
+        assert!(matches!(unreachable, WebhookResponse::Unreachable(_)));
+    }
+}
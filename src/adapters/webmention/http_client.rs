@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::header::{CONTENT_TYPE, LINK};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::adapters::public_http::{check_url, public_only};
+use crate::adapters::webmention::parse;
+use crate::application::common::job_gateway::JobWriter;
+use crate::application::common::webmention_client::WebmentionClient;
+use crate::application::common::webmention_gateway::{WebmentionGateway, WebmentionReader, WebmentionWriter};
+use crate::domain::models::job::Job;
+use crate::domain::models::webmention::{Webmention, WebmentionId};
+
+/// Pages bigger than that are not worth parsing for a single link
+const BODY_MAX: usize = 1024 * 1024;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Payload `{"id"}` of the received mention
+pub const VERIFY_JOB: &str = "webmention.verify";
+/// Payload `{"source", "html"}` of the note
+pub const SEND_JOB: &str = "webmention.send";
+
+
+/// Queues the work as jobs, which the job workers hand back to
+/// [`HttpWebmentionClient::verify`] and [`HttpWebmentionClient::send`]
+pub struct HttpWebmentionClient {
+    inner: Inner,
+    job_writer: Box<dyn JobWriter>,
+}
+
+struct Inner {
+    http: reqwest::Client,
+    webmention_gateway: Box<dyn WebmentionGateway + Send + Sync>,
+    site_url: String,
+    /// Off only in tests, which talk to a server on localhost
+    public_only: bool,
+}
+
+impl Inner {
+    fn allowed(&self, url: &str) -> Result<(), String> {
+        if !self.public_only {
+            return Ok(());
+        }
+        let url = url::Url::parse(url).map_err(|e| e.to_string())?;
+        check_url(&url)
+    }
+}
+
+impl HttpWebmentionClient {
+    pub fn new(
+        webmention_gateway: Box<dyn WebmentionGateway + Send + Sync>,
+        job_writer: Box<dyn JobWriter>,
+        site_url: &str
+    ) -> Self {
+        let builder = reqwest::Client::builder()
+            .user_agent(concat!("jkearnsl/", env!("CARGO_PKG_VERSION"), " (Webmention)"))
+            .timeout(TIMEOUT);
+        let http = public_only(builder, 5)
+            .build()
+            .unwrap();
+
+        Self {
+            inner: Inner {
+                http,
+                webmention_gateway,
+                site_url: site_url.to_string(),
+                public_only: true,
+            },
+            job_writer,
+        }
+    }
+
+    /// Run by the [`VERIFY_JOB`], an error has the job tried again later
+    pub async fn verify(&self, id: &WebmentionId) -> Result<(), String> {
+        match self.inner.webmention_gateway.get_by_id(id).await {
+            Some(webmention) => verify(&self.inner, webmention).await,
+            // Removed with its note in the meantime
+            None => Ok(())
+        }
+    }
+
+    /// Run by the [`SEND_JOB`]. Failed targets are not tried again,
+    /// that would mention the note twice to the ones that got it
+    pub async fn send(&self, source: &str, html: &str) {
+        send(&self.inner, source, html).await
+    }
+}
+
+#[async_trait]
+impl WebmentionClient for HttpWebmentionClient {
+    async fn verify_later(&self, webmention: Webmention) {
+        self.job_writer.save_job(&Job::create(
+            VERIFY_JOB,
+            json!({ "id": webmention.id }),
+            Utc::now()
+        )).await;
+    }
+
+    async fn send_later(&self, source: String, html: String) {
+        self.job_writer.save_job(&Job::create(
+            SEND_JOB,
+            json!({ "source": source, "html": html }),
+            Utc::now()
+        )).await;
+    }
+}
+
+async fn verify(inner: &Inner, mut webmention: Webmention) -> Result<(), String> {
+    if let Err(error) = inner.allowed(&webmention.source) {
+        log::warn!("Refused to fetch webmention source {}: {}", webmention.source, error);
+        webmention.invalidate();
+        inner.webmention_gateway.save(&webmention).await;
+        return Ok(());
+    }
+
+    // Could be a hiccup on their side, the job is tried again
+    let page = fetch(&inner.http, &webmention.source).await
+        .map_err(|error| format!("Failed to fetch webmention source {}: {}", webmention.source, error))?;
+
+    match page {
+        Some(page) if parse::links_to(&page.body, &page.url, &webmention.target) => {
+            webmention.verify(parse::author(&page.body, &page.url));
+        }
+        _ => webmention.invalidate()
+    }
+
+    inner.webmention_gateway.save(&webmention).await;
+    Ok(())
+}
+
+async fn send(inner: &Inner, source: &str, html: &str) {
+    for target in parse::external_links(html, source, &inner.site_url) {
+        if let Err(error) = inner.allowed(&target) {
+            log::warn!("Refused to discover webmention endpoint of {}: {}", target, error);
+            continue;
+        }
+        let endpoint = match discover(&inner.http, &target).await {
+            Ok(Some(endpoint)) => endpoint,
+            Ok(None) => continue,
+            Err(error) => {
+                log::warn!("Failed to discover webmention endpoint of {}: {}", target, error);
+                continue;
+            }
+        };
+
+        if let Err(error) = inner.allowed(&endpoint) {
+            log::warn!("Refused to send webmention to {}: {}", endpoint, error);
+            continue;
+        }
+
+        let result = inner.http.post(&endpoint)
+            .form(&[("source", source), ("target", target.as_str())])
+            .send().await
+            .and_then(|response| response.error_for_status());
+        if let Err(error) = result {
+            log::warn!("Failed to send webmention to {}: {}", endpoint, error);
+        }
+    }
+}
+
+struct Page {
+    /// Final URL after redirects, relative links resolve against it
+    url: String,
+    body: String,
+}
+
+/// `None` when the page is gone for good
+async fn fetch(http: &reqwest::Client, url: &str) -> reqwest::Result<Option<Page>> {
+    let response = http.get(url).send().await?;
+    if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+        return Ok(None);
+    }
+    let response = response.error_for_status()?;
+    let url = response.url().to_string();
+    let body = read_limited(response).await?;
+    Ok(Some(Page { url, body }))
+}
+
+async fn discover(http: &reqwest::Client, target: &str) -> reqwest::Result<Option<String>> {
+    let response = http.get(target).send().await?.error_for_status()?;
+    let url = response.url().to_string();
+
+    let links = response.headers().get_all(LINK).iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>();
+    if let Some(endpoint) = parse::endpoint_from_link_headers(links.into_iter(), &url) {
+        return Ok(Some(endpoint));
+    }
+
+    let is_html = response.headers().get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if !is_html {
+        return Ok(None);
+    }
+    let body = read_limited(response).await?;
+    Ok(parse::endpoint_from_html(&body, &url))
+}
+
+async fn read_limited(mut response: reqwest::Response) -> reqwest::Result<String> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let rest = BODY_MAX - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(rest)]);
+        if body.len() >= BODY_MAX {
+            break;
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::test_server::{TestResponse, TestServer};
+    use crate::application::common::webmention_gateway::test::MockWebmentionGateway;
+    use crate::domain::models::webmention::WebmentionStatus;
+    use super::*;
+
+    const SITE: &str = "https://jkearnsl.su";
+    const TARGET: &str = "https://jkearnsl.su/notes/supa-title";
+
+    fn inner() -> Inner {
+        Inner {
+            http: reqwest::Client::new(),
+            webmention_gateway: Box::new(MockWebmentionGateway::new(vec![])),
+            site_url: SITE.to_string(),
+            public_only: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let server = TestServer::start(vec![
+            ("/reply", TestResponse::html(&format!(
+                "<div class=\"h-entry\"><a class=\"p-author h-card\" href=\"/\">Jane</a> \
+                 <a href=\"{}\">nice note</a></div>",
+                TARGET
+            ))),
+            ("/unrelated", TestResponse::html("<a href=\"https://example.com\">other</a>")),
+        ]).await;
+        let inner = inner();
+
+        for (path, status) in [
+            ("/reply", WebmentionStatus::Verified),
+            ("/unrelated", WebmentionStatus::Invalid),
+            ("/missing", WebmentionStatus::Invalid),
+        ] {
+            let webmention = Webmention::create(server.url(path), TARGET.to_string(), "note".to_string()).unwrap();
+            verify(&inner, webmention.clone()).await.unwrap();
+
+            let saved = inner.webmention_gateway.get_by_id(&webmention.id).await.unwrap();
+            assert_eq!(saved.status, status, "{}", path);
+        }
+
+        let verified = inner.webmention_gateway.list_by_note(&"note".to_string(), WebmentionStatus::Verified).await;
+        assert_eq!(verified[0].author.name.as_deref(), Some("Jane"));
+        assert_eq!(verified[0].author.url, Some(server.url("/")));
+    }
+
+    #[tokio::test]
+    async fn test_send() {
+        let server = TestServer::start(vec![
+            ("/by-header", TestResponse::html("").with_header("Link", "</endpoint>; rel=\"webmention\"")),
+            ("/by-html", TestResponse::html("<link rel=\"webmention\" href=\"/endpoint\">")),
+            ("/without", TestResponse::html("<p>no endpoint</p>")),
+            ("/endpoint", TestResponse::status(202)),
+        ]).await;
+        let html = format!(
+            "<a href=\"{}\">a</a> <a href=\"{}\">b</a> <a href=\"{}\">c</a> <a href=\"/notes/own\">own</a>",
+            server.url("/by-header"),
+            server.url("/by-html"),
+            server.url("/without")
+        );
+
+        send(&inner(), TARGET, &html).await;
+
+        let mentions = server.requests().await.into_iter()
+            .filter(|request| request.method == "POST")
+            .collect::<Vec<_>>();
+        assert_eq!(mentions.len(), 2);
+        assert!(mentions.iter().all(|request| request.path == "/endpoint"));
+        assert!(mentions[0].body.contains("source=https%3A%2F%2Fjkearnsl.su%2Fnotes%2Fsupa-title"));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_private_addresses() {
+        let server = TestServer::start(vec![
+            ("/reply", TestResponse::html(&format!("<a href=\"{}\">nice note</a>", TARGET))),
+        ]).await;
+        let inner = Inner { public_only: true, ..inner() };
+
+        let webmention = Webmention::create(server.url("/reply"), TARGET.to_string(), "note".to_string()).unwrap();
+        verify(&inner, webmention.clone()).await.unwrap();
+        send(&inner, TARGET, &format!("<a href=\"{}\">a</a>", server.url("/reply"))).await;
+
+        let saved = inner.webmention_gateway.get_by_id(&webmention.id).await.unwrap();
+        assert_eq!(saved.status, WebmentionStatus::Invalid);
+        assert!(server.requests().await.is_empty());
+    }
+}
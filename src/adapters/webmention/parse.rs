@@ -0,0 +1,179 @@
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+use crate::domain::models::webmention::MentionAuthor;
+
+
+/// Absolute http(s) links of the page, except links to `own_site`
+pub fn external_links(html: &str, base: &str, own_site: &str) -> Vec<String> {
+    let Ok(base) = Url::parse(base) else {
+        return vec![];
+    };
+    let own_host = Url::parse(own_site).ok().and_then(|url| url.host_str().map(str::to_string));
+    let document = Html::parse_fragment(html);
+    let selector = Selector::parse("a[href]").unwrap();
+
+    let mut links = Vec::new();
+    for element in document.select(&selector) {
+        let Some(url) = element.value().attr("href").and_then(|href| base.join(href).ok()) else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().map(str::to_string) == own_host {
+            continue;
+        }
+        let url = without_fragment(url);
+        if !links.contains(&url) {
+            links.push(url);
+        }
+    }
+    links
+}
+
+/// Whether any `href` or `src` of the page points to `target`
+pub fn links_to(html: &str, base: &str, target: &str) -> bool {
+    let (Ok(base), Ok(target)) = (Url::parse(base), Url::parse(target)) else {
+        return false;
+    };
+    let target = without_fragment(target);
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("[href], [src]").unwrap();
+
+    document.select(&selector).any(|element| {
+        ["href", "src"].iter()
+            .filter_map(|attr| element.value().attr(attr))
+            .filter_map(|value| base.join(value).ok())
+            .any(|url| without_fragment(url) == target)
+    })
+}
+
+/// Webmention endpoint advertised by `<link>` or `<a>` with `rel="webmention"`
+pub fn endpoint_from_html(html: &str, base: &str) -> Option<String> {
+    let base = Url::parse(base).ok()?;
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("link[rel][href], a[rel][href]").unwrap();
+
+    document.select(&selector)
+        .find(|element| has_rel(element.value().attr("rel").unwrap_or_default(), "webmention"))
+        .and_then(|element| base.join(element.value().attr("href")?).ok())
+        .map(String::from)
+}
+
+/// Webmention endpoint from `Link` header values, e.g. `<https://a/wm>; rel="webmention"`
+pub fn endpoint_from_link_headers<'a>(headers: impl Iterator<Item = &'a str>, base: &str) -> Option<String> {
+    let base = Url::parse(base).ok()?;
+    for header in headers {
+        for link in header.split(',') {
+            let mut parts = link.split(';');
+            let Some(url) = parts.next()
+                .map(str::trim)
+                .and_then(|url| url.strip_prefix('<'))
+                .and_then(|url| url.strip_suffix('>')) else {
+                continue;
+            };
+            let is_webmention = parts.any(|param| {
+                let Some((key, value)) = param.split_once('=') else {
+                    return false;
+                };
+                key.trim().eq_ignore_ascii_case("rel") && has_rel(value.trim().trim_matches('"'), "webmention")
+            });
+            if is_webmention {
+                return base.join(url).ok().map(String::from);
+            }
+        }
+    }
+    None
+}
+
+/// Author of the page from its h-card, preferring the author of the h-entry
+pub fn author(html: &str, base: &str) -> MentionAuthor {
+    let Ok(base) = Url::parse(base) else {
+        return MentionAuthor::default();
+    };
+    let document = Html::parse_document(html);
+    let card = [".h-entry .p-author.h-card", ".h-entry .h-card", ".h-card"].iter()
+        .find_map(|selector| document.select(&Selector::parse(selector).unwrap()).next());
+
+    let Some(card) = card else {
+        return MentionAuthor::default();
+    };
+
+    let first = |selector: &str| card.select(&Selector::parse(selector).unwrap()).next();
+    let resolve = |value: Option<&str>| value
+        .and_then(|value| base.join(value).ok())
+        .map(String::from);
+
+    let name = first(".p-name")
+        .map(|element| text(&element))
+        .or_else(|| Some(text(&card)))
+        .filter(|name| !name.is_empty());
+    let url = resolve(first(".u-url").and_then(|element| element.value().attr("href")))
+        .or_else(|| resolve(card.value().attr("href")));
+    let photo = resolve(first(".u-photo").and_then(|element| element.value().attr("src")));
+
+    MentionAuthor { name, url, photo }
+}
+
+fn text(element: &ElementRef) -> String {
+    element.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn has_rel(rel: &str, value: &str) -> bool {
+    rel.split_whitespace().any(|rel| rel.eq_ignore_ascii_case(value))
+}
+
+fn without_fragment(mut url: Url) -> String {
+    url.set_fragment(None);
+    url.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_links() {
+        let html = "<p><a href=\"https://example.com/a#top\">a</a> <a href=\"/notes/x\">own</a> \
+                    <a href=\"https://example.com/a\">again</a> <a href=\"mailto:me@example.com\">mail</a></p>";
+
+        assert_eq!(
+            external_links(html, "https://jkearnsl.su/notes/y", "https://jkearnsl.su"),
+            vec!["https://example.com/a".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_links_to() {
+        let html = "<a href=\"https://jkearnsl.su/notes/x#comments\">reply</a>";
+        assert!(links_to(html, "https://example.com/reply", "https://jkearnsl.su/notes/x"));
+        assert!(!links_to(html, "https://example.com/reply", "https://jkearnsl.su/notes/y"));
+    }
+
+    #[test]
+    fn test_endpoint_discovery() {
+        assert_eq!(
+            endpoint_from_link_headers(
+                ["<https://a.example/other>; rel=\"me\", </wm>; rel=\"webmention somethingelse\""].into_iter(),
+                "https://a.example/post"
+            ),
+            Some("https://a.example/wm".to_string())
+        );
+        assert_eq!(
+            endpoint_from_html("<link rel=\"webmention\" href=\"wm?x=1\">", "https://a.example/dir/post"),
+            Some("https://a.example/dir/wm?x=1".to_string())
+        );
+        assert_eq!(endpoint_from_html("<a href=\"/wm\">no rel</a>", "https://a.example/"), None);
+    }
+
+    #[test]
+    fn test_author() {
+        let html = "<div class=\"h-entry\"><a class=\"p-author h-card\" href=\"/me\">\
+                    <img class=\"u-photo\" src=\"/me.jpg\"> <span class=\"p-name\">Jane  Doe</span></a></div>";
+
+        assert_eq!(author(html, "https://example.com/reply"), MentionAuthor {
+            name: Some("Jane Doe".to_string()),
+            url: Some("https://example.com/me".to_string()),
+            photo: Some("https://example.com/me.jpg".to_string())
+        });
+        assert_eq!(author("<p>nobody</p>", "https://example.com/"), MentionAuthor::default());
+    }
+}
@@ -0,0 +1,24 @@
+use crate::application::common::actor_key_gateway::ActorKeyReader;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::services::activitypub::actor_document;
+use async_trait::async_trait;
+use serde_json::Value;
+
+pub struct GetActor<'a> {
+    pub actor: &'a LocalActor,
+    pub actor_key_reader: &'a dyn ActorKeyReader
+}
+
+#[async_trait]
+impl Interactor<(), Value> for GetActor<'_> {
+    async fn execute(&self, _data: ()) -> Result<Value, ApplicationError> {
+        // The key is created on startup
+        let key = self.actor_key_reader.get_current().await.ok_or_else(|| {
+            ApplicationError::UnexpectedError("Actor key is missing".to_string())
+        })?;
+
+        Ok(actor_document(self.actor, &key.public_key_pem))
+    }
+}
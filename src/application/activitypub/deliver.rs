@@ -0,0 +1,89 @@
+use chrono::Utc;
+use crate::application::common::delivery_gateway::{DeliveryGateway, DeliveryReader, DeliveryRemover, DeliveryWriter};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::federation_client::FederationClient;
+use crate::application::common::interactor::Interactor;
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// Deliveries handled by a single run
+pub const DELIVERY_BATCH: u64 = 50;
+
+#[derive(Debug, Default, Serialize)]
+pub struct ProcessDeliveriesResult {
+    pub delivered: u64,
+    pub retried: u64,
+    /// Failed too many times and were given up
+    pub dropped: u64
+}
+
+pub struct ProcessDeliveries<'a> {
+    pub delivery_gateway: &'a dyn DeliveryGateway,
+    pub federation_client: &'a dyn FederationClient
+}
+
+/// One pass over the delivery queue, meant to be run periodically
+#[async_trait]
+impl Interactor<(), ProcessDeliveriesResult> for ProcessDeliveries<'_> {
+    async fn execute(&self, _data: ()) -> Result<ProcessDeliveriesResult, ApplicationError> {
+        let mut result = ProcessDeliveriesResult::default();
+
+        for mut delivery in self.delivery_gateway.list_due(Utc::now(), &DELIVERY_BATCH).await {
+            match self.federation_client.deliver(&delivery.inbox, &delivery.activity).await {
+                Ok(()) => {
+                    self.delivery_gateway.remove(&delivery.id).await;
+                    result.delivered += 1;
+                }
+                Err(_) if delivery.fail(Utc::now()) => {
+                    self.delivery_gateway.save(&delivery).await;
+                    result.retried += 1;
+                }
+                Err(_) => {
+                    self.delivery_gateway.remove(&delivery.id).await;
+                    result.dropped += 1;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::delivery_gateway::test::MockDeliveryGateway;
+    use crate::application::common::federation_client::test::MockFederationClient;
+    use crate::domain::models::delivery::{Delivery, DELIVERY_ATTEMPTS_MAX};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_process_deliveries() {
+        let ok = Delivery::create("https://a.example/inbox".to_string(), "{}".to_string());
+        let flaky = Delivery::create("https://b.example/inbox".to_string(), "{}".to_string());
+        let mut dead = Delivery::create("https://b.example/inbox".to_string(), "{}".to_string());
+        dead.attempts = DELIVERY_ATTEMPTS_MAX - 1;
+
+        let delivery_gateway = MockDeliveryGateway::new(vec![ok, flaky.clone(), dead]);
+        let mut federation_client = MockFederationClient::new(vec![]);
+        federation_client.failing_inboxes = vec!["https://b.example/inbox".to_string()];
+
+        let interactor = ProcessDeliveries {
+            delivery_gateway: &delivery_gateway,
+            federation_client: &federation_client
+        };
+
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!((result.delivered, result.retried, result.dropped), (1, 1, 1));
+
+        let deliveries = delivery_gateway.deliveries.lock().await.clone();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].id, flaky.id);
+        assert_eq!(deliveries[0].attempts, 1);
+        assert!(deliveries[0].next_attempt_at > Utc::now());
+
+        // The retry is not due yet
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!((result.delivered, result.retried, result.dropped), (0, 0, 0));
+    }
+}
@@ -0,0 +1,21 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::follower_gateway::FollowerReader;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::services::activitypub::ordered_collection;
+use async_trait::async_trait;
+use serde_json::Value;
+
+pub struct GetFollowers<'a> {
+    pub actor: &'a LocalActor,
+    pub follower_reader: &'a dyn FollowerReader
+}
+
+/// Only the number of followers is public, not who they are
+#[async_trait]
+impl Interactor<(), Value> for GetFollowers<'_> {
+    async fn execute(&self, _data: ()) -> Result<Value, ApplicationError> {
+        let total = self.follower_reader.count().await;
+        Ok(ordered_collection(self.actor.followers(), total, vec![]))
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use crate::application::common::delivery_gateway::DeliveryWriter;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::federation_client::{FederationClient, SignedRequest};
+use crate::application::common::follower_gateway::{FollowerGateway, FollowerReader, FollowerRemover, FollowerWriter};
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::models::delivery::Delivery;
+use crate::domain::models::follower::Follower;
+use crate::domain::services::activitypub::{accept_activity, object_id};
+use async_trait::async_trait;
+use serde_json::Value;
+
+pub struct ReceiveActivity<'a> {
+    pub actor: &'a LocalActor,
+    pub follower_gateway: &'a dyn FollowerGateway,
+    pub delivery_writer: &'a dyn DeliveryWriter,
+    pub federation_client: &'a dyn FederationClient
+}
+
+/// Handles Follow and Undo{Follow}, other activities are accepted and ignored
+#[async_trait]
+impl Interactor<SignedRequest, ()> for ReceiveActivity<'_> {
+    async fn execute(&self, data: SignedRequest) -> Result<(), ApplicationError> {
+        let signer = self.federation_client.verify(&data).await.map_err(|_| {
+            ApplicationError::Unauthorized
+        })?;
+
+        let activity: Value = serde_json::from_slice(&data.body).map_err(|e| {
+            ApplicationError::ValidationError(HashMap::from([("body".to_string(), e.to_string())]))
+        })?;
+
+        // A server may only speak for its own actors
+        if object_id(&activity["actor"]) != Some(signer.id.as_str()) {
+            return Err(ApplicationError::Forbidden);
+        }
+
+        match activity["type"].as_str() {
+            Some("Follow") => {
+                if object_id(&activity["object"]) != Some(self.actor.id().as_str()) {
+                    return Err(ApplicationError::ValidationError(HashMap::from([(
+                        "object".to_string(),
+                        "is not this actor".to_string()
+                    )])));
+                }
+
+                // Repeated follows are answered again, the first Accept may have been lost
+                if self.follower_gateway.get_by_actor(&signer.id).await.is_none() {
+                    let follower = Follower::create(&signer).map_err(|e| {
+                        ApplicationError::ValidationError(e)
+                    })?;
+                    self.follower_gateway.save(&follower).await;
+                }
+
+                let accept = accept_activity(self.actor, &activity);
+                self.delivery_writer.save(&Delivery::create(signer.inbox, accept.to_string())).await;
+            }
+            Some("Undo") if activity["object"]["type"] == "Follow" => {
+                if let Some(follower) = self.follower_gateway.get_by_actor(&signer.id).await {
+                    self.follower_gateway.remove(&follower.id).await;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::delivery_gateway::test::MockDeliveryGateway;
+    use crate::application::common::federation_client::test::MockFederationClient;
+    use crate::application::common::follower_gateway::test::MockFollowerGateway;
+    use crate::domain::models::actor::RemoteActor;
+    use serde_json::json;
+    use super::*;
+
+    const REMOTE: &str = "https://mastodon.example/users/jane";
+
+    fn remote() -> RemoteActor {
+        RemoteActor {
+            id: REMOTE.to_string(),
+            inbox: format!("{}/inbox", REMOTE),
+            shared_inbox: Some("https://mastodon.example/inbox".to_string())
+        }
+    }
+
+    fn request(signer: Option<&str>, activity: Value) -> SignedRequest {
+        SignedRequest {
+            method: "POST".to_string(),
+            path: "/actor/inbox".to_string(),
+            headers: signer
+                .map(|signer| HashMap::from([("signature".to_string(), signer.to_string())]))
+                .unwrap_or_default(),
+            body: activity.to_string().into_bytes()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_and_undo() {
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let federation_client = MockFederationClient::new(vec![remote()]);
+
+        let interactor = ReceiveActivity {
+            actor: &actor,
+            follower_gateway: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            federation_client: &federation_client
+        };
+        let follow = json!({
+            "id": "https://mastodon.example/1",
+            "type": "Follow",
+            "actor": REMOTE,
+            "object": actor.id()
+        });
+
+        interactor.execute(request(Some(REMOTE), follow.clone())).await.unwrap();
+        interactor.execute(request(Some(REMOTE), follow.clone())).await.unwrap();
+
+        let followers = follower_gateway.followers.lock().await.clone();
+        assert_eq!(followers.len(), 1);
+        assert_eq!(followers[0].inbox, "https://mastodon.example/inbox");
+
+        let deliveries = delivery_gateway.deliveries.lock().await.clone();
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].inbox, format!("{}/inbox", REMOTE));
+        let accept: Value = serde_json::from_str(&deliveries[0].activity).unwrap();
+        assert_eq!(accept["type"], "Accept");
+        assert_eq!(accept["object"]["id"], "https://mastodon.example/1");
+
+        interactor.execute(request(Some(REMOTE), json!({
+            "type": "Undo",
+            "actor": REMOTE,
+            "object": follow
+        }))).await.unwrap();
+        assert!(follower_gateway.followers.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reject_unsigned_and_forged() {
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let federation_client = MockFederationClient::new(vec![remote()]);
+
+        let interactor = ReceiveActivity {
+            actor: &actor,
+            follower_gateway: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            federation_client: &federation_client
+        };
+        let follow = |by: &str| json!({
+            "type": "Follow",
+            "actor": by,
+            "object": actor.id()
+        });
+
+        let result = interactor.execute(request(None, follow(REMOTE))).await;
+        assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+
+        let result = interactor.execute(request(Some(REMOTE), follow("https://mastodon.example/users/bob"))).await;
+        assert!(matches!(result, Err(ApplicationError::Forbidden)));
+
+        assert!(follower_gateway.followers.lock().await.is_empty());
+        assert!(delivery_gateway.deliveries.lock().await.is_empty());
+    }
+}
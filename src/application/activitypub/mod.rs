@@ -0,0 +1,7 @@
+pub mod webfinger;
+pub mod actor;
+pub mod inbox;
+pub mod outbox;
+pub mod followers;
+pub mod deliver;
+pub mod publish;
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteReader;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::services::activitypub::{article, create_activity, ordered_collection};
+use crate::domain::services::markdown::render_markdown;
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// Remote servers only backfill a few recent posts anyway
+pub const OUTBOX_SIZE: u64 = 20;
+
+pub struct GetOutbox<'a> {
+    pub actor: &'a LocalActor,
+    pub note_reader: &'a dyn NoteReader
+}
+
+#[async_trait]
+impl Interactor<(), Value> for GetOutbox<'_> {
+    async fn execute(&self, _data: ()) -> Result<Value, ApplicationError> {
+        let total = self.note_reader.count().await;
+
+        let mut items = Vec::new();
+        for item in self.note_reader.range(&OUTBOX_SIZE, &0).await {
+            let Some(note) = self.note_reader.get_by_id(&item.id).await else {
+                continue;
+            };
+            let html = render_markdown(&note.body, &HashMap::new());
+            items.push(create_activity(self.actor, article(self.actor, &note, &html)));
+        }
+
+        Ok(ordered_collection(self.actor.outbox(), total, items))
+    }
+}
@@ -0,0 +1,16 @@
+use serde_json::Value;
+use crate::application::common::delivery_gateway::DeliveryWriter;
+use crate::application::common::follower_gateway::FollowerReader;
+use crate::domain::models::delivery::Delivery;
+
+/// Queues the activity for every follower inbox, shared inboxes get a single copy
+pub async fn publish_to_followers(
+    follower_reader: &dyn FollowerReader,
+    delivery_writer: &dyn DeliveryWriter,
+    activity: &Value
+) {
+    let activity = activity.to_string();
+    for inbox in follower_reader.inboxes().await {
+        delivery_writer.save(&Delivery::create(inbox, activity.clone())).await;
+    }
+}
@@ -0,0 +1,60 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::services::activitypub::webfinger;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Deserialize)]
+pub struct GetWebfingerRequest {
+    /// `acct:username@host` or the actor IRI
+    pub resource: String
+}
+
+pub struct GetWebfinger<'a> {
+    pub actor: &'a LocalActor
+}
+
+#[async_trait]
+impl Interactor<GetWebfingerRequest, Value> for GetWebfinger<'_> {
+    async fn execute(
+        &self,
+        data: GetWebfingerRequest
+    ) -> Result<Value, ApplicationError> {
+
+        let known = match data.resource.strip_prefix("acct:") {
+            Some(acct) => acct.eq_ignore_ascii_case(&self.actor.acct()),
+            None => data.resource == self.actor.id()
+        };
+        if !known {
+            return Err(ApplicationError::NotFound);
+        }
+
+        Ok(webfinger(self.actor))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_webfinger() {
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let interactor = GetWebfinger { actor: &actor };
+
+        for resource in ["acct:blog@jkearnsl.su", "acct:Blog@JKearnsl.su", "https://jkearnsl.su/actor"] {
+            let result = interactor.execute(GetWebfingerRequest {
+                resource: resource.to_string()
+            }).await.unwrap();
+            assert_eq!(result["links"][0]["href"], "https://jkearnsl.su/actor");
+        }
+
+        let result = interactor.execute(GetWebfingerRequest {
+            resource: "acct:admin@jkearnsl.su".to_string()
+        }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound)));
+    }
+}
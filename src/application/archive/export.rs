@@ -0,0 +1,106 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::media_gateway::MediaReader;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::pagination::Cursor;
+use crate::application::common::project_gateway::ProjectReader;
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::UserReader;
+use crate::domain::models::archive::{note_path, ArchiveEntry, Manifest, MANIFEST_PATH};
+use crate::domain::services::front_matter::render_note;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+const PAGE_SIZE: u64 = 100;
+
+#[derive(Debug, Serialize)]
+pub struct ExportContentResult {
+    /// Written out by the presentation layer, to a directory or a tarball
+    #[serde(skip)]
+    pub entries: Vec<ArchiveEntry>,
+    pub notes: usize,
+    pub projects: usize,
+    pub media: usize,
+    pub users: usize
+}
+
+pub struct ExportContent<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub media_reader: &'a dyn MediaReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Notes as Markdown files with front matter, the rest in `manifest.json`,
+/// see [`Manifest`]. Everything is read in one unit of work, so the archive
+/// is consistent even while the site is being edited.
+///
+/// The trash, comments, subscribers and statistics are not exported
+#[async_trait]
+impl Interactor<(), ExportContentResult> for ExportContent<'_> {
+    async fn execute(&self, _data: ()) -> Result<ExportContentResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        let mut notes = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = unit.note_gateway().range_by_cursor(cursor.as_ref(), &PAGE_SIZE).await;
+            for item in &page {
+                notes.extend(unit.note_gateway().get_by_id(&item.id).await);
+            }
+            cursor = next_page(page.last().map(|last| (last.created_at, &last.id)), page.len());
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let mut projects = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = unit.project_gateway().get_projects_by_cursor(cursor.as_ref(), &PAGE_SIZE).await;
+            cursor = next_page(page.last().map(|last| (last.created_at, &last.id)), page.len());
+            projects.extend(page);
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let users = unit.user_gateway().get_all().await;
+        unit.rollback().await.map_err(ApplicationError::UnexpectedError)?;
+        let media = self.media_reader.get_all().await;
+
+        let result = ExportContentResult {
+            entries: Vec::new(),
+            notes: notes.len(),
+            projects: projects.len(),
+            media: media.len(),
+            users: users.len()
+        };
+        let manifest = Manifest::create(&notes, projects, media, &users);
+
+        let mut entries = vec![ArchiveEntry {
+            path: MANIFEST_PATH.to_string(),
+            content: serde_json::to_vec_pretty(&manifest).unwrap()
+        }];
+        entries.extend(notes.iter().map(|note| ArchiveEntry {
+            path: note_path(note),
+            content: render_note(note).into_bytes()
+        }));
+
+        Ok(ExportContentResult { entries, ..result })
+    }
+}
+
+/// `None` after the last page, a short one
+fn next_page(last: Option<(DateTime<Utc>, &String)>, len: usize) -> Option<Cursor> {
+    match last {
+        Some((created_at, id)) if len as u64 == PAGE_SIZE => Some(Cursor::after(created_at, id.clone())),
+        _ => None
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::{HashMap, HashSet};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::media_gateway::{MediaGateway, MediaReader, MediaWriter};
+use crate::application::common::media_storage::MediaStorage;
+use crate::application::common::note_gateway::{NoteReader, NoteWriter};
+use crate::application::common::project_gateway::{ProjectReader, ProjectWriter};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::{UserReader, UserWriter};
+use crate::domain::models::archive::{ArchiveEntry, Manifest, MANIFEST_PATH};
+use crate::domain::models::audit::{note_summary, project_summary, user_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::hash::Hash;
+use crate::domain::models::project::Project;
+use crate::domain::models::user::User;
+use crate::domain::services::front_matter::parse_note;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[derive(Debug)]
+pub struct RestoreContentRequest {
+    pub entries: Vec<ArchiveEntry>,
+    /// Check the archive and report what would change without writing anything
+    pub dry_run: bool,
+    /// Given to the users that do not exist yet, archives carry no passwords
+    pub password_hash: Hash,
+    /// Filled in by the presentation layer for the audit log
+    pub ip: Option<String>
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct RestoreCounts {
+    pub created: u64,
+    pub updated: u64,
+    pub unchanged: u64,
+    /// In the trash, they come back by restoring them from there
+    pub skipped: u64
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct RestoreContentResult {
+    pub dry_run: bool,
+    pub notes: RestoreCounts,
+    pub projects: RestoreCounts,
+    pub media: RestoreCounts,
+    pub users: RestoreCounts,
+    /// Created users, they can not sign in until their password is set
+    pub users_without_password: Vec<String>,
+    /// Hashes of media whose files are not in the media storage
+    pub missing_files: Vec<String>
+}
+
+pub struct RestoreContent<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub media_gateway: &'a dyn MediaGateway,
+    pub media_storage: &'a dyn MediaStorage,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Reads an archive made by [`ExportContent`](super::export::ExportContent).
+/// Notes and projects are written as they are in the archive, dates included,
+/// replacing the stored ones with the same id. A replaced one gets the version
+/// after the stored one, so editors still holding that version get a conflict.
+/// Notes and projects whose id is in the trash are skipped, the trash decides
+/// whether they come back. Users are matched by username and existing ones are left alone.
+///
+/// Notes, projects and users are restored in one unit of work with their
+/// audit entries: a broken archive changes nothing
+#[async_trait]
+impl Interactor<RestoreContentRequest, RestoreContentResult> for RestoreContent<'_> {
+    async fn execute(&self, data: RestoreContentRequest) -> Result<RestoreContentResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let invalid = |field: &str, message: String| ApplicationError::ValidationError(
+            HashMap::from([(field.to_string(), message)])
+        );
+        let invalid_fields = |prefix: &str, errors: HashMap<String, String>| ApplicationError::ValidationError(
+            errors.into_iter().map(|(field, message)| (format!("{} {}", prefix, field), message)).collect()
+        );
+
+        let entries: HashMap<String, Vec<u8>> = data.entries.into_iter()
+            .map(|entry| (entry.path, entry.content))
+            .collect();
+        let manifest = entries.get(MANIFEST_PATH)
+            .ok_or_else(|| invalid("archive", format!("has no {}", MANIFEST_PATH)))
+            .and_then(|content| Manifest::parse(content).map_err(|e| invalid("archive", e)))?;
+
+        let mut notes = Vec::new();
+        for archived in &manifest.notes {
+            let content = entries.get(&archived.path)
+                .ok_or_else(|| invalid(&archived.path, "is missing".to_string()))?;
+            let note = std::str::from_utf8(content)
+                .map_err(|_| "is not UTF-8".to_string())
+                .and_then(parse_note)
+                .map_err(|e| invalid(&archived.path, e))?;
+            if note.id != archived.id {
+                return Err(invalid(&archived.path, format!("has id {}, expected {}", note.id, archived.id)));
+            }
+            notes.push(note);
+        }
+
+        let mut projects = Vec::new();
+        for project in &manifest.projects {
+            projects.push(Project::import(
+                project.id.clone(),
+                project.title.clone(),
+                project.description.clone(),
+                project.url.clone(),
+                project.created_at
+            ).map_err(|e| invalid_fields(&format!("project {}", project.id), e))?);
+        }
+
+        let mut result = RestoreContentResult {
+            dry_run: data.dry_run,
+            ..Default::default()
+        };
+        let entry = |action, target, id: &String, before, after| AuditEntry::create(
+            self.id_provider.username().cloned(),
+            action,
+            target,
+            id.clone(),
+            before,
+            after,
+            data.ip.clone()
+        );
+
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        // Readers act as if these were gone, saving them would bring them back half way
+        let trashed_notes: HashSet<String> = unit.note_gateway().get_deleted().await.into_iter()
+            .map(|item| item.id)
+            .collect();
+        let trashed_projects: HashSet<String> = unit.project_gateway().get_deleted_projects().await.into_iter()
+            .map(|item| item.id)
+            .collect();
+
+        for mut note in notes {
+            if trashed_notes.contains(&note.id) {
+                result.notes.skipped += 1;
+                continue;
+            }
+            match unit.note_gateway().get_by_id(&note.id).await {
+                Some(existing) => {
+                    note.version = existing.version;
+                    if existing == note {
+                        result.notes.unchanged += 1;
+                        continue;
+                    }
+                    note.version += 1;
+                    result.notes.updated += 1;
+                    unit.audit_writer().save(&entry(AuditAction::Update, AuditTarget::Note, &note.id, Some(note_summary(&existing)), Some(note_summary(&note)))).await;
+                }
+                None => {
+                    result.notes.created += 1;
+                    unit.audit_writer().save(&entry(AuditAction::Create, AuditTarget::Note, &note.id, None, Some(note_summary(&note)))).await;
+                }
+            }
+            unit.note_gateway().save(&note).await;
+        }
+
+        for mut project in projects {
+            if trashed_projects.contains(&project.id) {
+                result.projects.skipped += 1;
+                continue;
+            }
+            match unit.project_gateway().get_project(&project.id).await {
+                Some(existing) => {
+                    project.version = existing.version;
+                    if existing == project {
+                        result.projects.unchanged += 1;
+                        continue;
+                    }
+                    project.version += 1;
+                    result.projects.updated += 1;
+                    unit.audit_writer().save(&entry(AuditAction::Update, AuditTarget::Project, &project.id, Some(project_summary(&existing)), Some(project_summary(&project)))).await;
+                }
+                None => {
+                    result.projects.created += 1;
+                    unit.audit_writer().save(&entry(AuditAction::Create, AuditTarget::Project, &project.id, None, Some(project_summary(&project)))).await;
+                }
+            }
+            unit.project_gateway().save_project(&project).await;
+        }
+
+        let taken: HashSet<String> = unit.user_gateway().get_all().await.into_iter()
+            .map(|user| user.id)
+            .collect();
+        for archived in &manifest.users {
+            if unit.user_gateway().get_by_username(&archived.username).await.is_some() {
+                result.users.unchanged += 1;
+                continue;
+            }
+            let mut user = User::create(archived.username.clone(), data.password_hash.clone())
+                .map_err(|e| invalid_fields(&format!("user {}", archived.username), e))?;
+            if !taken.contains(&archived.id) {
+                user.id = archived.id.clone();
+            }
+            unit.user_gateway().save(&user).await;
+            result.users.created += 1;
+            result.users_without_password.push(user.username.clone());
+            unit.audit_writer().save(&entry(AuditAction::Create, AuditTarget::User, &user.id, None, Some(user_summary(&user)))).await;
+        }
+
+        match data.dry_run {
+            true => unit.rollback().await,
+            false => unit.commit().await
+        }.map_err(ApplicationError::UnexpectedError)?;
+
+        // Media records are immutable, the ones already stored are kept
+        for media in &manifest.media {
+            match self.media_gateway.get_by_hash(&media.hash).await {
+                Some(_) => result.media.unchanged += 1,
+                None => {
+                    result.media.created += 1;
+                    if !data.dry_run {
+                        self.media_gateway.save(media).await;
+                    }
+                }
+            }
+            if self.media_storage.get(&media.hash).await.is_none() {
+                result.missing_files.push(media.hash.to_string());
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::archive::export::ExportContent;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::media_gateway::test::MockMediaGateway;
+    use crate::application::common::media_storage::test::MockMediaStorage;
+    use crate::application::common::note_gateway::NoteRemover;
+    use crate::application::common::project_gateway::ProjectRemover;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use crate::domain::models::media::Media;
+    use crate::domain::models::note::Note;
+    use crate::domain::models::project::PROJECT_TITLE_MAX;
+    use crate::domain::models::user::USERNAME_MAX;
+    use super::*;
+
+    fn id_provider() -> Box<MockIdProvider> {
+        Box::new(MockIdProvider {
+            session: None,
+            is_auth: true,
+            username: Some("test".parse().unwrap())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_restore_exported_content() {
+        let source = MockUnitOfWork::new();
+        let source_media = MockMediaGateway::new(HashMap::new());
+        // Slugs are not unique, both notes are restored
+        for title in ["First", "First"] {
+            let mut note = Note::create(title.to_string(), format!("{}\n---\n", title)).unwrap();
+            note.version = 4;
+            source.note_gateway.save(&note).await;
+        }
+        source.project_gateway.save_project(
+            &Project::create("Project".to_string(), "About".to_string(), None).unwrap()
+        ).await;
+        source.user_gateway.save(&User::create("alice".to_string(), Hash([1; Hash::SIZE])).unwrap()).await;
+        let media = Media::create(Hash::sha256(b"file"), Some("image/png"), 4, "a.png".to_string(), "alice".to_string(), None).unwrap();
+        source_media.save(&media).await;
+
+        let exported = ExportContent {
+            unit_of_work: &source,
+            media_reader: &source_media,
+            id_provider: id_provider()
+        }.execute(()).await.unwrap();
+        assert_eq!((exported.notes, exported.projects, exported.media, exported.users), (2, 1, 1, 1));
+        assert_eq!(exported.entries.len(), 3);
+
+        let target = MockUnitOfWork::new();
+        let target_media = MockMediaGateway::new(HashMap::new());
+        let media_storage = MockMediaStorage::new();
+        let interactor = RestoreContent {
+            unit_of_work: &target,
+            media_gateway: &target_media,
+            media_storage: &media_storage,
+            id_provider: id_provider()
+        };
+        let request = |dry_run| RestoreContentRequest {
+            entries: exported.entries.clone(),
+            dry_run,
+            password_hash: Hash([0; Hash::SIZE]),
+            ip: None
+        };
+
+        let dry_run = interactor.execute(request(true)).await.unwrap();
+        assert_eq!(dry_run.notes.created, 2);
+        assert!(target.note_gateway.notes.lock().await.is_empty());
+        assert!(target.audit_gateway.entries.lock().await.is_empty());
+
+        let restored = interactor.execute(request(false)).await.unwrap();
+        assert_eq!((restored.notes.created, restored.projects.created, restored.media.created), (2, 1, 1));
+        assert_eq!(restored.users_without_password, vec!["alice"]);
+        assert_eq!(restored.missing_files, vec![media.hash.to_string()]);
+        assert_eq!(*target.note_gateway.notes.lock().await, *source.note_gateway.notes.lock().await);
+        assert_eq!(*target.project_gateway.projects.lock().await, *source.project_gateway.projects.lock().await);
+        assert_eq!(target_media.get_all().await, vec![media]);
+        assert_eq!(target.user_gateway.users.lock().await[0].id, source.user_gateway.users.lock().await[0].id);
+        assert_eq!(target.audit_gateway.entries.lock().await.len(), 4);
+
+        let again = interactor.execute(request(false)).await.unwrap();
+        assert_eq!((again.notes.unchanged, again.projects.unchanged, again.users.unchanged), (2, 1, 1));
+
+        // Replaced notes move past the stored version, not back to the archived one
+        let edited = source.note_gateway.notes.lock().await.keys().next().unwrap().clone();
+        target.note_gateway.notes.lock().await.get_mut(&edited).unwrap().version = 7;
+        let replaced = interactor.execute(request(false)).await.unwrap();
+        assert_eq!((replaced.notes.unchanged, replaced.notes.updated), (2, 0));
+        target.note_gateway.notes.lock().await.get_mut(&edited).unwrap().body = "Edited".to_string();
+        let replaced = interactor.execute(request(false)).await.unwrap();
+        assert_eq!((replaced.notes.unchanged, replaced.notes.updated), (1, 1));
+        let note = target.note_gateway.get_by_id(&edited).await.unwrap();
+        assert_eq!((note.body, note.version), (source.note_gateway.notes.lock().await[&edited].body.clone(), 8));
+
+        let trashed = source.note_gateway.notes.lock().await.keys().next().unwrap().clone();
+        target.note_gateway.remove(&trashed).await;
+        let project_id = source.project_gateway.projects.lock().await.keys().next().unwrap().clone();
+        target.project_gateway.remove_project(&project_id).await;
+        let skipped = interactor.execute(request(false)).await.unwrap();
+        assert_eq!((skipped.notes.unchanged, skipped.notes.skipped, skipped.projects.skipped), (1, 1, 1));
+        assert!(target.note_gateway.get_by_id(&trashed).await.is_none());
+
+        let mut broken = request(false);
+        broken.entries.retain(|entry| entry.path != MANIFEST_PATH);
+        assert!(matches!(interactor.execute(broken).await, Err(ApplicationError::ValidationError(_))));
+
+        // Manifest projects and users go through the same checks as new ones
+        let with_manifest = |change: &dyn Fn(&mut Manifest)| {
+            let mut request = request(false);
+            let entry = request.entries.iter_mut().find(|entry| entry.path == MANIFEST_PATH).unwrap();
+            let mut manifest = Manifest::parse(&entry.content).unwrap();
+            change(&mut manifest);
+            entry.content = serde_json::to_vec(&manifest).unwrap();
+            request
+        };
+        let project_key = format!("project {} title", project_id);
+        let invalid = with_manifest(&|manifest| manifest.projects[0].title = "a".repeat(PROJECT_TITLE_MAX + 1));
+        assert!(matches!(
+            interactor.execute(invalid).await,
+            Err(ApplicationError::ValidationError(errors)) if errors.contains_key(&project_key)
+        ));
+        let user_key = format!("user {} username", "b".repeat(USERNAME_MAX + 1));
+        let invalid = with_manifest(&|manifest| manifest.users[0].username = "b".repeat(USERNAME_MAX + 1));
+        assert!(matches!(
+            interactor.execute(invalid).await,
+            Err(ApplicationError::ValidationError(errors)) if errors.contains_key(&user_key)
+        ));
+        assert_eq!(target.user_gateway.users.lock().await.len(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use crate::application::common::audit_gateway::{AuditFilter, AuditReader};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::pagination::{keyset_page, Cursor};
+use crate::domain::models::audit::{AuditAction, AuditEntry, AuditTarget};
+use crate::domain::services::validator::validate_per_page;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ListAuditRequest {
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub target: Option<AuditTarget>,
+    pub target_id: Option<String>,
+    /// Inclusive
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive
+    pub until: Option<DateTime<Utc>>,
+    pub cursor: Option<String>,
+    pub per_page: u64
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAuditResult {
+    pub items: Vec<AuditEntry>,
+    pub next: Option<String>,
+    pub prev: Option<String>
+}
+
+pub struct ListAudit<'a> {
+    pub audit_reader: &'a dyn AuditReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Newest first
+#[async_trait]
+impl Interactor<ListAuditRequest, ListAuditResult> for ListAudit<'_> {
+    async fn execute(&self, data: ListAuditRequest) -> Result<ListAuditResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        validate_per_page(&data.per_page).map_err(|e| {
+            ApplicationError::ValidationError(HashMap::from([("per_page".to_string(), e)]))
+        })?;
+
+        let cursor = match data.cursor {
+            Some(cursor) => Some(Cursor::decode(&cursor).map_err(|e| {
+                ApplicationError::ValidationError(HashMap::from([("cursor".to_string(), e)]))
+            })?),
+            None => None
+        };
+
+        let filter = AuditFilter {
+            actor: data.actor,
+            action: data.action,
+            target: data.target,
+            target_id: data.target_id,
+            since: data.since,
+            until: data.until
+        };
+        let rows = self.audit_reader.list(&filter, cursor.as_ref(), &(data.per_page + 1)).await;
+        let page = keyset_page(rows, data.per_page, cursor.as_ref(), |entry| {
+            (entry.created_at, entry.id.clone())
+        });
+
+        Ok(ListAuditResult {
+            items: page.items,
+            next: page.next,
+            prev: page.prev
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::audit_gateway::test::MockAuditGateway;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_audit() {
+        let entry = |actor: &str, action: AuditAction| AuditEntry::create(
+            Some(actor.to_string()),
+            action,
+            AuditTarget::Note,
+            "note".to_string(),
+            None,
+            None,
+            None
+        );
+        let audit_gateway = MockAuditGateway::new(vec![
+            entry("admin", AuditAction::Create),
+            entry("bot", AuditAction::Update),
+            entry("bot", AuditAction::Update),
+            entry("bot", AuditAction::Delete)
+        ]);
+
+        let interactor = ListAudit {
+            audit_reader: &audit_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".to_string())
+            })
+        };
+        let request = |cursor: Option<String>| ListAuditRequest {
+            actor: Some("bot".to_string()),
+            action: Some(AuditAction::Update),
+            target: None,
+            target_id: None,
+            since: None,
+            until: None,
+            cursor,
+            per_page: 1
+        };
+
+        let first = interactor.execute(request(None)).await.unwrap();
+        assert_eq!(first.items.len(), 1);
+        let second = interactor.execute(request(first.next)).await.unwrap();
+        assert_eq!(second.items.len(), 1);
+        assert_ne!(second.items[0].id, first.items[0].id);
+        assert!(second.next.is_none());
+    }
+}
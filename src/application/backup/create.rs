@@ -0,0 +1,92 @@
+use crate::application::common::backup_storage::BackupStorage;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::backup::{Backup, BackupRetention};
+use crate::domain::services::backup::backups_to_keep;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct CreateBackupResult {
+    pub backup: Backup,
+    /// Snapshots dropped by the rotation
+    pub removed: Vec<String>
+}
+
+pub struct CreateBackup<'a> {
+    pub backup_storage: &'a dyn BackupStorage,
+    pub retention: &'a BackupRetention,
+    /// Run `PRAGMA integrity_check` on the snapshot before keeping it
+    pub integrity_check: bool
+}
+
+/// Takes a snapshot and rotates the old ones. Runs on a schedule,
+/// [`TriggerBackup`](super::trigger::TriggerBackup) is the same on demand
+#[async_trait]
+impl Interactor<(), CreateBackupResult> for CreateBackup<'_> {
+    async fn execute(&self, _data: ()) -> Result<CreateBackupResult, ApplicationError> {
+        let backup = self.backup_storage.create(self.integrity_check).await.map_err(
+            ApplicationError::UnexpectedError
+        )?;
+
+        let backups = self.backup_storage.list().await.map_err(ApplicationError::UnexpectedError)?;
+        let keep = backups_to_keep(&backups, self.retention);
+
+        // A snapshot that could not be removed is tried again next time
+        let mut removed = Vec::new();
+        for old in backups.into_iter().filter(|old| !keep.contains(&old.name)) {
+            if self.backup_storage.remove(&old.name).await.is_ok() {
+                removed.push(old.name);
+            }
+        }
+
+        Ok(CreateBackupResult { backup, removed })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::application::common::backup_storage::test::MockBackupStorage;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_backup() {
+        let backup_storage = MockBackupStorage::new(vec![]);
+        *backup_storage.next.lock().await = Utc.with_ymd_and_hms(2024, 1, 1, 3, 0, 0).unwrap();
+
+        let interactor = CreateBackup {
+            backup_storage: &backup_storage,
+            retention: &BackupRetention { daily: 3, weekly: 0, monthly: 0 },
+            integrity_check: true
+        };
+        for _ in 0..5 {
+            interactor.execute(()).await.unwrap();
+        }
+
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!(result.backup.created_at, Utc.with_ymd_and_hms(2024, 1, 6, 3, 0, 0).unwrap());
+        assert_eq!(result.removed.len(), 1);
+
+        let backups = backup_storage.list().await.unwrap();
+        assert_eq!(backups.len(), 3);
+        assert!(backups.iter().all(|b| b.created_at > result.backup.created_at - Duration::days(3)));
+    }
+
+    #[tokio::test]
+    async fn test_create_backup_corrupted() {
+        let mut backup_storage = MockBackupStorage::new(vec![]);
+        backup_storage.corrupted = true;
+
+        let interactor = CreateBackup {
+            backup_storage: &backup_storage,
+            retention: &BackupRetention { daily: 3, weekly: 0, monthly: 0 },
+            integrity_check: true
+        };
+        let result = interactor.execute(()).await;
+
+        assert!(matches!(result, Err(ApplicationError::UnexpectedError(_))));
+        assert!(backup_storage.list().await.unwrap().is_empty());
+    }
+}
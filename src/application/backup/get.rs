@@ -0,0 +1,79 @@
+use crate::application::common::backup_storage::BackupStorage;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::backup::backup_created_at;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetBackupRequest {
+    pub name: String
+}
+
+pub struct GetBackupResult {
+    pub name: String,
+    /// Gzip compressed SQLite database
+    pub data: Vec<u8>
+}
+
+pub struct GetBackup<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub backup_storage: &'a dyn BackupStorage
+}
+
+#[async_trait]
+impl Interactor<GetBackupRequest, GetBackupResult> for GetBackup<'_> {
+    async fn execute(&self, data: GetBackupRequest) -> Result<GetBackupResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        // Only names the storage gives out, never a path
+        if backup_created_at(&data.name).is_none() {
+            return Err(ApplicationError::NotFound);
+        }
+
+        match self.backup_storage.get(&data.name).await {
+            Some(backup) => Ok(GetBackupResult { name: data.name, data: backup }),
+            None => Err(ApplicationError::NotFound)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use crate::application::common::backup_storage::test::MockBackupStorage;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::domain::models::backup::{backup_name, Backup};
+    use super::*;
+
+    fn id_provider(is_auth: bool) -> Box<MockIdProvider> {
+        Box::new(MockIdProvider {
+            session: None,
+            username: None,
+            is_auth
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_backup() {
+        let created_at = Utc::now();
+        let name = backup_name(created_at);
+        let backup_storage = MockBackupStorage::new(vec![Backup { name: name.clone(), created_at, size: 8 }]);
+
+        let interactor = GetBackup { id_provider: id_provider(true), backup_storage: &backup_storage };
+        let result = interactor.execute(GetBackupRequest { name: name.clone() }).await.unwrap();
+        assert_eq!(result.data, b"snapshot");
+
+        let result = interactor.execute(GetBackupRequest { name: "../database".to_string() }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound)));
+
+        let interactor = GetBackup { id_provider: id_provider(false), backup_storage: &backup_storage };
+        let result = interactor.execute(GetBackupRequest { name }).await;
+        assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+    }
+}
@@ -0,0 +1,26 @@
+use crate::application::common::backup_storage::BackupStorage;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::backup::Backup;
+use async_trait::async_trait;
+
+pub struct ListBackups<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub backup_storage: &'a dyn BackupStorage
+}
+
+/// Newest first
+#[async_trait]
+impl Interactor<(), Vec<Backup>> for ListBackups<'_> {
+    async fn execute(&self, _data: ()) -> Result<Vec<Backup>, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let mut backups = self.backup_storage.list().await.map_err(ApplicationError::UnexpectedError)?;
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(backups)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod create;
+pub mod trigger;
+pub mod list;
+pub mod get;
@@ -0,0 +1,57 @@
+use crate::application::backup::create::{CreateBackup, CreateBackupResult};
+use crate::application::common::backup_storage::BackupStorage;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::backup::BackupRetention;
+use async_trait::async_trait;
+
+pub struct TriggerBackup<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub backup_storage: &'a dyn BackupStorage,
+    pub retention: &'a BackupRetention,
+    pub integrity_check: bool
+}
+
+#[async_trait]
+impl Interactor<(), CreateBackupResult> for TriggerBackup<'_> {
+    async fn execute(&self, _data: ()) -> Result<CreateBackupResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        CreateBackup {
+            backup_storage: self.backup_storage,
+            retention: self.retention,
+            integrity_check: self.integrity_check
+        }.execute(()).await
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::backup_storage::test::MockBackupStorage;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trigger_backup_unauthorized() {
+        let backup_storage = MockBackupStorage::new(vec![]);
+
+        let interactor = TriggerBackup {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                username: None,
+                is_auth: false
+            }),
+            backup_storage: &backup_storage,
+            retention: &BackupRetention { daily: 7, weekly: 4, monthly: 12 },
+            integrity_check: false
+        };
+
+        assert!(matches!(interactor.execute(()).await, Err(ApplicationError::Unauthorized)));
+        assert!(backup_storage.list().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::application::common::comment_gateway::{CommentGateway, CommentReader, CommentWriter};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::comment::CommentId;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ApproveCommentRequest {
+    pub id: CommentId
+}
+
+pub struct ApproveComment<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub comment_gateway: &'a dyn CommentGateway
+}
+
+#[async_trait]
+impl Interactor<ApproveCommentRequest, ()> for ApproveComment<'_> {
+    async fn execute(&self, data: ApproveCommentRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let mut comment = self.comment_gateway.get_by_id(&data.id).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        comment.approve();
+        self.comment_gateway.save(&comment).await;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::comment_gateway::test::MockCommentGateway;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::domain::models::comment::{Comment, CommentStatus};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_approve_comment() {
+        let comment = Comment::create("note".to_string(), None, "A".to_string(), "Hi".to_string()).unwrap();
+        let comment_gateway = MockCommentGateway::new(vec![comment.clone()]);
+
+        let interactor = ApproveComment {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("admin".to_string())
+            }),
+            comment_gateway: &comment_gateway
+        };
+
+        interactor.execute(ApproveCommentRequest { id: comment.id.clone() }).await.unwrap();
+
+        let stored = comment_gateway.get_by_id(&comment.id).await.unwrap();
+        assert_eq!(stored.status, CommentStatus::Approved);
+    }
+}
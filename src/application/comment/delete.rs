@@ -0,0 +1,68 @@
+use crate::application::common::comment_gateway::{CommentGateway, CommentReader, CommentRemover};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::comment::CommentId;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteCommentRequest {
+    pub id: CommentId
+}
+
+pub struct DeleteComment<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub comment_gateway: &'a dyn CommentGateway
+}
+
+/// Removes the comment and the whole thread of replies under it
+#[async_trait]
+impl Interactor<DeleteCommentRequest, ()> for DeleteComment<'_> {
+    async fn execute(&self, data: DeleteCommentRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        if self.comment_gateway.get_by_id(&data.id).await.is_none() {
+            return Err(ApplicationError::NotFound);
+        }
+
+        self.comment_gateway.remove(&data.id).await;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::comment_gateway::test::MockCommentGateway;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::domain::models::comment::Comment;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_delete_comment_with_replies() {
+        let root = Comment::create("note".to_string(), None, "A".to_string(), "Hi".to_string()).unwrap();
+        let reply = Comment::create("note".to_string(), Some(root.id.clone()), "B".to_string(), "Hey".to_string()).unwrap();
+        let other = Comment::create("note".to_string(), None, "C".to_string(), "Yo".to_string()).unwrap();
+        let comment_gateway = MockCommentGateway::new(vec![root.clone(), reply, other]);
+
+        let interactor = DeleteComment {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("admin".to_string())
+            }),
+            comment_gateway: &comment_gateway
+        };
+
+        interactor.execute(DeleteCommentRequest { id: root.id }).await.unwrap();
+
+        let comments = comment_gateway.comments.lock().await;
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "C");
+    }
+}
@@ -0,0 +1,82 @@
+use crate::application::common::comment_gateway::CommentReader;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::comment::{Comment, CommentId, CommentStatus};
+use crate::domain::models::note::NoteId;
+use crate::domain::services::markdown::render_markdown_lite;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ListCommentsRequest {
+    pub note_id: NoteId
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentNode {
+    pub id: CommentId,
+    pub author: String,
+    pub html: String,
+    pub created_at: DateTime<Utc>,
+    pub replies: Vec<CommentNode>
+}
+
+pub struct ListComments<'a> {
+    pub comment_reader: &'a dyn CommentReader
+}
+
+/// Approved comments of a note as a tree. Replies to hidden comments are hidden too
+#[async_trait]
+impl Interactor<ListCommentsRequest, Vec<CommentNode>> for ListComments<'_> {
+    async fn execute(
+        &self,
+        data: ListCommentsRequest
+    ) -> Result<Vec<CommentNode>, ApplicationError> {
+
+        let comments = self.comment_reader.list_by_note(&data.note_id, CommentStatus::Approved).await;
+        Ok(build_thread(&comments, None))
+    }
+}
+
+fn build_thread(comments: &[Comment], parent_id: Option<&CommentId>) -> Vec<CommentNode> {
+    comments.iter()
+        .filter(|c| c.parent_id.as_ref() == parent_id)
+        .map(|c| CommentNode {
+            id: c.id.clone(),
+            author: c.author.clone(),
+            html: render_markdown_lite(&c.body),
+            created_at: c.created_at,
+            replies: build_thread(comments, Some(&c.id))
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::comment_gateway::test::MockCommentGateway;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_comments_thread() {
+        let note_id = "note".to_string();
+        let mut root = Comment::create(note_id.clone(), None, "A".to_string(), "Root".to_string()).unwrap();
+        root.approve();
+        let mut reply = Comment::create(note_id.clone(), Some(root.id.clone()), "B".to_string(), "**Reply**".to_string()).unwrap();
+        reply.approve();
+        let pending = Comment::create(note_id.clone(), None, "C".to_string(), "Spam".to_string()).unwrap();
+        let mut other = Comment::create("other".to_string(), None, "D".to_string(), "Other".to_string()).unwrap();
+        other.approve();
+
+        let comment_gateway = MockCommentGateway::new(vec![root, reply, pending, other]);
+        let interactor = ListComments { comment_reader: &comment_gateway };
+
+        let result = interactor.execute(ListCommentsRequest { note_id }).await.unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].author, "A");
+        assert_eq!(result[0].replies.len(), 1);
+        assert_eq!(result[0].replies[0].html, "<p><strong>Reply</strong></p>\n");
+    }
+}
@@ -0,0 +1,6 @@
+pub mod submit;
+pub mod list;
+pub mod queue;
+pub mod approve;
+pub mod reject;
+pub mod delete;
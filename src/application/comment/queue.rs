@@ -0,0 +1,61 @@
+use crate::application::common::comment_gateway::CommentReader;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::comment::{Comment, CommentStatus};
+use async_trait::async_trait;
+
+pub struct GetModerationQueue<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub comment_reader: &'a dyn CommentReader
+}
+
+/// Comments waiting for approval, oldest first
+#[async_trait]
+impl Interactor<(), Vec<Comment>> for GetModerationQueue<'_> {
+    async fn execute(&self, _data: ()) -> Result<Vec<Comment>, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        Ok(self.comment_reader.list_by_status(CommentStatus::Pending).await)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::comment_gateway::test::MockCommentGateway;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_moderation_queue() {
+        let pending = Comment::create("note".to_string(), None, "A".to_string(), "Hi".to_string()).unwrap();
+        let mut approved = pending.clone();
+        approved.id = "approved".to_string();
+        approved.approve();
+        let comment_gateway = MockCommentGateway::new(vec![pending, approved]);
+
+        let interactor = GetModerationQueue {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("admin".to_string())
+            }),
+            comment_reader: &comment_gateway
+        };
+        assert_eq!(interactor.execute(()).await.unwrap().len(), 1);
+
+        let interactor = GetModerationQueue {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: false,
+                username: None
+            }),
+            comment_reader: &comment_gateway
+        };
+        assert!(matches!(interactor.execute(()).await, Err(ApplicationError::Unauthorized)));
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use crate::application::common::comment_gateway::{CommentGateway, CommentReader, CommentWriter};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::rate_limiter::RateLimiter;
+use crate::domain::models::comment::{Comment, CommentId, CommentStatus};
+use crate::domain::models::note::NoteId;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitCommentRequest {
+    pub note_id: NoteId,
+    pub parent_id: Option<CommentId>,
+    pub author: String,
+    pub body: String,
+    /// Honeypot: hidden in the form, so only bots fill it in
+    #[serde(default)]
+    pub website: String,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub ip: String
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubmitCommentResult {
+    pub id: CommentId,
+    pub status: CommentStatus
+}
+
+pub struct SubmitComment<'a> {
+    pub comment_gateway: &'a dyn CommentGateway,
+    pub note_reader: &'a dyn NoteReader,
+    pub rate_limiter: &'a dyn RateLimiter
+}
+
+#[async_trait]
+impl Interactor<SubmitCommentRequest, SubmitCommentResult> for SubmitComment<'_> {
+    async fn execute(
+        &self,
+        data: SubmitCommentRequest
+    ) -> Result<SubmitCommentResult, ApplicationError> {
+
+        if !self.rate_limiter.hit(&data.ip).await {
+            return Err(ApplicationError::TooManyRequests);
+        }
+
+        let comment = Comment::create(
+            data.note_id,
+            data.parent_id,
+            data.author,
+            data.body
+        ).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        // Bots get the same answer as people, but nothing is stored
+        if !data.website.is_empty() {
+            return Ok(SubmitCommentResult {
+                id: comment.id,
+                status: comment.status
+            });
+        }
+
+        if self.note_reader.get_by_id(&comment.note_id).await.is_none() {
+            return Err(ApplicationError::NotFound);
+        }
+
+        if let Some(parent_id) = &comment.parent_id {
+            let parent = self.comment_gateway.get_by_id(parent_id).await;
+            if !parent.is_some_and(|p| p.note_id == comment.note_id && p.is_visible()) {
+                return Err(ApplicationError::ValidationError(HashMap::from([(
+                    "parent_id".to_string(),
+                    "Comment to reply to does not exist".to_string()
+                )])));
+            }
+        }
+
+        self.comment_gateway.save(&comment).await;
+
+        Ok(SubmitCommentResult {
+            id: comment.id,
+            status: comment.status
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::comment_gateway::test::MockCommentGateway;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::rate_limiter::test::MockRateLimiter;
+    use crate::domain::models::note::Note;
+    use super::*;
+
+    fn request(note_id: &NoteId) -> SubmitCommentRequest {
+        SubmitCommentRequest {
+            note_id: note_id.clone(),
+            parent_id: None,
+            author: "Reader".to_string(),
+            body: "Nice *note*".to_string(),
+            website: String::new(),
+            ip: "127.0.0.1".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_comment() {
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let note_gateway = MockNoteGateway::new(HashMap::from([(note.id.clone(), note.clone())]));
+        let comment_gateway = MockCommentGateway::new(vec![]);
+        let rate_limiter = MockRateLimiter::new(2);
+
+        let interactor = SubmitComment {
+            comment_gateway: &comment_gateway,
+            note_reader: &note_gateway,
+            rate_limiter: &rate_limiter
+        };
+
+        let result = interactor.execute(request(&note.id)).await.unwrap();
+        assert_eq!(result.status, CommentStatus::Pending);
+        assert_eq!(comment_gateway.comments.lock().await.len(), 1);
+
+        // Reply to a comment that is not approved yet
+        let mut reply = request(&note.id);
+        reply.parent_id = Some(result.id);
+        assert!(interactor.execute(reply).await.is_err());
+
+        let result = interactor.execute(request(&note.id)).await;
+        assert!(matches!(result, Err(ApplicationError::TooManyRequests)));
+    }
+
+    #[tokio::test]
+    async fn test_submit_comment_honeypot() {
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let note_gateway = MockNoteGateway::new(HashMap::from([(note.id.clone(), note.clone())]));
+        let comment_gateway = MockCommentGateway::new(vec![]);
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = SubmitComment {
+            comment_gateway: &comment_gateway,
+            note_reader: &note_gateway,
+            rate_limiter: &rate_limiter
+        };
+
+        let mut data = request(&note.id);
+        data.website = "http://spam.example".to_string();
+
+        assert!(interactor.execute(data).await.is_ok());
+        assert!(comment_gateway.comments.lock().await.is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use crate::domain::models::actor::ActorKey;
+
+
+#[async_trait]
+pub trait ActorKeyReader{
+    /// The newest key, the one requests are signed with
+    async fn get_current(&self) -> Option<ActorKey>;
+}
+
+#[async_trait]
+pub trait ActorKeyWriter{
+    async fn save(&self, key: &ActorKey);
+}
+
+pub trait ActorKeyGateway: ActorKeyReader + ActorKeyWriter {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockActorKeyGateway {
+        pub keys: Mutex<Vec<ActorKey>>
+    }
+
+    impl MockActorKeyGateway {
+        pub fn new(keys: Vec<ActorKey>) -> Self {
+            Self {
+                keys: Mutex::new(keys)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ActorKeyReader for MockActorKeyGateway {
+        async fn get_current(&self) -> Option<ActorKey> {
+            self.keys.lock().await.iter().max_by_key(|k| k.created_at).cloned()
+        }
+    }
+
+    #[async_trait]
+    impl ActorKeyWriter for MockActorKeyGateway {
+        async fn save(&self, key: &ActorKey) {
+            let mut keys = self.keys.lock().await;
+            keys.retain(|k| k.id != key.id);
+            keys.push(key.clone());
+        }
+    }
+
+    impl ActorKeyGateway for MockActorKeyGateway {}
+}
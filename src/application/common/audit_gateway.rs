@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::application::common::pagination::Cursor;
+use crate::domain::models::audit::{AuditAction, AuditEntry, AuditTarget};
+
+
+/// Every set field narrows the listing down
+#[derive(Clone, Debug, Default)]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub target: Option<AuditTarget>,
+    pub target_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.actor.as_ref().map_or(true, |actor| entry.actor.as_ref() == Some(actor))
+            && self.action.map_or(true, |action| entry.action == action)
+            && self.target.map_or(true, |target| entry.target == target)
+            && self.target_id.as_ref().map_or(true, |id| &entry.target_id == id)
+            && self.since.map_or(true, |since| entry.created_at >= since)
+            && self.until.map_or(true, |until| entry.created_at < until)
+    }
+}
+
+#[async_trait]
+pub trait AuditReader: Send + Sync {
+    /// Keyset listing, same contract as
+    /// [`NoteReader::range_by_cursor`](crate::application::common::note_gateway::NoteReader::range_by_cursor)
+    async fn list(&self, filter: &AuditFilter, cursor: Option<&Cursor>, limit: &u64) -> Vec<AuditEntry>;
+}
+
+#[async_trait]
+pub trait AuditWriter: Send + Sync {
+    async fn save(&self, entry: &AuditEntry);
+}
+
+pub trait AuditGateway: AuditReader + AuditWriter {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use crate::application::common::pagination::Direction;
+    use super::*;
+
+    pub struct MockAuditGateway {
+        pub entries: Mutex<Vec<AuditEntry>>
+    }
+
+    impl MockAuditGateway {
+        pub fn new(entries: Vec<AuditEntry>) -> Self {
+            Self {
+                entries: Mutex::new(entries)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AuditReader for MockAuditGateway {
+        async fn list(&self, filter: &AuditFilter, cursor: Option<&Cursor>, limit: &u64) -> Vec<AuditEntry> {
+            let mut entries: Vec<AuditEntry> = self.entries.lock().await.iter()
+                .filter(|e| filter.matches(e))
+                .cloned()
+                .collect();
+            entries.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+
+            let rows: Vec<AuditEntry> = match cursor {
+                None => entries,
+                Some(cursor) => {
+                    let key = (cursor.created_at, cursor.id.clone());
+                    match cursor.direction {
+                        Direction::After => entries.into_iter()
+                            .filter(|e| (e.created_at, e.id.clone()) < key)
+                            .collect(),
+                        Direction::Before => entries.into_iter()
+                            .rev()
+                            .filter(|e| (e.created_at, e.id.clone()) > key)
+                            .collect(),
+                    }
+                }
+            };
+            rows.into_iter().take(*limit as usize).collect()
+        }
+    }
+
+    #[async_trait]
+    impl AuditWriter for MockAuditGateway {
+        async fn save(&self, entry: &AuditEntry) {
+            self.entries.lock().await.push(entry.clone());
+        }
+    }
+
+    impl AuditGateway for MockAuditGateway {}
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use crate::domain::models::backup::Backup;
+
+/// Compressed snapshots of the live database
+#[async_trait]
+pub trait BackupStorage: Send + Sync {
+    /// Takes a consistent snapshot while the site keeps running.
+    /// With `verify` a snapshot that fails the integrity check is discarded
+    /// and an error is returned instead
+    async fn create(&self, verify: bool) -> Result<Backup, String>;
+    async fn list(&self) -> Result<Vec<Backup>, String>;
+    async fn get(&self, name: &str) -> Option<Vec<u8>>;
+    async fn remove(&self, name: &str) -> Result<(), String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use chrono::{DateTime, Utc};
+    use tokio::sync::Mutex;
+    use crate::domain::models::backup::backup_name;
+    use super::*;
+
+    pub struct MockBackupStorage {
+        pub backups: Mutex<Vec<(Backup, Vec<u8>)>>,
+        /// Time of the next snapshot, each one moves it by a day
+        pub next: Mutex<DateTime<Utc>>,
+        /// Snapshots fail the integrity check
+        pub corrupted: bool
+    }
+
+    impl MockBackupStorage {
+        pub fn new(backups: Vec<Backup>) -> Self {
+            Self {
+                backups: Mutex::new(backups.into_iter().map(|backup| (backup, b"snapshot".to_vec())).collect()),
+                next: Mutex::new(Utc::now()),
+                corrupted: false
+            }
+        }
+    }
+
+    #[async_trait]
+    impl BackupStorage for MockBackupStorage {
+        async fn create(&self, verify: bool) -> Result<Backup, String> {
+            if verify && self.corrupted {
+                return Err("integrity check failed".to_string());
+            }
+            let mut next = self.next.lock().await;
+            let backup = Backup { name: backup_name(*next), created_at: *next, size: 8 };
+            *next += chrono::Duration::days(1);
+            self.backups.lock().await.push((backup.clone(), b"snapshot".to_vec()));
+            Ok(backup)
+        }
+
+        async fn list(&self) -> Result<Vec<Backup>, String> {
+            Ok(self.backups.lock().await.iter().map(|(backup, _)| backup.clone()).collect())
+        }
+
+        async fn get(&self, name: &str) -> Option<Vec<u8>> {
+            self.backups.lock().await.iter()
+                .find(|(backup, _)| backup.name == name)
+                .map(|(_, data)| data.clone())
+        }
+
+        async fn remove(&self, name: &str) -> Result<(), String> {
+            self.backups.lock().await.retain(|(backup, _)| backup.name != name);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use crate::domain::models::comment::{Comment, CommentId, CommentStatus};
+use crate::domain::models::note::NoteId;
+
+
+#[async_trait]
+pub trait CommentReader{
+    async fn get_by_id(&self, id: &CommentId) -> Option<Comment>;
+    /// Comments of a note in the given status, oldest first
+    async fn list_by_note(&self, note_id: &NoteId, status: CommentStatus) -> Vec<Comment>;
+    /// Comments of all notes in the given status, oldest first
+    async fn list_by_status(&self, status: CommentStatus) -> Vec<Comment>;
+}
+
+#[async_trait]
+pub trait CommentWriter{
+    async fn save(&self, comment: &Comment);
+}
+
+#[async_trait]
+pub trait CommentRemover {
+    /// Removes the comment together with all replies to it
+    async fn remove(&self, comment_id: &CommentId);
+    /// Removes every comment of the note, used when the note is purged
+    async fn remove_by_note(&self, note_id: &NoteId);
+}
+
+pub trait CommentGateway: CommentReader + CommentWriter + CommentRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockCommentGateway {
+        pub comments: Mutex<Vec<Comment>>
+    }
+
+    impl MockCommentGateway {
+        pub fn new(comments: Vec<Comment>) -> Self {
+            Self {
+                comments: Mutex::new(comments)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommentReader for MockCommentGateway {
+        async fn get_by_id(&self, id: &CommentId) -> Option<Comment> {
+            self.comments.lock().await.iter().find(|c| c.id == *id).cloned()
+        }
+
+        async fn list_by_note(&self, note_id: &NoteId, status: CommentStatus) -> Vec<Comment> {
+            self.comments.lock().await.iter()
+                .filter(|c| c.note_id == *note_id && c.status == status)
+                .cloned()
+                .collect()
+        }
+
+        async fn list_by_status(&self, status: CommentStatus) -> Vec<Comment> {
+            self.comments.lock().await.iter()
+                .filter(|c| c.status == status)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl CommentWriter for MockCommentGateway {
+        async fn save(&self, comment: &Comment) {
+            let mut comments = self.comments.lock().await;
+            match comments.iter_mut().find(|c| c.id == comment.id) {
+                Some(existing) => *existing = comment.clone(),
+                None => comments.push(comment.clone())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CommentRemover for MockCommentGateway {
+        async fn remove(&self, comment_id: &CommentId) {
+            let mut comments = self.comments.lock().await;
+            let mut removed = vec![comment_id.clone()];
+            while let Some(id) = removed.pop() {
+                comments.retain(|c| c.id != id);
+                removed.extend(comments.iter()
+                    .filter(|c| c.parent_id.as_ref() == Some(&id))
+                    .map(|c| c.id.clone()));
+            }
+        }
+
+        async fn remove_by_note(&self, note_id: &NoteId) {
+            self.comments.lock().await.retain(|c| c.note_id != *note_id);
+        }
+    }
+
+    impl CommentGateway for MockCommentGateway {}
+}
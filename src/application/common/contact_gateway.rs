@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::models::contact_message::{ContactMessage, ContactMessageId, ContactMessageStatus};
+
+
+#[async_trait]
+pub trait ContactMessageReader{
+    async fn get_by_id(&self, id: &ContactMessageId) -> Option<ContactMessage>;
+    /// Newest first
+    async fn list(&self, limit: &u64, offset: &u64) -> Vec<ContactMessage>;
+    /// Oldest first
+    async fn list_by_status(&self, status: ContactMessageStatus) -> Vec<ContactMessage>;
+}
+
+#[async_trait]
+pub trait ContactMessageWriter{
+    async fn save(&self, message: &ContactMessage);
+    /// Remembers the form token until `expires_at`, false when it was already used
+    async fn use_token(&self, token_hash: &str, expires_at: DateTime<Utc>) -> bool;
+}
+
+pub trait ContactMessageGateway: ContactMessageReader + ContactMessageWriter {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockContactMessageGateway {
+        pub messages: Mutex<Vec<ContactMessage>>,
+        pub used_tokens: Mutex<Vec<String>>
+    }
+
+    impl MockContactMessageGateway {
+        pub fn new(messages: Vec<ContactMessage>) -> Self {
+            Self {
+                messages: Mutex::new(messages),
+                used_tokens: Mutex::new(vec![])
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ContactMessageReader for MockContactMessageGateway {
+        async fn get_by_id(&self, id: &ContactMessageId) -> Option<ContactMessage> {
+            self.messages.lock().await.iter().find(|m| m.id == *id).cloned()
+        }
+
+        async fn list(&self, limit: &u64, offset: &u64) -> Vec<ContactMessage> {
+            let mut messages = self.messages.lock().await.clone();
+            messages.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+            messages.into_iter()
+                .skip(*offset as usize)
+                .take(*limit as usize)
+                .collect()
+        }
+
+        async fn list_by_status(&self, status: ContactMessageStatus) -> Vec<ContactMessage> {
+            self.messages.lock().await.iter()
+                .filter(|m| m.status == status)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl ContactMessageWriter for MockContactMessageGateway {
+        async fn save(&self, message: &ContactMessage) {
+            let mut messages = self.messages.lock().await;
+            match messages.iter_mut().find(|m| m.id == message.id) {
+                Some(existing) => *existing = message.clone(),
+                None => messages.push(message.clone())
+            }
+        }
+
+        async fn use_token(&self, token_hash: &str, _expires_at: DateTime<Utc>) -> bool {
+            let mut used_tokens = self.used_tokens.lock().await;
+            if used_tokens.iter().any(|used| used == token_hash) {
+                return false;
+            }
+            used_tokens.push(token_hash.to_string());
+            true
+        }
+    }
+
+    impl ContactMessageGateway for MockContactMessageGateway {}
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use crate::domain::models::counter::Counter;
+
+
+#[async_trait]
+pub trait CounterReader{
+    async fn get_by_name(&self, name: &str) -> Option<Counter>;
+}
+
+#[async_trait]
+pub trait CounterWriter{
+    /// Adds one to the counter, creating it when needed, and returns the new count
+    async fn increment(&self, name: &str) -> u64;
+}
+
+pub trait CounterGateway: CounterReader + CounterWriter {}
+
+
+#[cfg(test)]
+pub mod test {
+    use chrono::Utc;
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockCounterGateway {
+        pub counters: Mutex<Vec<Counter>>
+    }
+
+    impl MockCounterGateway {
+        pub fn new(counters: Vec<Counter>) -> Self {
+            Self {
+                counters: Mutex::new(counters)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CounterReader for MockCounterGateway {
+        async fn get_by_name(&self, name: &str) -> Option<Counter> {
+            self.counters.lock().await.iter().find(|c| c.name == name).cloned()
+        }
+    }
+
+    #[async_trait]
+    impl CounterWriter for MockCounterGateway {
+        async fn increment(&self, name: &str) -> u64 {
+            let mut counters = self.counters.lock().await;
+            let now = Utc::now();
+            match counters.iter_mut().find(|c| c.name == name) {
+                Some(counter) => {
+                    counter.count += 1;
+                    counter.updated_at = now;
+                    counter.count
+                }
+                None => {
+                    counters.push(Counter {
+                        name: name.to_string(),
+                        count: 1,
+                        created_at: now,
+                        updated_at: now
+                    });
+                    1
+                }
+            }
+        }
+    }
+
+    impl CounterGateway for MockCounterGateway {}
+}
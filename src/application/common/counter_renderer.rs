@@ -0,0 +1,25 @@
+/// Draws the counter badge from the digit images of a theme
+pub trait CounterRenderer: Send + Sync {
+    fn has_theme(&self, theme: &str) -> bool;
+    /// SVG image of `digits`, `None` for an unknown theme
+    fn render(&self, theme: &str, digits: &str) -> Option<String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    /// Knows only the `default` theme and "renders" the digits as they are
+    pub struct MockCounterRenderer;
+
+    impl CounterRenderer for MockCounterRenderer {
+        fn has_theme(&self, theme: &str) -> bool {
+            theme == "default"
+        }
+
+        fn render(&self, theme: &str, digits: &str) -> Option<String> {
+            self.has_theme(theme).then(|| format!("<svg>{}</svg>", digits))
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::models::delivery::{Delivery, DeliveryId};
+
+
+#[async_trait]
+pub trait DeliveryReader{
+    /// Deliveries whose next attempt is due at `now`, oldest first
+    async fn list_due(&self, now: DateTime<Utc>, limit: &u64) -> Vec<Delivery>;
+}
+
+#[async_trait]
+pub trait DeliveryWriter{
+    async fn save(&self, delivery: &Delivery);
+}
+
+#[async_trait]
+pub trait DeliveryRemover {
+    async fn remove(&self, delivery_id: &DeliveryId);
+}
+
+pub trait DeliveryGateway: DeliveryReader + DeliveryWriter + DeliveryRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockDeliveryGateway {
+        pub deliveries: Mutex<Vec<Delivery>>
+    }
+
+    impl MockDeliveryGateway {
+        pub fn new(deliveries: Vec<Delivery>) -> Self {
+            Self {
+                deliveries: Mutex::new(deliveries)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DeliveryReader for MockDeliveryGateway {
+        async fn list_due(&self, now: DateTime<Utc>, limit: &u64) -> Vec<Delivery> {
+            let mut due = self.deliveries.lock().await.iter()
+                .filter(|d| d.next_attempt_at <= now)
+                .cloned()
+                .collect::<Vec<_>>();
+            due.sort_by_key(|d| d.next_attempt_at);
+            due.truncate(*limit as usize);
+            due
+        }
+    }
+
+    #[async_trait]
+    impl DeliveryWriter for MockDeliveryGateway {
+        async fn save(&self, delivery: &Delivery) {
+            let mut deliveries = self.deliveries.lock().await;
+            match deliveries.iter_mut().find(|d| d.id == delivery.id) {
+                Some(existing) => *existing = delivery.clone(),
+                None => deliveries.push(delivery.clone())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DeliveryRemover for MockDeliveryGateway {
+        async fn remove(&self, delivery_id: &DeliveryId) {
+            self.deliveries.lock().await.retain(|d| d.id != *delivery_id);
+        }
+    }
+
+    impl DeliveryGateway for MockDeliveryGateway {}
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use crate::domain::models::event::{DomainEvent, EventKind};
+
+pub trait EventPublisher: Send + Sync {
+    /// Returns immediately, subscribers run in the background
+    /// and their failures never reach the caller
+    fn publish(&self, event: DomainEvent);
+}
+
+/// Reacts to domain events, e.g. invalidates caches or notifies webhooks
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Used in logs
+    fn name(&self) -> &'static str;
+    /// The bus only hands over events of these kinds
+    fn kinds(&self) -> &[EventKind];
+    async fn handle(&self, event: &DomainEvent) -> Result<(), String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::sync::Mutex;
+    use super::*;
+
+    pub struct MockEventPublisher {
+        pub events: Mutex<Vec<DomainEvent>>
+    }
+
+    impl MockEventPublisher {
+        pub fn new() -> Self {
+            Self {
+                events: Mutex::new(vec![])
+            }
+        }
+    }
+
+    impl EventPublisher for MockEventPublisher {
+        fn publish(&self, event: DomainEvent) {
+            self.events.lock().unwrap().push(event);
+        }
+    }
+}
@@ -24,6 +24,11 @@ pub enum ApplicationError {
     Unauthorized,
     #[error("Forbidden: You do not have permission to perform this action!")]
     Forbidden,
+    #[error("TooManyRequests")]
+    TooManyRequests,
+    /// The entity was changed since the client read it
+    #[error("Conflict: current version is {current_version}")]
+    Conflict { current_version: i64 },
     #[error("UnexpectedError: {0}")]
     UnexpectedError(String),
 }
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use async_trait::async_trait;
+use crate::domain::models::actor::RemoteActor;
+
+
+/// Incoming request as it hit the inbox, everything a signature may cover
+#[derive(Clone, Debug)]
+pub struct SignedRequest {
+    pub method: String,
+    /// Path with the query string
+    pub path: String,
+    /// Header names in lowercase
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+/// Network side of ActivityPub
+#[async_trait]
+pub trait FederationClient: Send + Sync {
+    /// Checks the HTTP signature and the digest of the request,
+    /// returns the actor that signed it
+    async fn verify(&self, request: &SignedRequest) -> Result<RemoteActor, String>;
+    /// Posts a serialized activity to the inbox, signed by our actor
+    async fn deliver(&self, inbox: &str, activity: &str) -> Result<(), String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::sync::Mutex;
+    use super::*;
+
+    /// Trusts a `signature` header holding the signer's actor IRI
+    pub struct MockFederationClient {
+        pub actors: Vec<RemoteActor>,
+        pub failing_inboxes: Vec<String>,
+        pub delivered: Mutex<Vec<(String, String)>>
+    }
+
+    impl MockFederationClient {
+        pub fn new(actors: Vec<RemoteActor>) -> Self {
+            Self {
+                actors,
+                failing_inboxes: vec![],
+                delivered: Mutex::new(vec![])
+            }
+        }
+    }
+
+    #[async_trait]
+    impl FederationClient for MockFederationClient {
+        async fn verify(&self, request: &SignedRequest) -> Result<RemoteActor, String> {
+            let signer = request.headers.get("signature").ok_or("Missing signature")?;
+            self.actors.iter()
+                .find(|a| a.id == *signer)
+                .cloned()
+                .ok_or_else(|| "Unknown key".to_string())
+        }
+
+        async fn deliver(&self, inbox: &str, activity: &str) -> Result<(), String> {
+            if self.failing_inboxes.iter().any(|i| i == inbox) {
+                return Err("Connection refused".to_string());
+            }
+            self.delivered.lock().unwrap().push((inbox.to_string(), activity.to_string()));
+            Ok(())
+        }
+    }
+}
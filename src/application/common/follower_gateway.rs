@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use crate::domain::models::follower::{Follower, FollowerId};
+
+
+#[async_trait]
+pub trait FollowerReader{
+    async fn get_by_actor(&self, actor: &str) -> Option<Follower>;
+    /// Distinct inboxes of all followers
+    async fn inboxes(&self) -> Vec<String>;
+    async fn count(&self) -> u64;
+}
+
+#[async_trait]
+pub trait FollowerWriter{
+    async fn save(&self, follower: &Follower);
+}
+
+#[async_trait]
+pub trait FollowerRemover {
+    async fn remove(&self, follower_id: &FollowerId);
+}
+
+pub trait FollowerGateway: FollowerReader + FollowerWriter + FollowerRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockFollowerGateway {
+        pub followers: Mutex<Vec<Follower>>
+    }
+
+    impl MockFollowerGateway {
+        pub fn new(followers: Vec<Follower>) -> Self {
+            Self {
+                followers: Mutex::new(followers)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl FollowerReader for MockFollowerGateway {
+        async fn get_by_actor(&self, actor: &str) -> Option<Follower> {
+            self.followers.lock().await.iter().find(|f| f.actor == actor).cloned()
+        }
+
+        async fn inboxes(&self) -> Vec<String> {
+            let mut inboxes = self.followers.lock().await.iter()
+                .map(|f| f.inbox.clone())
+                .collect::<Vec<_>>();
+            inboxes.sort();
+            inboxes.dedup();
+            inboxes
+        }
+
+        async fn count(&self) -> u64 {
+            self.followers.lock().await.len() as u64
+        }
+    }
+
+    #[async_trait]
+    impl FollowerWriter for MockFollowerGateway {
+        async fn save(&self, follower: &Follower) {
+            let mut followers = self.followers.lock().await;
+            match followers.iter_mut().find(|f| f.id == follower.id) {
+                Some(existing) => *existing = follower.clone(),
+                None => followers.push(follower.clone())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl FollowerRemover for MockFollowerGateway {
+        async fn remove(&self, follower_id: &FollowerId) {
+            self.followers.lock().await.retain(|f| f.id != *follower_id);
+        }
+    }
+
+    impl FollowerGateway for MockFollowerGateway {}
+}
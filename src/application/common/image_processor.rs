@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ImageProcessor: Send + Sync {
+    /// Removes EXIF, GPS and other embedded metadata, keeping the pixels intact
+    async fn strip_metadata(&self, mime: &str, data: Vec<u8>) -> Result<Vec<u8>, String>;
+    async fn dimensions(&self, data: &[u8]) -> Option<(u32, u32)>;
+    /// Scales the image down to each of `widths` keeping the aspect ratio and encodes
+    /// the copies as WebP. Returns the encoded data and the resulting height of each,
+    /// in the order of `widths`
+    async fn resize_to_webp(&self, data: &[u8], widths: &[u32]) -> Result<Vec<(Vec<u8>, u32)>, String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    /// Pretends every image is 2000x1000 and prefixes derived data with its width
+    pub struct MockImageProcessor;
+
+    #[async_trait]
+    impl ImageProcessor for MockImageProcessor {
+        async fn strip_metadata(&self, _mime: &str, data: Vec<u8>) -> Result<Vec<u8>, String> {
+            Ok(data)
+        }
+
+        async fn dimensions(&self, _data: &[u8]) -> Option<(u32, u32)> {
+            Some((2000, 1000))
+        }
+
+        async fn resize_to_webp(&self, data: &[u8], widths: &[u32]) -> Result<Vec<(Vec<u8>, u32)>, String> {
+            Ok(widths.iter().map(|width| {
+                let mut result = b"RIFF\0\0\0\0WEBP".to_vec();
+                result.extend_from_slice(width.to_string().as_bytes());
+                result.extend_from_slice(data);
+                (result, width / 2)
+            }).collect())
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::application::common::pagination::Cursor;
+use crate::domain::models::job::{Job, JobId, JobSchedule, JobStatus};
+
+
+/// Every set field narrows the listing down
+#[derive(Clone, Debug, Default)]
+pub struct JobFilter {
+    pub status: Option<JobStatus>,
+    pub kind: Option<String>,
+}
+
+impl JobFilter {
+    pub fn matches(&self, job: &Job) -> bool {
+        self.status.map_or(true, |status| job.status == status)
+            && self.kind.as_ref().map_or(true, |kind| &job.kind == kind)
+    }
+}
+
+#[async_trait]
+pub trait JobReader: Send + Sync {
+    /// Keyset listing, same contract as
+    /// [`NoteReader::range_by_cursor`](crate::application::common::note_gateway::NoteReader::range_by_cursor)
+    async fn list_jobs(&self, filter: &JobFilter, cursor: Option<&Cursor>, limit: &u64) -> Vec<Job>;
+    async fn get_job(&self, job_id: &JobId) -> Option<Job>;
+    /// Whether a job of this kind is queued or running
+    async fn has_pending(&self, kind: &str) -> bool;
+    async fn get_schedule(&self, kind: &str) -> Option<JobSchedule>;
+}
+
+#[async_trait]
+pub trait JobWriter: Send + Sync {
+    async fn save_job(&self, job: &Job);
+    /// Marks the queued job that is due the longest as running and returns it.
+    /// Two workers never get the same job
+    async fn claim_next(&self, now: DateTime<Utc>) -> Option<Job>;
+    /// Queues running jobs again, there are none left running after a restart.
+    /// Returns how many there were
+    async fn requeue_running(&self, now: DateTime<Utc>) -> u64;
+    /// Removes done jobs last updated before `before`, returns how many
+    async fn remove_done_before(&self, before: DateTime<Utc>) -> u64;
+    async fn save_schedule(&self, schedule: &JobSchedule);
+}
+
+pub trait JobGateway: JobReader + JobWriter {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use crate::application::common::pagination::Direction;
+    use super::*;
+
+    pub struct MockJobGateway {
+        pub jobs: Mutex<Vec<Job>>,
+        pub schedules: Mutex<Vec<JobSchedule>>
+    }
+
+    impl MockJobGateway {
+        pub fn new(jobs: Vec<Job>) -> Self {
+            Self {
+                jobs: Mutex::new(jobs),
+                schedules: Mutex::new(Vec::new())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl JobReader for MockJobGateway {
+        async fn list_jobs(&self, filter: &JobFilter, cursor: Option<&Cursor>, limit: &u64) -> Vec<Job> {
+            let mut jobs: Vec<Job> = self.jobs.lock().await.iter()
+                .filter(|j| filter.matches(j))
+                .cloned()
+                .collect();
+            jobs.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+
+            let rows: Vec<Job> = match cursor {
+                None => jobs,
+                Some(cursor) => {
+                    let key = (cursor.created_at, cursor.id.clone());
+                    match cursor.direction {
+                        Direction::After => jobs.into_iter()
+                            .filter(|j| (j.created_at, j.id.clone()) < key)
+                            .collect(),
+                        Direction::Before => jobs.into_iter()
+                            .rev()
+                            .filter(|j| (j.created_at, j.id.clone()) > key)
+                            .collect(),
+                    }
+                }
+            };
+            rows.into_iter().take(*limit as usize).collect()
+        }
+
+        async fn get_job(&self, job_id: &JobId) -> Option<Job> {
+            self.jobs.lock().await.iter().find(|j| j.id == *job_id).cloned()
+        }
+
+        async fn has_pending(&self, kind: &str) -> bool {
+            self.jobs.lock().await.iter().any(|j| {
+                j.kind == kind && matches!(j.status, JobStatus::Queued | JobStatus::Running)
+            })
+        }
+
+        async fn get_schedule(&self, kind: &str) -> Option<JobSchedule> {
+            self.schedules.lock().await.iter().find(|s| s.kind == kind).cloned()
+        }
+    }
+
+    #[async_trait]
+    impl JobWriter for MockJobGateway {
+        async fn save_job(&self, job: &Job) {
+            let mut jobs = self.jobs.lock().await;
+            match jobs.iter_mut().find(|j| j.id == job.id) {
+                Some(existing) => *existing = job.clone(),
+                None => jobs.push(job.clone())
+            }
+        }
+
+        async fn claim_next(&self, now: DateTime<Utc>) -> Option<Job> {
+            let mut jobs = self.jobs.lock().await;
+            let job = jobs.iter_mut()
+                .filter(|j| j.status == JobStatus::Queued && j.run_at <= now)
+                .min_by(|a, b| (a.run_at, &a.id).cmp(&(b.run_at, &b.id)))?;
+            job.status = JobStatus::Running;
+            job.updated_at = Some(now);
+            Some(job.clone())
+        }
+
+        async fn requeue_running(&self, now: DateTime<Utc>) -> u64 {
+            let mut count = 0;
+            for job in self.jobs.lock().await.iter_mut().filter(|j| j.status == JobStatus::Running) {
+                job.status = JobStatus::Queued;
+                job.updated_at = Some(now);
+                count += 1;
+            }
+            count
+        }
+
+        async fn remove_done_before(&self, before: DateTime<Utc>) -> u64 {
+            let mut jobs = self.jobs.lock().await;
+            let count = jobs.len();
+            jobs.retain(|j| j.status != JobStatus::Done || j.updated_at.map_or(true, |at| at >= before));
+            (count - jobs.len()) as u64
+        }
+
+        async fn save_schedule(&self, schedule: &JobSchedule) {
+            let mut schedules = self.schedules.lock().await;
+            schedules.retain(|s| s.kind != schedule.kind);
+            schedules.push(schedule.clone());
+        }
+    }
+
+    impl JobGateway for MockJobGateway {}
+}
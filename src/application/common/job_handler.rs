@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use crate::domain::services::recurrence::Recurrence;
+
+
+/// Runs jobs of one kind. An error requeues the job with backoff
+/// until it runs out of attempts
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    fn kind(&self) -> &'static str;
+    async fn run(&self, payload: &Value) -> Result<(), String>;
+}
+
+/// Job of `kind` enqueued with an empty payload whenever `recurrence` is due
+#[derive(Clone, Debug)]
+pub struct ScheduledJob {
+    pub kind: &'static str,
+    pub expression: String,
+    pub recurrence: Recurrence,
+}
+
+impl ScheduledJob {
+    pub fn new(kind: &'static str, expression: &str) -> Result<Self, String> {
+        Ok(Self {
+            kind,
+            expression: expression.to_string(),
+            recurrence: Recurrence::parse(expression)?
+        })
+    }
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::sync::Mutex;
+    use super::*;
+
+    /// Fails the first `failures` runs
+    pub struct MockJobHandler {
+        pub kind: &'static str,
+        pub failures: Mutex<u32>,
+        pub runs: Mutex<Vec<Value>>
+    }
+
+    impl MockJobHandler {
+        pub fn new(kind: &'static str, failures: u32) -> Self {
+            Self {
+                kind,
+                failures: Mutex::new(failures),
+                runs: Mutex::new(Vec::new())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl JobHandler for MockJobHandler {
+        fn kind(&self) -> &'static str {
+            self.kind
+        }
+
+        async fn run(&self, payload: &Value) -> Result<(), String> {
+            self.runs.lock().unwrap().push(payload.clone());
+            let mut failures = self.failures.lock().unwrap();
+            match *failures {
+                0 => Ok(()),
+                _ => {
+                    *failures -= 1;
+                    Err("mock failure".to_string())
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use crate::domain::models::mail::Mail;
+
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, mail: &Mail) -> Result<(), String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::sync::Mutex;
+    use super::*;
+
+    pub struct MockMailer {
+        pub sent: Mutex<Vec<Mail>>,
+        /// Recipients the mail to which fails
+        pub failing: Vec<String>
+    }
+
+    impl MockMailer {
+        pub fn new() -> Self {
+            Self {
+                sent: Mutex::new(vec![]),
+                failing: vec![]
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Mailer for MockMailer {
+        async fn send(&self, mail: &Mail) -> Result<(), String> {
+            if self.failing.contains(&mail.to) {
+                return Err("Connection refused".to_string());
+            }
+            self.sent.lock().unwrap().push(mail.clone());
+            Ok(())
+        }
+    }
+}
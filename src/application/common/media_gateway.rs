@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::media::Media;
+
+
+#[async_trait]
+pub trait MediaReader{
+    async fn get_by_hash(&self, hash: &Hash) -> Option<Media>;
+    /// Derived copies of `source`, narrowest first
+    async fn get_variants(&self, source: &Hash) -> Vec<Media>;
+    /// Originals and variants, oldest first
+    async fn get_all(&self) -> Vec<Media>;
+}
+
+#[async_trait]
+pub trait MediaWriter{
+    async fn save(&self, media: &Media);
+}
+
+#[async_trait]
+pub trait MediaRemover {
+    async fn remove(&self, hash: &Hash);
+}
+
+pub trait MediaGateway: MediaReader + MediaWriter + MediaRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockMediaGateway {
+        pub media: Mutex<HashMap<Hash, Media>>
+    }
+
+    impl MockMediaGateway {
+        pub fn new(media: HashMap<Hash, Media>) -> Self {
+            Self {
+                media: Mutex::new(media)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MediaReader for MockMediaGateway {
+        async fn get_by_hash(&self, hash: &Hash) -> Option<Media> {
+            self.media.lock().await.get(hash).cloned()
+        }
+
+        async fn get_variants(&self, source: &Hash) -> Vec<Media> {
+            let mut variants: Vec<Media> = self.media.lock().await.values()
+                .filter(|m| m.source.as_ref() == Some(source))
+                .cloned()
+                .collect();
+            variants.sort_by_key(|m| m.width);
+            variants
+        }
+
+        async fn get_all(&self) -> Vec<Media> {
+            let mut media: Vec<Media> = self.media.lock().await.values().cloned().collect();
+            media.sort_by(|a, b| (a.created_at, &a.hash.0).cmp(&(b.created_at, &b.hash.0)));
+            media
+        }
+    }
+
+    #[async_trait]
+    impl MediaWriter for MockMediaGateway {
+        async fn save(&self, media: &Media) {
+            self.media.lock().await.insert(media.hash.clone(), media.clone());
+        }
+    }
+
+    #[async_trait]
+    impl MediaRemover for MockMediaGateway {
+        async fn remove(&self, hash: &Hash) {
+            self.media.lock().await.remove(hash);
+        }
+    }
+
+    impl MediaGateway for MockMediaGateway {}
+}
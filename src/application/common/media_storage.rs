@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use crate::domain::models::hash::Hash;
+
+/// Blob storage addressed by the content hash.
+///
+/// Writing the same hash twice must be a no-op, so implementations
+/// are free to skip data that is already stored
+#[async_trait]
+pub trait MediaStorage: Send + Sync {
+    async fn put(&self, hash: &Hash, data: &[u8]) -> Result<(), String>;
+    async fn get(&self, hash: &Hash) -> Option<Vec<u8>>;
+    async fn remove(&self, hash: &Hash) -> Result<(), String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockMediaStorage {
+        pub blobs: Mutex<HashMap<Hash, Vec<u8>>>
+    }
+
+    impl MockMediaStorage {
+        pub fn new() -> Self {
+            Self {
+                blobs: Mutex::new(HashMap::new())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MediaStorage for MockMediaStorage {
+        async fn put(&self, hash: &Hash, data: &[u8]) -> Result<(), String> {
+            self.blobs.lock().await.insert(hash.clone(), data.to_vec());
+            Ok(())
+        }
+
+        async fn get(&self, hash: &Hash) -> Option<Vec<u8>> {
+            self.blobs.lock().await.get(hash).cloned()
+        }
+
+        async fn remove(&self, hash: &Hash) -> Result<(), String> {
+            self.blobs.lock().await.remove(hash);
+            Ok(())
+        }
+    }
+}
@@ -5,3 +5,31 @@ pub mod exceptions;
 pub mod project_gateway;
 pub mod interactor;
 pub mod user_gateway;
+pub mod pagination;
+pub mod media_gateway;
+pub mod media_storage;
+pub mod image_processor;
+pub mod social_card;
+pub mod rate_limiter;
+pub mod comment_gateway;
+pub mod webmention_gateway;
+pub mod webmention_client;
+pub mod follower_gateway;
+pub mod actor_key_gateway;
+pub mod delivery_gateway;
+pub mod federation_client;
+pub mod subscriber_gateway;
+pub mod mailer;
+pub mod contact_gateway;
+pub mod page_view_gateway;
+pub mod visitor_salt;
+pub mod counter_gateway;
+pub mod counter_renderer;
+pub mod backup_storage;
+pub mod unit_of_work;
+pub mod audit_gateway;
+pub mod event_bus;
+pub mod webhook_gateway;
+pub mod webhook_client;
+pub mod job_gateway;
+pub mod job_handler;
@@ -1,22 +1,46 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::application::common::pagination::Cursor;
 use crate::domain::models::note::{Note, NoteId, NoteListItem};
+use crate::domain::models::trash::TrashItem;
 
 
+/// Deleted notes stay in the trash, every reader method except
+/// [`NoteReader::get_deleted`] acts as if they were gone
 #[async_trait]
-pub trait NoteReader{
+pub trait NoteReader: Send + Sync {
     async fn get_by_id(&self, id: &NoteId) -> Option<Note>;
     async fn get_by_slug(&self, slug: &str) -> Option<Note>;
+    /// Newest first, ordered by `(created_at, id)`
     async fn range(&self, limit: &u64, offset: &u64) -> Vec<NoteListItem>;
+    /// Keyset variant of [`NoteReader::range`]. Without a cursor returns the first page.
+    ///
+    /// Rows for [`Direction::Before`](crate::application::common::pagination::Direction)
+    /// are returned in ascending order, starting from the cursor
+    async fn range_by_cursor(&self, cursor: Option<&Cursor>, limit: &u64) -> Vec<NoteListItem>;
+    async fn count(&self) -> u64;
+    /// The trash, most recently deleted first
+    async fn get_deleted(&self) -> Vec<TrashItem>;
 }
 
 #[async_trait]
-pub trait NoteWriter{
+pub trait NoteWriter: Send + Sync {
     async fn save(&self, note: &Note);
+    /// Writes the note only if the stored one is still at `expected_version`,
+    /// returns `false` when someone else got there first
+    async fn update(&self, note: &Note, expected_version: i64) -> bool;
 }
 
 #[async_trait]
-pub trait NoteRemover {
+pub trait NoteRemover: Send + Sync {
+    /// Moves the note to the trash
     async fn remove(&self, note_id: &NoteId);
+    /// Takes the note out of the trash, `false` when it is not there
+    async fn restore(&self, note_id: &NoteId) -> bool;
+    /// Deletes a note from the trash for good, `false` when it is not there
+    async fn purge(&self, note_id: &NoteId) -> bool;
+    /// Purges the notes deleted before `before`, returns their ids
+    async fn purge_deleted_before(&self, before: &DateTime<Utc>) -> Vec<NoteId>;
 }
 
 pub trait NoteGateway: NoteReader + NoteWriter + NoteRemover {}
@@ -25,43 +49,120 @@ pub trait NoteGateway: NoteReader + NoteWriter + NoteRemover {}
 #[cfg(test)]
 pub mod test {
     use std::collections::HashMap;
+    use chrono::{DateTime, Utc};
     use crate::domain::models::note::{Note, NoteListItem};
-    use crate::application::common::note_gateway::{NoteReader, NoteWriter, NoteRemover};
+    use crate::domain::models::trash::{TrashItem, TrashKind};
+    use crate::application::common::note_gateway::{NoteGateway, NoteReader, NoteWriter, NoteRemover};
+    use crate::application::common::pagination::{Cursor, Direction};
     use async_trait::async_trait;
     use tokio::sync::Mutex;
     use crate::domain::models::note::NoteId;
 
     pub struct MockNoteGateway {
-        pub notes: Mutex<HashMap<NoteId, Note>>
+        pub notes: Mutex<HashMap<NoteId, Note>>,
+        /// Notes in the trash and when they were put there
+        pub deleted: Mutex<HashMap<NoteId, DateTime<Utc>>>
     }
     
     impl MockNoteGateway {
         pub fn new(notes: HashMap<NoteId, Note>) -> Self {
             Self {
-                notes: Mutex::new(notes)
+                notes: Mutex::new(notes),
+                deleted: Mutex::new(HashMap::new())
             }
         }
+
+        pub async fn copy(&self) -> Self {
+            Self {
+                notes: Mutex::new(self.notes.lock().await.clone()),
+                deleted: Mutex::new(self.deleted.lock().await.clone())
+            }
+        }
+
+        async fn is_deleted(&self, id: &NoteId) -> bool {
+            self.deleted.lock().await.contains_key(id)
+        }
     }
 
     #[async_trait]
     impl NoteReader for MockNoteGateway {
         async fn get_by_id(&self, id: &NoteId) -> Option<Note> {
+            if self.is_deleted(id).await {
+                return None;
+            }
             self.notes.lock().await.get(id).cloned()
         }
 
         async fn get_by_slug(&self, slug: &str) -> Option<Note> {
-            self.notes.lock().await.values().find(|n| n.slug == slug).cloned()
+            let deleted = self.deleted.lock().await;
+            self.notes.lock().await.values()
+                .find(|n| n.slug == slug && !deleted.contains_key(&n.id))
+                .cloned()
         }
 
         async fn range(&self, limit: &u64, offset: &u64) -> Vec<NoteListItem> {
-            self.notes.lock().await.values().cloned().collect().map(|n| NoteListItem {
-                id: n.id,
-                slug: n.slug,
-                title: n.title,
-                description: n.description,
-                created_at: n.created_at,
-                updated_at: n.updated_at
-            }).collect()
+            self.sorted().await
+                .into_iter()
+                .skip(*offset as usize)
+                .take(*limit as usize)
+                .collect()
+        }
+
+        async fn range_by_cursor(&self, cursor: Option<&Cursor>, limit: &u64) -> Vec<NoteListItem> {
+            let notes = self.sorted().await;
+            let rows: Vec<NoteListItem> = match cursor {
+                None => notes,
+                Some(cursor) => {
+                    let key = (cursor.created_at, cursor.id.clone());
+                    match cursor.direction {
+                        Direction::After => notes.into_iter()
+                            .filter(|n| (n.created_at, n.id.clone()) < key)
+                            .collect(),
+                        Direction::Before => notes.into_iter()
+                            .rev()
+                            .filter(|n| (n.created_at, n.id.clone()) > key)
+                            .collect(),
+                    }
+                }
+            };
+            rows.into_iter().take(*limit as usize).collect()
+        }
+
+        async fn count(&self) -> u64 {
+            self.sorted().await.len() as u64
+        }
+
+        async fn get_deleted(&self) -> Vec<TrashItem> {
+            let deleted = self.deleted.lock().await;
+            let notes = self.notes.lock().await;
+            let mut items: Vec<TrashItem> = deleted.iter().map(|(id, deleted_at)| TrashItem {
+                kind: TrashKind::Note,
+                id: id.clone(),
+                title: notes[id].title.clone(),
+                deleted_at: *deleted_at
+            }).collect();
+            items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+            items
+        }
+    }
+
+    impl MockNoteGateway {
+        async fn sorted(&self) -> Vec<NoteListItem> {
+            let deleted = self.deleted.lock().await;
+            let mut notes: Vec<NoteListItem> = self.notes.lock().await.values()
+                .filter(|n| !deleted.contains_key(&n.id))
+                .cloned()
+                .map(|n| NoteListItem {
+                    id: n.id,
+                    slug: n.slug,
+                    title: n.title,
+                    description: n.description,
+                    created_at: n.created_at,
+                    updated_at: n.updated_at
+                })
+                .collect();
+            notes.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+            notes
         }
     }
 
@@ -70,12 +171,56 @@ pub mod test {
         async fn save(&self, note: &Note) {
             self.notes.lock().await.insert(note.id.clone(), note.clone());
         }
+
+        async fn update(&self, note: &Note, expected_version: i64) -> bool {
+            if self.is_deleted(&note.id).await {
+                return false;
+            }
+            let mut notes = self.notes.lock().await;
+            match notes.get(&note.id) {
+                Some(stored) if stored.version == expected_version => {
+                    notes.insert(note.id.clone(), note.clone());
+                    true
+                }
+                _ => false
+            }
+        }
     }
 
     #[async_trait]
     impl NoteRemover for MockNoteGateway {
         async fn remove(&self, note_id: &NoteId) {
+            if self.notes.lock().await.contains_key(note_id) {
+                self.deleted.lock().await.entry(note_id.clone()).or_insert_with(Utc::now);
+            }
+        }
+
+        async fn restore(&self, note_id: &NoteId) -> bool {
+            self.deleted.lock().await.remove(note_id).is_some()
+        }
+
+        async fn purge(&self, note_id: &NoteId) -> bool {
+            if self.deleted.lock().await.remove(note_id).is_none() {
+                return false;
+            }
             self.notes.lock().await.remove(note_id);
+            true
+        }
+
+        async fn purge_deleted_before(&self, before: &DateTime<Utc>) -> Vec<NoteId> {
+            let mut deleted = self.deleted.lock().await;
+            let mut notes = self.notes.lock().await;
+            let expired: Vec<NoteId> = deleted.iter()
+                .filter(|(_, deleted_at)| *deleted_at < before)
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in &expired {
+                deleted.remove(id);
+                notes.remove(id);
+            }
+            expired
         }
     }
+
+    impl NoteGateway for MockNoteGateway {}
 }
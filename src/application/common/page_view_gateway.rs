@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use crate::domain::models::page_view::{DailyStat, PageView};
+
+
+#[async_trait]
+pub trait PageViewReader{
+    /// Days that still have raw views, oldest first
+    async fn days(&self) -> Vec<NaiveDate>;
+    async fn list_by_day(&self, day: NaiveDate) -> Vec<PageView>;
+}
+
+#[async_trait]
+pub trait PageViewWriter{
+    async fn save(&self, view: &PageView);
+}
+
+#[async_trait]
+pub trait PageViewRemover {
+    async fn remove_before(&self, day: NaiveDate);
+}
+
+pub trait PageViewGateway: PageViewReader + PageViewWriter + PageViewRemover {}
+
+#[async_trait]
+pub trait DailyStatReader{
+    /// Both ends are inclusive
+    async fn range(&self, from: NaiveDate, to: NaiveDate) -> Vec<DailyStat>;
+}
+
+#[async_trait]
+pub trait DailyStatWriter{
+    /// Replaces every aggregate of `day`
+    async fn replace_day(&self, day: NaiveDate, stats: &[DailyStat]);
+}
+
+pub trait DailyStatGateway: DailyStatReader + DailyStatWriter {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockPageViewGateway {
+        pub views: Mutex<Vec<PageView>>
+    }
+
+    impl MockPageViewGateway {
+        pub fn new(views: Vec<PageView>) -> Self {
+            Self {
+                views: Mutex::new(views)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PageViewReader for MockPageViewGateway {
+        async fn days(&self) -> Vec<NaiveDate> {
+            let mut days = self.views.lock().await.iter().map(|v| v.day).collect::<Vec<_>>();
+            days.sort();
+            days.dedup();
+            days
+        }
+
+        async fn list_by_day(&self, day: NaiveDate) -> Vec<PageView> {
+            self.views.lock().await.iter().filter(|v| v.day == day).cloned().collect()
+        }
+    }
+
+    #[async_trait]
+    impl PageViewWriter for MockPageViewGateway {
+        async fn save(&self, view: &PageView) {
+            self.views.lock().await.push(view.clone());
+        }
+    }
+
+    #[async_trait]
+    impl PageViewRemover for MockPageViewGateway {
+        async fn remove_before(&self, day: NaiveDate) {
+            self.views.lock().await.retain(|v| v.day >= day);
+        }
+    }
+
+    impl PageViewGateway for MockPageViewGateway {}
+
+    pub struct MockDailyStatGateway {
+        pub stats: Mutex<Vec<DailyStat>>
+    }
+
+    impl MockDailyStatGateway {
+        pub fn new(stats: Vec<DailyStat>) -> Self {
+            Self {
+                stats: Mutex::new(stats)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl DailyStatReader for MockDailyStatGateway {
+        async fn range(&self, from: NaiveDate, to: NaiveDate) -> Vec<DailyStat> {
+            self.stats.lock().await.iter()
+                .filter(|s| s.day >= from && s.day <= to)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl DailyStatWriter for MockDailyStatGateway {
+        async fn replace_day(&self, day: NaiveDate, stats: &[DailyStat]) {
+            let mut existing = self.stats.lock().await;
+            existing.retain(|s| s.day != day);
+            existing.extend_from_slice(stats);
+        }
+    }
+
+    impl DailyStatGateway for MockDailyStatGateway {}
+}
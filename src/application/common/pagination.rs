@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Items older than the cursor (next page)
+    After,
+    /// Items newer than the cursor (previous page)
+    Before,
+}
+
+/// Keyset position in a listing ordered by `(created_at, id)` descending.
+///
+/// Clients receive it only in the encoded form and must treat it as opaque
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+    pub direction: Direction,
+}
+
+impl Cursor {
+    pub fn after(created_at: DateTime<Utc>, id: String) -> Self {
+        Self { created_at, id, direction: Direction::After }
+    }
+
+    pub fn before(created_at: DateTime<Utc>, id: String) -> Self {
+        Self { created_at, id, direction: Direction::Before }
+    }
+
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            Direction::After => 'a',
+            Direction::Before => 'b',
+        };
+        // Stored timestamps keep nanoseconds, anything coarser repeats
+        // the boundary row on the next page
+        hex::encode(format!(
+            "{}:{}.{:09}:{}",
+            direction,
+            self.created_at.timestamp(),
+            self.created_at.timestamp_subsec_nanos(),
+            self.id
+        ))
+    }
+
+    pub fn decode(value: &str) -> Result<Self, String> {
+        let invalid = || "Cursor is not valid".to_string();
+
+        let raw = hex::decode(value).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+
+        let mut parts = raw.splitn(3, ':');
+        let direction = match parts.next() {
+            Some("a") => Direction::After,
+            Some("b") => Direction::Before,
+            _ => return Err(invalid())
+        };
+        let created_at = parts.next()
+            .and_then(|time| time.split_once('.'))
+            .and_then(|(seconds, nanos)| Some((seconds.parse::<i64>().ok()?, nanos.parse::<u32>().ok()?)))
+            .and_then(|(seconds, nanos)| DateTime::<Utc>::from_timestamp(seconds, nanos))
+            .ok_or_else(invalid)?;
+        let id = parts.next()
+            .filter(|id| !id.is_empty())
+            .ok_or_else(invalid)?
+            .to_string();
+
+        Ok(Self { created_at, id, direction })
+    }
+}
+
+/// Page of a keyset listing with opaque cursors of the neighbouring pages
+#[derive(Clone, Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+/// Builds a page from `limit + 1` rows fetched for `cursor`.
+///
+/// The extra row only tells whether there is one more page in the
+/// requested direction, it is never returned to the client
+pub fn keyset_page<T>(
+    mut rows: Vec<T>,
+    limit: u64,
+    cursor: Option<&Cursor>,
+    key: impl Fn(&T) -> (DateTime<Utc>, String),
+) -> Page<T> {
+    let limit = limit as usize;
+    let has_more = rows.len() > limit;
+    rows.truncate(limit);
+
+    let backwards = matches!(cursor, Some(Cursor { direction: Direction::Before, .. }));
+    if backwards {
+        // Rows of the previous page are fetched in ascending order
+        rows.reverse();
+    }
+
+    let (has_next, has_prev) = match backwards {
+        true => (true, has_more),
+        false => (has_more, cursor.is_some()),
+    };
+
+    let next = match (has_next, rows.last()) {
+        (true, Some(last)) => {
+            let (created_at, id) = key(last);
+            Some(Cursor::after(created_at, id).encode())
+        }
+        _ => None
+    };
+    let prev = match (has_prev, rows.first()) {
+        (true, Some(first)) => {
+            let (created_at, id) = key(first);
+            Some(Cursor::before(created_at, id).encode())
+        }
+        _ => None
+    };
+
+    Page { items: rows, next, prev }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor::before(Utc::now(), "abc:def".to_string());
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+
+        assert_eq!(decoded.id, cursor.id);
+        assert_eq!(decoded.direction, Direction::Before);
+        assert_eq!(decoded.created_at, cursor.created_at);
+    }
+
+    #[test]
+    fn test_cursor_decode_garbage() {
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode(&hex::encode("x:1:id")).is_err());
+        assert!(Cursor::decode(&hex::encode("a:1.0:")).is_err());
+        assert!(Cursor::decode(&hex::encode("a:1:id")).is_err());
+    }
+}
@@ -1,21 +1,192 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::application::common::pagination::Cursor;
 use crate::domain::models::project::{Project, ProjectId};
+use crate::domain::models::trash::TrashItem;
 
 
+/// Deleted projects are hidden the same way as
+/// [deleted notes](crate::application::common::note_gateway::NoteReader)
 #[async_trait]
-pub trait ProjectReader{
+pub trait ProjectReader: Send + Sync {
     async fn get_project(&self, id: &ProjectId) -> Option<Project>;
+    /// Newest first, ordered by `(created_at, id)`
     async fn get_projects_range(&self, limit: &u64, offset: &u64) -> Vec<Project>;
+    /// Keyset variant of [`ProjectReader::get_projects_range`], same contract
+    /// as [`NoteReader::range_by_cursor`](crate::application::common::note_gateway::NoteReader::range_by_cursor)
+    async fn get_projects_by_cursor(&self, cursor: Option<&Cursor>, limit: &u64) -> Vec<Project>;
+    /// The trash, most recently deleted first
+    async fn get_deleted_projects(&self) -> Vec<TrashItem>;
 }
 
 #[async_trait]
-pub trait ProjectWriter{
+pub trait ProjectWriter: Send + Sync {
     async fn save_project(&self, project: &Project);
+    /// Same contract as [`NoteWriter::update`](crate::application::common::note_gateway::NoteWriter::update)
+    async fn update_project(&self, project: &Project, expected_version: i64) -> bool;
 }
 
 #[async_trait]
-pub trait ProjectRemover {
+pub trait ProjectRemover: Send + Sync {
+    /// Moves the project to the trash
     async fn remove_project(&self, project_id: &ProjectId);
+    /// Same contract as [`NoteRemover::restore`](crate::application::common::note_gateway::NoteRemover::restore)
+    async fn restore_project(&self, project_id: &ProjectId) -> bool;
+    async fn purge_project(&self, project_id: &ProjectId) -> bool;
+    async fn purge_deleted_projects_before(&self, before: &DateTime<Utc>) -> u64;
 }
 
 pub trait ProjectGateway: ProjectReader + ProjectWriter + ProjectRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+    use crate::application::common::pagination::{Cursor, Direction};
+    use crate::domain::models::project::{Project, ProjectId};
+    use crate::domain::models::trash::TrashKind;
+    use super::*;
+
+    pub struct MockProjectGateway {
+        pub projects: Mutex<HashMap<ProjectId, Project>>,
+        /// Projects in the trash and when they were put there
+        pub deleted: Mutex<HashMap<ProjectId, DateTime<Utc>>>
+    }
+
+    impl MockProjectGateway {
+        pub fn new(projects: HashMap<ProjectId, Project>) -> Self {
+            Self {
+                projects: Mutex::new(projects),
+                deleted: Mutex::new(HashMap::new())
+            }
+        }
+
+        pub async fn copy(&self) -> Self {
+            Self {
+                projects: Mutex::new(self.projects.lock().await.clone()),
+                deleted: Mutex::new(self.deleted.lock().await.clone())
+            }
+        }
+
+        async fn sorted(&self) -> Vec<Project> {
+            let deleted = self.deleted.lock().await;
+            let mut projects: Vec<Project> = self.projects.lock().await.values()
+                .filter(|p| !deleted.contains_key(&p.id))
+                .cloned()
+                .collect();
+            projects.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+            projects
+        }
+    }
+
+    #[async_trait]
+    impl ProjectReader for MockProjectGateway {
+        async fn get_project(&self, id: &ProjectId) -> Option<Project> {
+            if self.deleted.lock().await.contains_key(id) {
+                return None;
+            }
+            self.projects.lock().await.get(id).cloned()
+        }
+
+        async fn get_projects_range(&self, limit: &u64, offset: &u64) -> Vec<Project> {
+            self.sorted().await
+                .into_iter()
+                .skip(*offset as usize)
+                .take(*limit as usize)
+                .collect()
+        }
+
+        async fn get_projects_by_cursor(&self, cursor: Option<&Cursor>, limit: &u64) -> Vec<Project> {
+            let projects = self.sorted().await;
+            let rows: Vec<Project> = match cursor {
+                None => projects,
+                Some(cursor) => {
+                    let key = (cursor.created_at, cursor.id.clone());
+                    match cursor.direction {
+                        Direction::After => projects.into_iter()
+                            .filter(|p| (p.created_at, p.id.clone()) < key)
+                            .collect(),
+                        Direction::Before => projects.into_iter()
+                            .rev()
+                            .filter(|p| (p.created_at, p.id.clone()) > key)
+                            .collect(),
+                    }
+                }
+            };
+            rows.into_iter().take(*limit as usize).collect()
+        }
+
+        async fn get_deleted_projects(&self) -> Vec<TrashItem> {
+            let deleted = self.deleted.lock().await;
+            let projects = self.projects.lock().await;
+            let mut items: Vec<TrashItem> = deleted.iter().map(|(id, deleted_at)| TrashItem {
+                kind: TrashKind::Project,
+                id: id.clone(),
+                title: projects[id].title.clone(),
+                deleted_at: *deleted_at
+            }).collect();
+            items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+            items
+        }
+    }
+
+    #[async_trait]
+    impl ProjectWriter for MockProjectGateway {
+        async fn save_project(&self, project: &Project) {
+            self.projects.lock().await.insert(project.id.clone(), project.clone());
+        }
+
+        async fn update_project(&self, project: &Project, expected_version: i64) -> bool {
+            if self.deleted.lock().await.contains_key(&project.id) {
+                return false;
+            }
+            let mut projects = self.projects.lock().await;
+            match projects.get(&project.id) {
+                Some(stored) if stored.version == expected_version => {
+                    projects.insert(project.id.clone(), project.clone());
+                    true
+                }
+                _ => false
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ProjectRemover for MockProjectGateway {
+        async fn remove_project(&self, project_id: &ProjectId) {
+            if self.projects.lock().await.contains_key(project_id) {
+                self.deleted.lock().await.entry(project_id.clone()).or_insert_with(Utc::now);
+            }
+        }
+
+        async fn restore_project(&self, project_id: &ProjectId) -> bool {
+            self.deleted.lock().await.remove(project_id).is_some()
+        }
+
+        async fn purge_project(&self, project_id: &ProjectId) -> bool {
+            if self.deleted.lock().await.remove(project_id).is_none() {
+                return false;
+            }
+            self.projects.lock().await.remove(project_id);
+            true
+        }
+
+        async fn purge_deleted_projects_before(&self, before: &DateTime<Utc>) -> u64 {
+            let mut deleted = self.deleted.lock().await;
+            let mut projects = self.projects.lock().await;
+            let expired: Vec<ProjectId> = deleted.iter()
+                .filter(|(_, deleted_at)| *deleted_at < before)
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in &expired {
+                deleted.remove(id);
+                projects.remove(id);
+            }
+            expired.len() as u64
+        }
+    }
+
+    impl ProjectGateway for MockProjectGateway {}
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait RateLimiter: Send + Sync {
+    /// Counts a hit for `key` and tells whether it is still within the limit
+    async fn hit(&self, key: &str) -> bool;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use tokio::sync::Mutex;
+    use super::*;
+
+    /// Allows `limit` hits per key, forever
+    pub struct MockRateLimiter {
+        pub limit: usize,
+        pub hits: Mutex<HashMap<String, usize>>
+    }
+
+    impl MockRateLimiter {
+        pub fn new(limit: usize) -> Self {
+            Self {
+                limit,
+                hits: Mutex::new(HashMap::new())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RateLimiter for MockRateLimiter {
+        async fn hit(&self, key: &str) -> bool {
+            let mut hits = self.hits.lock().await;
+            let count = hits.entry(key.to_string()).or_insert(0);
+            *count += 1;
+            *count <= self.limit
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use crate::domain::models::note::NoteId;
+
+/// Renders the preview image shown by chats and social networks
+#[async_trait]
+pub trait SocialCardRenderer: Send + Sync {
+    /// PNG image with the given title
+    async fn render(&self, title: &str) -> Result<Vec<u8>, String>;
+}
+
+#[async_trait]
+pub trait SocialCardCache: Send + Sync {
+    async fn get(&self, note_id: &NoteId, revision: i64) -> Option<Vec<u8>>;
+    async fn put(&self, note_id: &NoteId, revision: i64, card: Vec<u8>);
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockSocialCardRenderer {
+        pub calls: AtomicUsize
+    }
+
+    impl MockSocialCardRenderer {
+        pub fn new() -> Self {
+            Self {
+                calls: AtomicUsize::new(0)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SocialCardRenderer for MockSocialCardRenderer {
+        async fn render(&self, title: &str) -> Result<Vec<u8>, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(title.as_bytes().to_vec())
+        }
+    }
+
+    pub struct MockSocialCardCache {
+        pub cards: Mutex<HashMap<(NoteId, i64), Vec<u8>>>
+    }
+
+    impl MockSocialCardCache {
+        pub fn new() -> Self {
+            Self {
+                cards: Mutex::new(HashMap::new())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SocialCardCache for MockSocialCardCache {
+        async fn get(&self, note_id: &NoteId, revision: i64) -> Option<Vec<u8>> {
+            self.cards.lock().await.get(&(note_id.clone(), revision)).cloned()
+        }
+
+        async fn put(&self, note_id: &NoteId, revision: i64, card: Vec<u8>) {
+            self.cards.lock().await.insert((note_id.clone(), revision), card);
+        }
+    }
+}
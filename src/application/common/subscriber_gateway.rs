@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use crate::domain::models::subscriber::{Subscriber, SubscriberId, SubscriberStatus};
+
+
+#[async_trait]
+pub trait SubscriberReader{
+    async fn get_by_id(&self, id: &SubscriberId) -> Option<Subscriber>;
+    async fn get_by_email(&self, email: &str) -> Option<Subscriber>;
+    async fn list_by_status(&self, status: SubscriberStatus) -> Vec<Subscriber>;
+}
+
+#[async_trait]
+pub trait SubscriberWriter{
+    async fn save(&self, subscriber: &Subscriber);
+}
+
+#[async_trait]
+pub trait SubscriberRemover {
+    async fn remove(&self, subscriber_id: &SubscriberId);
+}
+
+pub trait SubscriberGateway: SubscriberReader + SubscriberWriter + SubscriberRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockSubscriberGateway {
+        pub subscribers: Mutex<Vec<Subscriber>>
+    }
+
+    impl MockSubscriberGateway {
+        pub fn new(subscribers: Vec<Subscriber>) -> Self {
+            Self {
+                subscribers: Mutex::new(subscribers)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SubscriberReader for MockSubscriberGateway {
+        async fn get_by_id(&self, id: &SubscriberId) -> Option<Subscriber> {
+            self.subscribers.lock().await.iter().find(|s| s.id == *id).cloned()
+        }
+
+        async fn get_by_email(&self, email: &str) -> Option<Subscriber> {
+            self.subscribers.lock().await.iter().find(|s| s.email == email).cloned()
+        }
+
+        async fn list_by_status(&self, status: SubscriberStatus) -> Vec<Subscriber> {
+            self.subscribers.lock().await.iter()
+                .filter(|s| s.status == status)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl SubscriberWriter for MockSubscriberGateway {
+        async fn save(&self, subscriber: &Subscriber) {
+            let mut subscribers = self.subscribers.lock().await;
+            match subscribers.iter_mut().find(|s| s.id == subscriber.id) {
+                Some(existing) => *existing = subscriber.clone(),
+                None => subscribers.push(subscriber.clone())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SubscriberRemover for MockSubscriberGateway {
+        async fn remove(&self, subscriber_id: &SubscriberId) {
+            self.subscribers.lock().await.retain(|s| s.id != *subscriber_id);
+        }
+    }
+
+    impl SubscriberGateway for MockSubscriberGateway {}
+}
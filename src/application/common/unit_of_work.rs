@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::note_gateway::NoteGateway;
+use crate::application::common::project_gateway::ProjectGateway;
+use crate::application::common::user_gateway::UserGateway;
+
+/// Starts units of work: groups of reads and writes that are applied
+/// together or not at all
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn Unit + '_>, String>;
+}
+
+/// Gateways of a unit see its own uncommitted writes, nobody else does.
+/// A unit dropped without [`Unit::commit`] is rolled back
+#[async_trait]
+pub trait Unit: Send + Sync {
+    fn note_gateway(&self) -> &dyn NoteGateway;
+    fn project_gateway(&self) -> &dyn ProjectGateway;
+    fn user_gateway(&self) -> &dyn UserGateway;
+    /// Entries about the writes of the unit, kept only when they are
+    fn audit_writer(&self) -> &dyn AuditWriter;
+    async fn commit(self: Box<Self>) -> Result<(), String>;
+    async fn rollback(self: Box<Self>) -> Result<(), String>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use crate::application::common::audit_gateway::test::MockAuditGateway;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::project_gateway::test::MockProjectGateway;
+    use crate::application::common::user_gateway::test::MockUserGateway;
+    use super::*;
+
+    /// Committed state lives in the gateways, a unit works on copies
+    /// of them and writes them back only on commit
+    pub struct MockUnitOfWork {
+        pub note_gateway: MockNoteGateway,
+        pub project_gateway: MockProjectGateway,
+        pub user_gateway: MockUserGateway,
+        pub audit_gateway: MockAuditGateway
+    }
+
+    impl MockUnitOfWork {
+        pub fn new() -> Self {
+            Self {
+                note_gateway: MockNoteGateway::new(HashMap::new()),
+                project_gateway: MockProjectGateway::new(HashMap::new()),
+                user_gateway: MockUserGateway::new(vec![]),
+                audit_gateway: MockAuditGateway::new(vec![])
+            }
+        }
+    }
+
+    pub struct MockUnit<'a> {
+        committed: &'a MockUnitOfWork,
+        note_gateway: MockNoteGateway,
+        project_gateway: MockProjectGateway,
+        user_gateway: MockUserGateway,
+        /// Only the entries of the unit, appended to the committed ones
+        audit_gateway: MockAuditGateway
+    }
+
+    #[async_trait]
+    impl UnitOfWork for MockUnitOfWork {
+        async fn begin(&self) -> Result<Box<dyn Unit + '_>, String> {
+            Ok(Box::new(MockUnit {
+                committed: self,
+                note_gateway: self.note_gateway.copy().await,
+                project_gateway: self.project_gateway.copy().await,
+                user_gateway: MockUserGateway::new(self.user_gateway.users.lock().await.clone()),
+                audit_gateway: MockAuditGateway::new(vec![])
+            }))
+        }
+    }
+
+    #[async_trait]
+    impl Unit for MockUnit<'_> {
+        fn note_gateway(&self) -> &dyn NoteGateway {
+            &self.note_gateway
+        }
+
+        fn project_gateway(&self) -> &dyn ProjectGateway {
+            &self.project_gateway
+        }
+
+        fn user_gateway(&self) -> &dyn UserGateway {
+            &self.user_gateway
+        }
+
+        fn audit_writer(&self) -> &dyn AuditWriter {
+            &self.audit_gateway
+        }
+
+        async fn commit(self: Box<Self>) -> Result<(), String> {
+            *self.committed.note_gateway.notes.lock().await = self.note_gateway.notes.into_inner();
+            *self.committed.note_gateway.deleted.lock().await = self.note_gateway.deleted.into_inner();
+            *self.committed.project_gateway.projects.lock().await = self.project_gateway.projects.into_inner();
+            *self.committed.project_gateway.deleted.lock().await = self.project_gateway.deleted.into_inner();
+            *self.committed.user_gateway.users.lock().await = self.user_gateway.users.into_inner();
+            self.committed.audit_gateway.entries.lock().await.extend(self.audit_gateway.entries.into_inner());
+            Ok(())
+        }
+
+        async fn rollback(self: Box<Self>) -> Result<(), String> {
+            Ok(())
+        }
+    }
+}
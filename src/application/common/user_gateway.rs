@@ -3,18 +3,21 @@ use crate::domain::models::user::{User, UserId};
 
 
 #[async_trait]
-pub trait UserReader{
+pub trait UserReader: Send + Sync {
     async fn get_by_username(&self, username: &str) -> Option<User>;
     async fn get_all(&self) -> Vec<User>;
 }
 
 #[async_trait]
-pub trait UserWriter{
+pub trait UserWriter: Send + Sync {
     async fn save(&self, user: &User);
+    /// Adds a new user, `false` when the username is already taken.
+    /// The unique index decides, so concurrent inserts can not both win
+    async fn insert(&self, user: &User) -> bool;
 }
 
 #[async_trait]
-pub trait UserRemover {
+pub trait UserRemover: Send + Sync {
     async fn remove(&self, user_id: &UserId);
 }
 
@@ -52,7 +55,20 @@ pub mod test {
     #[async_trait]
     impl UserWriter for MockUserGateway {
         async fn save(&self, user: &User) {
-            self.users.lock().await.push(user.clone());
+            let mut users = self.users.lock().await;
+            match users.iter_mut().find(|u| u.id == user.id) {
+                Some(existing) => *existing = user.clone(),
+                None => users.push(user.clone())
+            }
+        }
+
+        async fn insert(&self, user: &User) -> bool {
+            let mut users = self.users.lock().await;
+            if users.iter().any(|u| u.username == user.username) {
+                return false;
+            }
+            users.push(user.clone());
+            true
         }
     }
 
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+
+/// Salt for visitor hashes. A new one is used every day and old ones
+/// are forgotten, so hashes of different days can not be linked
+#[async_trait]
+pub trait VisitorSalt: Send + Sync {
+    async fn salt(&self, day: NaiveDate) -> Vec<u8>;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    pub struct MockVisitorSalt;
+
+    #[async_trait]
+    impl VisitorSalt for MockVisitorSalt {
+        async fn salt(&self, day: NaiveDate) -> Vec<u8> {
+            day.to_string().into_bytes()
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+
+
+/// How a receiver answered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WebhookResponse {
+    Status(u16),
+    /// No response at all, e.g. a timeout or a refused connection
+    Unreachable(String),
+}
+
+#[async_trait]
+pub trait WebhookClient: Send + Sync {
+    /// Posts the JSON body with the given extra headers
+    async fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> WebhookResponse;
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    pub struct PostedWebhook {
+        pub url: String,
+        pub headers: HashMap<String, String>,
+        pub body: String
+    }
+
+    /// Answers 200 unless the URL has another status in `statuses`
+    pub struct MockWebhookClient {
+        pub statuses: HashMap<String, WebhookResponse>,
+        pub posted: Mutex<Vec<PostedWebhook>>
+    }
+
+    impl MockWebhookClient {
+        pub fn new(statuses: HashMap<String, WebhookResponse>) -> Self {
+            Self {
+                statuses,
+                posted: Mutex::new(vec![])
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebhookClient for MockWebhookClient {
+        async fn post(&self, url: &str, headers: &[(&str, String)], body: &str) -> WebhookResponse {
+            self.posted.lock().unwrap().push(PostedWebhook {
+                url: url.to_string(),
+                headers: headers.iter().map(|(name, value)| (name.to_string(), value.clone())).collect(),
+                body: body.to_string()
+            });
+            self.statuses.get(url).cloned().unwrap_or(WebhookResponse::Status(200))
+        }
+    }
+}
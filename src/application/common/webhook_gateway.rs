@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::models::event::EventKind;
+use crate::domain::models::webhook::{Webhook, WebhookDelivery, WebhookDeliveryId, WebhookId};
+
+
+#[async_trait]
+pub trait WebhookReader {
+    async fn list_webhooks(&self) -> Vec<Webhook>;
+    async fn get_webhook(&self, webhook_id: &WebhookId) -> Option<Webhook>;
+    /// Webhooks that want events of this kind
+    async fn list_subscribed(&self, kind: EventKind) -> Vec<Webhook>;
+}
+
+#[async_trait]
+pub trait WebhookWriter {
+    async fn save_webhook(&self, webhook: &Webhook);
+}
+
+#[async_trait]
+pub trait WebhookRemover {
+    /// Also removes its deliveries. Returns `false` when there was no such webhook
+    async fn remove_webhook(&self, webhook_id: &WebhookId) -> bool;
+}
+
+#[async_trait]
+pub trait WebhookDeliveryReader {
+    /// Pending deliveries whose next attempt is due at `now`, oldest first
+    async fn list_due_deliveries(&self, now: DateTime<Utc>, limit: &u64) -> Vec<WebhookDelivery>;
+    async fn get_delivery(&self, delivery_id: &WebhookDeliveryId) -> Option<WebhookDelivery>;
+    /// Newest first
+    async fn list_deliveries(&self, webhook_id: &WebhookId, limit: &u64) -> Vec<WebhookDelivery>;
+}
+
+#[async_trait]
+pub trait WebhookDeliveryWriter {
+    async fn save_delivery(&self, delivery: &WebhookDelivery);
+}
+
+pub trait WebhookGateway: WebhookReader + WebhookWriter + WebhookRemover
+    + WebhookDeliveryReader + WebhookDeliveryWriter + Send + Sync {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use crate::domain::models::webhook::WebhookDeliveryStatus;
+    use super::*;
+
+    pub struct MockWebhookGateway {
+        pub webhooks: Mutex<Vec<Webhook>>,
+        pub deliveries: Mutex<Vec<WebhookDelivery>>
+    }
+
+    impl MockWebhookGateway {
+        pub fn new(webhooks: Vec<Webhook>, deliveries: Vec<WebhookDelivery>) -> Self {
+            Self {
+                webhooks: Mutex::new(webhooks),
+                deliveries: Mutex::new(deliveries)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebhookReader for MockWebhookGateway {
+        async fn list_webhooks(&self) -> Vec<Webhook> {
+            self.webhooks.lock().await.clone()
+        }
+
+        async fn get_webhook(&self, webhook_id: &WebhookId) -> Option<Webhook> {
+            self.webhooks.lock().await.iter().find(|w| w.id == *webhook_id).cloned()
+        }
+
+        async fn list_subscribed(&self, kind: EventKind) -> Vec<Webhook> {
+            self.webhooks.lock().await.iter().filter(|w| w.wants(kind)).cloned().collect()
+        }
+    }
+
+    #[async_trait]
+    impl WebhookWriter for MockWebhookGateway {
+        async fn save_webhook(&self, webhook: &Webhook) {
+            let mut webhooks = self.webhooks.lock().await;
+            match webhooks.iter_mut().find(|w| w.id == webhook.id) {
+                Some(existing) => *existing = webhook.clone(),
+                None => webhooks.push(webhook.clone())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebhookRemover for MockWebhookGateway {
+        async fn remove_webhook(&self, webhook_id: &WebhookId) -> bool {
+            let mut webhooks = self.webhooks.lock().await;
+            let before = webhooks.len();
+            webhooks.retain(|w| w.id != *webhook_id);
+            self.deliveries.lock().await.retain(|d| d.webhook_id != *webhook_id);
+            webhooks.len() != before
+        }
+    }
+
+    #[async_trait]
+    impl WebhookDeliveryReader for MockWebhookGateway {
+        async fn list_due_deliveries(&self, now: DateTime<Utc>, limit: &u64) -> Vec<WebhookDelivery> {
+            let mut due = self.deliveries.lock().await.iter()
+                .filter(|d| d.status == WebhookDeliveryStatus::Pending && d.next_attempt_at <= now)
+                .cloned()
+                .collect::<Vec<_>>();
+            due.sort_by_key(|d| d.next_attempt_at);
+            due.truncate(*limit as usize);
+            due
+        }
+
+        async fn get_delivery(&self, delivery_id: &WebhookDeliveryId) -> Option<WebhookDelivery> {
+            self.deliveries.lock().await.iter().find(|d| d.id == *delivery_id).cloned()
+        }
+
+        async fn list_deliveries(&self, webhook_id: &WebhookId, limit: &u64) -> Vec<WebhookDelivery> {
+            let mut deliveries = self.deliveries.lock().await.iter()
+                .filter(|d| d.webhook_id == *webhook_id)
+                .cloned()
+                .collect::<Vec<_>>();
+            deliveries.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+            deliveries.truncate(*limit as usize);
+            deliveries
+        }
+    }
+
+    #[async_trait]
+    impl WebhookDeliveryWriter for MockWebhookGateway {
+        async fn save_delivery(&self, delivery: &WebhookDelivery) {
+            let mut deliveries = self.deliveries.lock().await;
+            match deliveries.iter_mut().find(|d| d.id == delivery.id) {
+                Some(existing) => *existing = delivery.clone(),
+                None => deliveries.push(delivery.clone())
+            }
+        }
+    }
+
+    impl WebhookGateway for MockWebhookGateway {}
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use crate::domain::models::webmention::Webmention;
+
+/// Network side of Webmention. Both methods only queue the work,
+/// it is done by a background job that is tried again when it fails
+#[async_trait]
+pub trait WebmentionClient: Send + Sync {
+    /// Fetches the source and marks the mention verified or invalid
+    async fn verify_later(&self, webmention: Webmention);
+    /// Notifies every page linked from `html` that `source` mentions it
+    async fn send_later(&self, source: String, html: String);
+}
+
+
+#[cfg(test)]
+pub mod test {
+    use std::sync::Mutex;
+    use super::*;
+
+    pub struct MockWebmentionClient {
+        pub verified: Mutex<Vec<Webmention>>,
+        pub sent: Mutex<Vec<(String, String)>>
+    }
+
+    impl MockWebmentionClient {
+        pub fn new() -> Self {
+            Self {
+                verified: Mutex::new(vec![]),
+                sent: Mutex::new(vec![])
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebmentionClient for MockWebmentionClient {
+        async fn verify_later(&self, webmention: Webmention) {
+            self.verified.lock().unwrap().push(webmention);
+        }
+
+        async fn send_later(&self, source: String, html: String) {
+            self.sent.lock().unwrap().push((source, html));
+        }
+    }
+}
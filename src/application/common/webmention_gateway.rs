@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use crate::domain::models::note::NoteId;
+use crate::domain::models::webmention::{Webmention, WebmentionId, WebmentionStatus};
+
+
+#[async_trait]
+pub trait WebmentionReader{
+    async fn get_by_id(&self, id: &WebmentionId) -> Option<Webmention>;
+    async fn get_by_source_target(&self, source: &str, target: &str) -> Option<Webmention>;
+    /// Mentions of a note in the given status, oldest first
+    async fn list_by_note(&self, note_id: &NoteId, status: WebmentionStatus) -> Vec<Webmention>;
+}
+
+#[async_trait]
+pub trait WebmentionWriter{
+    async fn save(&self, webmention: &Webmention);
+}
+
+#[async_trait]
+pub trait WebmentionRemover {
+    async fn remove(&self, webmention_id: &WebmentionId);
+    /// Removes every mention of the note, used when the note is purged
+    async fn remove_by_note(&self, note_id: &NoteId);
+}
+
+pub trait WebmentionGateway: WebmentionReader + WebmentionWriter + WebmentionRemover {}
+
+
+#[cfg(test)]
+pub mod test {
+    use tokio::sync::Mutex;
+    use super::*;
+
+    pub struct MockWebmentionGateway {
+        pub webmentions: Mutex<Vec<Webmention>>
+    }
+
+    impl MockWebmentionGateway {
+        pub fn new(webmentions: Vec<Webmention>) -> Self {
+            Self {
+                webmentions: Mutex::new(webmentions)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebmentionReader for MockWebmentionGateway {
+        async fn get_by_id(&self, id: &WebmentionId) -> Option<Webmention> {
+            self.webmentions.lock().await.iter().find(|w| w.id == *id).cloned()
+        }
+
+        async fn get_by_source_target(&self, source: &str, target: &str) -> Option<Webmention> {
+            self.webmentions.lock().await.iter()
+                .find(|w| w.source == source && w.target == target)
+                .cloned()
+        }
+
+        async fn list_by_note(&self, note_id: &NoteId, status: WebmentionStatus) -> Vec<Webmention> {
+            self.webmentions.lock().await.iter()
+                .filter(|w| w.note_id == *note_id && w.status == status)
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl WebmentionWriter for MockWebmentionGateway {
+        async fn save(&self, webmention: &Webmention) {
+            let mut webmentions = self.webmentions.lock().await;
+            match webmentions.iter_mut().find(|w| w.id == webmention.id) {
+                Some(existing) => *existing = webmention.clone(),
+                None => webmentions.push(webmention.clone())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl WebmentionRemover for MockWebmentionGateway {
+        async fn remove(&self, webmention_id: &WebmentionId) {
+            self.webmentions.lock().await.retain(|w| w.id != *webmention_id);
+        }
+
+        async fn remove_by_note(&self, note_id: &NoteId) {
+            self.webmentions.lock().await.retain(|w| w.note_id != *note_id);
+        }
+    }
+
+    impl WebmentionGateway for MockWebmentionGateway {}
+}
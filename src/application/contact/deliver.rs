@@ -0,0 +1,84 @@
+use crate::application::common::contact_gateway::{ContactMessageGateway, ContactMessageReader, ContactMessageWriter};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::mailer::Mailer;
+use crate::domain::models::contact_message::ContactMessageStatus;
+use crate::domain::services::contact::contact_mail;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct DeliverContactMessagesResult {
+    pub delivered: u64,
+    /// Left for the next run
+    pub failed: u64
+}
+
+pub struct DeliverContactMessages<'a> {
+    pub contact_gateway: &'a dyn ContactMessageGateway,
+    pub mailer: &'a dyn Mailer,
+    pub recipient: &'a str
+}
+
+/// Mails the messages that were stored while the mailer was failing
+#[async_trait]
+impl Interactor<(), DeliverContactMessagesResult> for DeliverContactMessages<'_> {
+    async fn execute(&self, _data: ()) -> Result<DeliverContactMessagesResult, ApplicationError> {
+        let mut result = DeliverContactMessagesResult::default();
+
+        for mut message in self.contact_gateway.list_by_status(ContactMessageStatus::Pending).await {
+            match self.mailer.send(&contact_mail(self.recipient, &message)).await {
+                Ok(()) => {
+                    message.delivered();
+                    self.contact_gateway.save(&message).await;
+                    result.delivered += 1;
+                }
+                Err(_) => result.failed += 1
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::contact_gateway::test::MockContactMessageGateway;
+    use crate::application::common::mailer::test::MockMailer;
+    use crate::domain::models::contact_message::ContactMessage;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_deliver_pending() {
+        let message = |status| {
+            let mut message = ContactMessage::create(
+                "Reader".to_string(),
+                "reader@example.com".to_string(),
+                String::new(),
+                "Hi".to_string(),
+                "127.0.0.1".to_string()
+            ).unwrap();
+            message.status = status;
+            message
+        };
+        let contact_gateway = MockContactMessageGateway::new(vec![
+            message(ContactMessageStatus::Pending),
+            message(ContactMessageStatus::Spam),
+            message(ContactMessageStatus::Delivered),
+        ]);
+        let mailer = MockMailer::new();
+
+        let interactor = DeliverContactMessages {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            recipient: "owner@jkearnsl.su"
+        };
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!(result.delivered, 1);
+        assert_eq!(mailer.sent.lock().unwrap().len(), 1);
+
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!(result.delivered, 0);
+    }
+}
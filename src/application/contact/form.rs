@@ -0,0 +1,29 @@
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::domain::services::contact::form_token;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct GetContactFormResult {
+    /// Sent back with the message, also the proof-of-work challenge
+    pub token: String,
+    /// Leading zero bits required from `sha256("{token}:{nonce}")`, 0 when disabled
+    pub pow_difficulty: u8
+}
+
+pub struct GetContactForm<'a> {
+    pub secret: &'a [u8],
+    pub pow_difficulty: u8
+}
+
+#[async_trait]
+impl Interactor<(), GetContactFormResult> for GetContactForm<'_> {
+    async fn execute(&self, _data: ()) -> Result<GetContactFormResult, ApplicationError> {
+        Ok(GetContactFormResult {
+            token: form_token(self.secret, Utc::now()),
+            pow_difficulty: self.pow_difficulty
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use crate::application::common::contact_gateway::ContactMessageReader;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::domain::models::contact_message::ContactMessage;
+use crate::domain::services::validator::{page_offset, validate_per_page};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ListContactMessagesRequest {
+    pub page: u64,
+    pub per_page: u64
+}
+
+pub struct ListContactMessages<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub contact_reader: &'a dyn ContactMessageReader
+}
+
+/// Every stored message including spam, newest first
+#[async_trait]
+impl Interactor<ListContactMessagesRequest, Vec<ContactMessage>> for ListContactMessages<'_> {
+    async fn execute(&self, data: ListContactMessagesRequest) -> Result<Vec<ContactMessage>, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let mut errors = HashMap::new();
+        if let Err(e) = validate_per_page(&data.per_page) {
+            errors.insert("per_page".to_string(), e);
+        }
+        let offset = page_offset(&data.page, &data.per_page).unwrap_or_else(|e| {
+            errors.insert("page".to_string(), e);
+            0
+        });
+        if !errors.is_empty() {
+            return Err(ApplicationError::ValidationError(errors));
+        }
+
+        Ok(self.contact_reader.list(&data.per_page, &offset).await)
+    }
+}
@@ -0,0 +1,4 @@
+pub mod form;
+pub mod submit;
+pub mod deliver;
+pub mod list;
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use chrono::{Duration, Utc};
+use crate::application::common::contact_gateway::{ContactMessageGateway, ContactMessageWriter};
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::mailer::Mailer;
+use crate::application::common::rate_limiter::RateLimiter;
+use crate::domain::models::contact_message::ContactMessage;
+use crate::domain::services::contact::{
+    contact_mail,
+    form_issued_at,
+    form_token_hash,
+    verify_proof_of_work,
+    FORM_MIN_FILL_SECONDS,
+    FORM_TOKEN_TTL_HOURS
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SubmitContactRequest {
+    pub name: String,
+    pub email: String,
+    #[serde(default)]
+    pub subject: String,
+    pub body: String,
+    /// From [`GetContactForm`](super::form::GetContactForm)
+    pub token: String,
+    /// Proof-of-work solution, only checked when it is enabled
+    #[serde(default)]
+    pub nonce: String,
+    /// Honeypot: hidden in the form, so only bots fill it in
+    #[serde(default)]
+    pub website: String,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub ip: String
+}
+
+pub struct SubmitContact<'a> {
+    pub contact_gateway: &'a dyn ContactMessageGateway,
+    pub mailer: &'a dyn Mailer,
+    pub rate_limiter: &'a dyn RateLimiter,
+    /// Owner address the messages are mailed to
+    pub recipient: &'a str,
+    pub secret: &'a [u8],
+    pub pow_difficulty: u8
+}
+
+/// Stores the message before mailing it, so nothing is lost while SMTP is down.
+/// Undelivered messages are retried by [`DeliverContactMessages`](super::deliver::DeliverContactMessages)
+#[async_trait]
+impl Interactor<SubmitContactRequest, ()> for SubmitContact<'_> {
+    async fn execute(&self, data: SubmitContactRequest) -> Result<(), ApplicationError> {
+        if !self.rate_limiter.hit(&data.ip).await {
+            return Err(ApplicationError::TooManyRequests);
+        }
+
+        let now = Utc::now();
+        let issued_at = form_issued_at(self.secret, &data.token)
+            .filter(|issued_at| now - *issued_at <= Duration::hours(FORM_TOKEN_TTL_HOURS))
+            .ok_or_else(|| ApplicationError::ValidationError(HashMap::from([(
+                "token".to_string(),
+                "Form has expired, reload the page".to_string()
+            )])))?;
+
+        if self.pow_difficulty > 0 && !verify_proof_of_work(&data.token, &data.nonce, self.pow_difficulty) {
+            return Err(ApplicationError::ValidationError(HashMap::from([(
+                "nonce".to_string(),
+                "Proof of work is not valid".to_string()
+            )])));
+        }
+
+        let mut message = ContactMessage::create(
+            data.name,
+            data.email,
+            data.subject,
+            data.body,
+            data.ip
+        ).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        // Taken only once the message is valid, so fixing a typo needs no new form
+        let expires_at = issued_at + Duration::hours(FORM_TOKEN_TTL_HOURS);
+        if !self.contact_gateway.use_token(&form_token_hash(&data.token), expires_at).await {
+            return Err(ApplicationError::ValidationError(HashMap::from([(
+                "token".to_string(),
+                "Form was already sent, reload the page".to_string()
+            )])));
+        }
+
+        // Bots get the same answer as people, the message is kept for review only
+        if !data.website.is_empty() || now - issued_at < Duration::seconds(FORM_MIN_FILL_SECONDS) {
+            message.spam();
+            self.contact_gateway.save(&message).await;
+            return Ok(());
+        }
+
+        self.contact_gateway.save(&message).await;
+
+        if self.mailer.send(&contact_mail(self.recipient, &message)).await.is_ok() {
+            message.delivered();
+            self.contact_gateway.save(&message).await;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::contact_gateway::test::MockContactMessageGateway;
+    use crate::application::common::mailer::test::MockMailer;
+    use crate::application::common::rate_limiter::test::MockRateLimiter;
+    use crate::domain::models::contact_message::ContactMessageStatus;
+    use crate::domain::services::contact::form_token;
+    use super::*;
+
+    fn request(token: String) -> SubmitContactRequest {
+        SubmitContactRequest {
+            name: "Reader".to_string(),
+            email: "reader@example.com".to_string(),
+            subject: "Hello".to_string(),
+            body: "Nice blog".to_string(),
+            token,
+            nonce: String::new(),
+            website: String::new(),
+            ip: "127.0.0.1".to_string()
+        }
+    }
+
+    fn filled_token() -> String {
+        form_token(b"secret", Utc::now() - Duration::minutes(1))
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let contact_gateway = MockContactMessageGateway::new(vec![]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = SubmitContact {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            recipient: "owner@jkearnsl.su",
+            secret: b"secret",
+            pow_difficulty: 0
+        };
+        interactor.execute(request(filled_token())).await.unwrap();
+
+        let messages = contact_gateway.messages.lock().await.clone();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].status, ContactMessageStatus::Delivered);
+
+        let sent = mailer.sent.lock().unwrap().clone();
+        assert_eq!(sent[0].to, "owner@jkearnsl.su");
+        assert!(sent[0].headers.contains(&("Reply-To".to_string(), "reader@example.com".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_submit_mailer_down() {
+        let contact_gateway = MockContactMessageGateway::new(vec![]);
+        let mut mailer = MockMailer::new();
+        mailer.failing = vec!["owner@jkearnsl.su".to_string()];
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = SubmitContact {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            recipient: "owner@jkearnsl.su",
+            secret: b"secret",
+            pow_difficulty: 0
+        };
+        interactor.execute(request(filled_token())).await.unwrap();
+
+        let messages = contact_gateway.messages.lock().await.clone();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].status, ContactMessageStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_submit_spam() {
+        let contact_gateway = MockContactMessageGateway::new(vec![]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = SubmitContact {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            recipient: "owner@jkearnsl.su",
+            secret: b"secret",
+            pow_difficulty: 0
+        };
+
+        let mut honeypot = request(filled_token());
+        honeypot.website = "https://spam.example".to_string();
+        interactor.execute(honeypot).await.unwrap();
+
+        // Submitted right after the form was shown
+        interactor.execute(request(form_token(b"secret", Utc::now()))).await.unwrap();
+
+        let messages = contact_gateway.messages.lock().await.clone();
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|m| m.status == ContactMessageStatus::Spam));
+        assert!(mailer.sent.lock().unwrap().is_empty());
+
+        let expired = request(form_token(b"secret", Utc::now() - Duration::days(2)));
+        assert!(matches!(interactor.execute(expired).await, Err(ApplicationError::ValidationError(_))));
+
+        let forged = request(form_token(b"other", Utc::now() - Duration::minutes(1)));
+        assert!(matches!(interactor.execute(forged).await, Err(ApplicationError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_submit_proof_of_work() {
+        let contact_gateway = MockContactMessageGateway::new(vec![]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = SubmitContact {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            recipient: "owner@jkearnsl.su",
+            secret: b"secret",
+            pow_difficulty: 8
+        };
+
+        let token = filled_token();
+        let nonce = (0u64..)
+            .find(|nonce| verify_proof_of_work(&token, &nonce.to_string(), 8))
+            .unwrap()
+            .to_string();
+        let unsolved = (0u64..)
+            .find(|nonce| !verify_proof_of_work(&token, &nonce.to_string(), 8))
+            .unwrap()
+            .to_string();
+
+        let mut wrong = request(token.clone());
+        wrong.nonce = unsolved;
+        assert!(matches!(interactor.execute(wrong).await, Err(ApplicationError::ValidationError(_))));
+
+        let mut solved = request(token);
+        solved.nonce = nonce;
+        interactor.execute(solved).await.unwrap();
+        assert_eq!(mailer.sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_rate_limit() {
+        let contact_gateway = MockContactMessageGateway::new(vec![]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(1);
+
+        let interactor = SubmitContact {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            recipient: "owner@jkearnsl.su",
+            secret: b"secret",
+            pow_difficulty: 0
+        };
+        interactor.execute(request(filled_token())).await.unwrap();
+
+        let result = interactor.execute(request(filled_token())).await;
+        assert!(matches!(result, Err(ApplicationError::TooManyRequests)));
+    }
+
+    #[tokio::test]
+    async fn test_submit_replayed_token() {
+        let contact_gateway = MockContactMessageGateway::new(vec![]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = SubmitContact {
+            contact_gateway: &contact_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            recipient: "owner@jkearnsl.su",
+            secret: b"secret",
+            pow_difficulty: 0
+        };
+        let token = filled_token();
+
+        let mut invalid = request(token.clone());
+        invalid.email = "not an email".to_string();
+        assert!(matches!(interactor.execute(invalid).await, Err(ApplicationError::ValidationError(_))));
+
+        interactor.execute(request(token.clone())).await.unwrap();
+        let result = interactor.execute(request(token)).await;
+
+        assert!(matches!(result, Err(ApplicationError::ValidationError(e)) if e.contains_key("token")));
+        assert_eq!(contact_gateway.messages.lock().await.len(), 1);
+        assert_eq!(mailer.sent.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use crate::application::common::counter_gateway::{CounterGateway, CounterReader, CounterWriter};
+use crate::application::common::counter_renderer::CounterRenderer;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::rate_limiter::RateLimiter;
+use crate::domain::models::counter::{
+    counter_digits,
+    validate_counter_name,
+    COUNTER_LENGTH_DEFAULT,
+    COUNTER_LENGTH_MAX,
+    COUNTER_THEME_DEFAULT
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct HitCounterRequest {
+    /// Filled in by the presentation layer from the path
+    #[serde(skip)]
+    pub name: String,
+    pub theme: Option<String>,
+    /// Minimal number of digits
+    pub length: Option<usize>,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub ip: String
+}
+
+pub struct HitCounter<'a> {
+    pub counter_gateway: &'a dyn CounterGateway,
+    pub counter_renderer: &'a dyn CounterRenderer,
+    /// Allows one hit per counter and IP within the dedupe window
+    pub dedupe: &'a dyn RateLimiter
+}
+
+/// Counts the visit and returns the SVG badge. Repeated loads from the same
+/// IP within the window show the count without changing it
+#[async_trait]
+impl Interactor<HitCounterRequest, String> for HitCounter<'_> {
+    async fn execute(&self, data: HitCounterRequest) -> Result<String, ApplicationError> {
+        let mut errors = HashMap::new();
+
+        if let Err(e) = validate_counter_name(&data.name) {
+            errors.insert("name".to_string(), e);
+        }
+        let theme = data.theme.unwrap_or_else(|| COUNTER_THEME_DEFAULT.to_string());
+        if !self.counter_renderer.has_theme(&theme) {
+            errors.insert("theme".to_string(), format!("Theme {} does not exist", theme));
+        }
+        let length = data.length.unwrap_or(COUNTER_LENGTH_DEFAULT);
+        if length == 0 || length > COUNTER_LENGTH_MAX {
+            errors.insert(
+                "length".to_string(),
+                format!("should be between 1 and {}", COUNTER_LENGTH_MAX)
+            );
+        }
+        if !errors.is_empty() {
+            return Err(ApplicationError::ValidationError(errors));
+        }
+
+        let count = match self.dedupe.hit(&format!("{}\0{}", data.name, data.ip)).await {
+            true => self.counter_gateway.increment(&data.name).await,
+            false => self.counter_gateway.get_by_name(&data.name).await.map_or(0, |c| c.count)
+        };
+
+        self.counter_renderer.render(&theme, &counter_digits(count, length)).ok_or_else(|| {
+            ApplicationError::UnexpectedError(format!("Theme {} disappeared", theme))
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::counter_gateway::test::MockCounterGateway;
+    use crate::application::common::counter_renderer::test::MockCounterRenderer;
+    use crate::application::common::rate_limiter::test::MockRateLimiter;
+    use super::*;
+
+    fn request(name: &str, ip: &str) -> HitCounterRequest {
+        HitCounterRequest {
+            name: name.to_string(),
+            theme: None,
+            length: None,
+            ip: ip.to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hit() {
+        let counter_gateway = MockCounterGateway::new(vec![]);
+        let dedupe = MockRateLimiter::new(1);
+
+        let interactor = HitCounter {
+            counter_gateway: &counter_gateway,
+            counter_renderer: &MockCounterRenderer,
+            dedupe: &dedupe
+        };
+
+        assert_eq!(interactor.execute(request("@JKearnsl", "1.1.1.1")).await.unwrap(), "<svg>0000001</svg>");
+        // Same visitor reloads the page
+        assert_eq!(interactor.execute(request("@JKearnsl", "1.1.1.1")).await.unwrap(), "<svg>0000001</svg>");
+        assert_eq!(interactor.execute(request("@JKearnsl", "2.2.2.2")).await.unwrap(), "<svg>0000002</svg>");
+        // Counters are independent
+        assert_eq!(interactor.execute(request("other", "1.1.1.1")).await.unwrap(), "<svg>0000001</svg>");
+
+        let mut short = request("@JKearnsl", "3.3.3.3");
+        short.length = Some(1);
+        assert_eq!(interactor.execute(short).await.unwrap(), "<svg>3</svg>");
+    }
+
+    #[tokio::test]
+    async fn test_hit_invalid() {
+        let counter_gateway = MockCounterGateway::new(vec![]);
+        let dedupe = MockRateLimiter::new(1);
+
+        let interactor = HitCounter {
+            counter_gateway: &counter_gateway,
+            counter_renderer: &MockCounterRenderer,
+            dedupe: &dedupe
+        };
+
+        let mut unknown_theme = request("blog", "1.1.1.1");
+        unknown_theme.theme = Some("rule34".to_string());
+        assert!(matches!(interactor.execute(unknown_theme).await, Err(ApplicationError::ValidationError(_))));
+
+        assert!(matches!(interactor.execute(request("a/b", "1.1.1.1")).await, Err(ApplicationError::ValidationError(_))));
+        assert!(counter_gateway.counters.lock().await.is_empty());
+    }
+}
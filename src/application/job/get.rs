@@ -0,0 +1,29 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::JobReader;
+use crate::domain::models::job::{Job, JobId};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetJobRequest {
+    pub id: JobId
+}
+
+pub struct GetJob<'a> {
+    pub job_reader: &'a dyn JobReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<GetJobRequest, Job> for GetJob<'_> {
+    async fn execute(&self, data: GetJobRequest) -> Result<Job, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        self.job_reader.get_job(&data.id).await.ok_or(ApplicationError::NotFound)
+    }
+}
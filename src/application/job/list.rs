@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::{JobFilter, JobReader};
+use crate::application::common::pagination::{keyset_page, Cursor};
+use crate::domain::models::job::{Job, JobStatus};
+use crate::domain::services::validator::validate_per_page;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ListJobsRequest {
+    pub status: Option<JobStatus>,
+    pub kind: Option<String>,
+    pub cursor: Option<String>,
+    pub per_page: u64
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListJobsResult {
+    pub items: Vec<Job>,
+    pub next: Option<String>,
+    pub prev: Option<String>
+}
+
+pub struct ListJobs<'a> {
+    pub job_reader: &'a dyn JobReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Newest first
+#[async_trait]
+impl Interactor<ListJobsRequest, ListJobsResult> for ListJobs<'_> {
+    async fn execute(&self, data: ListJobsRequest) -> Result<ListJobsResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        validate_per_page(&data.per_page).map_err(|e| {
+            ApplicationError::ValidationError(HashMap::from([("per_page".to_string(), e)]))
+        })?;
+
+        let cursor = match data.cursor {
+            Some(cursor) => Some(Cursor::decode(&cursor).map_err(|e| {
+                ApplicationError::ValidationError(HashMap::from([("cursor".to_string(), e)]))
+            })?),
+            None => None
+        };
+
+        let filter = JobFilter {
+            status: data.status,
+            kind: data.kind
+        };
+        let rows = self.job_reader.list_jobs(&filter, cursor.as_ref(), &(data.per_page + 1)).await;
+        let page = keyset_page(rows, data.per_page, cursor.as_ref(), |job| {
+            (job.created_at, job.id.clone())
+        });
+
+        Ok(ListJobsResult {
+            items: page.items,
+            next: page.next,
+            prev: page.prev
+        })
+    }
+}
@@ -0,0 +1,7 @@
+pub mod run_next;
+pub mod schedule;
+pub mod recover;
+pub mod prune;
+pub mod list;
+pub mod get;
+pub mod retry;
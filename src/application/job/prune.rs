@@ -0,0 +1,23 @@
+use std::time::Duration;
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::JobWriter;
+use async_trait::async_trait;
+
+pub struct PruneJobs<'a> {
+    pub job_writer: &'a dyn JobWriter,
+    /// How long done jobs are kept
+    pub retention: &'a Duration
+}
+
+/// Scheduled, removes done jobs past the retention. Dead ones stay until retried
+#[async_trait]
+impl Interactor<(), u64> for PruneJobs<'_> {
+    async fn execute(&self, _data: ()) -> Result<u64, ApplicationError> {
+        let retention = chrono::Duration::from_std(*self.retention)
+            .map_err(|e| ApplicationError::UnexpectedError(e.to_string()))?;
+
+        Ok(self.job_writer.remove_done_before(Utc::now() - retention).await)
+    }
+}
@@ -0,0 +1,18 @@
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::JobWriter;
+use async_trait::async_trait;
+
+pub struct RecoverJobs<'a> {
+    pub job_writer: &'a dyn JobWriter
+}
+
+/// Run at startup, before the workers. Jobs that were running when the
+/// process stopped are queued again, returns how many there were
+#[async_trait]
+impl Interactor<(), u64> for RecoverJobs<'_> {
+    async fn execute(&self, _data: ()) -> Result<u64, ApplicationError> {
+        Ok(self.job_writer.requeue_running(Utc::now()).await)
+    }
+}
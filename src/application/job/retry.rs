@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::{JobReader, JobWriter};
+use crate::domain::models::job::{Job, JobId};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RetryJobRequest {
+    pub id: JobId
+}
+
+pub struct RetryJob<'a> {
+    pub job_reader: &'a dyn JobReader,
+    pub job_writer: &'a dyn JobWriter,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Queues a dead job again with a fresh set of attempts
+#[async_trait]
+impl Interactor<RetryJobRequest, Job> for RetryJob<'_> {
+    async fn execute(&self, data: RetryJobRequest) -> Result<Job, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let mut job = self.job_reader.get_job(&data.id).await.ok_or(ApplicationError::NotFound)?;
+        if !job.retry(Utc::now()) {
+            return Err(ApplicationError::ValidationError(HashMap::from([(
+                "status".to_string(),
+                format!("Only dead jobs can be retried, this one is {}", job.status.as_str())
+            )])));
+        }
+
+        self.job_writer.save_job(&job).await;
+        Ok(job)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::job_gateway::test::MockJobGateway;
+    use crate::domain::models::job::JobStatus;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_retry_job() {
+        let queued = Job::create("backup", json!({}), Utc::now());
+        let mut dead = Job::create("backup", json!({}), Utc::now());
+        while dead.fail(Utc::now(), "disk full".to_string()) {}
+
+        let job_gateway = MockJobGateway::new(vec![queued.clone(), dead.clone()]);
+        let interactor = RetryJob {
+            job_reader: &job_gateway,
+            job_writer: &job_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let retried = interactor.execute(RetryJobRequest { id: dead.id.clone() }).await.unwrap();
+        assert_eq!((retried.status, retried.attempts), (JobStatus::Queued, 0));
+        assert_eq!(job_gateway.get_job(&dead.id).await.unwrap().status, JobStatus::Queued);
+
+        let error = interactor.execute(RetryJobRequest { id: queued.id }).await.unwrap_err();
+        assert!(matches!(error, ApplicationError::ValidationError(_)));
+        let error = interactor.execute(RetryJobRequest { id: "missing".to_string() }).await.unwrap_err();
+        assert!(matches!(error, ApplicationError::NotFound));
+    }
+}
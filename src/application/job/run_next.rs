@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::JobWriter;
+use crate::application::common::job_handler::JobHandler;
+use crate::domain::models::job::Job;
+use async_trait::async_trait;
+
+pub struct RunNextJob<'a> {
+    pub job_writer: &'a dyn JobWriter,
+    pub handlers: &'a [Arc<dyn JobHandler>]
+}
+
+/// Claims the next due job and runs it with the handler of its kind.
+/// The handler runs in a task of its own, so a panic fails the job
+/// instead of leaving it running and taking the worker down.
+/// Returns the job as it was saved afterwards, `None` when nothing was due
+#[async_trait]
+impl Interactor<(), Option<Job>> for RunNextJob<'_> {
+    async fn execute(&self, _data: ()) -> Result<Option<Job>, ApplicationError> {
+        let Some(mut job) = self.job_writer.claim_next(Utc::now()).await else {
+            return Ok(None);
+        };
+
+        let result = match self.handlers.iter().find(|handler| handler.kind() == job.kind) {
+            Some(handler) => {
+                let handler = handler.clone();
+                let payload = job.payload.clone();
+                tokio::spawn(async move { handler.run(&payload).await }).await
+                    .unwrap_or_else(|error| Err(format!("Handler panicked: {}", error)))
+            }
+            // Left over from an older version or a handler that is disabled now
+            None => Err(format!("No handler for {:?}", job.kind))
+        };
+        match result {
+            Ok(()) => job.complete(Utc::now()),
+            Err(error) => {
+                job.fail(Utc::now(), error);
+            }
+        }
+
+        self.job_writer.save_job(&job).await;
+        Ok(Some(job))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+    use crate::application::common::job_gateway::test::MockJobGateway;
+    use crate::application::common::job_handler::test::MockJobHandler;
+    use crate::domain::models::job::JobStatus;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_next_job() {
+        let now = Utc::now();
+        let first = Job::create("flaky", json!({"n": 1}), now - Duration::minutes(2));
+        let second = Job::create("unknown", json!({}), now - Duration::minutes(1));
+        let later = Job::create("flaky", json!({}), now + Duration::hours(1));
+        let job_gateway = MockJobGateway::new(vec![later, second.clone(), first.clone()]);
+        let handler = Arc::new(MockJobHandler::new("flaky", 1));
+        let handlers: Vec<Arc<dyn JobHandler>> = vec![handler.clone()];
+
+        let interactor = RunNextJob {
+            job_writer: &job_gateway,
+            handlers: &handlers
+        };
+
+        let failed = interactor.execute(()).await.unwrap().unwrap();
+        assert_eq!((failed.id.as_str(), failed.status), (first.id.as_str(), JobStatus::Queued));
+        assert_eq!(failed.last_error.as_deref(), Some("mock failure"));
+
+        let unknown = interactor.execute(()).await.unwrap().unwrap();
+        assert_eq!(unknown.id, second.id);
+        assert!(unknown.last_error.unwrap().contains("No handler"));
+
+        // The retry of the first one is not due yet
+        assert!(interactor.execute(()).await.unwrap().is_none());
+        assert_eq!(handler.runs.lock().unwrap().clone(), vec![json!({"n": 1})]);
+    }
+
+    struct Panicking;
+
+    #[async_trait]
+    impl JobHandler for Panicking {
+        fn kind(&self) -> &'static str {
+            "panicking"
+        }
+
+        async fn run(&self, _payload: &serde_json::Value) -> Result<(), String> {
+            panic!("boom")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_next_job_panics() {
+        let job = Job::create("panicking", json!({}), Utc::now() - Duration::minutes(1));
+        let job_gateway = MockJobGateway::new(vec![job.clone()]);
+        let handlers: Vec<Arc<dyn JobHandler>> = vec![Arc::new(Panicking)];
+
+        let interactor = RunNextJob {
+            job_writer: &job_gateway,
+            handlers: &handlers
+        };
+
+        let failed = interactor.execute(()).await.unwrap().unwrap();
+        assert_eq!((failed.id.as_str(), failed.status), (job.id.as_str(), JobStatus::Queued));
+        assert!(failed.last_error.unwrap().contains("panicked"));
+    }
+}
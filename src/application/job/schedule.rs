@@ -0,0 +1,97 @@
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::{JobGateway, JobReader, JobWriter};
+use crate::application::common::job_handler::ScheduledJob;
+use crate::domain::models::job::{Job, JobSchedule};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Debug, Default, Serialize)]
+pub struct EnqueueScheduledJobsResult {
+    pub enqueued: u64,
+    /// Due while the previous run was still pending
+    pub skipped: u64
+}
+
+pub struct EnqueueScheduledJobs<'a> {
+    pub job_gateway: &'a dyn JobGateway,
+    pub schedules: &'a [ScheduledJob]
+}
+
+/// Enqueues recurring jobs that are due, meant to be run periodically.
+/// A schedule seen for the first time, or with a changed recurrence, is due at once
+#[async_trait]
+impl Interactor<(), EnqueueScheduledJobsResult> for EnqueueScheduledJobs<'_> {
+    async fn execute(&self, _data: ()) -> Result<EnqueueScheduledJobsResult, ApplicationError> {
+        let mut result = EnqueueScheduledJobsResult::default();
+        let now = Utc::now();
+
+        for scheduled in self.schedules {
+            let due = match self.job_gateway.get_schedule(scheduled.kind).await {
+                Some(schedule) if schedule.expression == scheduled.expression => schedule.next_run_at <= now,
+                _ => true
+            };
+            if !due {
+                continue;
+            }
+            // Cron expressions such as `0 0 31 2 *` are never due
+            let Some(next_run_at) = scheduled.recurrence.next_after(now) else {
+                continue;
+            };
+
+            // Runs do not pile up behind a slow or failing one
+            match self.job_gateway.has_pending(scheduled.kind).await {
+                true => result.skipped += 1,
+                false => {
+                    self.job_gateway.save_job(&Job::create(scheduled.kind, json!({}), now)).await;
+                    result.enqueued += 1;
+                }
+            }
+            self.job_gateway.save_schedule(&JobSchedule {
+                kind: scheduled.kind.to_string(),
+                expression: scheduled.expression.clone(),
+                next_run_at
+            }).await;
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::job_gateway::test::MockJobGateway;
+    use crate::application::common::job_gateway::JobFilter;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_enqueue_scheduled_jobs() {
+        let job_gateway = MockJobGateway::new(vec![]);
+        let mut schedules = vec![
+            ScheduledJob::new("backup", "@daily").unwrap(),
+            ScheduledJob::new("deliveries", "@every 30s").unwrap()
+        ];
+        async fn run(job_gateway: &MockJobGateway, schedules: &[ScheduledJob]) -> EnqueueScheduledJobsResult {
+            EnqueueScheduledJobs { job_gateway, schedules }.execute(()).await.unwrap()
+        }
+
+        let first = run(&job_gateway, &schedules).await;
+        assert_eq!((first.enqueued, first.skipped), (2, 0));
+
+        // Not due yet
+        let second = run(&job_gateway, &schedules).await;
+        assert_eq!((second.enqueued, second.skipped), (0, 0));
+
+        // Due because the recurrence changed, but the first run is still queued
+        schedules[0] = ScheduledJob::new("backup", "@every 6h").unwrap();
+        let third = run(&job_gateway, &schedules).await;
+        assert_eq!((third.enqueued, third.skipped), (0, 1));
+
+        let jobs = job_gateway.list_jobs(&JobFilter::default(), None, &10).await;
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(job_gateway.get_schedule("backup").await.unwrap().expression, "@every 6h");
+    }
+}
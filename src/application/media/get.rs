@@ -0,0 +1,91 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::media_gateway::MediaReader;
+use crate::application::common::media_storage::MediaStorage;
+use crate::domain::models::hash::Hash;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetMediaRequest {
+    pub hash: String
+}
+
+#[derive(Debug)]
+pub struct GetMediaResult {
+    pub hash: Hash,
+    pub mime: String,
+    pub data: Vec<u8>
+}
+
+pub struct GetMedia<'a> {
+    pub media_reader: &'a dyn MediaReader,
+    pub media_storage: &'a dyn MediaStorage
+}
+
+#[async_trait]
+impl Interactor<GetMediaRequest, GetMediaResult> for GetMedia<'_> {
+    async fn execute(
+        &self,
+        data: GetMediaRequest
+    ) -> Result<GetMediaResult, ApplicationError> {
+
+        let hash = Hash::from_hex(&data.hash).map_err(|_| ApplicationError::NotFound)?;
+
+        let media = self.media_reader.get_by_hash(&hash).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        let data = self.media_storage.get(&media.hash).await.ok_or_else(|| {
+            ApplicationError::UnexpectedError(format!("Media {} is missing in storage", media.hash.to_string()))
+        })?;
+
+        Ok(GetMediaResult {
+            hash: media.hash,
+            mime: media.mime,
+            data
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::application::common::media_gateway::test::MockMediaGateway;
+    use crate::application::common::media_storage::test::MockMediaStorage;
+    use crate::domain::models::media::Media;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_media() {
+        let data = b"GIF89a....".to_vec();
+        let media = Media::create(
+            Hash::sha256(&data),
+            Some("image/gif"),
+            data.len(),
+            "pic.gif".to_string(),
+            "test".to_string(),
+            Some((1, 1))
+        ).unwrap();
+
+        let media_gateway = MockMediaGateway::new(HashMap::from([(media.hash.clone(), media.clone())]));
+        let media_storage = MockMediaStorage::new();
+        media_storage.put(&media.hash, &data).await.unwrap();
+
+        let interactor = GetMedia {
+            media_reader: &media_gateway,
+            media_storage: &media_storage
+        };
+
+        let result = interactor.execute(GetMediaRequest {
+            hash: media.hash.to_string()
+        }).await.unwrap();
+        assert_eq!(result.mime, "image/gif");
+        assert_eq!(result.data, data);
+
+        let result = interactor.execute(GetMediaRequest {
+            hash: "nothing".to_string()
+        }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound)));
+    }
+}
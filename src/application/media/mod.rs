@@ -0,0 +1,2 @@
+pub mod upload;
+pub mod get;
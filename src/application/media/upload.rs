@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::image_processor::ImageProcessor;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::media_gateway::{MediaGateway, MediaReader, MediaWriter};
+use crate::application::common::media_storage::MediaStorage;
+use crate::domain::models::hash::Hash;
+use crate::domain::models::media::{Media, MEDIA_VARIANT_MIME, MEDIA_VARIANT_WIDTHS};
+use crate::domain::services::mime::sniff_mime;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug)]
+pub struct UploadMediaRequest {
+    pub name: String,
+    pub data: Vec<u8>
+}
+
+#[derive(Debug, Serialize)]
+pub struct UploadMediaResult {
+    pub hash: Hash,
+    pub url: String,
+    pub mime: String,
+    pub size: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub created_at: DateTime<Utc>
+}
+
+pub struct UploadMedia<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub media_gateway: &'a dyn MediaGateway,
+    pub media_storage: &'a dyn MediaStorage,
+    pub image_processor: &'a dyn ImageProcessor
+}
+
+#[async_trait]
+impl Interactor<UploadMediaRequest, UploadMediaResult> for UploadMedia<'_> {
+    async fn execute(
+        &self,
+        data: UploadMediaRequest
+    ) -> Result<UploadMediaResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let mime = sniff_mime(&data.data);
+        let is_image = mime.is_some_and(|mime| mime.starts_with("image/"));
+
+        // Photos carry EXIF with GPS coordinates, it must never be published
+        let content = match (mime, is_image) {
+            (Some(mime), true) => self.image_processor.strip_metadata(mime, data.data).await
+                .map_err(|e| ApplicationError::ValidationError(
+                    HashMap::from([("file".to_string(), e)])
+                ))?,
+            _ => data.data
+        };
+
+        let hash = Hash::sha256(&content);
+
+        // Already stored, the content is the same by definition. Variants
+        // missing from an earlier upload are made now
+        if let Some(media) = self.media_gateway.get_by_hash(&hash).await {
+            let variants = self.make_variants(&media, &content).await?;
+            self.store_variants(&variants).await?;
+            return Ok(map_media_to_result(media));
+        }
+
+        let dimensions = match is_image {
+            true => self.image_processor.dimensions(&content).await,
+            false => None
+        };
+
+        let media = Media::create(
+            hash,
+            mime,
+            content.len(),
+            data.name,
+            self.id_provider.username().cloned().unwrap_or_default(),
+            dimensions
+        ).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        // Nothing is written until every variant is made, a failed
+        // upload leaves no record behind
+        let variants = self.make_variants(&media, &content).await?;
+
+        self.media_storage.put(&media.hash, &content).await.map_err(|e| {
+            ApplicationError::UnexpectedError(e)
+        })?;
+        self.media_gateway.save(&media).await;
+        self.store_variants(&variants).await?;
+
+        Ok(map_media_to_result(media))
+    }
+}
+
+impl UploadMedia<'_> {
+    /// Resized WebP copies for `srcset`, plus a full size WebP.
+    /// Only the widths `media` has no variant of yet are made
+    async fn make_variants(&self, media: &Media, content: &[u8]) -> Result<Vec<(Media, Vec<u8>)>, ApplicationError> {
+        let Some(width) = media.width.filter(|_| media.mime != "image/gif") else {
+            return Ok(Vec::new());
+        };
+        let existing: Vec<u32> = self.media_gateway.get_variants(&media.hash).await
+            .iter()
+            .filter_map(|variant| variant.width)
+            .collect();
+
+        let widths = MEDIA_VARIANT_WIDTHS.iter()
+            .copied()
+            .filter(|w| *w < width)
+            .chain(std::iter::once(width))
+            .filter(|w| !existing.contains(w))
+            .collect::<Vec<_>>();
+        if widths.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // The original is decoded once for all the widths
+        let resized = self.image_processor.resize_to_webp(content, &widths).await
+            .map_err(ApplicationError::UnexpectedError)?;
+
+        let mut variants = Vec::new();
+        for (variant_width, (data, variant_height)) in widths.into_iter().zip(resized) {
+            let variant = media.variant(
+                Hash::sha256(&data),
+                MEDIA_VARIANT_MIME,
+                data.len(),
+                variant_width,
+                variant_height
+            );
+            variants.push((variant, data));
+        }
+        Ok(variants)
+    }
+
+    async fn store_variants(&self, variants: &[(Media, Vec<u8>)]) -> Result<(), ApplicationError> {
+        for (variant, data) in variants {
+            self.media_storage.put(&variant.hash, data).await
+                .map_err(ApplicationError::UnexpectedError)?;
+            self.media_gateway.save(variant).await;
+        }
+        Ok(())
+    }
+}
+
+fn map_media_to_result(media: Media) -> UploadMediaResult {
+    UploadMediaResult {
+        url: media.url(),
+        hash: media.hash,
+        mime: media.mime,
+        size: media.size,
+        width: media.width,
+        height: media.height,
+        created_at: media.created_at
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::image_processor::test::MockImageProcessor;
+    use crate::application::common::media_gateway::test::MockMediaGateway;
+    use crate::application::common::media_gateway::MediaRemover;
+    use crate::application::common::media_storage::test::MockMediaStorage;
+    use crate::domain::models::media::MEDIA_SIZE_MAX;
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    fn id_provider(is_auth: bool) -> Box<MockIdProvider> {
+        Box::new(MockIdProvider {
+            session: None,
+            is_auth,
+            username: Some("test".to_string())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_upload_media() {
+        let media_gateway = MockMediaGateway::new(HashMap::new());
+        let media_storage = MockMediaStorage::new();
+
+        let interactor = UploadMedia {
+            id_provider: id_provider(true),
+            media_gateway: &media_gateway,
+            media_storage: &media_storage,
+            image_processor: &MockImageProcessor
+        };
+
+        let result = interactor.execute(UploadMediaRequest {
+            name: "pic.png".to_string(),
+            data: PNG.to_vec()
+        }).await.unwrap();
+
+        assert_eq!(result.mime, "image/png");
+        assert_eq!(result.hash, Hash::sha256(PNG));
+        assert_eq!(result.url, format!("/media/{}", Hash::sha256(PNG).to_string()));
+        assert_eq!(result.width, Some(2000));
+
+        // Original, 480w, 960w, 1920w and full size WebP
+        assert_eq!(media_storage.blobs.lock().await.len(), 5);
+        let variants = media_gateway.get_variants(&result.hash).await;
+        assert_eq!(
+            variants.iter().map(|v| v.width.unwrap()).collect::<Vec<_>>(),
+            vec![480, 960, 1920, 2000]
+        );
+        assert!(variants.iter().all(|v| v.mime == "image/webp"));
+
+        // Same content is deduplicated
+        interactor.execute(UploadMediaRequest {
+            name: "copy.png".to_string(),
+            data: PNG.to_vec()
+        }).await.unwrap();
+        assert_eq!(media_gateway.media.lock().await.len(), 5);
+
+        // A variant lost since is made again
+        let lost = variants.iter().find(|v| v.width == Some(960)).unwrap().hash.clone();
+        media_gateway.remove(&lost).await;
+        interactor.execute(UploadMediaRequest {
+            name: "copy.png".to_string(),
+            data: PNG.to_vec()
+        }).await.unwrap();
+        assert_eq!(media_gateway.get_variants(&result.hash).await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_failed_variant_leaves_nothing() {
+        struct BrokenImageProcessor;
+
+        #[async_trait]
+        impl ImageProcessor for BrokenImageProcessor {
+            async fn strip_metadata(&self, _mime: &str, data: Vec<u8>) -> Result<Vec<u8>, String> {
+                Ok(data)
+            }
+
+            async fn dimensions(&self, _data: &[u8]) -> Option<(u32, u32)> {
+                Some((2000, 1000))
+            }
+
+            async fn resize_to_webp(&self, _data: &[u8], _widths: &[u32]) -> Result<Vec<(Vec<u8>, u32)>, String> {
+                Err("broken".to_string())
+            }
+        }
+
+        let media_gateway = MockMediaGateway::new(HashMap::new());
+        let media_storage = MockMediaStorage::new();
+        let interactor = UploadMedia {
+            id_provider: id_provider(true),
+            media_gateway: &media_gateway,
+            media_storage: &media_storage,
+            image_processor: &BrokenImageProcessor
+        };
+
+        let result = interactor.execute(UploadMediaRequest {
+            name: "pic.png".to_string(),
+            data: PNG.to_vec()
+        }).await;
+        assert!(result.is_err());
+        assert!(media_gateway.media.lock().await.is_empty());
+        assert!(media_storage.blobs.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_pdf_without_variants() {
+        let media_gateway = MockMediaGateway::new(HashMap::new());
+        let media_storage = MockMediaStorage::new();
+
+        let interactor = UploadMedia {
+            id_provider: id_provider(true),
+            media_gateway: &media_gateway,
+            media_storage: &media_storage,
+            image_processor: &MockImageProcessor
+        };
+
+        let result = interactor.execute(UploadMediaRequest {
+            name: "cv.pdf".to_string(),
+            data: b"%PDF-1.7".to_vec()
+        }).await.unwrap();
+
+        assert_eq!(result.width, None);
+        assert_eq!(media_storage.blobs.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_unauthorized() {
+        let media_gateway = MockMediaGateway::new(HashMap::new());
+        let media_storage = MockMediaStorage::new();
+
+        let interactor = UploadMedia {
+            id_provider: id_provider(false),
+            media_gateway: &media_gateway,
+            media_storage: &media_storage,
+            image_processor: &MockImageProcessor
+        };
+
+        let result = interactor.execute(UploadMediaRequest {
+            name: "pic.png".to_string(),
+            data: PNG.to_vec()
+        }).await;
+
+        assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+        assert!(media_storage.blobs.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_media_rejected() {
+        let media_gateway = MockMediaGateway::new(HashMap::new());
+        let media_storage = MockMediaStorage::new();
+
+        let interactor = UploadMedia {
+            id_provider: id_provider(true),
+            media_gateway: &media_gateway,
+            media_storage: &media_storage,
+            image_processor: &MockImageProcessor
+        };
+
+        let result = interactor.execute(UploadMediaRequest {
+            name: "evil.svg".to_string(),
+            data: b"<svg onload=\"alert(1)\"/>".to_vec()
+        }).await;
+        assert!(result.is_err());
+
+        let mut data = PNG.to_vec();
+        data.resize(MEDIA_SIZE_MAX + 1, 0);
+        let result = interactor.execute(UploadMediaRequest {
+            name: "huge.png".to_string(),
+            data
+        }).await;
+        assert!(result.is_err());
+
+        assert!(media_storage.blobs.lock().await.is_empty());
+    }
+}
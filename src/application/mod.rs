@@ -1,4 +1,19 @@
 pub mod note;
+pub mod project;
+pub mod media;
+pub mod comment;
+pub mod webmention;
+pub mod activitypub;
+pub mod newsletter;
+pub mod contact;
+pub mod stats;
+pub mod counter;
+pub mod backup;
+pub mod trash;
+pub mod audit;
+pub mod webhook;
+pub mod job;
 pub mod session;
 pub mod user;
 pub mod common;
+pub mod archive;
@@ -0,0 +1,70 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::subscriber_gateway::{SubscriberGateway, SubscriberReader, SubscriberWriter};
+use crate::domain::models::subscriber::SubscriberId;
+use crate::domain::services::newsletter::CONFIRM_PURPOSE;
+use crate::domain::services::signed_token::verify_token;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmSubscriptionRequest {
+    pub id: SubscriberId,
+    pub token: String
+}
+
+pub struct ConfirmSubscription<'a> {
+    pub subscriber_gateway: &'a dyn SubscriberGateway,
+    pub secret: &'a [u8]
+}
+
+#[async_trait]
+impl Interactor<ConfirmSubscriptionRequest, ()> for ConfirmSubscription<'_> {
+    async fn execute(&self, data: ConfirmSubscriptionRequest) -> Result<(), ApplicationError> {
+        if !verify_token(self.secret, CONFIRM_PURPOSE, &data.id, &data.token) {
+            return Err(ApplicationError::NotFound);
+        }
+
+        let mut subscriber = self.subscriber_gateway.get_by_id(&data.id).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        subscriber.confirm();
+        self.subscriber_gateway.save(&subscriber).await;
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::subscriber_gateway::test::MockSubscriberGateway;
+    use crate::domain::models::subscriber::{Subscriber, SubscriberStatus};
+    use crate::domain::services::signed_token::sign_token;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_confirm() {
+        let subscriber = Subscriber::create("reader@example.com".to_string()).unwrap();
+        let subscriber_gateway = MockSubscriberGateway::new(vec![subscriber.clone()]);
+
+        let interactor = ConfirmSubscription {
+            subscriber_gateway: &subscriber_gateway,
+            secret: b"secret"
+        };
+
+        let result = interactor.execute(ConfirmSubscriptionRequest {
+            id: subscriber.id.clone(),
+            token: sign_token(b"other", CONFIRM_PURPOSE, &subscriber.id)
+        }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound)));
+
+        interactor.execute(ConfirmSubscriptionRequest {
+            id: subscriber.id.clone(),
+            token: sign_token(b"secret", CONFIRM_PURPOSE, &subscriber.id)
+        }).await.unwrap();
+
+        let saved = subscriber_gateway.get_by_id(&subscriber.id).await.unwrap();
+        assert_eq!(saved.status, SubscriberStatus::Confirmed);
+        assert!(saved.confirmed_at.is_some());
+    }
+}
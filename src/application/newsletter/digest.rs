@@ -0,0 +1,150 @@
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::mailer::Mailer;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::subscriber_gateway::{SubscriberGateway, SubscriberReader, SubscriberWriter};
+use crate::domain::models::subscriber::SubscriberStatus;
+use crate::domain::services::newsletter::digest_mail;
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// A digest never lists more notes than that
+pub const DIGEST_NOTES_MAX: u64 = 20;
+
+#[derive(Debug, Default, Serialize)]
+pub struct SendDigestResult {
+    pub sent: u64,
+    /// Left for the next run
+    pub failed: u64
+}
+
+pub struct SendDigest<'a> {
+    pub subscriber_gateway: &'a dyn SubscriberGateway,
+    pub note_reader: &'a dyn NoteReader,
+    pub mailer: &'a dyn Mailer,
+    pub site_url: &'a str,
+    pub secret: &'a [u8]
+}
+
+/// Mails every confirmed subscriber the notes published since their last digest
+#[async_trait]
+impl Interactor<(), SendDigestResult> for SendDigest<'_> {
+    async fn execute(&self, _data: ()) -> Result<SendDigestResult, ApplicationError> {
+        let mut result = SendDigestResult::default();
+
+        let recent = self.note_reader.range_by_cursor(None, &DIGEST_NOTES_MAX).await;
+        let started_at = Utc::now();
+
+        for mut subscriber in self.subscriber_gateway.list_by_status(SubscriberStatus::Confirmed).await {
+            let cutoff = subscriber.digest_cutoff();
+            let mut notes = recent.iter()
+                .filter(|note| cutoff.map_or(true, |cutoff| note.created_at > cutoff))
+                .cloned()
+                .collect::<Vec<_>>();
+            if notes.is_empty() {
+                continue;
+            }
+            // Oldest first reads better in a mail
+            notes.reverse();
+
+            match self.mailer.send(&digest_mail(self.site_url, self.secret, &subscriber, &notes)).await {
+                Ok(()) => {
+                    subscriber.digest_sent_at = Some(started_at);
+                    self.subscriber_gateway.save(&subscriber).await;
+                    result.sent += 1;
+                }
+                Err(_) => result.failed += 1
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use chrono::Duration;
+    use crate::application::common::mailer::test::MockMailer;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::subscriber_gateway::test::MockSubscriberGateway;
+    use crate::domain::models::note::Note;
+    use crate::domain::models::subscriber::Subscriber;
+    use super::*;
+
+    fn note(title: &str, age: Duration) -> (String, Note) {
+        let mut note = Note::create(title.to_string(), "Test".to_string()).unwrap();
+        note.created_at = Utc::now() - age;
+        (note.id.clone(), note)
+    }
+
+    fn subscriber(email: &str, confirmed_ago: Duration) -> Subscriber {
+        let mut subscriber = Subscriber::create(email.to_string()).unwrap();
+        subscriber.confirm();
+        subscriber.confirmed_at = Some(Utc::now() - confirmed_ago);
+        subscriber
+    }
+
+    #[tokio::test]
+    async fn test_send_digest() {
+        let note_gateway = MockNoteGateway::new(HashMap::from([
+            note("Old note", Duration::days(10)),
+            note("Fresh note", Duration::hours(1)),
+        ]));
+        let subscriber_gateway = MockSubscriberGateway::new(vec![
+            subscriber("old@example.com", Duration::days(30)),
+            subscriber("new@example.com", Duration::days(1)),
+            Subscriber::create("pending@example.com".to_string()).unwrap(),
+        ]);
+        let mailer = MockMailer::new();
+
+        let interactor = SendDigest {
+            subscriber_gateway: &subscriber_gateway,
+            note_reader: &note_gateway,
+            mailer: &mailer,
+            site_url: "https://jkearnsl.su",
+            secret: b"secret"
+        };
+
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!(result.sent, 2);
+
+        let sent = mailer.sent.lock().unwrap().clone();
+        let old = sent.iter().find(|m| m.to == "old@example.com").unwrap();
+        assert_eq!(old.subject, "2 new notes");
+        assert!(old.text.find("Old note").unwrap() < old.text.find("Fresh note").unwrap());
+        let new = sent.iter().find(|m| m.to == "new@example.com").unwrap();
+        assert_eq!(new.subject, "Fresh note");
+        assert!(new.headers.contains(&(
+            "List-Unsubscribe-Post".to_string(),
+            "List-Unsubscribe=One-Click".to_string()
+        )));
+
+        // Nothing new since the last run
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!(result.sent, 0);
+    }
+
+    #[tokio::test]
+    async fn test_send_digest_failure_is_retried() {
+        let note_gateway = MockNoteGateway::new(HashMap::from([note("Fresh note", Duration::hours(1))]));
+        let subscriber_gateway = MockSubscriberGateway::new(vec![subscriber("old@example.com", Duration::days(30))]);
+        let mut mailer = MockMailer::new();
+        mailer.failing = vec!["old@example.com".to_string()];
+
+        let interactor = SendDigest {
+            subscriber_gateway: &subscriber_gateway,
+            note_reader: &note_gateway,
+            mailer: &mailer,
+            site_url: "https://jkearnsl.su",
+            secret: b"secret"
+        };
+
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!((result.sent, result.failed), (0, 1));
+        let subscribers = subscriber_gateway.subscribers.lock().await;
+        assert!(subscribers[0].digest_sent_at.is_none());
+    }
+}
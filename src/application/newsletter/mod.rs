@@ -0,0 +1,4 @@
+pub mod subscribe;
+pub mod confirm;
+pub mod unsubscribe;
+pub mod digest;
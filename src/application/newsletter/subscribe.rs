@@ -0,0 +1,121 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::mailer::Mailer;
+use crate::application::common::rate_limiter::RateLimiter;
+use crate::application::common::subscriber_gateway::{SubscriberGateway, SubscriberReader, SubscriberWriter};
+use crate::domain::models::subscriber::{Subscriber, SubscriberStatus};
+use crate::domain::services::newsletter::confirmation_mail;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct SubscribeRequest {
+    pub email: String,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub ip: String
+}
+
+pub struct Subscribe<'a> {
+    pub subscriber_gateway: &'a dyn SubscriberGateway,
+    pub mailer: &'a dyn Mailer,
+    pub rate_limiter: &'a dyn RateLimiter,
+    pub site_url: &'a str,
+    pub secret: &'a [u8]
+}
+
+/// Sends a confirmation mail. The answer is the same whether the address
+/// is new, pending or already confirmed, so it tells nothing about subscribers
+#[async_trait]
+impl Interactor<SubscribeRequest, ()> for Subscribe<'_> {
+    async fn execute(&self, data: SubscribeRequest) -> Result<(), ApplicationError> {
+        if !self.rate_limiter.hit(&data.ip).await {
+            return Err(ApplicationError::TooManyRequests);
+        }
+
+        let new = Subscriber::create(data.email).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        let subscriber = match self.subscriber_gateway.get_by_email(&new.email).await {
+            Some(existing) if existing.status == SubscriberStatus::Confirmed => return Ok(()),
+            Some(existing) => existing,
+            None => {
+                self.subscriber_gateway.save(&new).await;
+                new
+            }
+        };
+
+        self.mailer.send(&confirmation_mail(self.site_url, self.secret, &subscriber)).await.map_err(|e| {
+            ApplicationError::UnexpectedError(format!("Failed to send confirmation: {}", e))
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::mailer::test::MockMailer;
+    use crate::application::common::rate_limiter::test::MockRateLimiter;
+    use crate::application::common::subscriber_gateway::test::MockSubscriberGateway;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribe() {
+        let subscriber_gateway = MockSubscriberGateway::new(vec![]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = Subscribe {
+            subscriber_gateway: &subscriber_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            site_url: "https://jkearnsl.su",
+            secret: b"secret"
+        };
+        let request = |email: &str| SubscribeRequest {
+            email: email.to_string(),
+            ip: "127.0.0.1".to_string()
+        };
+
+        interactor.execute(request(" Reader@Example.com ")).await.unwrap();
+        // Lost the first mail, asks again
+        interactor.execute(request("reader@example.com")).await.unwrap();
+
+        let subscribers = subscriber_gateway.subscribers.lock().await.clone();
+        assert_eq!(subscribers.len(), 1);
+        assert_eq!(subscribers[0].email, "reader@example.com");
+        assert_eq!(subscribers[0].status, SubscriberStatus::Pending);
+
+        let sent = mailer.sent.lock().unwrap().clone();
+        assert_eq!(sent.len(), 2);
+        assert!(sent[0].text.contains(&format!("/api/newsletter/confirm?id={}&token=", subscribers[0].id)));
+
+        let result = interactor.execute(request("not an address")).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_confirmed() {
+        let mut subscriber = Subscriber::create("reader@example.com".to_string()).unwrap();
+        subscriber.confirm();
+        let subscriber_gateway = MockSubscriberGateway::new(vec![subscriber]);
+        let mailer = MockMailer::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = Subscribe {
+            subscriber_gateway: &subscriber_gateway,
+            mailer: &mailer,
+            rate_limiter: &rate_limiter,
+            site_url: "https://jkearnsl.su",
+            secret: b"secret"
+        };
+
+        interactor.execute(SubscribeRequest {
+            email: "reader@example.com".to_string(),
+            ip: "127.0.0.1".to_string()
+        }).await.unwrap();
+
+        assert!(mailer.sent.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::subscriber_gateway::{SubscriberGateway, SubscriberReader, SubscriberRemover};
+use crate::domain::models::subscriber::SubscriberId;
+use crate::domain::services::newsletter::UNSUBSCRIBE_PURPOSE;
+use crate::domain::services::signed_token::verify_token;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeRequest {
+    pub id: SubscriberId,
+    pub token: String
+}
+
+pub struct Unsubscribe<'a> {
+    pub subscriber_gateway: &'a dyn SubscriberGateway,
+    pub secret: &'a [u8]
+}
+
+/// Forgets the address entirely. Repeating it is not an error,
+/// mail clients may send the one-click request more than once
+#[async_trait]
+impl Interactor<UnsubscribeRequest, ()> for Unsubscribe<'_> {
+    async fn execute(&self, data: UnsubscribeRequest) -> Result<(), ApplicationError> {
+        if !verify_token(self.secret, UNSUBSCRIBE_PURPOSE, &data.id, &data.token) {
+            return Err(ApplicationError::NotFound);
+        }
+
+        if let Some(subscriber) = self.subscriber_gateway.get_by_id(&data.id).await {
+            self.subscriber_gateway.remove(&subscriber.id).await;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::subscriber_gateway::test::MockSubscriberGateway;
+    use crate::domain::models::subscriber::Subscriber;
+    use crate::domain::services::newsletter::CONFIRM_PURPOSE;
+    use crate::domain::services::signed_token::sign_token;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unsubscribe() {
+        let subscriber = Subscriber::create("reader@example.com".to_string()).unwrap();
+        let subscriber_gateway = MockSubscriberGateway::new(vec![subscriber.clone()]);
+
+        let interactor = Unsubscribe {
+            subscriber_gateway: &subscriber_gateway,
+            secret: b"secret"
+        };
+
+        // A confirmation token is not good for unsubscribing
+        let result = interactor.execute(UnsubscribeRequest {
+            id: subscriber.id.clone(),
+            token: sign_token(b"secret", CONFIRM_PURPOSE, &subscriber.id)
+        }).await;
+        assert!(result.is_err());
+
+        for _ in 0..2 {
+            interactor.execute(UnsubscribeRequest {
+                id: subscriber.id.clone(),
+                token: sign_token(b"secret", UNSUBSCRIBE_PURPOSE, &subscriber.id)
+            }).await.unwrap();
+        }
+        assert!(subscriber_gateway.subscribers.lock().await.is_empty());
+    }
+}
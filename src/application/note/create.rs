@@ -1,8 +1,20 @@
+use std::collections::HashMap;
+use crate::application::activitypub::publish::publish_to_followers;
+use crate::application::common::delivery_gateway::DeliveryWriter;
+use crate::application::common::event_bus::EventPublisher;
 use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::follower_gateway::FollowerReader;
 use crate::application::common::id_provider::IdProvider;
 use crate::application::common::interactor::Interactor;
 use crate::application::common::note_gateway::NoteWriter;
-use crate::domain::models::note::{Note, NoteId};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::webmention_client::WebmentionClient;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::models::audit::{note_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::note::{note_url, Note, NoteId};
+use crate::domain::services::activitypub::{article, create_activity};
+use crate::domain::services::markdown::render_markdown;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -10,7 +22,10 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize)]
 pub struct CreateNoteRequest {
     pub title: String,
-    pub body: String
+    pub body: String,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
 }
 
 #[derive(Debug, Serialize)]
@@ -24,7 +39,12 @@ pub struct CreateNoteResult {
 }
 
 pub struct CreateNote<'a> {
-    pub note_writer: &'a dyn NoteWriter,
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub webmention_client: &'a dyn WebmentionClient,
+    pub follower_reader: &'a dyn FollowerReader,
+    pub delivery_writer: &'a dyn DeliveryWriter,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub actor: &'a LocalActor,
     pub id_provider: Box<dyn IdProvider>
 }
 
@@ -43,8 +63,32 @@ impl Interactor<CreateNoteRequest, CreateNoteResult> for CreateNote<'_> {
             ApplicationError::ValidationError(e)
         })?;
         
-        self.note_writer.save(&note).await;
-        
+        // The note and its audit entry are written together or not at all
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+        unit.note_gateway().save(&note).await;
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Create,
+            AuditTarget::Note,
+            note.id.clone(),
+            None,
+            Some(note_summary(&note)),
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::note_published(&note));
+
+        let html = render_markdown(&note.body, &HashMap::new());
+
+        // Let the pages we link to know about the note
+        self.webmention_client.send_later(note_url(&self.actor.site_url, &note.slug), html.clone()).await;
+
+        publish_to_followers(
+            self.follower_reader,
+            self.delivery_writer,
+            &create_activity(self.actor, article(self.actor, &note, &html))
+        ).await;
+
         Ok(CreateNoteResult {
             id: note.id,
             title: note.title,
@@ -59,9 +103,15 @@ impl Interactor<CreateNoteRequest, CreateNoteResult> for CreateNote<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use crate::application::common::event_bus::test::MockEventPublisher;
+    use crate::application::common::delivery_gateway::test::MockDeliveryGateway;
+    use crate::application::common::follower_gateway::test::MockFollowerGateway;
     use crate::application::common::id_provider::test::MockIdProvider;
-    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use crate::application::common::webmention_client::test::MockWebmentionClient;
+    use crate::domain::models::actor::RemoteActor;
+    use crate::domain::models::event::EventKind;
+    use crate::domain::models::follower::Follower;
     use crate::domain::models::note::{Note, NOTE_BODY_MAX, NOTE_TITLE_MAX};
     use super::*;
 
@@ -73,22 +123,54 @@ mod tests {
             username: Some("test".parse().unwrap())
         };
 
-        let note_gateway = MockNoteGateway::new(HashMap::default());
+        let unit_of_work = MockUnitOfWork::new();
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(["alice", "bob"].iter().map(|name| {
+            Follower::create(&RemoteActor {
+                id: format!("https://mastodon.example/users/{}", name),
+                inbox: format!("https://mastodon.example/users/{}/inbox", name),
+                shared_inbox: Some("https://mastodon.example/inbox".to_string())
+            }).unwrap()
+        }).collect());
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
 
         let interactor = CreateNote {
-            note_writer: &note_gateway,
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            event_publisher: &event_publisher,
+            actor: &actor,
             id_provider: Box::new(id_provider)
         };
 
         let request = CreateNoteRequest {
             title: "Test".to_string(),
-            body: "Test".to_string()
+            body: "See [this](https://example.com/post)".to_string(),
+            ip: None
         };
 
         let result = interactor.execute(request).await.unwrap();
 
         assert_eq!(result.title, "Test");
-        assert_eq!(result.body, "Test");
+        assert_eq!(result.body, "See [this](https://example.com/post)");
+
+        let sent = webmention_client.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].1.contains("href=\"https://example.com/post\""));
+
+        // Both followers share an inbox
+        let deliveries = delivery_gateway.deliveries.lock().await;
+        assert_eq!(deliveries.len(), 1);
+        assert!(deliveries[0].activity.contains("\"type\":\"Create\""));
+
+        assert!(unit_of_work.note_gateway.notes.lock().await.contains_key(&result.id));
+        let entries = unit_of_work.audit_gateway.entries.lock().await;
+        assert_eq!((entries[0].actor.as_deref(), entries[0].action), (Some("test"), AuditAction::Create));
+        assert_eq!(event_publisher.events.lock().unwrap()[0].kind(), EventKind::NotePublished);
     }
 
     #[tokio::test]
@@ -99,24 +181,29 @@ mod tests {
             username: Some("test".parse().unwrap())
         };
 
-        let note_gateway = MockNoteGateway::new(
-            HashMap::from([(
-                "test".to_string(),
-                Note::create(
-                    "Test".to_string(),
-                    "Test".to_string()
-                ).unwrap()
-            )])
-        );
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.note_gateway.save(&Note::create("Test".to_string(), "Test".to_string()).unwrap()).await;
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
 
         let interactor = CreateNote {
-            note_writer: &note_gateway,
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            event_publisher: &event_publisher,
+            actor: &actor,
             id_provider: Box::new(id_provider)
         };
 
         let request = CreateNoteRequest {
             title: "a".repeat(NOTE_TITLE_MAX + 1),
-            body: "Test".to_string()
+            body: "Test".to_string(),
+            ip: None
         };
 
         let result = interactor.execute(request).await;
@@ -132,24 +219,29 @@ mod tests {
             username: Some("test".parse().unwrap())
         };
 
-        let note_gateway = MockNoteGateway::new(
-            HashMap::from([(
-                "test".to_string(),
-                Note::create(
-                    "Test".to_string(),
-                    "Test".to_string()
-                ).unwrap()
-            )])
-        );
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.note_gateway.save(&Note::create("Test".to_string(), "Test".to_string()).unwrap()).await;
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
 
         let interactor = CreateNote {
-            note_writer: &note_gateway,
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            event_publisher: &event_publisher,
+            actor: &actor,
             id_provider: Box::new(id_provider)
         };
 
         let request = CreateNoteRequest {
             title: "Test".to_string(),
-            body: "a".repeat(NOTE_BODY_MAX + 1)
+            body: "a".repeat(NOTE_BODY_MAX + 1),
+            ip: None
         };
         
         let result = interactor.execute(request).await;
@@ -0,0 +1,99 @@
+use crate::application::common::event_bus::EventPublisher;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::{NoteReader, NoteRemover};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::domain::models::audit::{note_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::note::NoteId;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteNoteRequest {
+    pub id: NoteId,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+pub struct DeleteNote<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Moves the note to the trash, see [`crate::application::trash`]
+#[async_trait]
+impl Interactor<DeleteNoteRequest, ()> for DeleteNote<'_> {
+    async fn execute(&self, data: DeleteNoteRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        // Moved to the trash together with its audit entry or not at all
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        let note = unit.note_gateway().get_by_id(&data.id).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        unit.note_gateway().remove(&note.id).await;
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Delete,
+            AuditTarget::Note,
+            note.id.clone(),
+            Some(note_summary(&note)),
+            None,
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::NoteDeleted { id: note.id });
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::event_bus::test::MockEventPublisher;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::note_gateway::NoteWriter;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use crate::domain::models::note::Note;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_delete_note() {
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.note_gateway.save(&note).await;
+
+        let event_publisher = MockEventPublisher::new();
+
+        let interactor = DeleteNote {
+            unit_of_work: &unit_of_work,
+            event_publisher: &event_publisher,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        interactor.execute(DeleteNoteRequest { id: note.id.clone(), ip: None }).await.unwrap();
+
+        assert!(unit_of_work.note_gateway.get_by_id(&note.id).await.is_none());
+        assert_eq!(unit_of_work.note_gateway.get_deleted().await.len(), 1);
+        assert_eq!(unit_of_work.audit_gateway.entries.lock().await[0].action, AuditAction::Delete);
+        assert_eq!(
+            *event_publisher.events.lock().unwrap(),
+            vec![DomainEvent::NoteDeleted { id: note.id.clone() }]
+        );
+        let result = interactor.execute(DeleteNoteRequest { id: note.id.clone(), ip: None }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound)));
+    }
+}
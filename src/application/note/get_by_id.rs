@@ -21,6 +21,8 @@ pub struct GetByIdNoteResult {
     pub body: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Expected by [`UpdateNote`](crate::application::note::update::UpdateNote)
+    pub version: i64,
 }
 
 pub struct GetByIdNote<'a> {
@@ -44,7 +46,8 @@ impl Interactor<GetByIdNoteRequest, GetByIdNoteResult> for GetByIdNote<'_> {
             description: note.description,
             body: note.body,
             created_at: note.created_at,
-            updated_at: note.updated_at
+            updated_at: note.updated_at,
+            version: note.version
         })
     }
 }
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
 use crate::application::common::exceptions::ApplicationError;
-use crate::application::common::id_provider::IdProvider;
 use crate::application::common::interactor::Interactor;
-use crate::application::common::note_gateway::{NoteReader, NoteWriter};
-use crate::domain::models::note::{Note, NoteId};
+use crate::application::common::media_gateway::MediaReader;
+use crate::application::common::note_gateway::NoteReader;
+use crate::domain::models::media::ResponsiveImage;
+use crate::domain::models::note::NoteId;
+use crate::domain::services::markdown::{media_hashes, render_markdown};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -19,12 +22,19 @@ pub struct GetBySlugNoteResult {
     pub title: String,
     pub description: String,
     pub body: String,
+    /// Rendered `body`
+    pub html: String,
+    /// See [`Note::revision`](crate::domain::models::note::Note::revision)
+    pub revision: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Expected by [`UpdateNote`](crate::application::note::update::UpdateNote)
+    pub version: i64,
 }
 
 pub struct GetBySlugNote<'a> {
-    pub note_reader: &'a dyn NoteReader
+    pub note_reader: &'a dyn NoteReader,
+    pub media_reader: &'a dyn MediaReader
 }
 
 #[async_trait]
@@ -36,15 +46,28 @@ impl Interactor<GetBySlugNoteRequest, GetBySlugNoteResult> for GetBySlugNote<'_>
 
         let note = self.note_reader.get_by_slug(&data.slug).await
             .ok_or(ApplicationError::NotFound)?;
-        
+
+        let mut images = HashMap::new();
+        for hash in media_hashes(&note.body) {
+            if let Some(original) = self.media_reader.get_by_hash(&hash).await {
+                let variants = self.media_reader.get_variants(&hash).await;
+                images.insert(hash, ResponsiveImage { original, variants });
+            }
+        }
+        let html = render_markdown(&note.body, &images);
+        let revision = note.revision();
+
         Ok(GetBySlugNoteResult {
             id: note.id,
             slug: note.slug,
             title: note.title,
             description: note.description,
             body: note.body,
+            html,
+            revision,
             created_at: note.created_at,
-            updated_at: note.updated_at
+            updated_at: note.updated_at,
+            version: note.version
         })
     }
 }
@@ -52,10 +75,9 @@ impl Interactor<GetBySlugNoteRequest, GetBySlugNoteResult> for GetBySlugNote<'_>
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::media_gateway::test::MockMediaGateway;
     use crate::application::common::note_gateway::test::MockNoteGateway;
-    use crate::domain::models::note::{Note, NOTE_BODY_MAX, NOTE_TITLE_MAX};
+    use crate::domain::models::note::Note;
     use super::*;
 
     #[tokio::test]
@@ -66,8 +88,11 @@ mod tests {
             vec![(note.id.clone(), note.clone())]
         ));
 
+        let media_gateway = MockMediaGateway::new(HashMap::new());
+
         let interactor = GetBySlugNote {
             note_reader: &note_gateway,
+            media_reader: &media_gateway
         };
 
         let request = GetBySlugNoteRequest {
@@ -77,5 +102,6 @@ mod tests {
         let result = interactor.execute(request).await.unwrap();
 
         assert_eq!(result.title, "Supa title for you");
+        assert_eq!(result.html, "<p>Test</p>\n");
     }
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::social_card::{SocialCardCache, SocialCardRenderer};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetNoteCardRequest {
+    pub slug: String
+}
+
+#[derive(Debug)]
+pub struct GetNoteCardResult {
+    pub revision: i64,
+    /// PNG image
+    pub card: Vec<u8>
+}
+
+pub struct GetNoteCard<'a> {
+    pub note_reader: &'a dyn NoteReader,
+    pub card_renderer: &'a dyn SocialCardRenderer,
+    pub card_cache: &'a dyn SocialCardCache
+}
+
+#[async_trait]
+impl Interactor<GetNoteCardRequest, GetNoteCardResult> for GetNoteCard<'_> {
+    async fn execute(
+        &self,
+        data: GetNoteCardRequest
+    ) -> Result<GetNoteCardResult, ApplicationError> {
+
+        let note = self.note_reader.get_by_slug(&data.slug).await
+            .ok_or(ApplicationError::NotFound)?;
+        let revision = note.revision();
+
+        if let Some(card) = self.card_cache.get(&note.id, revision).await {
+            return Ok(GetNoteCardResult { revision, card });
+        }
+
+        let card = self.card_renderer.render(&note.title).await
+            .map_err(ApplicationError::UnexpectedError)?;
+        self.card_cache.put(&note.id, revision, card.clone()).await;
+
+        Ok(GetNoteCardResult { revision, card })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::Ordering;
+    use chrono::Duration;
+    use crate::application::common::note_gateway::NoteWriter;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::social_card::test::{MockSocialCardCache, MockSocialCardRenderer};
+    use crate::domain::models::note::Note;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_card_cached_by_revision() {
+        let mut note = Note::create("Supa title for you".to_string(), "Test".to_string()).unwrap();
+        let note_gateway = MockNoteGateway::new(HashMap::from([(note.id.clone(), note.clone())]));
+        let card_renderer = MockSocialCardRenderer::new();
+        let card_cache = MockSocialCardCache::new();
+
+        let interactor = GetNoteCard {
+            note_reader: &note_gateway,
+            card_renderer: &card_renderer,
+            card_cache: &card_cache
+        };
+        let request = || GetNoteCardRequest { slug: note.slug.clone() };
+
+        let first = interactor.execute(request()).await.unwrap();
+        let second = interactor.execute(request()).await.unwrap();
+        assert_eq!(first.card, b"Supa title for you");
+        assert_eq!(first.revision, second.revision);
+        assert_eq!(card_renderer.calls.load(Ordering::SeqCst), 1);
+
+        note.title = "Another title".to_string();
+        note.updated_at = Some(note.created_at + Duration::seconds(1));
+        note_gateway.save(&note).await;
+
+        let third = interactor.execute(request()).await.unwrap();
+        assert_eq!(third.card, b"Another title");
+        assert_ne!(third.revision, first.revision);
+        assert_eq!(card_renderer.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_card_not_found() {
+        let note_gateway = MockNoteGateway::new(HashMap::new());
+        let interactor = GetNoteCard {
+            note_reader: &note_gateway,
+            card_renderer: &MockSocialCardRenderer::new(),
+            card_cache: &MockSocialCardCache::new()
+        };
+
+        let result = interactor.execute(GetNoteCardRequest { slug: "nothing".to_string() }).await;
+        assert!(matches!(result, Err(ApplicationError::NotFound)));
+    }
+}
@@ -0,0 +1,450 @@
+use std::collections::{HashMap, HashSet};
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::{NoteReader, NoteWriter};
+use crate::domain::models::audit::{note_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::note::{Note, NoteId};
+use crate::domain::models::trash::TrashKind;
+use crate::domain::services::front_matter::parse_markdown_note;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Files taken by a single import
+pub const IMPORT_FILES_MAX: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+pub struct ImportNotesRequest {
+    #[serde(skip)]
+    pub files: Vec<ImportFile>,
+    /// Report what would happen without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+#[derive(Clone, Debug)]
+pub struct ImportFile {
+    /// Relative to the imported directory or archive, only used in the report
+    pub path: String,
+    pub content: Vec<u8>
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOutcome {
+    Created,
+    Updated,
+    /// Already imported as it is
+    Unchanged,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedFile {
+    pub path: String,
+    pub outcome: ImportOutcome,
+    pub id: Option<NoteId>,
+    pub slug: Option<String>,
+    /// Field name to message, set when the file failed
+    pub errors: HashMap<String, String>,
+    pub warnings: Vec<String>
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportNotesResult {
+    pub dry_run: bool,
+    pub created: u64,
+    pub updated: u64,
+    pub unchanged: u64,
+    pub skipped: u64,
+    pub failed: u64,
+    pub files: Vec<ImportedFile>
+}
+
+pub struct ImportNotes<'a> {
+    pub note_reader: &'a dyn NoteReader,
+    pub note_writer: &'a dyn NoteWriter,
+    pub audit_writer: &'a dyn AuditWriter,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Imports Markdown files with front matter as notes, see
+/// [`parse_markdown_note`]. A file matches an existing note by its `id`,
+/// then by its slug, so importing the same files again changes nothing.
+///
+/// Imported notes are old posts: they are not announced to followers,
+/// webmention targets or webhooks. Drafts are skipped, and so are files
+/// whose `id` is in the trash: deleted notes come back only when they are
+/// restored from the trash
+#[async_trait]
+impl Interactor<ImportNotesRequest, ImportNotesResult> for ImportNotes<'_> {
+    async fn execute(&self, data: ImportNotesRequest) -> Result<ImportNotesResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        if data.files.len() > IMPORT_FILES_MAX {
+            return Err(ApplicationError::ValidationError(HashMap::from([(
+                "files".to_string(),
+                format!("are too many: {} > {}", data.files.len(), IMPORT_FILES_MAX)
+            )])));
+        }
+
+        let mut result = ImportNotesResult {
+            dry_run: data.dry_run,
+            ..Default::default()
+        };
+        // Path of the file that claimed an id or a slug earlier in this import
+        let mut claimed: HashMap<String, String> = HashMap::new();
+        // Readers act as if these were gone, saving them would bring them back half way
+        let trashed: HashSet<NoteId> = self.note_reader.get_deleted().await.into_iter()
+            .filter(|item| item.kind == TrashKind::Note)
+            .map(|item| item.id)
+            .collect();
+
+        for file in data.files {
+            let mut imported = ImportedFile {
+                path: file.path.clone(),
+                outcome: ImportOutcome::Failed,
+                id: None,
+                slug: None,
+                errors: HashMap::new(),
+                warnings: Vec::new()
+            };
+            match self.import_file(&file, data.dry_run, &data.ip, &trashed, &mut claimed, &mut imported).await {
+                Ok(outcome) => imported.outcome = outcome,
+                Err(errors) => imported.errors = errors
+            }
+
+            match imported.outcome {
+                ImportOutcome::Created => result.created += 1,
+                ImportOutcome::Updated => result.updated += 1,
+                ImportOutcome::Unchanged => result.unchanged += 1,
+                ImportOutcome::Skipped => result.skipped += 1,
+                ImportOutcome::Failed => result.failed += 1
+            }
+            result.files.push(imported);
+        }
+
+        Ok(result)
+    }
+}
+
+impl ImportNotes<'_> {
+    async fn import_file(
+        &self,
+        file: &ImportFile,
+        dry_run: bool,
+        ip: &Option<String>,
+        trashed: &HashSet<NoteId>,
+        claimed: &mut HashMap<String, String>,
+        imported: &mut ImportedFile
+    ) -> Result<ImportOutcome, HashMap<String, String>> {
+        let error = |field: &str, message: String| HashMap::from([(field.to_string(), message)]);
+
+        let content = std::str::from_utf8(&file.content)
+            .map_err(|_| error("file", "is not UTF-8".to_string()))?;
+        let parsed = parse_markdown_note(&file.path, content)?;
+        imported.slug = Some(parsed.slug.clone());
+        imported.id = parsed.id.clone();
+
+        if parsed.draft {
+            imported.warnings.push("Drafts are not imported".to_string());
+            return Ok(ImportOutcome::Skipped);
+        }
+        if parsed.id.as_ref().is_some_and(|id| trashed.contains(id)) {
+            imported.warnings.push("The note is in the trash, restore it to import it again".to_string());
+            return Ok(ImportOutcome::Skipped);
+        }
+        if !parsed.tags.is_empty() {
+            imported.warnings.push(format!("Notes have no tags, {} dropped", parsed.tags.join(", ")));
+        }
+
+        for key in parsed.id.iter().map(|id| format!("id:{}", id)).chain([format!("slug:{}", parsed.slug)]) {
+            if let Some(other) = claimed.get(&key) {
+                let (field, value) = key.split_once(':').unwrap();
+                return Err(error(field, format!("{:?} is already used by {}", value, other)));
+            }
+        }
+
+        let existing = match &parsed.id {
+            Some(id) => self.note_reader.get_by_id(id).await,
+            None => None
+        };
+        let existing = match existing {
+            Some(note) => Some(note),
+            None => match self.note_reader.get_by_slug(&parsed.slug).await {
+                Some(note) if parsed.id.as_ref().is_some_and(|id| *id != note.id) => {
+                    return Err(error("slug", format!("{:?} belongs to another note {}", parsed.slug, note.id)));
+                }
+                note => note
+            }
+        };
+
+        let mut note = Note::import(
+            parsed.id,
+            parsed.slug,
+            parsed.title,
+            parsed.description,
+            parsed.body,
+            parsed.created_at,
+            parsed.updated_at
+        )?;
+
+        let outcome = match &existing {
+            Some(current) => {
+                note.id = current.id.clone();
+                note.version = current.version + 1;
+                if same_content(current, &note) {
+                    ImportOutcome::Unchanged
+                } else {
+                    ImportOutcome::Updated
+                }
+            }
+            None => ImportOutcome::Created
+        };
+        imported.id = Some(note.id.clone());
+        claimed.insert(format!("id:{}", note.id), file.path.clone());
+        claimed.insert(format!("slug:{}", note.slug), file.path.clone());
+
+        if dry_run || outcome == ImportOutcome::Unchanged {
+            return Ok(outcome);
+        }
+
+        // Written as a whole, the dates come from the file. An update only
+        // lands on the version read above, an edit in between wins
+        let written = match &existing {
+            Some(current) => self.note_writer.update(&note, current.version).await,
+            None => {
+                self.note_writer.save(&note).await;
+                true
+            }
+        };
+        if !written {
+            return Err(error("version", "the note changed during the import, import the file again".to_string()));
+        }
+        self.audit_writer.save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            match outcome {
+                ImportOutcome::Created => AuditAction::Create,
+                _ => AuditAction::Update
+            },
+            AuditTarget::Note,
+            note.id.clone(),
+            existing.as_ref().map(note_summary),
+            Some(note_summary(&note)),
+            ip.clone()
+        )).await;
+
+        Ok(outcome)
+    }
+}
+
+fn same_content(current: &Note, imported: &Note) -> bool {
+    current.slug == imported.slug
+        && current.title == imported.title
+        && current.description == imported.description
+        && current.body == imported.body
+        && current.created_at == imported.created_at
+        && current.updated_at == imported.updated_at
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::audit_gateway::test::MockAuditGateway;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::note_gateway::NoteRemover;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::domain::models::note::NOTE_DESCRIPTION_MAX;
+    use super::*;
+
+    fn file(path: &str, content: &str) -> ImportFile {
+        ImportFile {
+            path: path.to_string(),
+            content: content.as_bytes().to_vec()
+        }
+    }
+
+    fn files() -> Vec<ImportFile> {
+        vec![
+            file("2019-01-01-first.md", "---\ntitle: First\ntags: [old]\n---\nOne"),
+            file("second.md", "+++\ntitle = \"Second\"\ndate = 2019-02-01\nid = \"AbCdEfGh12345678\"\n+++\nTwo"),
+            file("draft.md", "---\ntitle: Draft\ndate: 2019-03-01\ndraft: true\n---\n"),
+            file("broken.md", "---\ntitle: Broken\n---\nNo date"),
+            file("copy.md", "---\ntitle: Copy\nslug: first\ndate: 2019-04-01\n---\n"),
+            file("binary.md", "")
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_import_notes() {
+        let note_gateway = MockNoteGateway::new(HashMap::new());
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let interactor = ImportNotes {
+            note_reader: &note_gateway,
+            note_writer: &note_gateway,
+            audit_writer: &audit_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+        let mut binary = files();
+        binary[5].content = vec![0xff, 0xfe];
+
+        let dry_run = interactor.execute(ImportNotesRequest {
+            files: binary.clone(),
+            dry_run: true,
+            ip: None
+        }).await.unwrap();
+        assert_eq!(
+            (dry_run.created, dry_run.skipped, dry_run.failed),
+            (2, 1, 3)
+        );
+        assert!(note_gateway.get_by_slug("first").await.is_none());
+        assert_eq!(dry_run.files[0].warnings.len(), 1);
+        assert!(dry_run.files[3].errors.contains_key("date"));
+        assert!(dry_run.files[4].errors["slug"].contains("first.md"));
+        assert!(dry_run.files[5].errors.contains_key("file"));
+
+        let first = interactor.execute(ImportNotesRequest {
+            files: binary.clone(),
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+        assert_eq!(first.created, 2);
+        let note = note_gateway.get_by_id(&"AbCdEfGh12345678".to_string()).await.unwrap();
+        assert_eq!((note.slug.as_str(), note.created_at.to_rfc3339().as_str()), ("second", "2019-02-01T00:00:00+00:00"));
+        assert_eq!(audit_gateway.entries.lock().await.len(), 2);
+
+        let mut changed = binary.clone();
+        changed[0] = file("2019-01-01-first.md", "---\ntitle: First, edited\n---\nOne");
+        let again = interactor.execute(ImportNotesRequest {
+            files: changed,
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+        assert_eq!((again.created, again.updated, again.unchanged), (0, 1, 1));
+        let note = note_gateway.get_by_slug("first").await.unwrap();
+        assert_eq!((note.title.as_str(), note.version), ("First, edited", 2));
+    }
+
+    #[tokio::test]
+    async fn test_import_conflict() {
+        /// Someone saves the note between the read and the write of the import
+        struct RacingNoteWriter<'a>(&'a MockNoteGateway);
+
+        #[async_trait]
+        impl NoteWriter for RacingNoteWriter<'_> {
+            async fn save(&self, note: &Note) {
+                self.0.save(note).await
+            }
+
+            async fn update(&self, note: &Note, expected_version: i64) -> bool {
+                self.0.notes.lock().await.get_mut(&note.id).unwrap().version += 1;
+                self.0.update(note, expected_version).await
+            }
+        }
+
+        let note_gateway = MockNoteGateway::new(HashMap::new());
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let interactor = ImportNotes {
+            note_reader: &note_gateway,
+            note_writer: &RacingNoteWriter(&note_gateway),
+            audit_writer: &audit_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        interactor.execute(ImportNotesRequest {
+            files: vec![files()[1].clone()],
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+        let edited = file("second.md", "+++\ntitle = \"Second, edited\"\ndate = 2019-02-01\nid = \"AbCdEfGh12345678\"\n+++\nTwo");
+        let again = interactor.execute(ImportNotesRequest {
+            files: vec![edited],
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+
+        assert_eq!((again.updated, again.failed), (0, 1));
+        assert!(again.files[0].errors.contains_key("version"));
+        let note = note_gateway.get_by_id(&"AbCdEfGh12345678".to_string()).await.unwrap();
+        assert_eq!((note.title.as_str(), note.version), ("Second", 2));
+        assert_eq!(audit_gateway.entries.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_trashed_note() {
+        let note_gateway = MockNoteGateway::new(HashMap::new());
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let interactor = ImportNotes {
+            note_reader: &note_gateway,
+            note_writer: &note_gateway,
+            audit_writer: &audit_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+        let second = files()[1].clone();
+
+        interactor.execute(ImportNotesRequest {
+            files: vec![second.clone()],
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+        note_gateway.remove(&"AbCdEfGh12345678".to_string()).await;
+
+        let again = interactor.execute(ImportNotesRequest {
+            files: vec![second],
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+        assert_eq!((again.created, again.updated, again.skipped), (0, 0, 1));
+        assert!(again.files[0].warnings[0].contains("trash"));
+        assert!(note_gateway.get_by_id(&"AbCdEfGh12345678".to_string()).await.is_none());
+        assert_eq!(audit_gateway.entries.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_multibyte_body() {
+        let note_gateway = MockNoteGateway::new(HashMap::new());
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let interactor = ImportNotes {
+            note_reader: &note_gateway,
+            note_writer: &note_gateway,
+            audit_writer: &audit_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+        // Two bytes a character, the description taken from it is longer than the limit in bytes
+        let body = "Привет, мир! ".repeat(40);
+
+        let result = interactor.execute(ImportNotesRequest {
+            files: vec![file("2019-01-01-privet.md", &format!("---\ntitle: Привет\n---\n{}", body))],
+            dry_run: false,
+            ip: None
+        }).await.unwrap();
+        assert_eq!(result.created, 1, "{:?}", result.files[0].errors);
+        let note = note_gateway.get_by_slug(result.files[0].slug.as_ref().unwrap()).await.unwrap();
+        assert_eq!(note.description.chars().count(), NOTE_DESCRIPTION_MAX);
+        assert!(note.description.len() > NOTE_DESCRIPTION_MAX);
+    }
+}
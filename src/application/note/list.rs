@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::pagination::{keyset_page, Cursor};
+use crate::domain::models::note::NoteListItem;
+use crate::domain::services::validator::{page_offset, validate_per_page};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct GetNoteListRequest {
+    /// Opaque cursor from a previous result, takes precedence over `page`
+    pub cursor: Option<String>,
+    /// Offset pagination, kept for old clients
+    pub page: Option<u64>,
+    pub per_page: u64
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetNoteListResult {
+    pub items: Vec<NoteListItem>,
+    pub next: Option<String>,
+    pub prev: Option<String>
+}
+
+pub struct GetNoteList<'a> {
+    pub note_reader: &'a dyn NoteReader
+}
+
+#[async_trait]
+impl Interactor<GetNoteListRequest, GetNoteListResult> for GetNoteList<'_> {
+    async fn execute(
+        &self,
+        data: GetNoteListRequest
+    ) -> Result<GetNoteListResult, ApplicationError> {
+
+        validate_per_page(&data.per_page).map_err(|e| {
+            ApplicationError::ValidationError(HashMap::from([("per_page".to_string(), e)]))
+        })?;
+
+        let page = match (data.cursor, data.page) {
+            (Some(cursor), _) => {
+                let cursor = Cursor::decode(&cursor).map_err(|e| {
+                    ApplicationError::ValidationError(HashMap::from([("cursor".to_string(), e)]))
+                })?;
+                let rows = self.note_reader.range_by_cursor(Some(&cursor), &(data.per_page + 1)).await;
+                keyset_page(rows, data.per_page, Some(&cursor), note_key)
+            }
+            (None, Some(page)) => {
+                let offset = page_offset(&page, &data.per_page).map_err(|e| {
+                    ApplicationError::ValidationError(HashMap::from([("page".to_string(), e)]))
+                })?;
+                let rows = self.note_reader.range(&(data.per_page + 1), &offset).await;
+                let mut page = keyset_page(rows, data.per_page, None, note_key);
+                if offset == 0 {
+                    page.prev = None;
+                } else if let Some(first) = page.items.first() {
+                    let (created_at, id) = note_key(first);
+                    page.prev = Some(Cursor::before(created_at, id).encode());
+                }
+                page
+            }
+            (None, None) => {
+                let rows = self.note_reader.range_by_cursor(None, &(data.per_page + 1)).await;
+                keyset_page(rows, data.per_page, None, note_key)
+            }
+        };
+
+        Ok(GetNoteListResult {
+            items: page.items,
+            next: page.next,
+            prev: page.prev
+        })
+    }
+}
+
+fn note_key(note: &NoteListItem) -> (chrono::DateTime<chrono::Utc>, String) {
+    (note.created_at, note.id.clone())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::domain::models::note::Note;
+    use chrono::{Duration, Utc};
+
+    fn gateway(count: i64) -> MockNoteGateway {
+        let now = Utc::now();
+        MockNoteGateway::new((0..count).map(|i| {
+            let mut note = Note::create(format!("Note {}", i), "Test".to_string()).unwrap();
+            note.created_at = now - Duration::minutes(i);
+            (note.id.clone(), note)
+        }).collect())
+    }
+
+    #[tokio::test]
+    async fn test_walk_pages_by_cursor() {
+        let note_gateway = gateway(5);
+        let interactor = GetNoteList { note_reader: &note_gateway };
+
+        let first = interactor.execute(GetNoteListRequest {
+            cursor: None,
+            page: None,
+            per_page: 2
+        }).await.unwrap();
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.items[0].title, "Note 0");
+        assert!(first.prev.is_none());
+
+        let second = interactor.execute(GetNoteListRequest {
+            cursor: first.next,
+            page: None,
+            per_page: 2
+        }).await.unwrap();
+        assert_eq!(second.items[0].title, "Note 2");
+
+        let third = interactor.execute(GetNoteListRequest {
+            cursor: second.next,
+            page: None,
+            per_page: 2
+        }).await.unwrap();
+        assert_eq!(third.items.len(), 1);
+        assert!(third.next.is_none());
+
+        let back = interactor.execute(GetNoteListRequest {
+            cursor: third.prev,
+            page: None,
+            per_page: 2
+        }).await.unwrap();
+        assert_eq!(back.items[0].title, "Note 2");
+        assert_eq!(back.items[1].title, "Note 3");
+        assert!(back.prev.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_offset_page() {
+        let note_gateway = gateway(5);
+        let interactor = GetNoteList { note_reader: &note_gateway };
+
+        let result = interactor.execute(GetNoteListRequest {
+            cursor: None,
+            page: Some(2),
+            per_page: 2
+        }).await.unwrap();
+
+        assert_eq!(result.items[0].title, "Note 2");
+        assert!(result.next.is_some());
+        assert!(result.prev.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_page() {
+        let note_gateway = gateway(1);
+        let interactor = GetNoteList { note_reader: &note_gateway };
+
+        let result = interactor.execute(GetNoteListRequest {
+            cursor: None,
+            page: Some(0),
+            per_page: 2
+        }).await;
+        assert!(result.is_err());
+
+        let result = interactor.execute(GetNoteListRequest {
+            cursor: None,
+            page: Some(u64::MAX),
+            per_page: 100
+        }).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+
+        let result = interactor.execute(GetNoteListRequest {
+            cursor: Some("garbage".to_string()),
+            page: None,
+            per_page: 2
+        }).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_keeps_nanoseconds() {
+        let now = Utc::now();
+        let note_gateway = MockNoteGateway::new((0..3).map(|i| {
+            let mut note = Note::create(format!("Note {}", i), "Test".to_string()).unwrap();
+            note.created_at = now - Duration::nanoseconds(i * 100);
+            (note.id.clone(), note)
+        }).collect());
+        let interactor = GetNoteList { note_reader: &note_gateway };
+
+        let mut titles = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = interactor.execute(GetNoteListRequest {
+                cursor,
+                page: None,
+                per_page: 1
+            }).await.unwrap();
+            titles.extend(page.items.into_iter().map(|note| note.title));
+            match page.next {
+                Some(next) => cursor = Some(next),
+                None => break
+            }
+        }
+        assert_eq!(titles, vec!["Note 0", "Note 1", "Note 2"]);
+    }
+}
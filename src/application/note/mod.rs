@@ -1,3 +1,8 @@
 pub mod create;
 pub mod get_by_slug;
-pub mod get_by_id;
\ No newline at end of file
+pub mod get_by_id;
+pub mod list;
+pub mod get_card;
+pub mod update;
+pub mod delete;
+pub mod import;
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use crate::application::activitypub::publish::publish_to_followers;
+use crate::application::common::delivery_gateway::DeliveryWriter;
+use crate::application::common::event_bus::EventPublisher;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::follower_gateway::FollowerReader;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::{NoteReader, NoteWriter};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::webmention_client::WebmentionClient;
+use crate::domain::models::actor::LocalActor;
+use crate::domain::models::audit::{note_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::note::{note_url, NoteId};
+use crate::domain::services::activitypub::{article, update_activity};
+use crate::domain::services::markdown::render_markdown;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateNoteRequest {
+    /// Taken from the path
+    #[serde(skip)]
+    pub id: NoteId,
+    pub title: String,
+    pub body: String,
+    /// The version the edit was made against, can also come from `If-Match`
+    pub version: Option<i64>,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateNoteResult {
+    pub id: NoteId,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub version: i64,
+}
+
+pub struct UpdateNote<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub webmention_client: &'a dyn WebmentionClient,
+    pub follower_reader: &'a dyn FollowerReader,
+    pub delivery_writer: &'a dyn DeliveryWriter,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub actor: &'a LocalActor,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<UpdateNoteRequest, UpdateNoteResult> for UpdateNote<'_> {
+    async fn execute(
+        &self,
+        data: UpdateNoteRequest
+    ) -> Result<UpdateNoteResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let expected_version = data.version.ok_or(ApplicationError::ValidationError(
+            HashMap::from([("version".to_string(), "is required".to_string())])
+        ))?;
+
+        // The note and its audit entry are written together or not at all
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        let mut note = unit.note_gateway().get_by_id(&data.id).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        if note.version != expected_version {
+            return Err(ApplicationError::Conflict { current_version: note.version });
+        }
+
+        let before = note_summary(&note);
+        note.update(data.title, data.body).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        // Someone may have saved between the read and this write
+        if !unit.note_gateway().update(&note, expected_version).await {
+            let current = unit.note_gateway().get_by_id(&note.id).await
+                .ok_or(ApplicationError::NotFound)?;
+            return Err(ApplicationError::Conflict { current_version: current.version });
+        }
+
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Update,
+            AuditTarget::Note,
+            note.id.clone(),
+            Some(before),
+            Some(note_summary(&note)),
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::note_updated(&note));
+
+        let html = render_markdown(&note.body, &HashMap::new());
+
+        // Links added by the edit get their mention, the rest resend harmlessly
+        self.webmention_client.send_later(note_url(&self.actor.site_url, &note.slug), html.clone()).await;
+
+        publish_to_followers(
+            self.follower_reader,
+            self.delivery_writer,
+            &update_activity(self.actor, article(self.actor, &note, &html), note.version)
+        ).await;
+
+        Ok(UpdateNoteResult {
+            id: note.id,
+            slug: note.slug,
+            title: note.title,
+            description: note.description,
+            body: note.body,
+            created_at: note.created_at,
+            updated_at: note.updated_at,
+            version: note.version
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::delivery_gateway::test::MockDeliveryGateway;
+    use crate::application::common::event_bus::test::MockEventPublisher;
+    use crate::application::common::follower_gateway::test::MockFollowerGateway;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use crate::application::common::webmention_client::test::MockWebmentionClient;
+    use crate::domain::models::actor::RemoteActor;
+    use crate::domain::models::follower::Follower;
+    use crate::domain::models::note::{Note, NOTE_TITLE_MAX};
+    use super::*;
+
+    fn id_provider() -> Box<MockIdProvider> {
+        Box::new(MockIdProvider {
+            session: None,
+            is_auth: true,
+            username: Some("test".parse().unwrap())
+        })
+    }
+
+    fn request(id: &NoteId, title: &str, version: Option<i64>) -> UpdateNoteRequest {
+        UpdateNoteRequest {
+            id: id.clone(),
+            title: title.to_string(),
+            body: "Updated".to_string(),
+            version,
+            ip: None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_note() {
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.note_gateway.save(&note).await;
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(vec![Follower::create(&RemoteActor {
+            id: "https://mastodon.example/users/alice".to_string(),
+            inbox: "https://mastodon.example/users/alice/inbox".to_string(),
+            shared_inbox: None
+        }).unwrap()]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let interactor = UpdateNote {
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            actor: &actor,
+            event_publisher: &event_publisher,
+            id_provider: id_provider()
+        };
+
+        let result = interactor.execute(request(&note.id, "Supa title", Some(1))).await.unwrap();
+
+        assert_eq!((result.slug.as_str(), result.version), ("supa-title", 2));
+        assert!(result.updated_at.is_some());
+        assert_eq!(unit_of_work.note_gateway.notes.lock().await[&note.id].body, "Updated");
+        let entries = unit_of_work.audit_gateway.entries.lock().await;
+        assert_eq!(entries[0].before.as_ref().unwrap()["title"], "Test");
+        assert_eq!(entries[0].after.as_ref().unwrap()["version"], 2);
+        assert!(matches!(
+            event_publisher.events.lock().unwrap()[..],
+            [DomainEvent::NoteUpdated { version: 2, .. }]
+        ));
+        assert_eq!(webmention_client.sent.lock().unwrap()[0].0, "https://jkearnsl.su/notes/supa-title");
+        let deliveries = delivery_gateway.deliveries.lock().await;
+        assert_eq!(deliveries.len(), 1);
+        assert!(deliveries[0].activity.contains("\"type\":\"Update\""));
+    }
+
+    #[tokio::test]
+    async fn test_update_note_conflict() {
+        let mut note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        note.update("Other edit".to_string(), "Test".to_string()).unwrap();
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.note_gateway.save(&note).await;
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let interactor = UpdateNote {
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            actor: &actor,
+            event_publisher: &event_publisher,
+            id_provider: id_provider()
+        };
+
+        let result = interactor.execute(request(&note.id, "Stale edit", Some(1))).await;
+
+        assert!(matches!(result, Err(ApplicationError::Conflict { current_version: 2 })));
+        assert_eq!(unit_of_work.note_gateway.notes.lock().await[&note.id].title, "Other edit");
+        assert!(unit_of_work.audit_gateway.entries.lock().await.is_empty());
+        assert!(event_publisher.events.lock().unwrap().is_empty());
+        assert!(webmention_client.sent.lock().unwrap().is_empty());
+        assert!(delivery_gateway.deliveries.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_note_without_version() {
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.note_gateway.save(&note).await;
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let interactor = UpdateNote {
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            actor: &actor,
+            event_publisher: &event_publisher,
+            id_provider: id_provider()
+        };
+
+        let result = interactor.execute(request(&note.id, "Test", None)).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+
+        let result = interactor.execute(request(&note.id, &"a".repeat(NOTE_TITLE_MAX + 1), Some(1))).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_note_unauthorized() {
+        let unit_of_work = MockUnitOfWork::new();
+
+        let webmention_client = MockWebmentionClient::new();
+        let follower_gateway = MockFollowerGateway::new(vec![]);
+        let delivery_gateway = MockDeliveryGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let interactor = UpdateNote {
+            unit_of_work: &unit_of_work,
+            webmention_client: &webmention_client,
+            follower_reader: &follower_gateway,
+            delivery_writer: &delivery_gateway,
+            actor: &actor,
+            event_publisher: &event_publisher,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: false,
+                username: None
+            })
+        };
+
+        let result = interactor.execute(request(&"id".to_string(), "Test", Some(1))).await;
+
+        assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+    }
+}
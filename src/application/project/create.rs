@@ -0,0 +1,127 @@
+use crate::application::common::event_bus::EventPublisher;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::project_gateway::ProjectWriter;
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::domain::models::audit::{project_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::project::Project;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProjectRequest {
+    pub title: String,
+    pub description: String,
+    pub url: Option<String>,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+pub struct CreateProject<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<CreateProjectRequest, Project> for CreateProject<'_> {
+    async fn execute(
+        &self,
+        data: CreateProjectRequest
+    ) -> Result<Project, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let project = Project::create(data.title, data.description, data.url).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        // Written together with its audit entry or not at all
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+        unit.project_gateway().save_project(&project).await;
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Create,
+            AuditTarget::Project,
+            project.id.clone(),
+            None,
+            Some(project_summary(&project)),
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::project_created(&project));
+
+        Ok(project)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::event_bus::test::MockEventPublisher;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use crate::domain::models::project::PROJECT_TITLE_MAX;
+    use super::*;
+
+    fn request(title: &str) -> CreateProjectRequest {
+        CreateProjectRequest {
+            title: title.to_string(),
+            description: "About".to_string(),
+            url: None,
+            ip: None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_project() {
+        let unit_of_work = MockUnitOfWork::new();
+        let event_publisher = MockEventPublisher::new();
+        let interactor = CreateProject {
+            unit_of_work: &unit_of_work,
+            event_publisher: &event_publisher,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let project = interactor.execute(request("Project")).await.unwrap();
+
+        assert!(unit_of_work.project_gateway.projects.lock().await.contains_key(&project.id));
+        assert_eq!(unit_of_work.audit_gateway.entries.lock().await[0].action, AuditAction::Create);
+        assert_eq!(
+            event_publisher.events.lock().unwrap()[..],
+            [DomainEvent::ProjectCreated { id: project.id, title: "Project".to_string() }]
+        );
+
+        let result = interactor.execute(request(&"a".repeat(PROJECT_TITLE_MAX + 1))).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_project_unauthorized() {
+        let unit_of_work = MockUnitOfWork::new();
+        let event_publisher = MockEventPublisher::new();
+        let interactor = CreateProject {
+            unit_of_work: &unit_of_work,
+            event_publisher: &event_publisher,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: false,
+                username: None
+            })
+        };
+
+        let result = interactor.execute(request("Project")).await;
+
+        assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+        assert!(unit_of_work.project_gateway.projects.lock().await.is_empty());
+    }
+}
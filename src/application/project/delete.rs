@@ -0,0 +1,57 @@
+use crate::application::common::event_bus::EventPublisher;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::project_gateway::{ProjectReader, ProjectRemover};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::domain::models::audit::{project_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::project::ProjectId;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteProjectRequest {
+    pub id: ProjectId,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+pub struct DeleteProject<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Moves the project to the trash
+#[async_trait]
+impl Interactor<DeleteProjectRequest, ()> for DeleteProject<'_> {
+    async fn execute(&self, data: DeleteProjectRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        // Moved to the trash together with its audit entry or not at all
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        let project = unit.project_gateway().get_project(&data.id).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        unit.project_gateway().remove_project(&project.id).await;
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Delete,
+            AuditTarget::Project,
+            project.id.clone(),
+            Some(project_summary(&project)),
+            None,
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::ProjectDeleted { id: project.id });
+
+        Ok(())
+    }
+}
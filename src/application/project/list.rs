@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::pagination::{keyset_page, Cursor};
+use crate::application::common::project_gateway::ProjectReader;
+use crate::domain::models::project::Project;
+use crate::domain::services::validator::{page_offset, validate_per_page};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct GetProjectListRequest {
+    /// Opaque cursor from a previous result, takes precedence over `page`
+    pub cursor: Option<String>,
+    /// Offset pagination, kept for old clients
+    pub page: Option<u64>,
+    pub per_page: u64
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetProjectListResult {
+    pub items: Vec<Project>,
+    pub next: Option<String>,
+    pub prev: Option<String>
+}
+
+pub struct GetProjectList<'a> {
+    pub project_reader: &'a dyn ProjectReader
+}
+
+#[async_trait]
+impl Interactor<GetProjectListRequest, GetProjectListResult> for GetProjectList<'_> {
+    async fn execute(
+        &self,
+        data: GetProjectListRequest
+    ) -> Result<GetProjectListResult, ApplicationError> {
+
+        validate_per_page(&data.per_page).map_err(|e| {
+            ApplicationError::ValidationError(HashMap::from([("per_page".to_string(), e)]))
+        })?;
+
+        let page = match (data.cursor, data.page) {
+            (Some(cursor), _) => {
+                let cursor = Cursor::decode(&cursor).map_err(|e| {
+                    ApplicationError::ValidationError(HashMap::from([("cursor".to_string(), e)]))
+                })?;
+                let rows = self.project_reader.get_projects_by_cursor(
+                    Some(&cursor),
+                    &(data.per_page + 1)
+                ).await;
+                keyset_page(rows, data.per_page, Some(&cursor), project_key)
+            }
+            (None, Some(page)) => {
+                let offset = page_offset(&page, &data.per_page).map_err(|e| {
+                    ApplicationError::ValidationError(HashMap::from([("page".to_string(), e)]))
+                })?;
+                let rows = self.project_reader.get_projects_range(&(data.per_page + 1), &offset).await;
+                let mut page = keyset_page(rows, data.per_page, None, project_key);
+                if offset == 0 {
+                    page.prev = None;
+                } else if let Some(first) = page.items.first() {
+                    let (created_at, id) = project_key(first);
+                    page.prev = Some(Cursor::before(created_at, id).encode());
+                }
+                page
+            }
+            (None, None) => {
+                let rows = self.project_reader.get_projects_by_cursor(None, &(data.per_page + 1)).await;
+                keyset_page(rows, data.per_page, None, project_key)
+            }
+        };
+
+        Ok(GetProjectListResult {
+            items: page.items,
+            next: page.next,
+            prev: page.prev
+        })
+    }
+}
+
+fn project_key(project: &Project) -> (chrono::DateTime<chrono::Utc>, String) {
+    (project.created_at, project.id.clone())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::common::project_gateway::test::MockProjectGateway;
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_walk_pages_by_cursor() {
+        let now = Utc::now();
+        let project_gateway = MockProjectGateway::new((0..3).map(|i| {
+            let mut project = Project::create(
+                format!("Project {}", i),
+                "Test".to_string(),
+                None
+            ).unwrap();
+            project.created_at = now - Duration::minutes(i);
+            (project.id.clone(), project)
+        }).collect());
+
+        let interactor = GetProjectList { project_reader: &project_gateway };
+
+        let first = interactor.execute(GetProjectListRequest {
+            cursor: None,
+            page: None,
+            per_page: 2
+        }).await.unwrap();
+        assert_eq!(first.items.len(), 2);
+        assert!(first.next.is_some());
+
+        let second = interactor.execute(GetProjectListRequest {
+            cursor: first.next,
+            page: None,
+            per_page: 2
+        }).await.unwrap();
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].title, "Project 2");
+        assert!(second.next.is_none());
+        assert!(second.prev.is_some());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod create;
+pub mod list;
+pub mod update;
+pub mod delete;
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use crate::application::common::event_bus::EventPublisher;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::project_gateway::{ProjectReader, ProjectWriter};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::domain::models::audit::{project_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::project::{Project, ProjectId};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProjectRequest {
+    /// Taken from the path
+    #[serde(skip)]
+    pub id: ProjectId,
+    pub title: String,
+    pub description: String,
+    pub url: Option<String>,
+    /// See [`UpdateNoteRequest::version`](crate::application::note::update::UpdateNoteRequest::version)
+    pub version: Option<i64>,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+pub struct UpdateProject<'a> {
+    pub unit_of_work: &'a dyn UnitOfWork,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<UpdateProjectRequest, Project> for UpdateProject<'_> {
+    async fn execute(
+        &self,
+        data: UpdateProjectRequest
+    ) -> Result<Project, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let expected_version = data.version.ok_or(ApplicationError::ValidationError(
+            HashMap::from([("version".to_string(), "is required".to_string())])
+        ))?;
+
+        // Written together with its audit entry or not at all
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        let mut project = unit.project_gateway().get_project(&data.id).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        if project.version != expected_version {
+            return Err(ApplicationError::Conflict { current_version: project.version });
+        }
+
+        let before = project_summary(&project);
+        project.update(data.title, data.description, data.url).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        if !unit.project_gateway().update_project(&project, expected_version).await {
+            let current = unit.project_gateway().get_project(&project.id).await
+                .ok_or(ApplicationError::NotFound)?;
+            return Err(ApplicationError::Conflict { current_version: current.version });
+        }
+
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Update,
+            AuditTarget::Project,
+            project.id.clone(),
+            Some(before),
+            Some(project_summary(&project)),
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::project_updated(&project));
+
+        Ok(project)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::event_bus::test::MockEventPublisher;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use super::*;
+
+    fn request(id: &ProjectId, version: Option<i64>) -> UpdateProjectRequest {
+        UpdateProjectRequest {
+            id: id.clone(),
+            title: "Updated".to_string(),
+            description: "Updated".to_string(),
+            url: None,
+            version,
+            ip: None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_project() {
+        let project = Project::create(
+            "Test".to_string(),
+            "Test".to_string(),
+            Some("https://github.com/JKearnsl".to_string())
+        ).unwrap();
+        let unit_of_work = MockUnitOfWork::new();
+        unit_of_work.project_gateway.save_project(&project).await;
+
+        let event_publisher = MockEventPublisher::new();
+
+        let interactor = UpdateProject {
+            unit_of_work: &unit_of_work,
+            event_publisher: &event_publisher,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let result = interactor.execute(request(&project.id, Some(1))).await.unwrap();
+        assert_eq!((result.title.as_str(), result.url, result.version), ("Updated", None, 2));
+
+        let result = interactor.execute(request(&project.id, Some(1))).await;
+        assert!(matches!(result, Err(ApplicationError::Conflict { current_version: 2 })));
+
+        let result = interactor.execute(request(&project.id, None)).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+        assert_eq!(unit_of_work.audit_gateway.entries.lock().await.len(), 1);
+    }
+}
@@ -1,8 +1,12 @@
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::event_bus::EventPublisher;
 use crate::application::common::exceptions::ApplicationError;
 use crate::application::common::hasher::Hasher;
 use crate::application::common::id_provider::IdProvider;
 use crate::application::common::interactor::Interactor;
 use crate::application::common::user_gateway::UserReader;
+use crate::domain::models::audit::{AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
 use crate::domain::models::hash::Hash;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -11,11 +15,16 @@ use serde::{Deserialize, Serialize};
 pub struct CreateSessionRequest {
     pub username: String,
     pub password_hash: Hash,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
 }
 
 pub struct CreateSession<'a> {
     pub id_provider: Box<dyn IdProvider>,
     pub user_reader: &'a dyn UserReader,
+    pub audit_writer: &'a dyn AuditWriter,
+    pub event_publisher: &'a dyn EventPublisher,
 }
 
 #[async_trait]
@@ -29,16 +38,34 @@ impl Interactor<CreateSessionRequest, ()> for CreateSession<'_> {
             return Err(ApplicationError::Forbidden)
         }
 
-        let user = self.user_reader.get_by_username(&data.username).await;
-        
-        if user.is_none() {
-            return Err(ApplicationError::Unauthorized)
-        }
+        let valid = self.user_reader.get_by_username(&data.username).await
+            .is_some_and(|user| user.password_hash == data.password_hash);
 
-        if user.unwrap().password_hash != data.password_hash {
+        if !valid {
+            // Nobody is logged in, the entry keeps the name that was tried
+            self.audit_writer.save(&AuditEntry::create(
+                None,
+                AuditAction::LoginFailed,
+                AuditTarget::Session,
+                data.username,
+                None,
+                None,
+                data.ip
+            )).await;
             return Err(ApplicationError::Unauthorized)
         }
 
+        self.audit_writer.save(&AuditEntry::create(
+            Some(data.username.clone()),
+            AuditAction::Login,
+            AuditTarget::Session,
+            data.username.clone(),
+            None,
+            None,
+            data.ip
+        )).await;
+        self.event_publisher.publish(DomainEvent::UserLoggedIn { username: data.username });
+
         Ok(())
     }
 }
@@ -46,6 +73,8 @@ impl Interactor<CreateSessionRequest, ()> for CreateSession<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::common::audit_gateway::test::MockAuditGateway;
+    use crate::application::common::event_bus::test::MockEventPublisher;
     use crate::application::common::hasher::test::MockHasher;
     use crate::application::common::id_provider::test::MockIdProvider;
     use crate::application::common::user_gateway::test::MockUserGateway;
@@ -62,22 +91,74 @@ mod tests {
         let user_reader = MockUserGateway::new(vec![
             User::create(
                 "test".to_string(),
-                MockHasher.hash("password")
+                MockHasher.hash("password").await
             ).unwrap()
         ]);
 
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+
         let create_session = CreateSession {
             id_provider,
-            user_reader: &user_reader
+            user_reader: &user_reader,
+            audit_writer: &audit_gateway,
+            event_publisher: &event_publisher
         };
 
-        let create_session_dto = CreateSessionRequest {
-            username: "jkearnsl".to_string(),
-            password_hash: MockHasher.hash("password")
+        create_session.execute(CreateSessionRequest {
+            username: "test".to_string(),
+            password_hash: MockHasher.hash("password").await,
+            ip: Some("1.2.3.4".to_string())
+        }).await.unwrap();
+
+        let entries = audit_gateway.entries.lock().await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Login);
+        assert_eq!((entries[0].actor.as_deref(), entries[0].ip.as_deref()), (Some("test"), Some("1.2.3.4")));
+        assert!(matches!(
+            event_publisher.events.lock().unwrap().as_slice(),
+            [DomainEvent::UserLoggedIn { username }] if username == "test"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_session_wrong_password() {
+        let id_provider = Box::new(MockIdProvider {
+            is_auth: false,
+            session: None,
+            username: None
+        });
+
+        let user_reader = MockUserGateway::new(vec![
+            User::create(
+                "test".to_string(),
+                MockHasher.hash("password").await
+            ).unwrap()
+        ]);
+
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+
+        let create_session = CreateSession {
+            id_provider,
+            user_reader: &user_reader,
+            audit_writer: &audit_gateway,
+            event_publisher: &event_publisher
         };
 
-        let result = create_session.execute(create_session_dto).await;
-        assert!(result.is_ok());
-        assert_eq!(user_reader.users.lock().await.len(), 2);
+        for username in ["test", "nobody"] {
+            let result = create_session.execute(CreateSessionRequest {
+                username: username.to_string(),
+                password_hash: MockHasher.hash("wrong").await,
+                ip: Some("1.2.3.4".to_string())
+            }).await;
+            assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+        }
+
+        let entries = audit_gateway.entries.lock().await;
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|e| e.actor.is_none() && e.action == AuditAction::LoginFailed));
+        assert_eq!((entries[1].target_id.as_str(), entries[1].ip.as_deref()), ("nobody", Some("1.2.3.4")));
+        assert!(event_publisher.events.lock().unwrap().is_empty());
     }
 }
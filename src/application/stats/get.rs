@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDate;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::page_view_gateway::DailyStatReader;
+use crate::domain::models::note::note_slug_from_url;
+use crate::domain::models::page_view::UserAgentClass;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Longest range a single report covers
+pub const STATS_RANGE_DAYS_MAX: i64 = 366;
+/// Entries in each of the top lists
+pub const STATS_TOP_SIZE: usize = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct GetStatsRequest {
+    /// Inclusive
+    pub from: NaiveDate,
+    /// Inclusive
+    pub to: NaiveDate
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Counts {
+    pub views: u64,
+    /// Visitors are unique only within a day, page, referrer and user agent,
+    /// this sums them, so a reader of two pages on two days makes four visits
+    pub visits: u64
+}
+
+#[derive(Debug, Serialize)]
+pub struct PageStat {
+    pub path: String,
+    #[serde(flatten)]
+    pub counts: Counts
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReferrerStat {
+    pub domain: String,
+    #[serde(flatten)]
+    pub counts: Counts
+}
+
+#[derive(Debug, Serialize)]
+pub struct NoteStat {
+    pub slug: String,
+    #[serde(flatten)]
+    pub counts: Counts
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserAgentStat {
+    pub class: UserAgentClass,
+    #[serde(flatten)]
+    pub counts: Counts
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayStat {
+    pub day: NaiveDate,
+    #[serde(flatten)]
+    pub counts: Counts
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetStatsResult {
+    pub total: Counts,
+    pub days: Vec<DayStat>,
+    pub top_pages: Vec<PageStat>,
+    pub top_referrers: Vec<ReferrerStat>,
+    pub user_agents: Vec<UserAgentStat>,
+    /// Every note viewed in the range, most viewed first
+    pub notes: Vec<NoteStat>
+}
+
+pub struct GetStats<'a> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub daily_stat_reader: &'a dyn DailyStatReader
+}
+
+#[async_trait]
+impl Interactor<GetStatsRequest, GetStatsResult> for GetStats<'_> {
+    async fn execute(&self, data: GetStatsRequest) -> Result<GetStatsResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        if data.from > data.to {
+            return Err(ApplicationError::ValidationError(HashMap::from([(
+                "from".to_string(),
+                "should not be after `to`".to_string()
+            )])));
+        }
+        if (data.to - data.from).num_days() >= STATS_RANGE_DAYS_MAX {
+            return Err(ApplicationError::ValidationError(HashMap::from([(
+                "to".to_string(),
+                format!("range is longer than {} days", STATS_RANGE_DAYS_MAX)
+            )])));
+        }
+
+        let mut total = Counts::default();
+        let mut days = BTreeMap::<NaiveDate, Counts>::new();
+        let mut pages = HashMap::<String, Counts>::new();
+        let mut referrers = HashMap::<String, Counts>::new();
+        let mut user_agents = HashMap::<UserAgentClass, Counts>::new();
+        let mut notes = HashMap::<String, Counts>::new();
+
+        for stat in self.daily_stat_reader.range(data.from, data.to).await {
+            let add = |counts: &mut Counts| {
+                counts.views += stat.views;
+                counts.visits += stat.visitors;
+            };
+            add(&mut total);
+            add(days.entry(stat.day).or_default());
+            add(user_agents.entry(stat.user_agent).or_default());
+            if let Some(referrer) = &stat.referrer {
+                add(referrers.entry(referrer.clone()).or_default());
+            }
+            if let Some(slug) = note_slug_from_url("", &stat.path) {
+                add(notes.entry(slug.to_string()).or_default());
+            }
+            add(pages.entry(stat.path).or_default());
+        }
+
+        Ok(GetStatsResult {
+            total,
+            days: days.into_iter().map(|(day, counts)| DayStat { day, counts }).collect(),
+            top_pages: top(pages, STATS_TOP_SIZE)
+                .map(|(path, counts)| PageStat { path, counts })
+                .collect(),
+            top_referrers: top(referrers, STATS_TOP_SIZE)
+                .map(|(domain, counts)| ReferrerStat { domain, counts })
+                .collect(),
+            user_agents: top(user_agents, usize::MAX)
+                .map(|(class, counts)| UserAgentStat { class, counts })
+                .collect(),
+            notes: top(notes, usize::MAX)
+                .map(|(slug, counts)| NoteStat { slug, counts })
+                .collect()
+        })
+    }
+}
+
+/// Most viewed first, ties broken by key so the order is stable
+fn top<K: Ord>(counts: HashMap<K, Counts>, size: usize) -> impl Iterator<Item = (K, Counts)> {
+    let mut counts = counts.into_iter().collect::<Vec<_>>();
+    counts.sort_by(|(a_key, a), (b_key, b)| b.views.cmp(&a.views).then_with(|| a_key.cmp(b_key)));
+    counts.into_iter().take(size)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::page_view_gateway::test::MockDailyStatGateway;
+    use crate::domain::models::page_view::DailyStat;
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 1, d).unwrap()
+    }
+
+    fn stat(d: u32, path: &str, referrer: Option<&str>, views: u64) -> DailyStat {
+        DailyStat {
+            day: day(d),
+            path: path.to_string(),
+            referrer: referrer.map(str::to_string),
+            user_agent: UserAgentClass::Desktop,
+            views,
+            visitors: 1
+        }
+    }
+
+    fn id_provider(is_auth: bool) -> Box<MockIdProvider> {
+        Box::new(MockIdProvider {
+            session: None,
+            is_auth,
+            username: is_auth.then(|| "admin".to_string())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let daily_stat_gateway = MockDailyStatGateway::new(vec![
+            stat(1, "/", None, 5),
+            stat(1, "/notes/hello", Some("news.ycombinator.com"), 7),
+            stat(2, "/notes/hello", None, 1),
+            stat(2, "/notes/other", Some("google.com"), 2),
+            stat(9, "/", None, 100),
+        ]);
+
+        let interactor = GetStats {
+            id_provider: id_provider(true),
+            daily_stat_reader: &daily_stat_gateway
+        };
+        let result = interactor.execute(GetStatsRequest { from: day(1), to: day(2) }).await.unwrap();
+
+        assert_eq!(result.total.views, 15);
+        assert_eq!(result.total.visits, 4);
+        assert_eq!(result.days.len(), 2);
+        assert_eq!(result.top_pages[0].path, "/notes/hello");
+        assert_eq!(result.top_pages[0].counts.views, 8);
+        assert_eq!(result.top_referrers[0].domain, "news.ycombinator.com");
+        assert_eq!(result.notes.len(), 2);
+        assert_eq!(result.notes[0].slug, "hello");
+        assert_eq!(result.user_agents[0].counts.views, 15);
+    }
+
+    #[tokio::test]
+    async fn test_stats_invalid() {
+        let daily_stat_gateway = MockDailyStatGateway::new(vec![]);
+
+        let interactor = GetStats {
+            id_provider: id_provider(true),
+            daily_stat_reader: &daily_stat_gateway
+        };
+        let result = interactor.execute(GetStatsRequest { from: day(2), to: day(1) }).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+
+        let interactor = GetStats {
+            id_provider: id_provider(false),
+            daily_stat_reader: &daily_stat_gateway
+        };
+        let result = interactor.execute(GetStatsRequest { from: day(1), to: day(2) }).await;
+        assert!(matches!(result, Err(ApplicationError::Unauthorized)));
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::page_view_gateway::PageViewWriter;
+use crate::application::common::rate_limiter::RateLimiter;
+use crate::application::common::visitor_salt::VisitorSalt;
+use crate::domain::models::page_view::{PageView, UserAgentClass};
+use crate::domain::services::analytics::{is_counted, normalize_path, referrer_domain, visitor_hash};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RecordPageViewRequest {
+    pub path: String,
+    /// `document.referrer`, empty for direct visits
+    #[serde(default)]
+    pub referrer: String,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub ip: String,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub user_agent: String
+}
+
+pub struct RecordPageView<'a> {
+    pub page_view_writer: &'a dyn PageViewWriter,
+    pub visitor_salt: &'a dyn VisitorSalt,
+    pub rate_limiter: &'a dyn RateLimiter,
+    pub site_url: &'a str
+}
+
+/// Neither the IP nor the user agent is stored, only the salted hash of both
+/// and the class of the user agent
+#[async_trait]
+impl Interactor<RecordPageViewRequest, ()> for RecordPageView<'_> {
+    async fn execute(&self, data: RecordPageViewRequest) -> Result<(), ApplicationError> {
+        if !self.rate_limiter.hit(&data.ip).await {
+            return Err(ApplicationError::TooManyRequests);
+        }
+
+        let path = normalize_path(&data.path).ok_or_else(|| {
+            ApplicationError::ValidationError(HashMap::from([(
+                "path".to_string(),
+                "is not a site path".to_string()
+            )]))
+        })?;
+
+        let user_agent = UserAgentClass::classify(&data.user_agent);
+        if !is_counted(user_agent) {
+            return Ok(());
+        }
+
+        let day = Utc::now().date_naive();
+        let salt = self.visitor_salt.salt(day).await;
+
+        self.page_view_writer.save(&PageView {
+            day,
+            path,
+            referrer: referrer_domain(&data.referrer, self.site_url),
+            user_agent,
+            visitor: visitor_hash(&salt, &data.ip, &data.user_agent)
+        }).await;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::page_view_gateway::test::MockPageViewGateway;
+    use crate::application::common::rate_limiter::test::MockRateLimiter;
+    use crate::application::common::visitor_salt::test::MockVisitorSalt;
+    use super::*;
+
+    const FIREFOX: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:120.0) Gecko/20100101 Firefox/120.0";
+
+    #[tokio::test]
+    async fn test_record() {
+        let page_view_gateway = MockPageViewGateway::new(vec![]);
+        let rate_limiter = MockRateLimiter::new(10);
+        let interactor = RecordPageView {
+            page_view_writer: &page_view_gateway,
+            visitor_salt: &MockVisitorSalt,
+            rate_limiter: &rate_limiter,
+            site_url: "https://jkearnsl.su"
+        };
+        let request = |path: &str, referrer: &str, user_agent: &str| RecordPageViewRequest {
+            path: path.to_string(),
+            referrer: referrer.to_string(),
+            ip: "1.2.3.4".to_string(),
+            user_agent: user_agent.to_string()
+        };
+
+        interactor.execute(request("/notes/hello?utm_source=x", "https://news.ycombinator.com/item?id=1", FIREFOX)).await.unwrap();
+        interactor.execute(request("/", "https://jkearnsl.su/notes/hello", FIREFOX)).await.unwrap();
+        interactor.execute(request("/", "", "Googlebot/2.1")).await.unwrap();
+
+        let views = page_view_gateway.views.lock().await.clone();
+        assert_eq!(views.len(), 2);
+        assert_eq!(views[0].path, "/notes/hello");
+        assert_eq!(views[0].referrer.as_deref(), Some("news.ycombinator.com"));
+        assert_eq!(views[0].user_agent, UserAgentClass::Desktop);
+        assert_eq!(views[1].referrer, None);
+        assert_eq!(views[0].visitor, views[1].visitor);
+        assert!(!views[0].visitor.contains("1.2.3.4"));
+
+        let result = interactor.execute(request("https://evil.example/", "", FIREFOX)).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_record_rate_limited() {
+        let page_view_gateway = MockPageViewGateway::new(vec![]);
+        let rate_limiter = MockRateLimiter::new(1);
+        let interactor = RecordPageView {
+            page_view_writer: &page_view_gateway,
+            visitor_salt: &MockVisitorSalt,
+            rate_limiter: &rate_limiter,
+            site_url: "https://jkearnsl.su"
+        };
+        let request = || RecordPageViewRequest {
+            path: "/".to_string(),
+            referrer: String::new(),
+            ip: "1.2.3.4".to_string(),
+            user_agent: FIREFOX.to_string()
+        };
+
+        interactor.execute(request()).await.unwrap();
+        let result = interactor.execute(request()).await;
+
+        assert!(matches!(result, Err(ApplicationError::TooManyRequests)));
+        assert_eq!(page_view_gateway.views.lock().await.len(), 1);
+    }
+}
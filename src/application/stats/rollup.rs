@@ -0,0 +1,92 @@
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::page_view_gateway::{
+    DailyStatWriter,
+    PageViewGateway,
+    PageViewReader,
+    PageViewRemover
+};
+use crate::domain::services::analytics::rollup;
+use async_trait::async_trait;
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct RollupPageViewsResult {
+    pub days: u64,
+    pub views: u64
+}
+
+pub struct RollupPageViews<'a> {
+    pub page_view_gateway: &'a dyn PageViewGateway,
+    pub daily_stat_writer: &'a dyn DailyStatWriter
+}
+
+/// Rebuilds the aggregates of every day that still has raw views and drops
+/// the raw views of finished days. Today's views are kept, so the visitors
+/// of the whole day are still counted once
+#[async_trait]
+impl Interactor<(), RollupPageViewsResult> for RollupPageViews<'_> {
+    async fn execute(&self, _data: ()) -> Result<RollupPageViewsResult, ApplicationError> {
+        let mut result = RollupPageViewsResult::default();
+        let today = Utc::now().date_naive();
+
+        for day in self.page_view_gateway.days().await {
+            let views = self.page_view_gateway.list_by_day(day).await;
+            self.daily_stat_writer.replace_day(day, &rollup(&views)).await;
+            result.days += 1;
+            result.views += views.len() as u64;
+        }
+
+        self.page_view_gateway.remove_before(today).await;
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use crate::application::common::page_view_gateway::PageViewWriter;
+    use crate::application::common::page_view_gateway::test::{MockDailyStatGateway, MockPageViewGateway};
+    use crate::domain::models::page_view::{PageView, UserAgentClass};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rollup() {
+        let today = Utc::now().date_naive();
+        let view = |day, visitor: &str| PageView {
+            day,
+            path: "/".to_string(),
+            referrer: None,
+            user_agent: UserAgentClass::Desktop,
+            visitor: visitor.to_string()
+        };
+        let yesterday = today - Duration::days(1);
+        let page_view_gateway = MockPageViewGateway::new(vec![
+            view(yesterday, "a"),
+            view(yesterday, "a"),
+            view(today, "b"),
+        ]);
+        let daily_stat_gateway = MockDailyStatGateway::new(vec![]);
+
+        let interactor = RollupPageViews {
+            page_view_gateway: &page_view_gateway,
+            daily_stat_writer: &daily_stat_gateway
+        };
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!((result.days, result.views), (2, 3));
+
+        // Rolling up again must not count anything twice
+        page_view_gateway.save(&view(today, "c")).await;
+        interactor.execute(()).await.unwrap();
+
+        let stats = daily_stat_gateway.stats.lock().await.clone();
+        let of = |day| stats.iter().find(|s| s.day == day).unwrap();
+        assert_eq!((of(yesterday).views, of(yesterday).visitors), (2, 1));
+        assert_eq!((of(today).views, of(today).visitors), (2, 2));
+
+        let views = page_view_gateway.views.lock().await.clone();
+        assert!(views.iter().all(|v| v.day == today));
+    }
+}
@@ -0,0 +1,75 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::project_gateway::ProjectReader;
+use crate::domain::models::trash::TrashItem;
+use async_trait::async_trait;
+
+pub struct ListTrash<'a> {
+    pub note_reader: &'a dyn NoteReader,
+    pub project_reader: &'a dyn ProjectReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Notes and projects together, most recently deleted first
+#[async_trait]
+impl Interactor<(), Vec<TrashItem>> for ListTrash<'_> {
+    async fn execute(&self, _data: ()) -> Result<Vec<TrashItem>, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let mut items = self.note_reader.get_deleted().await;
+        items.extend(self.project_reader.get_deleted_projects().await);
+        items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Ok(items)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use chrono::{Duration, Utc};
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::project_gateway::test::MockProjectGateway;
+    use crate::domain::models::note::Note;
+    use crate::domain::models::project::Project;
+    use crate::domain::models::trash::TrashKind;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_trash() {
+        let note = Note::create("Note".to_string(), "Test".to_string()).unwrap();
+        let kept = Note::create("Kept".to_string(), "Test".to_string()).unwrap();
+        let project = Project::create("Project".to_string(), "Test".to_string(), None).unwrap();
+
+        let note_gateway = MockNoteGateway::new(HashMap::from([
+            (note.id.clone(), note.clone()),
+            (kept.id.clone(), kept.clone())
+        ]));
+        note_gateway.deleted.lock().await.insert(note.id.clone(), Utc::now() - Duration::days(2));
+        let project_gateway = MockProjectGateway::new(HashMap::from([(project.id.clone(), project.clone())]));
+        project_gateway.deleted.lock().await.insert(project.id.clone(), Utc::now() - Duration::days(1));
+
+        let interactor = ListTrash {
+            note_reader: &note_gateway,
+            project_reader: &project_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let items = interactor.execute(()).await.unwrap();
+
+        assert_eq!(
+            items.iter().map(|i| (i.kind, i.title.as_str())).collect::<Vec<_>>(),
+            vec![(TrashKind::Project, "Project"), (TrashKind::Note, "Note")]
+        );
+    }
+}
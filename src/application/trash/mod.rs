@@ -0,0 +1,6 @@
+//! Deleted notes and projects stay in the trash until they are
+//! restored, purged by hand or purged after the retention period
+pub mod list;
+pub mod restore;
+pub mod purge;
+pub mod purge_expired;
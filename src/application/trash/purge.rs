@@ -0,0 +1,67 @@
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::comment_gateway::CommentRemover;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteRemover;
+use crate::application::common::project_gateway::ProjectRemover;
+use crate::application::common::webmention_gateway::WebmentionRemover;
+use crate::domain::models::audit::{AuditAction, AuditEntry};
+use crate::domain::models::trash::TrashKind;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PurgeFromTrashRequest {
+    pub kind: TrashKind,
+    pub id: String,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+pub struct PurgeFromTrash<'a> {
+    pub note_remover: &'a dyn NoteRemover,
+    pub project_remover: &'a dyn ProjectRemover,
+    pub comment_remover: &'a dyn CommentRemover,
+    pub webmention_remover: &'a dyn WebmentionRemover,
+    pub audit_writer: &'a dyn AuditWriter,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Deletes an item for good. Only items already in the trash can be purged
+#[async_trait]
+impl Interactor<PurgeFromTrashRequest, ()> for PurgeFromTrash<'_> {
+    async fn execute(&self, data: PurgeFromTrashRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let purged = match data.kind {
+            TrashKind::Note => self.note_remover.purge(&data.id).await,
+            TrashKind::Project => self.project_remover.purge_project(&data.id).await,
+        };
+
+        if !purged {
+            return Err(ApplicationError::NotFound);
+        }
+        // Notes may be kept apart from their comments, nothing cascades
+        if data.kind == TrashKind::Note {
+            self.comment_remover.remove_by_note(&data.id).await;
+            self.webmention_remover.remove_by_note(&data.id).await;
+        }
+
+        self.audit_writer.save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Purge,
+            data.kind.into(),
+            data.id,
+            None,
+            None,
+            data.ip
+        )).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+use crate::application::common::comment_gateway::CommentRemover;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteRemover;
+use crate::application::common::project_gateway::ProjectRemover;
+use crate::application::common::webmention_gateway::WebmentionRemover;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct PurgeExpiredTrashResult {
+    pub notes: u64,
+    pub projects: u64
+}
+
+pub struct PurgeExpiredTrash<'a> {
+    pub note_remover: &'a dyn NoteRemover,
+    pub project_remover: &'a dyn ProjectRemover,
+    pub comment_remover: &'a dyn CommentRemover,
+    pub webmention_remover: &'a dyn WebmentionRemover,
+    /// How long an item stays in the trash
+    pub retention: &'a Duration
+}
+
+/// Scheduled, not exposed over HTTP
+#[async_trait]
+impl Interactor<(), PurgeExpiredTrashResult> for PurgeExpiredTrash<'_> {
+    async fn execute(&self, _data: ()) -> Result<PurgeExpiredTrashResult, ApplicationError> {
+        let retention = chrono::Duration::from_std(*self.retention)
+            .map_err(|e| ApplicationError::UnexpectedError(e.to_string()))?;
+        let before = Utc::now() - retention;
+
+        let notes = self.note_remover.purge_deleted_before(&before).await;
+        // Notes may be kept apart from their comments, nothing cascades
+        for note_id in &notes {
+            self.comment_remover.remove_by_note(note_id).await;
+            self.webmention_remover.remove_by_note(note_id).await;
+        }
+
+        Ok(PurgeExpiredTrashResult {
+            notes: notes.len() as u64,
+            projects: self.project_remover.purge_deleted_projects_before(&before).await
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use chrono::Duration as TimeDelta;
+    use crate::application::common::comment_gateway::test::MockCommentGateway;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::project_gateway::test::MockProjectGateway;
+    use crate::application::common::webmention_gateway::test::MockWebmentionGateway;
+    use crate::domain::models::comment::Comment;
+    use crate::domain::models::note::Note;
+    use crate::domain::models::webmention::Webmention;
+    use crate::domain::models::project::Project;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_purge_expired_trash() {
+        let old = Note::create("Old".to_string(), "Test".to_string()).unwrap();
+        let recent = Note::create("Recent".to_string(), "Test".to_string()).unwrap();
+        let project = Project::create("Project".to_string(), "Test".to_string(), None).unwrap();
+
+        let note_gateway = MockNoteGateway::new(HashMap::from([
+            (old.id.clone(), old.clone()),
+            (recent.id.clone(), recent.clone())
+        ]));
+        note_gateway.deleted.lock().await.extend([
+            (old.id.clone(), Utc::now() - TimeDelta::days(31)),
+            (recent.id.clone(), Utc::now() - TimeDelta::days(1))
+        ]);
+        let project_gateway = MockProjectGateway::new(HashMap::from([(project.id.clone(), project.clone())]));
+        project_gateway.deleted.lock().await.insert(project.id.clone(), Utc::now() - TimeDelta::days(40));
+
+        let comment = |note: &Note| Comment::create(note.id.clone(), None, "Reader".to_string(), "Nice".to_string()).unwrap();
+        let comment_gateway = MockCommentGateway::new(vec![comment(&old), comment(&recent)]);
+        let webmention_gateway = MockWebmentionGateway::new(vec![Webmention::create(
+            "https://example.com/reply".to_string(),
+            "https://jkearnsl.su/notes/old".to_string(),
+            old.id.clone()
+        ).unwrap()]);
+
+        let interactor = PurgeExpiredTrash {
+            note_remover: &note_gateway,
+            project_remover: &project_gateway,
+            comment_remover: &comment_gateway,
+            webmention_remover: &webmention_gateway,
+            retention: &Duration::from_secs(30 * 86_400)
+        };
+
+        let result = interactor.execute(()).await.unwrap();
+
+        assert_eq!((result.notes, result.projects), (1, 1));
+        assert!(!note_gateway.notes.lock().await.contains_key(&old.id));
+        assert!(note_gateway.deleted.lock().await.contains_key(&recent.id));
+        assert!(project_gateway.projects.lock().await.is_empty());
+        let comments = comment_gateway.comments.lock().await;
+        assert_eq!((comments.len(), &comments[0].note_id), (1, &recent.id));
+        assert!(webmention_gateway.webmentions.lock().await.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::event_bus::EventPublisher;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteRemover;
+use crate::application::common::project_gateway::ProjectRemover;
+use crate::domain::models::audit::{AuditAction, AuditEntry};
+use crate::domain::models::event::DomainEvent;
+use crate::domain::models::trash::TrashKind;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreFromTrashRequest {
+    pub kind: TrashKind,
+    pub id: String,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+pub struct RestoreFromTrash<'a> {
+    pub note_remover: &'a dyn NoteRemover,
+    pub project_remover: &'a dyn ProjectRemover,
+    pub audit_writer: &'a dyn AuditWriter,
+    pub event_publisher: &'a dyn EventPublisher,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<RestoreFromTrashRequest, ()> for RestoreFromTrash<'_> {
+    async fn execute(&self, data: RestoreFromTrashRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let restored = match data.kind {
+            TrashKind::Note => self.note_remover.restore(&data.id).await,
+            TrashKind::Project => self.project_remover.restore_project(&data.id).await,
+        };
+
+        if !restored {
+            return Err(ApplicationError::NotFound);
+        }
+
+        self.audit_writer.save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Restore,
+            data.kind.into(),
+            data.id.clone(),
+            None,
+            None,
+            data.ip
+        )).await;
+        self.event_publisher.publish(match data.kind {
+            TrashKind::Note => DomainEvent::NoteRestored { id: data.id },
+            TrashKind::Project => DomainEvent::ProjectRestored { id: data.id },
+        });
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::application::common::audit_gateway::test::MockAuditGateway;
+    use crate::application::common::event_bus::test::MockEventPublisher;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::note_gateway::NoteReader;
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::project_gateway::test::MockProjectGateway;
+    use crate::domain::models::note::Note;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_restore_from_trash() {
+        let note = Note::create("Test".to_string(), "Test".to_string()).unwrap();
+        let note_gateway = MockNoteGateway::new(HashMap::from([(note.id.clone(), note.clone())]));
+        let project_gateway = MockProjectGateway::new(HashMap::new());
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let event_publisher = MockEventPublisher::new();
+        note_gateway.remove(&note.id).await;
+
+        let interactor = RestoreFromTrash {
+            note_remover: &note_gateway,
+            project_remover: &project_gateway,
+            audit_writer: &audit_gateway,
+            event_publisher: &event_publisher,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let request = || RestoreFromTrashRequest { kind: TrashKind::Note, id: note.id.clone(), ip: None };
+        interactor.execute(request()).await.unwrap();
+
+        assert!(note_gateway.get_by_id(&note.id).await.is_some());
+        assert!(matches!(interactor.execute(request()).await, Err(ApplicationError::NotFound)));
+        assert_eq!(audit_gateway.entries.lock().await.len(), 1);
+        assert_eq!(
+            *event_publisher.events.lock().unwrap(),
+            vec![DomainEvent::NoteRestored { id: note.id.clone() }]
+        );
+    }
+}
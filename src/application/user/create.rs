@@ -1,25 +1,35 @@
 use std::collections::HashMap;
 use async_trait::async_trait;
 use serde::Serialize;
+use crate::application::common::event_bus::EventPublisher;
 use crate::application::common::exceptions::ApplicationError;
 use crate::application::common::id_provider::IdProvider;
 use crate::application::common::interactor::Interactor;
-use crate::application::common::user_gateway::{UserGateway, UserReader, UserWriter};
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::UserWriter;
+use crate::domain::models::audit::{user_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::event::DomainEvent;
 use crate::domain::models::hash::Hash;
 use crate::domain::models::user::User;
 
 #[derive(Debug, Serialize)]
 pub struct CreateUserRequest{
     pub username: String,
-    pub password_hash: Hash
+    pub password_hash: Hash,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
 }
 
 
 pub struct CreateUser<'interactor_life> {
     pub id_provider: Box<dyn IdProvider>,
-    pub user_gateway: &'interactor_life dyn UserGateway,
+    pub unit_of_work: &'interactor_life dyn UnitOfWork,
+    pub event_publisher: &'interactor_life dyn EventPublisher,
 }
 
+/// The insert itself claims the username, so two requests can not
+/// create the same user whatever the isolation level of the database
 #[async_trait]
 impl Interactor<CreateUserRequest, ()> for CreateUser<'_> {
     async fn execute(&self, data: CreateUserRequest) -> Result<(), ApplicationError> {
@@ -31,15 +41,26 @@ impl Interactor<CreateUserRequest, ()> for CreateUser<'_> {
             ApplicationError::ValidationError(e)
         })?;
         
-        if self.user_gateway.get_by_username(&user.username).await.is_some() {
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        if !unit.user_gateway().insert(&user).await {
             return Err(ApplicationError::ValidationError(HashMap::from([(
                 "username".to_string(), 
                 "Username already exists".to_string()
             )])));
         }
-        
-        self.user_gateway.save(&user).await;
-        
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Create,
+            AuditTarget::User,
+            user.id.clone(),
+            None,
+            Some(user_summary(&user)),
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+        self.event_publisher.publish(DomainEvent::UserCreated { username: user.username });
+
         Ok(())
     }
 }
@@ -48,35 +69,68 @@ impl Interactor<CreateUserRequest, ()> for CreateUser<'_> {
 
 #[cfg(test)]
 mod test {
-    use tokio::sync::Mutex;
+    use crate::application::common::event_bus::test::MockEventPublisher;
     use crate::application::common::hasher::Hasher;
     use crate::application::common::hasher::test::MockHasher;
     use crate::application::common::id_provider::test::MockIdProvider;
-    use crate::application::common::user_gateway::test::MockUserGateway;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
     use super::*;
 
-    #[tokio::test]
-    async fn test_create_user() {
-        let hasher = MockHasher;
-        let user_gateway = MockUserGateway {
-            users: Mutex::new(vec![])
-        };
-        let id_provider = Box::new(MockIdProvider {
+    fn id_provider() -> Box<MockIdProvider> {
+        Box::new(MockIdProvider {
             session: None,
             is_auth: true,
             username: Some("test_user".to_string())
-        });
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_user() {
+        let hasher = MockHasher;
+        let unit_of_work = MockUnitOfWork::new();
+        let event_publisher = MockEventPublisher::new();
         let interactor = CreateUser {
-            id_provider,
-            user_gateway: &user_gateway
+            id_provider: id_provider(),
+            unit_of_work: &unit_of_work,
+            event_publisher: &event_publisher
         };
 
         interactor.execute(CreateUserRequest {
             username: "test".to_string(),
-            password_hash: hasher.hash("password").await.unwrap()
+            password_hash: hasher.hash("password").await.unwrap(),
+            ip: None
         }).await.unwrap();
 
-        let users = user_gateway.users.lock().await;
+        let users = unit_of_work.user_gateway.users.lock().await;
         assert_eq!(users.len(), 1);
+        let entries = unit_of_work.audit_gateway.entries.lock().await;
+        assert_eq!(entries[0].actor.as_deref(), Some("test_user"));
+        assert_eq!(entries[0].after.as_ref().unwrap()["username"], "test");
+    }
+
+    #[tokio::test]
+    async fn test_create_user_taken() {
+        let hasher = MockHasher;
+        let unit_of_work = MockUnitOfWork::new();
+        let event_publisher = MockEventPublisher::new();
+        let interactor = CreateUser {
+            id_provider: id_provider(),
+            unit_of_work: &unit_of_work,
+            event_publisher: &event_publisher
+        };
+        let request = || async {
+            CreateUserRequest {
+                username: "test".to_string(),
+                password_hash: hasher.hash("password").await.unwrap(),
+                ip: None
+            }
+        };
+
+        interactor.execute(request().await).await.unwrap();
+        let result = interactor.execute(request().await).await;
+
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+        assert_eq!(unit_of_work.user_gateway.users.lock().await.len(), 1);
+        assert_eq!(unit_of_work.audit_gateway.entries.lock().await.len(), 1);
     }
 }
\ No newline at end of file
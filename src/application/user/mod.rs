@@ -1,3 +1,5 @@
 pub mod get_self;
 pub mod list;
-pub mod create;
\ No newline at end of file
+pub mod create;pub mod set_password;
+pub mod revoke_sessions;
+
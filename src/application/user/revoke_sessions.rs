@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use crate::application::common::audit_gateway::AuditWriter;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_gateway::JobWriter;
+use crate::application::common::user_gateway::UserReader;
+use crate::domain::models::audit::{AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::job::Job;
+
+/// Sessions live in the memory of the server, so they are dropped by
+/// a job its workers run, within a poll interval of a running server
+pub const REVOKE_SESSIONS_JOB: &str = "sessions.revoke";
+
+#[derive(Debug, Serialize)]
+pub struct RevokeUserSessionsRequest {
+    pub username: String,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+
+pub struct RevokeUserSessions<'interactor_life> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub user_reader: &'interactor_life dyn UserReader,
+    pub job_writer: &'interactor_life dyn JobWriter,
+    pub audit_writer: &'interactor_life dyn AuditWriter,
+}
+
+/// Signs the user out everywhere, e.g. after a password change
+#[async_trait]
+impl Interactor<RevokeUserSessionsRequest, ()> for RevokeUserSessions<'_> {
+    async fn execute(&self, data: RevokeUserSessionsRequest) -> Result<(), ApplicationError> {
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let user = self.user_reader.get_by_username(&data.username).await
+            .ok_or(ApplicationError::NotFound)?;
+
+        self.job_writer.save_job(&Job::create(
+            REVOKE_SESSIONS_JOB,
+            json!({ "username": user.username }),
+            Utc::now()
+        )).await;
+
+        self.audit_writer.save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Purge,
+            AuditTarget::Session,
+            user.username,
+            None,
+            None,
+            data.ip
+        )).await;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::application::common::audit_gateway::test::MockAuditGateway;
+    use crate::application::common::hasher::Hasher;
+    use crate::application::common::hasher::test::MockHasher;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::job_gateway::test::MockJobGateway;
+    use crate::application::common::user_gateway::test::MockUserGateway;
+    use crate::domain::models::user::User;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_revoke_user_sessions() {
+        let user = User::create("test".to_string(), MockHasher.hash("password").await).unwrap();
+        let user_gateway = MockUserGateway::new(vec![user]);
+        let job_gateway = MockJobGateway::new(vec![]);
+        let audit_gateway = MockAuditGateway::new(vec![]);
+        let interactor = RevokeUserSessions {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test_user".to_string())
+            }),
+            user_reader: &user_gateway,
+            job_writer: &job_gateway,
+            audit_writer: &audit_gateway
+        };
+
+        interactor.execute(RevokeUserSessionsRequest {
+            username: "test".to_string(),
+            ip: None
+        }).await.unwrap();
+
+        let jobs = job_gateway.jobs.lock().await.clone();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].kind, REVOKE_SESSIONS_JOB);
+        assert_eq!(jobs[0].payload, json!({ "username": "test" }));
+        assert_eq!(audit_gateway.entries.lock().await[0].target_id, "test");
+
+        let missing = interactor.execute(RevokeUserSessionsRequest {
+            username: "nobody".to_string(),
+            ip: None
+        }).await;
+        assert!(matches!(missing, Err(ApplicationError::NotFound)));
+        assert_eq!(job_gateway.jobs.lock().await.len(), 1);
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::{UserReader, UserWriter};
+use crate::domain::models::audit::{user_summary, AuditAction, AuditEntry, AuditTarget};
+use crate::domain::models::hash::Hash;
+
+#[derive(Debug, Serialize)]
+pub struct SetUserPasswordRequest {
+    pub username: String,
+    pub password_hash: Hash,
+    /// Filled in by the presentation layer for the audit log
+    #[serde(skip)]
+    pub ip: Option<String>
+}
+
+
+pub struct SetUserPassword<'interactor_life> {
+    pub id_provider: Box<dyn IdProvider>,
+    pub unit_of_work: &'interactor_life dyn UnitOfWork,
+}
+
+/// Replaces the password of an existing user, the hash is never written to the audit log
+#[async_trait]
+impl Interactor<SetUserPasswordRequest, ()> for SetUserPassword<'_> {
+    async fn execute(&self, data: SetUserPasswordRequest) -> Result<(), ApplicationError> {
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let unit = self.unit_of_work.begin().await.map_err(ApplicationError::UnexpectedError)?;
+
+        let mut user = unit.user_gateway().get_by_username(&data.username).await
+            .ok_or(ApplicationError::NotFound)?;
+        user.password_hash = data.password_hash;
+
+        unit.user_gateway().save(&user).await;
+        unit.audit_writer().save(&AuditEntry::create(
+            self.id_provider.username().cloned(),
+            AuditAction::Update,
+            AuditTarget::User,
+            user.id.clone(),
+            Some(user_summary(&user)),
+            Some(user_summary(&user)),
+            data.ip
+        )).await;
+        unit.commit().await.map_err(ApplicationError::UnexpectedError)?;
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::application::common::hasher::Hasher;
+    use crate::application::common::hasher::test::MockHasher;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::unit_of_work::test::MockUnitOfWork;
+    use crate::domain::models::user::User;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_user_password() {
+        let hasher = MockHasher;
+        let unit_of_work = MockUnitOfWork::new();
+        let user = User::create("test".to_string(), hasher.hash("old").await).unwrap();
+        unit_of_work.user_gateway.users.lock().await.push(user.clone());
+        let interactor = SetUserPassword {
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test_user".to_string())
+            }),
+            unit_of_work: &unit_of_work,
+        };
+
+        interactor.execute(SetUserPasswordRequest {
+            username: "test".to_string(),
+            password_hash: hasher.hash("new").await,
+            ip: None
+        }).await.unwrap();
+
+        let saved = unit_of_work.user_gateway.users.lock().await.clone();
+        assert_eq!(saved.len(), 1);
+        assert!(hasher.verify("new", &saved[0].password_hash).await);
+        assert_eq!(unit_of_work.audit_gateway.entries.lock().await[0].target_id, user.id);
+
+        let missing = interactor.execute(SetUserPasswordRequest {
+            username: "nobody".to_string(),
+            password_hash: hasher.hash("new").await,
+            ip: None
+        }).await;
+        assert!(matches!(missing, Err(ApplicationError::NotFound)));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webhook_gateway::WebhookWriter;
+use crate::domain::models::event::EventKind;
+use crate::domain::models::webhook::Webhook;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub events: Vec<EventKind>,
+    /// Generated when omitted
+    pub secret: Option<String>
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateWebhookResult {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    /// The only time the secret is shown
+    pub secret: String
+}
+
+pub struct CreateWebhook<'a> {
+    pub webhook_writer: &'a dyn WebhookWriter,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<CreateWebhookRequest, CreateWebhookResult> for CreateWebhook<'_> {
+    async fn execute(&self, data: CreateWebhookRequest) -> Result<CreateWebhookResult, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let webhook = Webhook::create(data.url, data.events, data.secret).map_err(|e| {
+            ApplicationError::ValidationError(e)
+        })?;
+
+        self.webhook_writer.save_webhook(&webhook).await;
+
+        Ok(CreateWebhookResult {
+            secret: webhook.secret.clone(),
+            webhook
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::webhook_gateway::test::MockWebhookGateway;
+    use super::*;
+
+    fn request(url: &str) -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: url.to_string(),
+            events: vec![EventKind::NotePublished],
+            secret: None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook() {
+        let webhook_gateway = MockWebhookGateway::new(vec![], vec![]);
+        let interactor = CreateWebhook {
+            webhook_writer: &webhook_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let result = interactor.execute(request("https://chat.example/hook")).await.unwrap();
+        assert_eq!(webhook_gateway.webhooks.lock().await[0].secret, result.secret);
+
+        let result = interactor.execute(request("chat.example")).await;
+        assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,33 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webhook_gateway::WebhookRemover;
+use crate::domain::models::webhook::WebhookId;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteWebhookRequest {
+    pub id: WebhookId
+}
+
+pub struct DeleteWebhook<'a> {
+    pub webhook_remover: &'a dyn WebhookRemover,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Pending deliveries and the delivery log go with it
+#[async_trait]
+impl Interactor<DeleteWebhookRequest, ()> for DeleteWebhook<'_> {
+    async fn execute(&self, data: DeleteWebhookRequest) -> Result<(), ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        match self.webhook_remover.remove_webhook(&data.id).await {
+            true => Ok(()),
+            false => Err(ApplicationError::NotFound)
+        }
+    }
+}
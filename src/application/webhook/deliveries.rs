@@ -0,0 +1,38 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webhook_gateway::{WebhookDeliveryReader, WebhookReader};
+use crate::domain::models::webhook::{WebhookDelivery, WebhookId};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// Older entries stay in the table but are not listed
+pub const DELIVERY_LOG_LIMIT: u64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct ListWebhookDeliveriesRequest {
+    pub webhook_id: WebhookId
+}
+
+pub struct ListWebhookDeliveries<'a> {
+    pub webhook_reader: &'a dyn WebhookReader,
+    pub delivery_reader: &'a dyn WebhookDeliveryReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Delivery log of a webhook, newest first
+#[async_trait]
+impl Interactor<ListWebhookDeliveriesRequest, Vec<WebhookDelivery>> for ListWebhookDeliveries<'_> {
+    async fn execute(&self, data: ListWebhookDeliveriesRequest) -> Result<Vec<WebhookDelivery>, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        if self.webhook_reader.get_webhook(&data.webhook_id).await.is_none() {
+            return Err(ApplicationError::NotFound);
+        }
+
+        Ok(self.delivery_reader.list_deliveries(&data.webhook_id, &DELIVERY_LOG_LIMIT).await)
+    }
+}
@@ -0,0 +1,23 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webhook_gateway::WebhookReader;
+use crate::domain::models::webhook::Webhook;
+use async_trait::async_trait;
+
+pub struct ListWebhooks<'a> {
+    pub webhook_reader: &'a dyn WebhookReader,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+#[async_trait]
+impl Interactor<(), Vec<Webhook>> for ListWebhooks<'_> {
+    async fn execute(&self, _data: ()) -> Result<Vec<Webhook>, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        Ok(self.webhook_reader.list_webhooks().await)
+    }
+}
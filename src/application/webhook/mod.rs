@@ -0,0 +1,7 @@
+pub mod create;
+pub mod list;
+pub mod delete;
+pub mod deliveries;
+pub mod redeliver;
+pub mod process;
+pub mod subscriber;
@@ -0,0 +1,118 @@
+use chrono::Utc;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webhook_client::{WebhookClient, WebhookResponse};
+use crate::application::common::webhook_gateway::{WebhookDeliveryReader, WebhookDeliveryWriter, WebhookGateway, WebhookReader};
+use crate::domain::services::webhook::{webhook_signature, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+use async_trait::async_trait;
+use serde::Serialize;
+
+/// Deliveries handled by a single run
+pub const WEBHOOK_DELIVERY_BATCH: u64 = 50;
+
+#[derive(Debug, Default, Serialize)]
+pub struct ProcessWebhookDeliveriesResult {
+    pub delivered: u64,
+    pub retried: u64,
+    /// Failed too many times and were given up
+    pub failed: u64
+}
+
+pub struct ProcessWebhookDeliveries<'a> {
+    pub webhook_gateway: &'a dyn WebhookGateway,
+    pub webhook_client: &'a dyn WebhookClient
+}
+
+/// One pass over the webhook queue, meant to be run periodically
+#[async_trait]
+impl Interactor<(), ProcessWebhookDeliveriesResult> for ProcessWebhookDeliveries<'_> {
+    async fn execute(&self, _data: ()) -> Result<ProcessWebhookDeliveriesResult, ApplicationError> {
+        let mut result = ProcessWebhookDeliveriesResult::default();
+
+        for mut delivery in self.webhook_gateway.list_due_deliveries(Utc::now(), &WEBHOOK_DELIVERY_BATCH).await {
+            // Deliveries are removed together with their webhook, this is only a race
+            let Some(webhook) = self.webhook_gateway.get_webhook(&delivery.webhook_id).await else {
+                continue;
+            };
+
+            let headers = [
+                (EVENT_HEADER, delivery.event.as_str().to_string()),
+                (DELIVERY_HEADER, delivery.id.clone()),
+                (SIGNATURE_HEADER, webhook_signature(&webhook.secret, &delivery.payload)),
+            ];
+            let retry = match self.webhook_client.post(&webhook.url, &headers, &delivery.payload).await {
+                WebhookResponse::Status(code) if (200..300).contains(&code) => {
+                    delivery.succeed(Utc::now(), code);
+                    None
+                }
+                WebhookResponse::Status(code) => Some(delivery.fail(Utc::now(), Some(code), format!("HTTP {}", code))),
+                WebhookResponse::Unreachable(error) => Some(delivery.fail(Utc::now(), None, error))
+            };
+            self.webhook_gateway.save_delivery(&delivery).await;
+
+            match retry {
+                None => result.delivered += 1,
+                Some(true) => result.retried += 1,
+                Some(false) => result.failed += 1
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::application::common::webhook_client::test::MockWebhookClient;
+    use crate::application::common::webhook_gateway::test::MockWebhookGateway;
+    use crate::domain::models::event::EventKind;
+    use crate::domain::models::webhook::{Webhook, WebhookDelivery, WebhookDeliveryStatus, WEBHOOK_ATTEMPTS_MAX};
+    use super::*;
+
+    #[tokio::test]
+    async fn test_process_webhook_deliveries() {
+        let webhook = |url: &str| Webhook::create(url.to_string(), vec![EventKind::NoteDeleted], None).unwrap();
+        let ok = webhook("https://ok.example/hook");
+        let down = webhook("https://down.example/hook");
+
+        let delivery = |webhook: &Webhook| WebhookDelivery::create(
+            webhook.id.clone(),
+            EventKind::NoteDeleted,
+            "{\"event\":\"note.deleted\"}".to_string()
+        );
+        let flaky = delivery(&down);
+        let mut dead = delivery(&down);
+        dead.attempts = WEBHOOK_ATTEMPTS_MAX - 1;
+
+        let webhook_gateway = MockWebhookGateway::new(
+            vec![ok.clone(), down],
+            vec![delivery(&ok), flaky.clone(), dead]
+        );
+        let webhook_client = MockWebhookClient::new(HashMap::from([
+            ("https://down.example/hook".to_string(), WebhookResponse::Status(503))
+        ]));
+
+        let interactor = ProcessWebhookDeliveries {
+            webhook_gateway: &webhook_gateway,
+            webhook_client: &webhook_client
+        };
+
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!((result.delivered, result.retried, result.failed), (1, 1, 1));
+
+        let posted = webhook_client.posted.lock().unwrap().clone();
+        let to_ok = posted.iter().find(|p| p.url == ok.url).unwrap();
+        assert_eq!(to_ok.headers[SIGNATURE_HEADER], webhook_signature(&ok.secret, &to_ok.body));
+        assert_eq!(to_ok.headers[EVENT_HEADER], "note.deleted");
+
+        let retried = webhook_gateway.get_delivery(&flaky.id).await.unwrap();
+        assert_eq!((retried.status, retried.response_code), (WebhookDeliveryStatus::Pending, Some(503)));
+        assert!(retried.next_attempt_at > Utc::now());
+
+        // The retry is not due yet
+        let result = interactor.execute(()).await.unwrap();
+        assert_eq!((result.delivered, result.retried, result.failed), (0, 0, 0));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::id_provider::IdProvider;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webhook_gateway::{WebhookDeliveryReader, WebhookDeliveryWriter};
+use crate::domain::models::webhook::{WebhookDelivery, WebhookDeliveryId};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RedeliverWebhookRequest {
+    pub delivery_id: WebhookDeliveryId
+}
+
+pub struct RedeliverWebhook<'a> {
+    pub delivery_reader: &'a dyn WebhookDeliveryReader,
+    pub delivery_writer: &'a dyn WebhookDeliveryWriter,
+    pub id_provider: Box<dyn IdProvider>
+}
+
+/// Queues the payload of any earlier delivery again, returns the new delivery
+#[async_trait]
+impl Interactor<RedeliverWebhookRequest, WebhookDelivery> for RedeliverWebhook<'_> {
+    async fn execute(&self, data: RedeliverWebhookRequest) -> Result<WebhookDelivery, ApplicationError> {
+
+        if !self.id_provider.is_auth() {
+            return Err(ApplicationError::Unauthorized);
+        }
+
+        let delivery = self.delivery_reader.get_delivery(&data.delivery_id).await
+            .ok_or(ApplicationError::NotFound)?
+            .redeliver();
+
+        self.delivery_writer.save_delivery(&delivery).await;
+        Ok(delivery)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use crate::application::common::id_provider::test::MockIdProvider;
+    use crate::application::common::webhook_gateway::test::MockWebhookGateway;
+    use crate::domain::models::event::EventKind;
+    use crate::domain::models::webhook::WebhookDeliveryStatus;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_redeliver_webhook() {
+        let mut failed = WebhookDelivery::create("hook".to_string(), EventKind::NoteDeleted, "{}".to_string());
+        while failed.fail(Utc::now(), Some(500), "500".to_string()) {}
+
+        let webhook_gateway = MockWebhookGateway::new(vec![], vec![failed.clone()]);
+        let interactor = RedeliverWebhook {
+            delivery_reader: &webhook_gateway,
+            delivery_writer: &webhook_gateway,
+            id_provider: Box::new(MockIdProvider {
+                session: None,
+                is_auth: true,
+                username: Some("test".parse().unwrap())
+            })
+        };
+
+        let delivery = interactor.execute(RedeliverWebhookRequest { delivery_id: failed.id.clone() }).await.unwrap();
+
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        let deliveries = webhook_gateway.deliveries.lock().await;
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].status, WebhookDeliveryStatus::Failed);
+    }
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use crate::application::common::event_bus::EventSubscriber;
+use crate::application::common::webhook_gateway::{WebhookDeliveryWriter, WebhookGateway, WebhookReader};
+use crate::domain::models::event::{DomainEvent, EventKind};
+use crate::domain::models::webhook::WebhookDelivery;
+use crate::domain::services::webhook::webhook_payload;
+
+/// Queues a delivery for every webhook that wants the event,
+/// they are sent by [`ProcessWebhookDeliveries`](super::process::ProcessWebhookDeliveries)
+pub struct WebhookSubscriber {
+    pub webhook_gateway: Box<dyn WebhookGateway>
+}
+
+#[async_trait]
+impl EventSubscriber for WebhookSubscriber {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    fn kinds(&self) -> &[EventKind] {
+        &EventKind::ALL
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), String> {
+        let webhooks = self.webhook_gateway.list_subscribed(event.kind()).await;
+        if webhooks.is_empty() {
+            return Ok(());
+        }
+
+        let payload = webhook_payload(event, Utc::now());
+        for webhook in webhooks {
+            let delivery = WebhookDelivery::create(webhook.id, event.kind(), payload.clone());
+            self.webhook_gateway.save_delivery(&delivery).await;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::webhook_gateway::WebhookDeliveryReader;
+    use crate::application::common::webhook_gateway::test::MockWebhookGateway;
+    use crate::domain::models::webhook::Webhook;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_webhook_subscriber() {
+        let webhook = |kind| Webhook::create("https://chat.example/hook".to_string(), vec![kind], None).unwrap();
+        let notes = webhook(EventKind::NoteUpdated);
+        let users = webhook(EventKind::UserCreated);
+
+        let subscriber = WebhookSubscriber {
+            webhook_gateway: Box::new(MockWebhookGateway::new(vec![notes.clone(), users], vec![]))
+        };
+
+        subscriber.handle(&DomainEvent::NoteUpdated {
+            id: "1".to_string(),
+            slug: "test".to_string(),
+            title: "Test".to_string(),
+            version: 2
+        }).await.unwrap();
+
+        let deliveries = subscriber.webhook_gateway.list_deliveries(&notes.id, &10).await;
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].event, EventKind::NoteUpdated);
+        assert!(deliveries[0].payload.contains("\"slug\":\"test\""));
+    }
+}
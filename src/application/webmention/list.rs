@@ -0,0 +1,42 @@
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::webmention_gateway::WebmentionReader;
+use crate::domain::models::note::NoteId;
+use crate::domain::models::webmention::{MentionAuthor, WebmentionStatus};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ListWebmentionsRequest {
+    pub note_id: NoteId
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebmentionItem {
+    pub source: String,
+    pub author: MentionAuthor,
+    pub verified_at: Option<DateTime<Utc>>
+}
+
+pub struct ListWebmentions<'a> {
+    pub webmention_reader: &'a dyn WebmentionReader
+}
+
+/// Verified mentions of a note
+#[async_trait]
+impl Interactor<ListWebmentionsRequest, Vec<WebmentionItem>> for ListWebmentions<'_> {
+    async fn execute(
+        &self,
+        data: ListWebmentionsRequest
+    ) -> Result<Vec<WebmentionItem>, ApplicationError> {
+
+        let webmentions = self.webmention_reader.list_by_note(&data.note_id, WebmentionStatus::Verified).await;
+
+        Ok(webmentions.into_iter().map(|w| WebmentionItem {
+            source: w.source,
+            author: w.author,
+            verified_at: w.verified_at
+        }).collect())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod receive;
+pub mod list;
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::common::note_gateway::NoteReader;
+use crate::application::common::rate_limiter::RateLimiter;
+use crate::application::common::webmention_client::WebmentionClient;
+use crate::application::common::webmention_gateway::{WebmentionGateway, WebmentionReader, WebmentionWriter};
+use crate::domain::models::note::note_slug_from_url;
+use crate::domain::models::webmention::{Webmention, WebmentionId};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ReceiveWebmentionRequest {
+    pub source: String,
+    pub target: String,
+    /// Filled in by the presentation layer
+    #[serde(skip)]
+    pub ip: String
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReceiveWebmentionResult {
+    pub id: WebmentionId
+}
+
+pub struct ReceiveWebmention<'a> {
+    pub webmention_gateway: &'a dyn WebmentionGateway,
+    pub webmention_client: &'a dyn WebmentionClient,
+    pub note_reader: &'a dyn NoteReader,
+    pub rate_limiter: &'a dyn RateLimiter,
+    pub site_url: &'a str
+}
+
+/// Accepts the mention right away, the source is verified in the background
+#[async_trait]
+impl Interactor<ReceiveWebmentionRequest, ReceiveWebmentionResult> for ReceiveWebmention<'_> {
+    async fn execute(
+        &self,
+        data: ReceiveWebmentionRequest
+    ) -> Result<ReceiveWebmentionResult, ApplicationError> {
+        // Every mention makes the server fetch the source
+        if !self.rate_limiter.hit(&data.ip).await {
+            return Err(ApplicationError::TooManyRequests);
+        }
+
+        let invalid_target = || ApplicationError::ValidationError(HashMap::from([(
+            "target".to_string(),
+            "is not a note of this site".to_string()
+        )]));
+
+        let slug = note_slug_from_url(self.site_url, &data.target).ok_or_else(invalid_target)?;
+        let note = self.note_reader.get_by_slug(slug).await.ok_or_else(invalid_target)?;
+
+        let webmention = match self.webmention_gateway.get_by_source_target(&data.source, &data.target).await {
+            Some(mut existing) => {
+                existing.resubmit();
+                existing
+            }
+            None => Webmention::create(data.source, data.target, note.id).map_err(|e| {
+                ApplicationError::ValidationError(e)
+            })?
+        };
+
+        self.webmention_gateway.save(&webmention).await;
+        self.webmention_client.verify_later(webmention.clone()).await;
+
+        Ok(ReceiveWebmentionResult {
+            id: webmention.id
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::application::common::note_gateway::test::MockNoteGateway;
+    use crate::application::common::rate_limiter::test::MockRateLimiter;
+    use crate::application::common::webmention_client::test::MockWebmentionClient;
+    use crate::application::common::webmention_gateway::test::MockWebmentionGateway;
+    use crate::domain::models::note::{note_url, Note};
+    use crate::domain::models::webmention::WebmentionStatus;
+    use super::*;
+
+    const SITE: &str = "https://jkearnsl.su";
+
+    #[tokio::test]
+    async fn test_receive_webmention() {
+        let note = Note::create("Supa title for you".to_string(), "Test".to_string()).unwrap();
+        let note_gateway = MockNoteGateway::new(HashMap::from([(note.id.clone(), note.clone())]));
+        let webmention_gateway = MockWebmentionGateway::new(vec![]);
+        let webmention_client = MockWebmentionClient::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = ReceiveWebmention {
+            webmention_gateway: &webmention_gateway,
+            webmention_client: &webmention_client,
+            note_reader: &note_gateway,
+            rate_limiter: &rate_limiter,
+            site_url: SITE
+        };
+        let request = || ReceiveWebmentionRequest {
+            source: "https://example.com/reply".to_string(),
+            target: note_url(SITE, &note.slug),
+            ip: "1.2.3.4".to_string()
+        };
+
+        let first = interactor.execute(request()).await.unwrap();
+        let second = interactor.execute(request()).await.unwrap();
+
+        // Repeated notification updates the same mention
+        assert_eq!(first.id, second.id);
+        let webmentions = webmention_gateway.webmentions.lock().await;
+        assert_eq!(webmentions.len(), 1);
+        assert_eq!(webmentions[0].note_id, note.id);
+        assert_eq!(webmentions[0].status, WebmentionStatus::Pending);
+        assert_eq!(webmention_client.verified.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_receive_webmention_foreign_target() {
+        let note_gateway = MockNoteGateway::new(HashMap::new());
+        let webmention_gateway = MockWebmentionGateway::new(vec![]);
+        let webmention_client = MockWebmentionClient::new();
+        let rate_limiter = MockRateLimiter::new(10);
+
+        let interactor = ReceiveWebmention {
+            webmention_gateway: &webmention_gateway,
+            webmention_client: &webmention_client,
+            note_reader: &note_gateway,
+            rate_limiter: &rate_limiter,
+            site_url: SITE
+        };
+
+        for target in ["https://other.site/notes/x", "https://jkearnsl.su/notes/missing"] {
+            let result = interactor.execute(ReceiveWebmentionRequest {
+                source: "https://example.com/reply".to_string(),
+                target: target.to_string(),
+                ip: "1.2.3.4".to_string()
+            }).await;
+            assert!(matches!(result, Err(ApplicationError::ValidationError(_))));
+        }
+        assert!(webmention_client.verified.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_receive_webmention_rate_limited() {
+        let note = Note::create("Supa title for you".to_string(), "Test".to_string()).unwrap();
+        let note_gateway = MockNoteGateway::new(HashMap::from([(note.id.clone(), note.clone())]));
+        let webmention_gateway = MockWebmentionGateway::new(vec![]);
+        let webmention_client = MockWebmentionClient::new();
+        let rate_limiter = MockRateLimiter::new(1);
+
+        let interactor = ReceiveWebmention {
+            webmention_gateway: &webmention_gateway,
+            webmention_client: &webmention_client,
+            note_reader: &note_gateway,
+            rate_limiter: &rate_limiter,
+            site_url: SITE
+        };
+        let request = |source: &str| ReceiveWebmentionRequest {
+            source: source.to_string(),
+            target: note_url(SITE, &note.slug),
+            ip: "1.2.3.4".to_string()
+        };
+
+        interactor.execute(request("https://example.com/a")).await.unwrap();
+        let result = interactor.execute(request("https://example.com/b")).await;
+
+        assert!(matches!(result, Err(ApplicationError::TooManyRequests)));
+        assert_eq!(webmention_client.verified.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::domain::models::job::JobStatus;
+
+
+#[derive(Parser)]
+#[command(version, about = "JKearnsl blog server")]
+pub struct Cli {
+    /// TOML file with settings, `JKEARNSL_*` environment variables override it
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Print results of administrative commands as JSON
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Without a command the server is started
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Except for `config`, commands work on the configured database directly,
+/// the server does not need to be running
+#[derive(Subcommand)]
+pub enum Command {
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Create missing tables and apply pending Postgres migrations, then exit
+    Migrate,
+    /// Manage users
+    User {
+        #[command(subcommand)]
+        command: UserCommand,
+    },
+    /// Inspect background jobs
+    Jobs {
+        #[command(subcommand)]
+        command: JobsCommand,
+    },
+    /// Import notes from a directory of Markdown files with front matter.
+    /// Running it again only applies what changed
+    Import {
+        dir: PathBuf,
+        /// Report what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write notes, projects, media records and users to an archive:
+    /// a directory, or a tarball when the path ends in `.tar.gz`
+    Export {
+        path: PathBuf,
+    },
+    /// Restore an archive written by `export`
+    Restore {
+        path: PathBuf,
+        /// Check the archive and report what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Validate the settings and print the effective ones with secrets redacted
+    Check,
+}
+
+#[derive(Subcommand)]
+pub enum UserCommand {
+    List,
+    /// The password is read from the first line of stdin
+    Create {
+        username: String,
+    },
+    /// The password is read from the first line of stdin,
+    /// sessions of the user are revoked afterwards
+    SetPassword {
+        username: String,
+    },
+    /// Sign the user out everywhere. A running server drops the
+    /// sessions within a job poll interval
+    RevokeSessions {
+        username: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum JobsCommand {
+    /// Newest first
+    List {
+        /// queued, running, done or dead
+        #[arg(long, value_parser = parse_job_status)]
+        status: Option<JobStatus>,
+        #[arg(long)]
+        kind: Option<String>,
+        #[arg(long, default_value_t = 20)]
+        limit: u64,
+    },
+    /// Queue a dead job again
+    Retry {
+        id: String,
+    },
+}
+
+fn parse_job_status(value: &str) -> Result<JobStatus, String> {
+    JobStatus::parse(value).ok_or_else(|| "expected queued, running, done or dead".to_string())
+}
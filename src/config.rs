@@ -1,7 +1,21 @@
+//! Settings come in layers: built-in defaults, then the TOML file given
+//! with `--config`, then `JKEARNSL_*` environment variables. Keys are
+//! dotted paths, `mail.smtp.host` in the file is `JKEARNSL_MAIL_SMTP_HOST`
+//! in the environment
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::thread;
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::services::recurrence::Recurrence;
+use crate::domain::services::validator::is_http_url;
+
+pub const ENV_PREFIX: &str = "JKEARNSL_";
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CredentialsConfig {
     pub username: String,
@@ -14,43 +28,607 @@ pub struct Tls {
     pub key: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MailConfig {
+    /// Without SMTP mail is written to `dir` instead of being sent
+    pub smtp: Option<SmtpConfig>,
+    pub dir: String,
+    pub from: String,
+    /// How often the newsletter digest goes out
+    pub digest_interval_hours: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContactConfig {
+    /// Where messages from the contact form are mailed
+    pub recipient: String,
+    /// Leading zero bits of the proof of work, 0 disables it
+    pub pow_difficulty: u8,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DatabaseConfig {
+    pub url: String,
+    /// SQLite takes one writer at a time, more only wait on each other
+    pub writers: u32,
+    pub readers: u32,
+    /// How long a connection waits for a lock before giving up
+    pub busy_timeout_ms: u64,
+    /// When set, notes, projects and users are kept there instead of SQLite
+    pub postgres_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BackupConfig {
+    pub dir: String,
+    /// How often a snapshot is taken, 0 disables the schedule
+    pub interval_hours: u64,
+    /// Cron expression used instead of `interval_hours`
+    pub schedule: Option<String>,
+    pub keep_daily: u32,
+    pub keep_weekly: u32,
+    pub keep_monthly: u32,
+    pub integrity_check: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobsConfig {
+    /// Background jobs run at the same time
+    pub workers: u32,
+    /// How long an idle worker waits before looking for due jobs again
+    pub poll_interval_ms: u64,
+    /// Done jobs are removed after that, failed ones stay until retried
+    pub keep_done_hours: u64,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub host: String,
     pub port: u16,
     pub workers: usize,
     pub tls: Option<Tls>,
-    pub credentials: CredentialsConfig
+    pub database: DatabaseConfig,
+    pub credentials: CredentialsConfig,
+    pub media_dir: String,
+    /// Public origin used in canonical URLs and social cards
+    pub site_url: String,
+    /// Reverse proxies whose `X-Forwarded-For` tells the client address
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Fediverse handle of the blog, `{actor_username}@{host}`
+    pub actor_username: String,
+    /// Key for signed links. When not set a random one is used
+    /// and links stop working after restart
+    pub secret_key: Option<String>,
+    pub mail: MailConfig,
+    pub contact: ContactConfig,
+    /// Hits of the same IP within that window count once
+    pub counter_dedupe_minutes: u64,
+    /// Deleted notes and projects are purged after that, 0 keeps them forever
+    pub trash_retention_days: u64,
+    pub backup: BackupConfig,
+    pub jobs: JobsConfig
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    String,
+    Integer,
+    Boolean,
+}
+
+struct Key {
+    name: &'static str,
+    kind: Kind,
+    default: Option<&'static str>,
+    /// Never printed, not even in error messages
+    secret: bool,
+}
+
+const fn key(name: &'static str, kind: Kind, default: Option<&'static str>) -> Key {
+    Key { name, kind, default, secret: false }
+}
+
+const fn secret(name: &'static str, default: Option<&'static str>) -> Key {
+    Key { name, kind: Kind::String, default, secret: true }
+}
+
+/// Every known setting, in the order `config check` prints them
+const KEYS: &[Key] = &[
+    key("host", Kind::String, Some("localhost")),
+    key("port", Kind::Integer, Some("8080")),
+    // Defaults to the number of CPUs
+    key("workers", Kind::Integer, None),
+    key("tls.cert", Kind::String, None),
+    key("tls.key", Kind::String, None),
+    key("database.url", Kind::String, Some("sqlite://database")),
+    key("database.writers", Kind::Integer, Some("1")),
+    key("database.readers", Kind::Integer, Some("8")),
+    key("database.busy_timeout_ms", Kind::Integer, Some("5000")),
+    // Carries the password
+    secret("database.postgres_url", None),
+    key("credentials.username", Kind::String, Some("admin")),
+    secret("credentials.password", Some("admin")),
+    key("media_dir", Kind::String, Some("media")),
+    key("site_url", Kind::String, Some("https://jkearnsl.su")),
+    // Comma separated IP addresses
+    key("trusted_proxies", Kind::String, Some("")),
+    key("actor_username", Kind::String, Some("blog")),
+    secret("secret_key", None),
+    key("mail.dir", Kind::String, Some("mail")),
+    key("mail.from", Kind::String, Some("JKearnsl <noreply@jkearnsl.su>")),
+    key("mail.digest_interval_hours", Kind::Integer, Some("24")),
+    key("mail.smtp.host", Kind::String, None),
+    key("mail.smtp.port", Kind::Integer, Some("587")),
+    key("mail.smtp.username", Kind::String, Some("")),
+    secret("mail.smtp.password", Some("")),
+    key("contact.recipient", Kind::String, Some("admin@jkearnsl.su")),
+    key("contact.pow_difficulty", Kind::Integer, Some("0")),
+    key("counter_dedupe_minutes", Kind::Integer, Some("30")),
+    key("trash_retention_days", Kind::Integer, Some("30")),
+    key("backup.dir", Kind::String, Some("backups")),
+    key("backup.interval_hours", Kind::Integer, Some("24")),
+    key("backup.schedule", Kind::String, None),
+    key("backup.keep_daily", Kind::Integer, Some("7")),
+    key("backup.keep_weekly", Kind::Integer, Some("4")),
+    key("backup.keep_monthly", Kind::Integer, Some("12")),
+    key("backup.integrity_check", Kind::Boolean, Some("true")),
+    key("jobs.workers", Kind::Integer, Some("2")),
+    key("jobs.poll_interval_ms", Kind::Integer, Some("1000")),
+    key("jobs.keep_done_hours", Kind::Integer, Some("24")),
+];
+
+fn find_key(name: &str) -> Option<&'static Key> {
+    KEYS.iter().find(|key| key.name == name)
+}
+
+pub fn env_name(key: &str) -> String {
+    format!("{}{}", ENV_PREFIX, key.replace('.', "_").to_uppercase())
+}
+
+/// Where the effective value of a setting comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(String),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "file {}", path.display()),
+            Source::Env(name) => write!(f, "env {}", name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigError {
+    pub key: String,
+    pub source: Source,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (from {}): {}", self.key, self.source, self.message)
+    }
+}
+
+/// Values of all layers merged, not parsed yet
+pub struct RawConfig {
+    values: BTreeMap<&'static str, (String, Source)>,
+}
+
+impl RawConfig {
+    /// Reads the file, if any, and the process environment
+    pub fn load(file: Option<&Path>) -> Result<Self, Vec<ConfigError>> {
+        let file = match file {
+            Some(path) => {
+                let source = Source::File(path.to_path_buf());
+                let content = std::fs::read_to_string(path).map_err(|e| vec![ConfigError {
+                    key: "--config".to_string(),
+                    source: source.clone(),
+                    message: e.to_string()
+                }])?;
+                let table = content.parse::<toml::Table>().map_err(|e| vec![ConfigError {
+                    key: "--config".to_string(),
+                    source: source.clone(),
+                    message: e.message().to_string()
+                }])?;
+                Some((path.to_path_buf(), table))
+            }
+            None => None
+        };
+        Self::from_layers(file, std::env::vars().collect())
+    }
+
+    fn from_layers(
+        file: Option<(PathBuf, toml::Table)>,
+        env: Vec<(String, String)>
+    ) -> Result<Self, Vec<ConfigError>> {
+        let mut values = BTreeMap::new();
+        let mut errors = Vec::new();
+
+        for key in KEYS {
+            if let Some(default) = key.default {
+                values.insert(key.name, (default.to_string(), Source::Default));
+            }
+        }
+
+        if let Some((path, table)) = file {
+            let mut flat = Vec::new();
+            flatten("", toml::Value::Table(table), &mut flat);
+            for (name, value) in flat {
+                let source = Source::File(path.clone());
+                let value = match value {
+                    toml::Value::String(value) => value,
+                    toml::Value::Integer(value) => value.to_string(),
+                    toml::Value::Boolean(value) => value.to_string(),
+                    toml::Value::Float(value) => value.to_string(),
+                    toml::Value::Datetime(value) => value.to_string(),
+                    toml::Value::Array(_) | toml::Value::Table(_) => {
+                        errors.push(ConfigError { key: name, source, message: "should be a plain value".to_string() });
+                        continue;
+                    }
+                };
+                match find_key(&name) {
+                    Some(key) => {
+                        values.insert(key.name, (value, source));
+                    }
+                    None => errors.push(ConfigError { key: name, source, message: "unknown setting".to_string() })
+                }
+            }
+        }
+
+        for (name, value) in env {
+            if !name.starts_with(ENV_PREFIX) {
+                continue;
+            }
+            let source = Source::Env(name.clone());
+            match KEYS.iter().find(|key| env_name(key.name) == name) {
+                Some(key) => {
+                    values.insert(key.name, (value, source));
+                }
+                None => errors.push(ConfigError {
+                    key: name,
+                    source,
+                    message: "unknown setting".to_string()
+                })
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(Self { values }),
+            false => Err(errors)
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&(String, Source)> {
+        self.values.get(key)
+    }
+
+    /// Effective settings as TOML with their sources, secrets replaced by `***`
+    pub fn redacted(&self) -> String {
+        let mut out = String::new();
+        for key in KEYS {
+            match self.values.get(key.name) {
+                Some((value, source)) => {
+                    let value = match (key.secret, key.kind) {
+                        (true, _) if value.is_empty() => "\"\"".to_string(),
+                        (true, _) => "\"***\"".to_string(),
+                        (false, Kind::Integer | Kind::Boolean) => value.clone(),
+                        (false, Kind::String) => toml::Value::String(value.clone()).to_string(),
+                    };
+                    out.push_str(&format!("{} = {}  # {}\n", key.name, value, source));
+                }
+                None => out.push_str(&format!("# {} is not set\n", key.name))
+            }
+        }
+        out
+    }
+}
+
+fn flatten(prefix: &str, value: toml::Value, out: &mut Vec<(String, toml::Value)>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (name, value) in table {
+                let name = match prefix.is_empty() {
+                    true => name,
+                    false => format!("{}.{}", prefix, name)
+                };
+                flatten(&name, value, out);
+            }
+        }
+        value => out.push((prefix.to_string(), value))
+    }
+}
+
+/// Collects every invalid value instead of stopping at the first one
+struct Parser<'a> {
+    raw: &'a RawConfig,
+    errors: Vec<ConfigError>,
+}
+
+impl Parser<'_> {
+    fn error(&mut self, key: &str, message: String) {
+        let source = self.raw.get(key).map_or(Source::Default, |(_, source)| source.clone());
+        self.errors.push(ConfigError { key: key.to_string(), source, message });
+    }
+
+    fn optional(&self, key: &str) -> Option<String> {
+        self.raw.get(key).map(|(value, _)| value.clone())
+    }
+
+    /// Only for keys with a default
+    fn string(&self, key: &str) -> String {
+        self.optional(key).unwrap_or_default()
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, key: &str) -> Option<T>
+    where
+        T::Err: fmt::Display
+    {
+        let (value, _) = self.raw.get(key)?;
+        match value.trim().parse() {
+            Ok(value) => Some(value),
+            Err(e) => {
+                let shown = match find_key(key).is_some_and(|key| key.secret) {
+                    true => "***".to_string(),
+                    false => value.clone()
+                };
+                self.error(key, format!("{:?} is not a valid number: {}", shown, e));
+                None
+            }
+        }
+    }
+
+    fn boolean(&mut self, key: &str) -> bool {
+        let value = self.string(key);
+        match value.trim() {
+            "true" | "1" => true,
+            "false" | "0" => false,
+            _ => {
+                self.error(key, format!("{:?} is not true or false", value));
+                false
+            }
+        }
+    }
+
+    fn positive(&mut self, key: &str) -> u32 {
+        match self.number::<u32>(key) {
+            Some(0) => {
+                self.error(key, "should be at least 1".to_string());
+                1
+            }
+            Some(value) => value,
+            None => 1
+        }
+    }
+
+    fn mailbox(&mut self, key: &str) -> String {
+        let value = self.string(key);
+        if let Err(e) = value.parse::<lettre::message::Mailbox>() {
+            self.error(key, format!("{:?} is not a valid address: {}", value, e));
+        }
+        value
+    }
 }
 
 impl Config {
-    pub fn from_env() -> Self {
-        let host = std::env::var("HOST").unwrap_or_else(|_| "localhost".to_string());
-        let port = std::env::var("PORT").unwrap_or_else(|_| 8080.to_string()).parse().unwrap();
-        
-        let workers = match thread::available_parallelism() {
-            Ok(parallelism) => usize::from(parallelism),
-            Err(_) => 1,
-        };
-        
-        let tsl_cert = std::env::var("TLS_CERT").ok();
-        let tsl_key = std::env::var("TLS_KEY").ok();
-        let tls = match (tsl_cert, tsl_key) {
+    /// Loads all layers, `Err` lists every problem found
+    pub fn load(file: Option<&Path>) -> Result<(Self, RawConfig), Vec<ConfigError>> {
+        let raw = RawConfig::load(file)?;
+        let config = Self::from_raw(&raw)?;
+        Ok((config, raw))
+    }
+
+    fn from_raw(raw: &RawConfig) -> Result<Self, Vec<ConfigError>> {
+        let mut p = Parser { raw, errors: Vec::new() };
+
+        let port = p.number("port").unwrap_or_default();
+        let workers = match p.number::<usize>("workers") {
+            Some(0) => {
+                p.error("workers", "should be at least 1".to_string());
+                1
+            }
+            Some(workers) => workers,
+            None => match thread::available_parallelism() {
+                Ok(parallelism) => usize::from(parallelism),
+                Err(_) => 1,
+            }
+        };
+
+        let tls = match (p.optional("tls.cert"), p.optional("tls.key")) {
             (Some(cert), Some(key)) => Some(Tls { cert, key }),
-            _ => None
+            (Some(_), None) => {
+                p.error("tls.cert", format!("is set without {}", env_name("tls.key")));
+                None
+            }
+            (None, Some(_)) => {
+                p.error("tls.key", format!("is set without {}", env_name("tls.cert")));
+                None
+            }
+            (None, None) => None
+        };
+
+        let database = DatabaseConfig {
+            url: p.string("database.url"),
+            writers: p.positive("database.writers"),
+            readers: p.positive("database.readers"),
+            busy_timeout_ms: p.number("database.busy_timeout_ms").unwrap_or_default(),
+            postgres_url: p.optional("database.postgres_url")
         };
+        if !database.url.starts_with("sqlite:") {
+            p.error("database.url", format!("{:?} is not an sqlite: URL", database.url));
+        }
+        if let Some(url) = &database.postgres_url {
+            if !url.starts_with("postgres://") && !url.starts_with("postgresql://") {
+                p.error("database.postgres_url", "is not a postgres:// URL".to_string());
+            } else if !cfg!(feature = "postgres") {
+                p.error("database.postgres_url", "needs a build with the postgres feature".to_string());
+            }
+        }
+
+        let site_url = p.string("site_url");
+        if !is_http_url(&site_url) {
+            p.error("site_url", format!("{:?} is not an http(s) URL", site_url));
+        }
 
-        let credentials = CredentialsConfig {
-            username: std::env::var("USERNAME").unwrap_or_else(|_| "admin".to_string()),
-            password: std::env::var("PASSWORD").unwrap_or_else(|_| "admin".to_string())
+        let mut trusted_proxies = Vec::new();
+        for proxy in p.string("trusted_proxies").split(',').map(str::trim).filter(|proxy| !proxy.is_empty()) {
+            match proxy.parse::<IpAddr>() {
+                Ok(proxy) => trusted_proxies.push(proxy),
+                Err(_) => p.error("trusted_proxies", format!("{:?} is not an IP address", proxy))
+            }
+        }
+
+        let digest_interval_hours = p.number("mail.digest_interval_hours").unwrap_or(1);
+        if digest_interval_hours == 0 {
+            p.error("mail.digest_interval_hours", "should be at least 1".to_string());
+        }
+        let smtp = match p.optional("mail.smtp.host") {
+            Some(host) => Some(SmtpConfig {
+                host,
+                port: p.number("mail.smtp.port").unwrap_or_default(),
+                username: p.string("mail.smtp.username"),
+                password: p.string("mail.smtp.password")
+            }),
+            None => None
+        };
+        let mail = MailConfig {
+            smtp,
+            dir: p.string("mail.dir"),
+            from: p.mailbox("mail.from"),
+            digest_interval_hours
         };
-        
-        Self {
-            host,
+
+        let pow_difficulty = p.number("contact.pow_difficulty").unwrap_or_default();
+        // Beyond that a browser needs minutes to send a message
+        if pow_difficulty > 24 {
+            p.error("contact.pow_difficulty", format!("{} is more than 24 bits", pow_difficulty));
+        }
+        let contact = ContactConfig {
+            recipient: p.mailbox("contact.recipient"),
+            pow_difficulty
+        };
+
+        let backup_schedule = p.optional("backup.schedule");
+        if let Some(Err(e)) = backup_schedule.as_deref().map(Recurrence::parse) {
+            p.error("backup.schedule", e);
+        }
+
+        let config = Self {
+            host: p.string("host"),
             port,
             workers,
             tls,
-            credentials
+            database,
+            credentials: CredentialsConfig {
+                username: p.string("credentials.username"),
+                password: p.string("credentials.password")
+            },
+            media_dir: p.string("media_dir"),
+            site_url,
+            trusted_proxies,
+            actor_username: p.string("actor_username"),
+            secret_key: p.optional("secret_key"),
+            mail,
+            contact,
+            counter_dedupe_minutes: p.number("counter_dedupe_minutes").unwrap_or_default(),
+            trash_retention_days: p.number("trash_retention_days").unwrap_or_default(),
+            backup: BackupConfig {
+                dir: p.string("backup.dir"),
+                interval_hours: p.number("backup.interval_hours").unwrap_or_default(),
+                schedule: backup_schedule,
+                keep_daily: p.number("backup.keep_daily").unwrap_or_default(),
+                keep_weekly: p.number("backup.keep_weekly").unwrap_or_default(),
+                keep_monthly: p.number("backup.keep_monthly").unwrap_or_default(),
+                integrity_check: p.boolean("backup.integrity_check")
+            },
+            jobs: JobsConfig {
+                workers: p.positive("jobs.workers"),
+                poll_interval_ms: p.number("jobs.poll_interval_ms").unwrap_or_default(),
+                keep_done_hours: p.number("jobs.keep_done_hours").unwrap_or_default()
+            }
+        };
+
+        match p.errors.is_empty() {
+            true => Ok(config),
+            false => Err(p.errors)
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn file(content: &str) -> Option<(PathBuf, toml::Table)> {
+        Some((PathBuf::from("config.toml"), content.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_layers() {
+        let raw = RawConfig::from_layers(
+            file("port = 9000\nworkers = 2\n[mail.smtp]\nhost = \"smtp.example.com\"\npassword = \"hunter2\""),
+            env(&[("JKEARNSL_PORT", "9100"), ("JKEARNSL_TLS_CERT", "cert.pem"), ("JKEARNSL_TLS_KEY", "key.pem"), ("JKEARNSL_BACKUP_INTEGRITY_CHECK", "false"), ("PATH", "/bin")])
+        ).unwrap();
+        let config = Config::from_raw(&raw).unwrap();
+
+        assert_eq!(config.port, 9100);
+        assert_eq!(config.workers, 2);
+        assert_eq!(config.host, "localhost");
+        assert_eq!(config.tls.unwrap().cert, "cert.pem");
+        let smtp = config.mail.smtp.unwrap();
+        assert_eq!((smtp.host.as_str(), smtp.port), ("smtp.example.com", 587));
+        assert!(!config.backup.integrity_check);
+
+        let redacted = raw.redacted();
+        assert!(redacted.contains("port = 9100  # env JKEARNSL_PORT\n"));
+        assert!(redacted.contains("workers = 2  # file config.toml\n"));
+        assert!(redacted.contains("host = \"localhost\"  # default\n"));
+        assert!(redacted.contains("mail.smtp.password = \"***\""));
+        assert!(redacted.contains("# secret_key is not set\n"));
+        assert!(!redacted.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_all_errors_reported() {
+        let raw = RawConfig::from_layers(
+            file("port = \"http\"\nsite_url = \"jkearnsl.su\"\n[backup]\nschedule = \"0 25 * * *\""),
+            env(&[("JKEARNSL_WORKERS", "many"), ("JKEARNSL_TLS_KEY", "key.pem"), ("JKEARNSL_JOBS_WORKERS", "0")])
+        ).unwrap();
+        let errors = Config::from_raw(&raw).unwrap_err();
+
+        let keys = errors.iter().map(|e| e.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, vec!["port", "workers", "tls.key", "site_url", "backup.schedule", "jobs.workers"]);
+        assert_eq!(errors[0].source, Source::File(PathBuf::from("config.toml")));
+        assert_eq!(errors[1].source, Source::Env("JKEARNSL_WORKERS".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_settings() {
+        let errors = RawConfig::from_layers(
+            file("prot = 8080\n[mail]\nfrom = \"a@b.c\"\nsmpt = 1"),
+            env(&[("JKEARNSL_PROT", "8080")])
+        ).err().unwrap();
+
+        let keys = errors.iter().map(|e| e.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, vec!["mail.smpt", "prot", "JKEARNSL_PROT"]);
+    }
+}
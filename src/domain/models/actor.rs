@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const ACTOR_KEY_ID_SIZE: usize = 16;
+
+
+/// The blog as an ActivityPub actor. There is exactly one, its IRIs are
+/// derived from the public origin of the site
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalActor {
+    pub site_url: String,
+    pub username: String,
+}
+
+impl LocalActor {
+    pub fn new(site_url: &str, username: &str) -> Self {
+        Self {
+            site_url: site_url.trim_end_matches('/').to_string(),
+            username: username.to_string(),
+        }
+    }
+
+    pub fn id(&self) -> String {
+        format!("{}/actor", self.site_url)
+    }
+
+    pub fn inbox(&self) -> String {
+        format!("{}/inbox", self.id())
+    }
+
+    pub fn outbox(&self) -> String {
+        format!("{}/outbox", self.id())
+    }
+
+    pub fn followers(&self) -> String {
+        format!("{}/followers", self.id())
+    }
+
+    pub fn key_id(&self) -> String {
+        format!("{}#main-key", self.id())
+    }
+
+    /// `username@host` as typed in a Mastodon search box
+    pub fn acct(&self) -> String {
+        let host = self.site_url.split("://").nth(1).unwrap_or(&self.site_url);
+        format!("{}@{}", self.username, host)
+    }
+}
+
+/// Key pair the actor signs its requests with. The private key never leaves the server
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActorKey {
+    pub id: String,
+    /// SPKI PEM, published in the actor document
+    pub public_key_pem: String,
+    /// PKCS#8 PEM
+    #[serde(skip_serializing)]
+    pub private_key_pem: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Actor of another server as described by its own document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemoteActor {
+    pub id: String,
+    pub inbox: String,
+    pub shared_inbox: Option<String>,
+}
+
+impl RemoteActor {
+    /// Servers with many followers accept a single delivery for all of them
+    pub fn delivery_inbox(&self) -> &str {
+        self.shared_inbox.as_deref().unwrap_or(&self.inbox)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_actor() {
+        let actor = LocalActor::new("https://jkearnsl.su/", "blog");
+
+        assert_eq!(actor.id(), "https://jkearnsl.su/actor");
+        assert_eq!(actor.inbox(), "https://jkearnsl.su/actor/inbox");
+        assert_eq!(actor.key_id(), "https://jkearnsl.su/actor#main-key");
+        assert_eq!(actor.acct(), "blog@jkearnsl.su");
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::media::Media;
+use crate::domain::models::note::{Note, NoteId};
+use crate::domain::models::project::Project;
+use crate::domain::models::user::{User, UserId};
+
+pub const ARCHIVE_FORMAT: &str = "jkearnsl-archive";
+/// Bumped when the layout changes, older archives must stay restorable
+pub const ARCHIVE_VERSION: u32 = 1;
+pub const MANIFEST_PATH: &str = "manifest.json";
+
+
+/// File of an archive, the path is relative and always uses `/`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub content: Vec<u8>
+}
+
+/// Everything except note bodies, which are kept as Markdown files next to it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub notes: Vec<ArchivedNote>,
+    pub projects: Vec<Project>,
+    /// Only the records, the files stay in the media storage
+    pub media: Vec<Media>,
+    pub users: Vec<ArchivedUser>
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedNote {
+    pub id: NoteId,
+    pub path: String
+}
+
+/// Password hashes are not exported
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedUser {
+    pub id: UserId,
+    pub username: String
+}
+
+impl Manifest {
+    pub fn create(notes: &[Note], projects: Vec<Project>, media: Vec<Media>, users: &[User]) -> Self {
+        Self {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            notes: notes.iter().map(|note| ArchivedNote {
+                id: note.id.clone(),
+                path: note_path(note)
+            }).collect(),
+            projects,
+            media,
+            users: users.iter().map(|user| ArchivedUser {
+                id: user.id.clone(),
+                username: user.username.clone()
+            }).collect()
+        }
+    }
+
+    pub fn parse(content: &[u8]) -> Result<Self, String> {
+        let manifest: Self = serde_json::from_slice(content)
+            .map_err(|e| format!("Manifest is not valid: {}", e))?;
+        if manifest.format != ARCHIVE_FORMAT {
+            return Err(format!("Unknown archive format {:?}", manifest.format));
+        }
+        if manifest.version > ARCHIVE_VERSION {
+            return Err(format!(
+                "Archive version {} is newer than the supported {}",
+                manifest.version,
+                ARCHIVE_VERSION
+            ));
+        }
+        Ok(manifest)
+    }
+}
+
+/// Slugs may repeat, the id keeps the paths apart
+pub fn note_path(note: &Note) -> String {
+    format!("notes/{}-{}.md", note.slug, note.id)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let note = Note::create("Hello".to_string(), "Text".to_string()).unwrap();
+        let manifest = Manifest::create(&[note.clone()], vec![], vec![], &[]);
+        assert_eq!(manifest.notes[0].path, format!("notes/{}-{}.md", note.slug, note.id));
+
+        let mut same_slug = Note::create("Hello".to_string(), "Other".to_string()).unwrap();
+        same_slug.slug = note.slug.clone();
+        assert_ne!(note_path(&same_slug), note_path(&note));
+
+        let content = serde_json::to_vec(&manifest).unwrap();
+        assert_eq!(Manifest::parse(&content).unwrap(), manifest);
+
+        let mut newer = manifest.clone();
+        newer.version = ARCHIVE_VERSION + 1;
+        assert!(Manifest::parse(&serde_json::to_vec(&newer).unwrap()).is_err());
+        assert!(Manifest::parse(b"{}").is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::note::Note;
+use crate::domain::models::project::Project;
+use crate::domain::models::trash::TrashKind;
+use crate::domain::models::user::User;
+
+pub type AuditEntryId = String;
+
+pub const AUDIT_ENTRY_ID_SIZE: usize = 16;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    /// Moved to the trash
+    Delete,
+    Restore,
+    /// Deleted for good
+    Purge,
+    Login,
+    /// Wrong username or password, the actor is unknown
+    LoginFailed,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Purge => "purge",
+            AuditAction::Login => "login",
+            AuditAction::LoginFailed => "login_failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "create" => Some(AuditAction::Create),
+            "update" => Some(AuditAction::Update),
+            "delete" => Some(AuditAction::Delete),
+            "restore" => Some(AuditAction::Restore),
+            "purge" => Some(AuditAction::Purge),
+            "login" => Some(AuditAction::Login),
+            "login_failed" => Some(AuditAction::LoginFailed),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditTarget {
+    Note,
+    Project,
+    User,
+    Session,
+}
+
+impl AuditTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditTarget::Note => "note",
+            AuditTarget::Project => "project",
+            AuditTarget::User => "user",
+            AuditTarget::Session => "session",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "note" => Some(AuditTarget::Note),
+            "project" => Some(AuditTarget::Project),
+            "user" => Some(AuditTarget::User),
+            "session" => Some(AuditTarget::Session),
+            _ => None
+        }
+    }
+}
+
+impl From<TrashKind> for AuditTarget {
+    fn from(kind: TrashKind) -> Self {
+        match kind {
+            TrashKind::Note => AuditTarget::Note,
+            TrashKind::Project => AuditTarget::Project,
+        }
+    }
+}
+
+/// Record of an administrative change: who did what to which entity.
+/// Entries are only ever appended
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: AuditEntryId,
+    /// Username of the actor, `None` for anonymous requests such as a failed login
+    pub actor: Option<String>,
+    pub action: AuditAction,
+    pub target: AuditTarget,
+    pub target_id: String,
+    /// Summary of the entity before the change, see [`note_summary`] and friends
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    /// `None` outside of HTTP, e.g. in the admin CLI
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>
+}
+
+impl AuditEntry {
+    pub fn create(
+        actor: Option<String>,
+        action: AuditAction,
+        target: AuditTarget,
+        target_id: String,
+        before: Option<Value>,
+        after: Option<Value>,
+        ip: Option<String>
+    ) -> Self {
+        Self {
+            id: generate_id(AUDIT_ENTRY_ID_SIZE),
+            actor,
+            action,
+            target,
+            target_id,
+            before,
+            after,
+            ip,
+            created_at: Utc::now()
+        }
+    }
+}
+
+/// The body is left out, its length is enough to tell an edit from a wipe
+pub fn note_summary(note: &Note) -> Value {
+    json!({
+        "title": note.title,
+        "slug": note.slug,
+        "body_length": note.body.len(),
+        "version": note.version
+    })
+}
+
+pub fn project_summary(project: &Project) -> Value {
+    json!({
+        "title": project.title,
+        "url": project.url,
+        "version": project.version
+    })
+}
+
+/// Never includes the password hash
+pub fn user_summary(user: &User) -> Value {
+    json!({
+        "username": user.username
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_round_trip() {
+        for action in [
+            AuditAction::Create,
+            AuditAction::Update,
+            AuditAction::Delete,
+            AuditAction::Restore,
+            AuditAction::Purge,
+            AuditAction::Login,
+            AuditAction::LoginFailed
+        ] {
+            assert_eq!(AuditAction::parse(action.as_str()), Some(action));
+        }
+        assert_eq!(AuditTarget::parse("note"), Some(AuditTarget::Note));
+        assert_eq!(AuditTarget::parse("comment"), None);
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const BACKUP_PREFIX: &str = "backup-";
+const BACKUP_EXTENSION: &str = ".sqlite.gz";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+
+/// Compressed snapshot of the database
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Backup {
+    /// File name, it also carries the creation time
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Compressed size in bytes
+    pub size: u64
+}
+
+/// How many snapshots are kept: the newest one of each of the last
+/// `daily` days, `weekly` weeks and `monthly` months
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BackupRetention {
+    pub daily: u32,
+    pub weekly: u32,
+    pub monthly: u32
+}
+
+pub fn backup_name(created_at: DateTime<Utc>) -> String {
+    format!("{}{}{}", BACKUP_PREFIX, created_at.format(BACKUP_TIME_FORMAT), BACKUP_EXTENSION)
+}
+
+/// Inverse of [`backup_name`]. Anything else, including paths, is rejected
+pub fn backup_created_at(name: &str) -> Option<DateTime<Utc>> {
+    let time = name.strip_prefix(BACKUP_PREFIX)?.strip_suffix(BACKUP_EXTENSION)?;
+    NaiveDateTime::parse_from_str(time, BACKUP_TIME_FORMAT).ok().map(|time| time.and_utc())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use super::*;
+
+    #[test]
+    fn test_backup_name() {
+        let created_at = Utc.with_ymd_and_hms(2024, 3, 1, 4, 5, 6).unwrap();
+        let name = backup_name(created_at);
+
+        assert_eq!(name, "backup-20240301-040506.sqlite.gz");
+        assert_eq!(backup_created_at(&name), Some(created_at));
+        assert_eq!(backup_created_at("../backup-20240301-040506.sqlite.gz"), None);
+        assert_eq!(backup_created_at("backup-20240301-040506.sqlite.gz/../../database"), None);
+        assert_eq!(backup_created_at("database"), None);
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::note::NoteId;
+
+pub type CommentId = String;
+
+pub const COMMENT_ID_SIZE: usize = 16;
+pub const COMMENT_AUTHOR_MAX: usize = 64;
+pub const COMMENT_BODY_MAX: usize = 4096;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentStatus {
+    /// Waiting in the moderation queue, not visible to readers
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl CommentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentStatus::Pending => "pending",
+            CommentStatus::Approved => "approved",
+            CommentStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(CommentStatus::Pending),
+            "approved" => Some(CommentStatus::Approved),
+            "rejected" => Some(CommentStatus::Rejected),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: CommentId,
+    pub note_id: NoteId,
+    /// Set for replies to another comment of the same note
+    pub parent_id: Option<CommentId>,
+    pub author: String,
+    /// Markdown-lite source, see [`render_markdown_lite`](crate::domain::services::markdown::render_markdown_lite)
+    pub body: String,
+    pub status: CommentStatus,
+    pub created_at: DateTime<Utc>
+}
+
+impl Comment {
+    pub fn create(
+        note_id: NoteId,
+        parent_id: Option<CommentId>,
+        author: String,
+        body: String
+    ) -> anyhow::Result<Self, HashMap<String, String>> {
+        let author = author.trim().to_string();
+        let body = body.trim().to_string();
+        let mut errors = HashMap::new();
+
+        if author.is_empty() {
+            errors.insert("author".to_string(), "is required".to_string());
+        } else if author.chars().count() > COMMENT_AUTHOR_MAX {
+            errors.insert(
+                "author".to_string(),
+                format!("is too long: {} > {}", author.chars().count(), COMMENT_AUTHOR_MAX)
+            );
+        }
+
+        if body.is_empty() {
+            errors.insert("body".to_string(), "is required".to_string());
+        } else if body.len() > COMMENT_BODY_MAX {
+            errors.insert(
+                "body".to_string(),
+                format!("is too long: {} > {}", body.len(), COMMENT_BODY_MAX)
+            );
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            id: generate_id(COMMENT_ID_SIZE),
+            note_id,
+            parent_id,
+            author,
+            body,
+            status: CommentStatus::Pending,
+            created_at: Utc::now()
+        })
+    }
+
+    pub fn approve(&mut self) {
+        self.status = CommentStatus::Approved;
+    }
+
+    pub fn reject(&mut self) {
+        self.status = CommentStatus::Rejected;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.status == CommentStatus::Approved
+    }
+}
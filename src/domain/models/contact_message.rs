@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+use crate::domain::services::validator::validate_email;
+
+pub type ContactMessageId = String;
+
+pub const CONTACT_MESSAGE_ID_SIZE: usize = 16;
+pub const CONTACT_NAME_MAX: usize = 100;
+pub const CONTACT_EMAIL_MAX: usize = 254;
+pub const CONTACT_SUBJECT_MAX: usize = 200;
+pub const CONTACT_BODY_MAX: usize = 10000;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactMessageStatus {
+    /// Stored, but the mail to the owner has not gone out yet
+    Pending,
+    Delivered,
+    /// Caught by a spam check, kept only for review
+    Spam,
+}
+
+impl ContactMessageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactMessageStatus::Pending => "pending",
+            ContactMessageStatus::Delivered => "delivered",
+            ContactMessageStatus::Spam => "spam",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(ContactMessageStatus::Pending),
+            "delivered" => Some(ContactMessageStatus::Delivered),
+            "spam" => Some(ContactMessageStatus::Spam),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactMessage {
+    pub id: ContactMessageId,
+    pub name: String,
+    /// Reply address of the sender
+    pub email: String,
+    pub subject: String,
+    pub body: String,
+    pub ip: String,
+    pub status: ContactMessageStatus,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>
+}
+
+impl ContactMessage {
+    pub fn create(
+        name: String,
+        email: String,
+        subject: String,
+        body: String,
+        ip: String
+    ) -> anyhow::Result<Self, HashMap<String, String>> {
+        let name = name.trim().to_string();
+        let email = email.trim().to_string();
+        let subject = subject.trim().to_string();
+        let body = body.trim().to_string();
+        let mut errors = HashMap::new();
+
+        // Name and subject end up in mail headers
+        if name.is_empty() {
+            errors.insert("name".to_string(), "is required".to_string());
+        } else if name.chars().count() > CONTACT_NAME_MAX {
+            errors.insert(
+                "name".to_string(),
+                format!("is too long: {} > {}", name.chars().count(), CONTACT_NAME_MAX)
+            );
+        } else if name.contains(char::is_control) {
+            errors.insert("name".to_string(), "contains control characters".to_string());
+        }
+
+        if let Err(e) = validate_email(&email, CONTACT_EMAIL_MAX) {
+            errors.insert("email".to_string(), e);
+        }
+
+        if subject.chars().count() > CONTACT_SUBJECT_MAX {
+            errors.insert(
+                "subject".to_string(),
+                format!("is too long: {} > {}", subject.chars().count(), CONTACT_SUBJECT_MAX)
+            );
+        } else if subject.contains(char::is_control) {
+            errors.insert("subject".to_string(), "contains control characters".to_string());
+        }
+
+        if body.is_empty() {
+            errors.insert("body".to_string(), "is required".to_string());
+        } else if body.len() > CONTACT_BODY_MAX {
+            errors.insert(
+                "body".to_string(),
+                format!("is too long: {} > {}", body.len(), CONTACT_BODY_MAX)
+            );
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            id: generate_id(CONTACT_MESSAGE_ID_SIZE),
+            name,
+            email,
+            subject,
+            body,
+            ip,
+            status: ContactMessageStatus::Pending,
+            created_at: Utc::now(),
+            delivered_at: None
+        })
+    }
+
+    pub fn delivered(&mut self) {
+        self.status = ContactMessageStatus::Delivered;
+        self.delivered_at = Some(Utc::now());
+    }
+
+    pub fn spam(&mut self) {
+        self.status = ContactMessageStatus::Spam;
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const COUNTER_NAME_MAX: usize = 64;
+/// Digits shown when the count is shorter
+pub const COUNTER_LENGTH_DEFAULT: usize = 7;
+pub const COUNTER_LENGTH_MAX: usize = 16;
+pub const COUNTER_THEME_DEFAULT: &str = "default";
+
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Counter {
+    pub name: String,
+    pub count: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>
+}
+
+/// Names end up in URLs, `@user` style handles are allowed
+pub fn validate_counter_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("is required".to_string());
+    }
+    if name.len() > COUNTER_NAME_MAX {
+        return Err(format!("is too long: {} > {}", name.len(), COUNTER_NAME_MAX));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '@' | '-' | '_' | '.')) {
+        return Err("may contain only latin letters, digits and `@-_.`".to_string());
+    }
+    Ok(())
+}
+
+/// Zero padded to `length` digits, longer counts are never cut
+pub fn counter_digits(count: u64, length: usize) -> String {
+    format!("{:0>width$}", count, width = length)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_name() {
+        assert!(validate_counter_name("@JKearnsl").is_ok());
+        assert!(validate_counter_name("blog.home_page-1").is_ok());
+        assert!(validate_counter_name("").is_err());
+        assert!(validate_counter_name("a/b").is_err());
+        assert!(validate_counter_name(&"a".repeat(COUNTER_NAME_MAX + 1)).is_err());
+    }
+
+    #[test]
+    fn test_counter_digits() {
+        assert_eq!(counter_digits(42, 7), "0000042");
+        assert_eq!(counter_digits(12345678, 7), "12345678");
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+
+pub type DeliveryId = String;
+
+pub const DELIVERY_ID_SIZE: usize = 16;
+/// With the backoff below the last attempt is made about a day after the first one
+pub const DELIVERY_ATTEMPTS_MAX: u32 = 9;
+const DELIVERY_BACKOFF_BASE_MINUTES: i64 = 5;
+
+
+/// Activity waiting to be posted to a remote inbox
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Delivery {
+    pub id: DeliveryId,
+    pub inbox: String,
+    /// Serialized activity, signed and posted as is
+    pub activity: String,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>
+}
+
+impl Delivery {
+    pub fn create(inbox: String, activity: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: generate_id(DELIVERY_ID_SIZE),
+            inbox,
+            activity,
+            attempts: 0,
+            next_attempt_at: now,
+            created_at: now
+        }
+    }
+
+    /// Schedules the next attempt with exponential backoff.
+    /// Returns `false` when the delivery should be given up
+    pub fn fail(&mut self, now: DateTime<Utc>) -> bool {
+        self.attempts += 1;
+        if self.attempts >= DELIVERY_ATTEMPTS_MAX {
+            return false;
+        }
+        let delay = DELIVERY_BACKOFF_BASE_MINUTES << (self.attempts - 1);
+        self.next_attempt_at = now + Duration::minutes(delay);
+        true
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let now = Utc::now();
+        let mut delivery = Delivery::create("https://example.com/inbox".to_string(), "{}".to_string());
+
+        assert!(delivery.fail(now));
+        assert_eq!(delivery.next_attempt_at, now + Duration::minutes(5));
+        assert!(delivery.fail(now));
+        assert_eq!(delivery.next_attempt_at, now + Duration::minutes(10));
+
+        while delivery.fail(now) {}
+        assert_eq!(delivery.attempts, DELIVERY_ATTEMPTS_MAX);
+    }
+}
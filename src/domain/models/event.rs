@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::models::note::{Note, NoteId};
+use crate::domain::models::project::{Project, ProjectId};
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    #[serde(rename = "note.published")]
+    NotePublished,
+    #[serde(rename = "note.updated")]
+    NoteUpdated,
+    #[serde(rename = "note.deleted")]
+    NoteDeleted,
+    #[serde(rename = "note.restored")]
+    NoteRestored,
+    #[serde(rename = "project.created")]
+    ProjectCreated,
+    #[serde(rename = "project.updated")]
+    ProjectUpdated,
+    #[serde(rename = "project.deleted")]
+    ProjectDeleted,
+    #[serde(rename = "project.restored")]
+    ProjectRestored,
+    #[serde(rename = "user.created")]
+    UserCreated,
+    #[serde(rename = "user.logged_in")]
+    UserLoggedIn,
+}
+
+impl EventKind {
+    pub const ALL: [EventKind; 10] = [
+        EventKind::NotePublished,
+        EventKind::NoteUpdated,
+        EventKind::NoteDeleted,
+        EventKind::NoteRestored,
+        EventKind::ProjectCreated,
+        EventKind::ProjectUpdated,
+        EventKind::ProjectDeleted,
+        EventKind::ProjectRestored,
+        EventKind::UserCreated,
+        EventKind::UserLoggedIn,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::NotePublished => "note.published",
+            EventKind::NoteUpdated => "note.updated",
+            EventKind::NoteDeleted => "note.deleted",
+            EventKind::NoteRestored => "note.restored",
+            EventKind::ProjectCreated => "project.created",
+            EventKind::ProjectUpdated => "project.updated",
+            EventKind::ProjectDeleted => "project.deleted",
+            EventKind::ProjectRestored => "project.restored",
+            EventKind::UserCreated => "user.created",
+            EventKind::UserLoggedIn => "user.logged_in",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        EventKind::ALL.into_iter().find(|kind| kind.as_str() == value)
+    }
+}
+
+/// Something that already happened, emitted by interactors after the change is stored
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+pub enum DomainEvent {
+    #[serde(rename = "note.published")]
+    NotePublished { id: NoteId, slug: String, title: String },
+    #[serde(rename = "note.updated")]
+    NoteUpdated { id: NoteId, slug: String, title: String, version: i64 },
+    /// Moved to the trash
+    #[serde(rename = "note.deleted")]
+    NoteDeleted { id: NoteId },
+    #[serde(rename = "note.restored")]
+    NoteRestored { id: NoteId },
+    #[serde(rename = "project.created")]
+    ProjectCreated { id: ProjectId, title: String },
+    #[serde(rename = "project.updated")]
+    ProjectUpdated { id: ProjectId, title: String, version: i64 },
+    #[serde(rename = "project.deleted")]
+    ProjectDeleted { id: ProjectId },
+    #[serde(rename = "project.restored")]
+    ProjectRestored { id: ProjectId },
+    #[serde(rename = "user.created")]
+    UserCreated { username: String },
+    #[serde(rename = "user.logged_in")]
+    UserLoggedIn { username: String },
+}
+
+impl DomainEvent {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            DomainEvent::NotePublished { .. } => EventKind::NotePublished,
+            DomainEvent::NoteUpdated { .. } => EventKind::NoteUpdated,
+            DomainEvent::NoteDeleted { .. } => EventKind::NoteDeleted,
+            DomainEvent::NoteRestored { .. } => EventKind::NoteRestored,
+            DomainEvent::ProjectCreated { .. } => EventKind::ProjectCreated,
+            DomainEvent::ProjectUpdated { .. } => EventKind::ProjectUpdated,
+            DomainEvent::ProjectDeleted { .. } => EventKind::ProjectDeleted,
+            DomainEvent::ProjectRestored { .. } => EventKind::ProjectRestored,
+            DomainEvent::UserCreated { .. } => EventKind::UserCreated,
+            DomainEvent::UserLoggedIn { .. } => EventKind::UserLoggedIn,
+        }
+    }
+
+    pub fn note_published(note: &Note) -> Self {
+        DomainEvent::NotePublished {
+            id: note.id.clone(),
+            slug: note.slug.clone(),
+            title: note.title.clone()
+        }
+    }
+
+    pub fn note_updated(note: &Note) -> Self {
+        DomainEvent::NoteUpdated {
+            id: note.id.clone(),
+            slug: note.slug.clone(),
+            title: note.title.clone(),
+            version: note.version
+        }
+    }
+
+    pub fn project_created(project: &Project) -> Self {
+        DomainEvent::ProjectCreated {
+            id: project.id.clone(),
+            title: project.title.clone()
+        }
+    }
+
+    pub fn project_updated(project: &Project) -> Self {
+        DomainEvent::ProjectUpdated {
+            id: project.id.clone(),
+            title: project.title.clone(),
+            version: project.version
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_kind_round_trip() {
+        for kind in EventKind::ALL {
+            assert_eq!(EventKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(EventKind::parse("note.eaten"), None);
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = DomainEvent::NoteDeleted { id: "abc".to_string() };
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["type"], event.kind().as_str());
+        assert_eq!(json["data"]["id"], "abc");
+        assert_eq!(serde_json::from_value::<DomainEvent>(json).unwrap(), event);
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::actor::RemoteActor;
+use crate::domain::services::validator::is_http_url;
+
+pub type FollowerId = String;
+
+pub const FOLLOWER_ID_SIZE: usize = 16;
+pub const FOLLOWER_URL_MAX: usize = 2048;
+
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Follower {
+    pub id: FollowerId,
+    /// Actor IRI of the follower
+    pub actor: String,
+    /// Where our activities are delivered, the shared inbox when there is one
+    pub inbox: String,
+    pub created_at: DateTime<Utc>
+}
+
+impl Follower {
+    pub fn create(remote: &RemoteActor) -> anyhow::Result<Self, HashMap<String, String>> {
+        let mut errors = HashMap::new();
+
+        for (field, url) in [("actor", remote.id.as_str()), ("inbox", remote.delivery_inbox())] {
+            if url.len() > FOLLOWER_URL_MAX {
+                errors.insert(
+                    field.to_string(),
+                    format!("is too long: {} > {}", url.len(), FOLLOWER_URL_MAX)
+                );
+            } else if !is_http_url(url) {
+                errors.insert(field.to_string(), "should be an http(s) URL".to_string());
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            id: generate_id(FOLLOWER_ID_SIZE),
+            actor: remote.id.clone(),
+            inbox: remote.delivery_inbox().to_string(),
+            created_at: Utc::now()
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
 
 const HASH_LENGTH: usize = 32;
 
@@ -11,6 +12,21 @@ impl Hash {
     pub fn to_string(&self) -> String {
         hex::encode(&self.0)
     }
+
+    /// SHA-256 digest of the content, used as a content address
+    pub fn sha256(data: &[u8]) -> Self {
+        Hash(Sha256::digest(data).into())
+    }
+
+    pub fn from_hex(value: &str) -> Result<Self, String> {
+        let bytes = hex::decode(value).map_err(|e| e.to_string())?;
+        if bytes.len() != HASH_LENGTH {
+            return Err(format!("expected {} bytes, got {}", HASH_LENGTH, bytes.len()));
+        }
+        let mut hash = [0; HASH_LENGTH];
+        hash.copy_from_slice(&bytes);
+        Ok(Hash(hash))
+    }
 }
 
 impl Serialize for Hash {
@@ -32,16 +48,6 @@ impl Deserialize for Hash {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
-        if bytes.len() != HASH_LENGTH {
-            return Err(serde::de::Error::custom(format!(
-                "expected {} bytes, got {}",
-                HASH_LENGTH,
-                bytes.len()
-            )));
-        }
-        let mut hash = [0; HASH_LENGTH];
-        hash.copy_from_slice(&bytes);
-        Ok(Hash(hash))
+        Hash::from_hex(&s).map_err(serde::de::Error::custom)
     }
 }
\ No newline at end of file
@@ -0,0 +1,151 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::domain::id_generator::generate_id;
+
+pub type JobId = String;
+
+pub const JOB_ID_SIZE: usize = 16;
+pub const JOB_KIND_MAX: usize = 64;
+/// With the backoff below the last attempt is made about 7.5 minutes after the first one
+pub const JOB_ATTEMPTS_MAX: u32 = 5;
+const JOB_BACKOFF_BASE_SECONDS: i64 = 30;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    /// Failed every attempt, stays until it is retried by hand
+    Dead,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Done => "done",
+            JobStatus::Dead => "dead",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "queued" => Some(JobStatus::Queued),
+            "running" => Some(JobStatus::Running),
+            "done" => Some(JobStatus::Done),
+            "dead" => Some(JobStatus::Dead),
+            _ => None
+        }
+    }
+}
+
+/// A unit of background work, `kind` picks the handler that runs it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: JobId,
+    pub kind: String,
+    /// Handed to the handler as is
+    pub payload: Value,
+    pub status: JobStatus,
+    pub attempts: u32,
+    pub max_attempts: u32,
+    /// Not run before that
+    pub run_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>
+}
+
+impl Job {
+    pub fn create(kind: &str, payload: Value, run_at: DateTime<Utc>) -> Self {
+        Self {
+            id: generate_id(JOB_ID_SIZE),
+            kind: kind.to_string(),
+            payload,
+            status: JobStatus::Queued,
+            attempts: 0,
+            max_attempts: JOB_ATTEMPTS_MAX,
+            run_at,
+            last_error: None,
+            created_at: Utc::now(),
+            updated_at: None
+        }
+    }
+
+    pub fn complete(&mut self, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.status = JobStatus::Done;
+        self.last_error = None;
+        self.updated_at = Some(now);
+    }
+
+    /// Requeues the job with exponential backoff.
+    /// Returns `false` when it is dead-lettered instead
+    pub fn fail(&mut self, now: DateTime<Utc>, error: String) -> bool {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.updated_at = Some(now);
+        if self.attempts >= self.max_attempts {
+            self.status = JobStatus::Dead;
+            return false;
+        }
+        self.status = JobStatus::Queued;
+        self.run_at = now + Duration::seconds(JOB_BACKOFF_BASE_SECONDS << (self.attempts - 1));
+        true
+    }
+
+    /// Gives a dead job a fresh set of attempts, the last error is kept for reference.
+    /// Returns `false` when the job is not dead
+    pub fn retry(&mut self, now: DateTime<Utc>) -> bool {
+        if self.status != JobStatus::Dead {
+            return false;
+        }
+        self.status = JobStatus::Queued;
+        self.attempts = 0;
+        self.run_at = now;
+        self.updated_at = Some(now);
+        true
+    }
+}
+
+/// Where a recurring job stands, kept so that restarts do not reset the schedule
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobSchedule {
+    pub kind: String,
+    /// The recurrence the next run was computed with, a changed one starts over
+    pub expression: String,
+    pub next_run_at: DateTime<Utc>
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    #[test]
+    fn test_job_lifecycle() {
+        let now = Utc::now();
+        let mut job = Job::create("backup", json!({}), now);
+
+        assert!(job.fail(now, "disk full".to_string()));
+        assert_eq!((job.status, job.run_at), (JobStatus::Queued, now + Duration::seconds(30)));
+        assert!(job.fail(now, "disk full".to_string()));
+        assert_eq!(job.run_at, now + Duration::seconds(60));
+        assert!(!job.retry(now));
+
+        while job.fail(now, "disk full".to_string()) {}
+        assert_eq!((job.status, job.attempts), (JobStatus::Dead, JOB_ATTEMPTS_MAX));
+
+        assert!(job.retry(now));
+        assert_eq!((job.status, job.attempts, job.run_at), (JobStatus::Queued, 0, now));
+        assert_eq!(job.last_error.as_deref(), Some("disk full"));
+
+        job.complete(now);
+        assert_eq!((job.status, job.last_error), (JobStatus::Done, None));
+    }
+}
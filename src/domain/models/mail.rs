@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+
+/// Outgoing message, the sender is set by the mailer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+    /// Extra headers such as `List-Unsubscribe`
+    pub headers: Vec<(String, String)>,
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::models::hash::Hash;
+
+pub const MEDIA_NAME_MAX: usize = 256;
+pub const MEDIA_SIZE_MAX: usize = 10 * 1024 * 1024;
+
+/// Widths of the resized copies made for uploaded images.
+/// Only widths smaller than the original are generated
+pub const MEDIA_VARIANT_WIDTHS: [u32; 3] = [480, 960, 1920];
+pub const MEDIA_VARIANT_MIME: &str = "image/webp";
+
+/// Types that are allowed to be uploaded.
+///
+/// SVG is deliberately absent: it may carry scripts and is served from our origin.
+/// AVIF is absent until its metadata can be stripped
+pub const MEDIA_ALLOWED_MIME: [&str; 5] = [
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+];
+
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Media {
+    /// SHA-256 of the content. The same file uploaded twice is stored once
+    pub hash: Hash,
+    /// Sniffed from the content, the client-provided type is not trusted
+    pub mime: String,
+    pub size: u64,
+    /// Name of the file as it was uploaded, kept for the editor only
+    pub name: String,
+    pub uploaded_by: String,
+    /// Pixel size, known for raster images only
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Set for derived copies (resized, converted) of another upload
+    pub source: Option<Hash>,
+    pub created_at: DateTime<Utc>
+}
+
+impl Media {
+    pub fn create(
+        hash: Hash,
+        mime: Option<&str>,
+        size: usize,
+        name: String,
+        uploaded_by: String,
+        dimensions: Option<(u32, u32)>
+    ) -> anyhow::Result<Self, HashMap<String, String>> {
+        if size == 0 {
+            return Err(HashMap::from([(
+                "file".to_string(),
+                "is empty".to_string()
+            )]));
+        }
+
+        if size > MEDIA_SIZE_MAX {
+            return Err(HashMap::from([(
+                "file".to_string(),
+                format!("is too large: {} > {}", size, MEDIA_SIZE_MAX)
+            )]));
+        }
+
+        let mime = match mime {
+            Some(mime) if MEDIA_ALLOWED_MIME.contains(&mime) => mime.to_string(),
+            _ => return Err(HashMap::from([(
+                "file".to_string(),
+                "has unsupported type".to_string()
+            )]))
+        };
+
+        if name.len() > MEDIA_NAME_MAX {
+            return Err(HashMap::from([(
+                "name".to_string(),
+                format!("is too long: {} > {}", name.len(), MEDIA_NAME_MAX)
+            )]));
+        }
+
+        Ok(Self {
+            hash,
+            mime,
+            size: size as u64,
+            name,
+            uploaded_by,
+            width: dimensions.map(|(width, _)| width),
+            height: dimensions.map(|(_, height)| height),
+            source: None,
+            created_at: Utc::now()
+        })
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.mime.starts_with("image/")
+    }
+
+    /// Derived copy of this media, e.g. a resized WebP
+    pub fn variant(&self, hash: Hash, mime: &str, size: usize, width: u32, height: u32) -> Self {
+        Self {
+            hash,
+            mime: mime.to_string(),
+            size: size as u64,
+            name: format!("{}@{}w", self.name, width),
+            uploaded_by: self.uploaded_by.clone(),
+            width: Some(width),
+            height: Some(height),
+            source: Some(self.hash.clone()),
+            created_at: Utc::now()
+        }
+    }
+
+    pub fn url(&self) -> String {
+        format!("/media/{}", self.hash.to_string())
+    }
+}
+
+/// Everything needed to render an `<img>` without layout shift
+#[derive(Clone, Debug)]
+pub struct ResponsiveImage {
+    pub original: Media,
+    pub variants: Vec<Media>
+}
@@ -1,4 +1,22 @@
 pub mod note;
 pub mod project;
 pub mod user;
-pub mod hash;
\ No newline at end of file
+pub mod hash;
+pub mod media;
+pub mod comment;
+pub mod webmention;
+pub mod actor;
+pub mod follower;
+pub mod delivery;
+pub mod mail;
+pub mod subscriber;
+pub mod contact_message;
+pub mod page_view;
+pub mod counter;
+pub mod backup;
+pub mod trash;
+pub mod audit;
+pub mod event;
+pub mod webhook;
+pub mod job;
+pub mod archive;
@@ -14,7 +14,7 @@ pub const NOTE_DESCRIPTION_MAX: usize = 256;
 pub const NOTE_BODY_MAX: usize = 32768;
 
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Note {
     /// Identifiers are used for internal calls, they are not cached
     /// Calling by [`NoteId`] creates or updates the cache
@@ -27,43 +27,128 @@ pub struct Note {
     pub description: String,
     pub body: String,
     pub created_at: DateTime<Utc>,
-    pub updated_at: Option<DateTime<Utc>>
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Bumped on every update. An update made against an older
+    /// version is refused instead of overwriting someone else's edit
+    pub version: i64
 }
 
 impl Note {
     pub fn create(title: String, body: String) -> anyhow::Result<Self, HashMap<String, String>> {
-        if title.len() > NOTE_TITLE_MAX {
-            return Err(HashMap::from([(
-                "title".to_string(), 
-                format!("is too long: {} > {}", title.len(), NOTE_TITLE_MAX)
-            )]));
-        }
-        
-        if body.len() > NOTE_BODY_MAX {
-            return Err(HashMap::from([(
-                "body".to_string(), 
-                format!("is too long: {} > {}", body.len(), NOTE_BODY_MAX)
-            )]));
-        }
-        
+        validate(&title, &body)?;
+
         Ok(Self {
             id: generate_id(NOTE_ID_SIZE),
-            title: title.clone(),
-            slug: slugify(&title[..50]),
+            slug: slugify(title.chars().take(50).collect::<String>()),
+            title,
             description: body.chars().take(NOTE_DESCRIPTION_MAX).collect(),
             body,
             created_at: Utc::now(),
-            updated_at: None
+            updated_at: None,
+            version: 1
         })
     }
 
-    pub fn update(&mut self, title: String, body: String) {
-        self.title = title.clone();
+    pub fn update(&mut self, title: String, body: String) -> anyhow::Result<(), HashMap<String, String>> {
+        validate(&title, &body)?;
+
         /// Even after updating the slug, the old slug should work correctly! 
         /// It is required to save in a separate index table!
-        self.slug = slugify(&title[..50]);
+        self.slug = slugify(title.chars().take(50).collect::<String>());
+        self.title = title;
+        self.description = body.chars().take(NOTE_DESCRIPTION_MAX).collect();
         self.body = body;
         self.updated_at = Some(Utc::now());
+        self.version += 1;
+        Ok(())
+    }
+
+    /// Note brought over from elsewhere, keeping its own identifier, slug and dates.
+    /// Without a description the start of the body is used, as in [`Note::create`]
+    pub fn import(
+        id: Option<NoteId>,
+        slug: String,
+        title: String,
+        description: Option<String>,
+        body: String,
+        created_at: DateTime<Utc>,
+        updated_at: Option<DateTime<Utc>>
+    ) -> anyhow::Result<Self, HashMap<String, String>> {
+        validate(&title, &body)?;
+
+        if let Some(id) = &id {
+            if id.len() != NOTE_ID_SIZE || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(HashMap::from([(
+                    "id".to_string(),
+                    format!("should be {} letters and digits", NOTE_ID_SIZE)
+                )]));
+            }
+        }
+        if slug.is_empty() || slug.len() > NOTE_TITLE_MAX || slugify(&slug) != slug {
+            return Err(HashMap::from([(
+                "slug".to_string(),
+                format!("{:?} is not a valid slug", slug)
+            )]));
+        }
+        let description = description.unwrap_or_else(|| body.chars().take(NOTE_DESCRIPTION_MAX).collect());
+        // Counted in characters, the default above is cut that way too
+        let description_length = description.chars().count();
+        if description_length > NOTE_DESCRIPTION_MAX {
+            return Err(HashMap::from([(
+                "description".to_string(),
+                format!("is too long: {} > {}", description_length, NOTE_DESCRIPTION_MAX)
+            )]));
+        }
+
+        Ok(Self {
+            id: id.unwrap_or_else(|| generate_id(NOTE_ID_SIZE)),
+            slug,
+            title,
+            description,
+            body,
+            created_at,
+            updated_at,
+            version: 1
+        })
+    }
+
+    /// Changes on every update, suitable for cache keys
+    pub fn revision(&self) -> i64 {
+        self.updated_at.unwrap_or(self.created_at).timestamp_millis()
+    }
+}
+
+fn validate(title: &str, body: &str) -> anyhow::Result<(), HashMap<String, String>> {
+    if title.len() > NOTE_TITLE_MAX {
+        return Err(HashMap::from([(
+            "title".to_string(), 
+            format!("is too long: {} > {}", title.len(), NOTE_TITLE_MAX)
+        )]));
+    }
+    
+    if body.len() > NOTE_BODY_MAX {
+        return Err(HashMap::from([(
+            "body".to_string(), 
+            format!("is too long: {} > {}", body.len(), NOTE_BODY_MAX)
+        )]));
+    }
+
+    Ok(())
+}
+
+/// Public address of the note page
+pub fn note_url(site_url: &str, slug: &str) -> String {
+    format!("{}/notes/{}", site_url.trim_end_matches('/'), slug)
+}
+
+/// Inverse of [`note_url`], ignores the query and the fragment
+pub fn note_slug_from_url<'a>(site_url: &str, url: &'a str) -> Option<&'a str> {
+    let prefix = note_url(site_url, "");
+    let slug = url.strip_prefix(prefix.as_str())?;
+    let slug = slug.split(['?', '#']).next().unwrap_or_default().trim_end_matches('/');
+    match slug.is_empty() || slug.contains('/') {
+        true => None,
+        false => Some(slug)
     }
 }
 
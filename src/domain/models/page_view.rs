@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+pub const PAGE_VIEW_PATH_MAX: usize = 512;
+pub const PAGE_VIEW_REFERRER_MAX: usize = 253;
+/// Hex characters kept from the visitor hash, plenty to tell a day's visitors apart
+pub const VISITOR_HASH_SIZE: usize = 32;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserAgentClass {
+    Desktop,
+    Mobile,
+    Tablet,
+    Bot,
+    Other,
+}
+
+impl UserAgentClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserAgentClass::Desktop => "desktop",
+            UserAgentClass::Mobile => "mobile",
+            UserAgentClass::Tablet => "tablet",
+            UserAgentClass::Bot => "bot",
+            UserAgentClass::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "desktop" => Some(UserAgentClass::Desktop),
+            "mobile" => Some(UserAgentClass::Mobile),
+            "tablet" => Some(UserAgentClass::Tablet),
+            "bot" => Some(UserAgentClass::Bot),
+            "other" => Some(UserAgentClass::Other),
+            _ => None
+        }
+    }
+
+    /// Coarse on purpose, the full user agent is never stored
+    pub fn classify(user_agent: &str) -> Self {
+        let ua = user_agent.to_ascii_lowercase();
+        if ua.is_empty() {
+            UserAgentClass::Other
+        } else if ["bot", "crawl", "spider", "slurp", "curl", "wget", "python", "http-client", "headless"]
+            .iter()
+            .any(|marker| ua.contains(marker)) {
+            UserAgentClass::Bot
+        } else if ua.contains("ipad") || ua.contains("tablet") || (ua.contains("android") && !ua.contains("mobile")) {
+            UserAgentClass::Tablet
+        } else if ua.contains("mobi") || ua.contains("iphone") || ua.contains("android") {
+            UserAgentClass::Mobile
+        } else if ua.contains("windows") || ua.contains("macintosh") || ua.contains("x11") || ua.contains("linux") {
+            UserAgentClass::Desktop
+        } else {
+            UserAgentClass::Other
+        }
+    }
+}
+
+/// Single view, kept only until it is rolled up into [`DailyStat`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PageView {
+    pub day: NaiveDate,
+    pub path: String,
+    /// Domain only, views from the site itself have none
+    pub referrer: Option<String>,
+    pub user_agent: UserAgentClass,
+    /// Hash of the IP and user agent with the salt of the day,
+    /// it can not be linked to anything once the salt is gone
+    pub visitor: String
+}
+
+/// Views of a path with the same referrer and user agent class in one day
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyStat {
+    pub day: NaiveDate,
+    pub path: String,
+    pub referrer: Option<String>,
+    pub user_agent: UserAgentClass,
+    pub views: u64,
+    pub visitors: u64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let cases = [
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 Chrome/120.0", UserAgentClass::Desktop),
+            ("Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) Mobile/15E148", UserAgentClass::Mobile),
+            ("Mozilla/5.0 (Linux; Android 14; Pixel 8) Mobile Safari/537.36", UserAgentClass::Mobile),
+            ("Mozilla/5.0 (iPad; CPU OS 17_0 like Mac OS X)", UserAgentClass::Tablet),
+            ("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)", UserAgentClass::Bot),
+            ("curl/8.4.0", UserAgentClass::Bot),
+            ("", UserAgentClass::Other),
+        ];
+        for (ua, class) in cases {
+            assert_eq!(UserAgentClass::classify(ua), class, "{}", ua);
+        }
+    }
+}
@@ -11,52 +11,92 @@ pub const PROJECT_DESCRIPTION_MAX: usize = 256;
 pub const PROJECT_URL_MAX: usize = 2048;
 
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Project {
     pub id: ProjectId,
     pub title: String,
     pub description: String,
     pub url: Option<String>,
-    pub created_at: DateTime<Utc>
+    pub created_at: DateTime<Utc>,
+    /// See [`Note::version`](crate::domain::models::note::Note::version)
+    pub version: i64
 }
 
 impl Project {
     pub fn create(title: String, description: String, url: Option<String>) -> anyhow::Result<Self, HashMap<String, String>> {
-        if title.len() > PROJECT_TITLE_MAX {
-            return Err(HashMap::from([(
-                "title".to_string(), 
-                format!("is too long: {} > {}", title.len(), PROJECT_TITLE_MAX)
-            )]));
-        }
-        
-        if description.len() > PROJECT_DESCRIPTION_MAX {
-            return Err(HashMap::from([(
-                "description".to_string(), 
-                format!("is too long: {} > {}", description.len(), PROJECT_DESCRIPTION_MAX)
-            )]));
-        }
-        
-        if let Some(url) = &url {
-            if url.len() > PROJECT_URL_MAX {
-                return Err(HashMap::from([(
-                    "url".to_string(), 
-                    format!("is too long: {} > {}", url.len(), PROJECT_URL_MAX)
-                )]));
-            }
-        }
+        validate(&title, &description, url.as_deref())?;
         
         Ok(Self {
             id: generate_id(PROJECT_ID_SIZE),
             title,
             description,
             url,
-            created_at: Utc::now()
+            created_at: Utc::now(),
+            version: 1
         })
     }
 
-    pub fn update(&mut self, title: String, description: String, url: Option<String>) {
+    pub fn update(&mut self, title: String, description: String, url: Option<String>) -> anyhow::Result<(), HashMap<String, String>> {
+        validate(&title, &description, url.as_deref())?;
+
         self.title = title;
         self.description = description;
         self.url = url;
+        self.version += 1;
+        Ok(())
     }
+
+    /// Project brought back from an archive, keeping its own identifier and date
+    pub fn import(
+        id: ProjectId,
+        title: String,
+        description: String,
+        url: Option<String>,
+        created_at: DateTime<Utc>
+    ) -> anyhow::Result<Self, HashMap<String, String>> {
+        validate(&title, &description, url.as_deref())?;
+
+        if id.len() != PROJECT_ID_SIZE || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(HashMap::from([(
+                "id".to_string(),
+                format!("should be {} letters and digits", PROJECT_ID_SIZE)
+            )]));
+        }
+
+        Ok(Self {
+            id,
+            title,
+            description,
+            url,
+            created_at,
+            version: 1
+        })
+    }
+}
+
+fn validate(title: &str, description: &str, url: Option<&str>) -> anyhow::Result<(), HashMap<String, String>> {
+    if title.len() > PROJECT_TITLE_MAX {
+        return Err(HashMap::from([(
+            "title".to_string(), 
+            format!("is too long: {} > {}", title.len(), PROJECT_TITLE_MAX)
+        )]));
+    }
+    
+    if description.len() > PROJECT_DESCRIPTION_MAX {
+        return Err(HashMap::from([(
+            "description".to_string(), 
+            format!("is too long: {} > {}", description.len(), PROJECT_DESCRIPTION_MAX)
+        )]));
+    }
+    
+    if let Some(url) = url {
+        if url.len() > PROJECT_URL_MAX {
+            return Err(HashMap::from([(
+                "url".to_string(), 
+                format!("is too long: {} > {}", url.len(), PROJECT_URL_MAX)
+            )]));
+        }
+    }
+
+    Ok(())
 }
\ No newline at end of file
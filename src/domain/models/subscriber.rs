@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+use crate::domain::services::validator::validate_email;
+
+pub type SubscriberId = String;
+
+pub const SUBSCRIBER_ID_SIZE: usize = 16;
+pub const SUBSCRIBER_EMAIL_MAX: usize = 254;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriberStatus {
+    /// The address is not confirmed yet, nothing but the confirmation is sent to it
+    Pending,
+    Confirmed,
+}
+
+impl SubscriberStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriberStatus::Pending => "pending",
+            SubscriberStatus::Confirmed => "confirmed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(SubscriberStatus::Pending),
+            "confirmed" => Some(SubscriberStatus::Confirmed),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Subscriber {
+    pub id: SubscriberId,
+    pub email: String,
+    pub status: SubscriberStatus,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    /// Notes published before that were already mailed
+    pub digest_sent_at: Option<DateTime<Utc>>
+}
+
+impl Subscriber {
+    pub fn create(email: String) -> anyhow::Result<Self, HashMap<String, String>> {
+        let email = email.trim().to_lowercase();
+
+        validate_email(&email, SUBSCRIBER_EMAIL_MAX).map_err(|e| {
+            HashMap::from([("email".to_string(), e)])
+        })?;
+
+        Ok(Self {
+            id: generate_id(SUBSCRIBER_ID_SIZE),
+            email,
+            status: SubscriberStatus::Pending,
+            created_at: Utc::now(),
+            confirmed_at: None,
+            digest_sent_at: None
+        })
+    }
+
+    pub fn confirm(&mut self) {
+        if self.status == SubscriberStatus::Pending {
+            self.status = SubscriberStatus::Confirmed;
+            self.confirmed_at = Some(Utc::now());
+        }
+    }
+
+    /// Notes published after that moment are due in the next digest
+    pub fn digest_cutoff(&self) -> Option<DateTime<Utc>> {
+        self.digest_sent_at.or(self.confirmed_at)
+    }
+}
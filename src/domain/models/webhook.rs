@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::event::EventKind;
+use crate::domain::services::validator::is_http_url;
+
+pub type WebhookId = String;
+pub type WebhookDeliveryId = String;
+
+pub const WEBHOOK_ID_SIZE: usize = 16;
+pub const WEBHOOK_URL_MAX: usize = 2048;
+pub const WEBHOOK_SECRET_SIZE: usize = 32;
+pub const WEBHOOK_SECRET_MIN: usize = 16;
+pub const WEBHOOK_SECRET_MAX: usize = 128;
+pub const WEBHOOK_DELIVERY_ID_SIZE: usize = 16;
+/// With the backoff below the last attempt is made about 8.5 hours after the first one
+pub const WEBHOOK_ATTEMPTS_MAX: u32 = 10;
+const WEBHOOK_BACKOFF_BASE_MINUTES: i64 = 1;
+
+
+/// Endpoint of an admin's tooling, notified about the chosen events
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: WebhookId,
+    pub url: String,
+    pub events: Vec<EventKind>,
+    /// Key of the payload signature, only shown once on creation
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub created_at: DateTime<Utc>
+}
+
+impl Webhook {
+    /// A secret is generated when none is given
+    pub fn create(
+        url: String,
+        events: Vec<EventKind>,
+        secret: Option<String>
+    ) -> anyhow::Result<Self, HashMap<String, String>> {
+        if url.len() > WEBHOOK_URL_MAX {
+            return Err(HashMap::from([(
+                "url".to_string(),
+                format!("is too long: {} > {}", url.len(), WEBHOOK_URL_MAX)
+            )]));
+        }
+
+        if !is_http_url(&url) {
+            return Err(HashMap::from([("url".to_string(), "is not an http(s) URL".to_string())]));
+        }
+
+        if events.is_empty() {
+            return Err(HashMap::from([("events".to_string(), "should not be empty".to_string())]));
+        }
+
+        if let Some(secret) = &secret {
+            if secret.len() < WEBHOOK_SECRET_MIN || secret.len() > WEBHOOK_SECRET_MAX {
+                return Err(HashMap::from([(
+                    "secret".to_string(),
+                    format!("should be {} to {} characters long", WEBHOOK_SECRET_MIN, WEBHOOK_SECRET_MAX)
+                )]));
+            }
+        }
+
+        let mut unique = Vec::with_capacity(events.len());
+        for kind in events {
+            if !unique.contains(&kind) {
+                unique.push(kind);
+            }
+        }
+
+        Ok(Self {
+            id: generate_id(WEBHOOK_ID_SIZE),
+            url,
+            events: unique,
+            secret: secret.unwrap_or_else(|| generate_id(WEBHOOK_SECRET_SIZE)),
+            created_at: Utc::now()
+        })
+    }
+
+    pub fn wants(&self, kind: EventKind) -> bool {
+        self.events.contains(&kind)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    /// Gave up after [`WEBHOOK_ATTEMPTS_MAX`] attempts
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(WebhookDeliveryStatus::Pending),
+            "delivered" => Some(WebhookDeliveryStatus::Delivered),
+            "failed" => Some(WebhookDeliveryStatus::Failed),
+            _ => None
+        }
+    }
+}
+
+/// One event for one webhook. Doubles as the delivery log,
+/// rows are kept after they are delivered or given up
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub webhook_id: WebhookId,
+    pub event: EventKind,
+    /// Serialized JSON body, signed and posted as is
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// Of the last attempt, `None` when it did not get a response
+    pub response_code: Option<u16>,
+    pub error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>
+}
+
+impl WebhookDelivery {
+    pub fn create(webhook_id: WebhookId, event: EventKind, payload: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: generate_id(WEBHOOK_DELIVERY_ID_SIZE),
+            webhook_id,
+            event,
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            response_code: None,
+            error: None,
+            next_attempt_at: now,
+            created_at: now,
+            updated_at: None
+        }
+    }
+
+    /// A fresh delivery of the same payload, the original stays in the log
+    pub fn redeliver(&self) -> Self {
+        Self::create(self.webhook_id.clone(), self.event, self.payload.clone())
+    }
+
+    pub fn succeed(&mut self, now: DateTime<Utc>, response_code: u16) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::Delivered;
+        self.response_code = Some(response_code);
+        self.error = None;
+        self.updated_at = Some(now);
+    }
+
+    /// Schedules the next attempt with exponential backoff.
+    /// Returns `false` when the delivery is given up
+    pub fn fail(&mut self, now: DateTime<Utc>, response_code: Option<u16>, error: String) -> bool {
+        self.attempts += 1;
+        self.response_code = response_code;
+        self.error = Some(error);
+        self.updated_at = Some(now);
+        if self.attempts >= WEBHOOK_ATTEMPTS_MAX {
+            self.status = WebhookDeliveryStatus::Failed;
+            return false;
+        }
+        let delay = WEBHOOK_BACKOFF_BASE_MINUTES << (self.attempts - 1);
+        self.next_attempt_at = now + Duration::minutes(delay);
+        true
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_webhook() {
+        let webhook = Webhook::create(
+            "https://chat.example/hook".to_string(),
+            vec![EventKind::NotePublished, EventKind::NoteUpdated, EventKind::NotePublished],
+            None
+        ).unwrap();
+        assert_eq!(webhook.events, vec![EventKind::NotePublished, EventKind::NoteUpdated]);
+        assert_eq!(webhook.secret.len(), WEBHOOK_SECRET_SIZE);
+        assert!(serde_json::to_value(&webhook).unwrap().get("secret").is_none());
+
+        assert!(Webhook::create("ftp://chat.example".to_string(), vec![EventKind::NotePublished], None).is_err());
+        assert!(Webhook::create("https://chat.example".to_string(), vec![], None).is_err());
+        assert!(Webhook::create(
+            "https://chat.example".to_string(),
+            vec![EventKind::NotePublished],
+            Some("short".to_string())
+        ).is_err());
+    }
+
+    #[test]
+    fn test_delivery_backoff() {
+        let now = Utc::now();
+        let mut delivery = WebhookDelivery::create("hook".to_string(), EventKind::NoteUpdated, "{}".to_string());
+
+        assert!(delivery.fail(now, Some(500), "500 Internal Server Error".to_string()));
+        assert_eq!(delivery.next_attempt_at, now + Duration::minutes(1));
+        assert!(delivery.fail(now, None, "timed out".to_string()));
+        assert_eq!(delivery.next_attempt_at, now + Duration::minutes(2));
+        assert_eq!(delivery.response_code, None);
+
+        while delivery.fail(now, None, "timed out".to_string()) {}
+        assert_eq!((delivery.attempts, delivery.status), (WEBHOOK_ATTEMPTS_MAX, WebhookDeliveryStatus::Failed));
+
+        let again = delivery.redeliver();
+        assert_eq!((again.attempts, again.status, again.payload.as_str()), (0, WebhookDeliveryStatus::Pending, "{}"));
+        assert_ne!(again.id, delivery.id);
+    }
+}
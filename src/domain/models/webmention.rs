@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::note::NoteId;
+use crate::domain::services::validator::is_http_url;
+
+pub type WebmentionId = String;
+
+pub const WEBMENTION_ID_SIZE: usize = 16;
+pub const WEBMENTION_URL_MAX: usize = 2048;
+pub const WEBMENTION_AUTHOR_NAME_MAX: usize = 128;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebmentionStatus {
+    /// Accepted, the source is not fetched yet
+    Pending,
+    /// The source links to the target
+    Verified,
+    /// The source is gone or does not link to the target
+    Invalid,
+}
+
+impl WebmentionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebmentionStatus::Pending => "pending",
+            WebmentionStatus::Verified => "verified",
+            WebmentionStatus::Invalid => "invalid",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(WebmentionStatus::Pending),
+            "verified" => Some(WebmentionStatus::Verified),
+            "invalid" => Some(WebmentionStatus::Invalid),
+            _ => None
+        }
+    }
+}
+
+/// Author of the source page, taken from its h-card
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionAuthor {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub photo: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Webmention {
+    pub id: WebmentionId,
+    /// Page that mentions the note
+    pub source: String,
+    /// URL of our note as it was sent
+    pub target: String,
+    pub note_id: NoteId,
+    pub status: WebmentionStatus,
+    pub author: MentionAuthor,
+    pub created_at: DateTime<Utc>,
+    pub verified_at: Option<DateTime<Utc>>
+}
+
+impl Webmention {
+    pub fn create(source: String, target: String, note_id: NoteId) -> anyhow::Result<Self, HashMap<String, String>> {
+        let mut errors = HashMap::new();
+
+        for (field, url) in [("source", &source), ("target", &target)] {
+            if url.len() > WEBMENTION_URL_MAX {
+                errors.insert(
+                    field.to_string(),
+                    format!("is too long: {} > {}", url.len(), WEBMENTION_URL_MAX)
+                );
+            } else if !is_http_url(url) {
+                errors.insert(field.to_string(), "should be an http(s) URL".to_string());
+            }
+        }
+
+        if errors.is_empty() && source == target {
+            errors.insert("source".to_string(), "should differ from target".to_string());
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(Self {
+            id: generate_id(WEBMENTION_ID_SIZE),
+            source,
+            target,
+            note_id,
+            status: WebmentionStatus::Pending,
+            author: MentionAuthor::default(),
+            created_at: Utc::now(),
+            verified_at: None
+        })
+    }
+
+    /// The sender may notify again after editing the source, it is checked anew
+    pub fn resubmit(&mut self) {
+        self.status = WebmentionStatus::Pending;
+    }
+
+    pub fn verify(&mut self, author: MentionAuthor) {
+        self.status = WebmentionStatus::Verified;
+        self.author = MentionAuthor {
+            name: author.name.map(|name| name.chars().take(WEBMENTION_AUTHOR_NAME_MAX).collect()),
+            url: author.url.filter(|url| is_http_url(url) && url.len() <= WEBMENTION_URL_MAX),
+            photo: author.photo.filter(|url| is_http_url(url) && url.len() <= WEBMENTION_URL_MAX),
+        };
+        self.verified_at = Some(Utc::now());
+    }
+
+    pub fn invalidate(&mut self) {
+        self.status = WebmentionStatus::Invalid;
+        self.verified_at = Some(Utc::now());
+    }
+}
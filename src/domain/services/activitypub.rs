@@ -0,0 +1,169 @@
+//! ActivityStreams documents of the blog actor
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+
+use crate::domain::models::actor::LocalActor;
+use crate::domain::models::note::{note_url, Note};
+
+pub const ACTIVITY_CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+pub const SECURITY_CONTEXT: &str = "https://w3id.org/security/v1";
+pub const PUBLIC_COLLECTION: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+
+pub fn webfinger(actor: &LocalActor) -> Value {
+    json!({
+        "subject": format!("acct:{}", actor.acct()),
+        "aliases": [actor.id()],
+        "links": [
+            {
+                "rel": "self",
+                "type": "application/activity+json",
+                "href": actor.id()
+            },
+            {
+                "rel": "http://webfinger.net/rel/profile-page",
+                "type": "text/html",
+                "href": actor.site_url
+            }
+        ]
+    })
+}
+
+pub fn actor_document(actor: &LocalActor, public_key_pem: &str) -> Value {
+    json!({
+        "@context": [ACTIVITY_CONTEXT, SECURITY_CONTEXT],
+        "id": actor.id(),
+        "type": "Person",
+        "preferredUsername": actor.username,
+        "name": actor.username,
+        "url": actor.site_url,
+        "inbox": actor.inbox(),
+        "outbox": actor.outbox(),
+        "followers": actor.followers(),
+        "manuallyApprovesFollowers": false,
+        "discoverable": true,
+        "publicKey": {
+            "id": actor.key_id(),
+            "owner": actor.id(),
+            "publicKeyPem": public_key_pem
+        }
+    })
+}
+
+/// `html` is the rendered body of the note
+pub fn article(actor: &LocalActor, note: &Note, html: &str) -> Value {
+    let url = note_url(&actor.site_url, &note.slug);
+    let mut article = json!({
+        "id": url,
+        "type": "Article",
+        "attributedTo": actor.id(),
+        "name": note.title,
+        "summary": note.description,
+        "content": html,
+        "mediaType": "text/html",
+        "url": url,
+        "published": timestamp(&note.created_at),
+        "to": [PUBLIC_COLLECTION],
+        "cc": [actor.followers()]
+    });
+    if let Some(updated_at) = note.updated_at {
+        article["updated"] = json!(timestamp(&updated_at));
+    }
+    article
+}
+
+pub fn create_activity(actor: &LocalActor, article: Value) -> Value {
+    json!({
+        "@context": ACTIVITY_CONTEXT,
+        "id": format!("{}#create", article["id"].as_str().unwrap_or_default()),
+        "type": "Create",
+        "actor": actor.id(),
+        "published": article["published"],
+        "to": article["to"],
+        "cc": article["cc"],
+        "object": article
+    })
+}
+
+/// `version` keeps the id of every edit distinct, servers drop activities they have seen
+pub fn update_activity(actor: &LocalActor, article: Value, version: i64) -> Value {
+    json!({
+        "@context": ACTIVITY_CONTEXT,
+        "id": format!("{}#updates/{}", article["id"].as_str().unwrap_or_default(), version),
+        "type": "Update",
+        "actor": actor.id(),
+        "published": article["updated"],
+        "to": article["to"],
+        "cc": article["cc"],
+        "object": article
+    })
+}
+
+/// Accepts a follow request, `follow` is the activity as it was received
+pub fn accept_activity(actor: &LocalActor, follow: &Value) -> Value {
+    let follow_id = follow["id"].as_str().unwrap_or_default();
+    json!({
+        "@context": ACTIVITY_CONTEXT,
+        "id": format!("{}#accepts/{}", actor.id(), hex::encode(follow_id)),
+        "type": "Accept",
+        "actor": actor.id(),
+        "object": follow
+    })
+}
+
+pub fn ordered_collection(id: String, total: u64, items: Vec<Value>) -> Value {
+    json!({
+        "@context": ACTIVITY_CONTEXT,
+        "id": id,
+        "type": "OrderedCollection",
+        "totalItems": total,
+        "orderedItems": items
+    })
+}
+
+/// Activity objects may be embedded or referenced by IRI
+pub fn object_id(value: &Value) -> Option<&str> {
+    value.as_str().or_else(|| value["id"].as_str())
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_article() {
+        let actor = LocalActor::new("https://jkearnsl.su", "blog");
+        let note = Note {
+            id: "id".to_string(),
+            slug: "supa-title".to_string(),
+            title: "Supa title".to_string(),
+            description: "Test".to_string(),
+            body: "Test".to_string(),
+            created_at: Utc::now(),
+            updated_at: None,
+            version: 1
+        };
+
+        let activity = create_activity(&actor, article(&actor, &note, "<p>Test</p>"));
+
+        assert_eq!(activity["type"], "Create");
+        assert_eq!(activity["id"], "https://jkearnsl.su/notes/supa-title#create");
+        assert_eq!(activity["object"]["type"], "Article");
+        assert_eq!(activity["object"]["attributedTo"], "https://jkearnsl.su/actor");
+        assert_eq!(activity["cc"][0], "https://jkearnsl.su/actor/followers");
+        assert!(activity["object"].get("updated").is_none());
+    }
+
+    #[test]
+    fn test_object_id() {
+        assert_eq!(object_id(&json!("https://a.example/1")), Some("https://a.example/1"));
+        assert_eq!(object_id(&json!({"id": "https://a.example/1"})), Some("https://a.example/1"));
+        assert_eq!(object_id(&json!(1)), None);
+    }
+}
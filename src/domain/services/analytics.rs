@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, HashSet};
+
+use sha2::{Digest, Sha256};
+
+use crate::domain::models::page_view::{
+    DailyStat,
+    PageView,
+    UserAgentClass,
+    PAGE_VIEW_PATH_MAX,
+    PAGE_VIEW_REFERRER_MAX,
+    VISITOR_HASH_SIZE
+};
+
+
+pub fn visitor_hash(salt: &[u8], ip: &str, user_agent: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(ip.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(user_agent.as_bytes());
+    let mut hash = hex::encode(hasher.finalize());
+    hash.truncate(VISITOR_HASH_SIZE);
+    hash
+}
+
+/// Path without the query and the fragment, `None` for anything that is not a site path
+pub fn normalize_path(path: &str) -> Option<String> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    if !path.starts_with('/') || path.starts_with("//") || path.len() > PAGE_VIEW_PATH_MAX {
+        return None;
+    }
+    if path.contains(|c: char| c.is_whitespace() || c.is_control()) {
+        return None;
+    }
+    Some(match path.len() > 1 {
+        true => path.trim_end_matches('/').to_string(),
+        false => path.to_string()
+    })
+}
+
+/// Lowercased host of the referrer, without `www.`. Internal navigation
+/// and anything that is not an http(s) URL count as no referrer
+pub fn referrer_domain(referrer: &str, site_url: &str) -> Option<String> {
+    let host = |url: &str| -> Option<String> {
+        let lower = url.trim().to_ascii_lowercase();
+        let rest = lower.strip_prefix("https://").or_else(|| lower.strip_prefix("http://"))?;
+        let authority = rest.split(['/', '?', '#']).next()?;
+        let host = authority.rsplit('@').next()?.split(':').next()?;
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        match host.is_empty() || host.len() > PAGE_VIEW_REFERRER_MAX {
+            true => None,
+            false => Some(host.to_string())
+        }
+    };
+
+    let domain = host(referrer)?;
+    match host(site_url) {
+        Some(site) if site == domain => None,
+        _ => Some(domain)
+    }
+}
+
+/// Daily aggregates of `views`, visitors are unique within a group
+pub fn rollup(views: &[PageView]) -> Vec<DailyStat> {
+    let mut groups: BTreeMap<_, (u64, HashSet<&str>)> = BTreeMap::new();
+    for view in views {
+        let (count, visitors) = groups
+            .entry((view.day, &view.path, &view.referrer, view.user_agent))
+            .or_default();
+        *count += 1;
+        visitors.insert(&view.visitor);
+    }
+
+    groups.into_iter()
+        .map(|((day, path, referrer, user_agent), (views, visitors))| DailyStat {
+            day,
+            path: path.clone(),
+            referrer: referrer.clone(),
+            user_agent,
+            views,
+            visitors: visitors.len() as u64
+        })
+        .collect()
+}
+
+/// Views worth counting, bots only skew the numbers
+pub fn is_counted(user_agent: UserAgentClass) -> bool {
+    user_agent != UserAgentClass::Bot
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use super::*;
+
+    #[test]
+    fn test_visitor_hash() {
+        let today = visitor_hash(b"today", "1.2.3.4", "Firefox");
+
+        assert_eq!(today.len(), VISITOR_HASH_SIZE);
+        assert_eq!(today, visitor_hash(b"today", "1.2.3.4", "Firefox"));
+        assert_ne!(today, visitor_hash(b"tomorrow", "1.2.3.4", "Firefox"));
+        assert_ne!(today, visitor_hash(b"today", "1.2.3.5", "Firefox"));
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("/notes/hello/?utm=x#top"), Some("/notes/hello".to_string()));
+        assert_eq!(normalize_path("/"), Some("/".to_string()));
+        assert_eq!(normalize_path("https://evil.example/"), None);
+        assert_eq!(normalize_path("//evil.example/"), None);
+        assert_eq!(normalize_path("/a b"), None);
+    }
+
+    #[test]
+    fn test_referrer_domain() {
+        let site = "https://jkearnsl.su";
+        assert_eq!(referrer_domain("https://www.Google.com/search?q=x", site), Some("google.com".to_string()));
+        assert_eq!(referrer_domain("http://user@news.ycombinator.com:80/item", site), Some("news.ycombinator.com".to_string()));
+        assert_eq!(referrer_domain("https://jkearnsl.su/notes/a", site), None);
+        assert_eq!(referrer_domain("android-app://org.telegram", site), None);
+        assert_eq!(referrer_domain("", site), None);
+    }
+
+    #[test]
+    fn test_rollup() {
+        let day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let view = |path: &str, visitor: &str| PageView {
+            day,
+            path: path.to_string(),
+            referrer: None,
+            user_agent: UserAgentClass::Desktop,
+            visitor: visitor.to_string()
+        };
+        let stats = rollup(&[view("/", "a"), view("/", "a"), view("/", "b"), view("/notes/x", "a")]);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].path.as_str(), stats[0].views, stats[0].visitors), ("/", 3, 2));
+        assert_eq!((stats[1].path.as_str(), stats[1].views, stats[1].visitors), ("/notes/x", 1, 1));
+    }
+}
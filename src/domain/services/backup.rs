@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+
+use chrono::Datelike;
+
+use crate::domain::models::backup::{Backup, BackupRetention};
+
+
+/// Grandfather-father-son rotation. A backup is kept when it is the newest
+/// one of a day, week or month that is still within the retention.
+/// The newest backup is always kept
+pub fn backups_to_keep(backups: &[Backup], retention: &BackupRetention) -> HashSet<String> {
+    let mut sorted = backups.iter().collect::<Vec<_>>();
+    sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let mut keep = HashSet::new();
+    if let Some(newest) = sorted.first() {
+        keep.insert(newest.name.clone());
+    }
+
+    let periods: [(u32, fn(&Backup) -> (i32, u32)); 3] = [
+        (retention.daily, |b| (b.created_at.year(), b.created_at.ordinal())),
+        (retention.weekly, |b| (b.created_at.iso_week().year(), b.created_at.iso_week().week())),
+        (retention.monthly, |b| (b.created_at.year(), b.created_at.month())),
+    ];
+    for (count, period) in periods {
+        let mut seen = HashSet::new();
+        for backup in &sorted {
+            if seen.len() >= count as usize {
+                break;
+            }
+            if seen.insert(period(backup)) {
+                keep.insert(backup.name.clone());
+            }
+        }
+    }
+
+    keep
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::domain::models::backup::backup_name;
+    use super::*;
+
+    #[test]
+    fn test_backups_to_keep() {
+        // Two backups a day for 90 days, newest first
+        let start = Utc.with_ymd_and_hms(2024, 6, 30, 18, 0, 0).unwrap();
+        let backups = (0..180)
+            .map(|i| {
+                let created_at = start - Duration::hours(12 * i);
+                Backup { name: backup_name(created_at), created_at, size: 1 }
+            })
+            .collect::<Vec<_>>();
+
+        let keep = backups_to_keep(&backups, &BackupRetention { daily: 7, weekly: 4, monthly: 3 });
+
+        // The evening backup of each of the last 7 days
+        for day in 0..7 {
+            assert!(keep.contains(&backups[day * 2].name));
+            assert!(!keep.contains(&backups[day * 2 + 1].name));
+        }
+        // Sunday 2024-06-23 closes the previous ISO week
+        assert!(keep.contains(&backup_name(Utc.with_ymd_and_hms(2024, 6, 23, 18, 0, 0).unwrap())));
+        // Last backups of May and April
+        assert!(keep.contains(&backup_name(Utc.with_ymd_and_hms(2024, 5, 31, 18, 0, 0).unwrap())));
+        assert!(keep.contains(&backup_name(Utc.with_ymd_and_hms(2024, 4, 30, 18, 0, 0).unwrap())));
+        assert!(!keep.contains(&backup_name(Utc.with_ymd_and_hms(2024, 4, 29, 18, 0, 0).unwrap())));
+        // 7 days, 3 more weeks, 2 more months overlap the rest
+        assert_eq!(keep.len(), 7 + 3 + 2);
+    }
+
+    #[test]
+    fn test_keep_newest() {
+        let created_at = Utc::now();
+        let backups = vec![Backup { name: backup_name(created_at), created_at, size: 1 }];
+
+        let keep = backups_to_keep(&backups, &BackupRetention { daily: 0, weekly: 0, monthly: 0 });
+        assert_eq!(keep.len(), 1);
+    }
+}
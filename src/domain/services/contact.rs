@@ -0,0 +1,108 @@
+//! Spam checks of the contact form. The form token carries the moment the
+//! form was shown, so both the time-to-submit check and the proof-of-work
+//! challenge need no server state. Only used tokens are remembered, until
+//! they expire, so one solved form is not replayed
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::domain::models::contact_message::ContactMessage;
+use crate::domain::models::mail::Mail;
+use crate::domain::services::signed_token::{sign_token, verify_token};
+
+pub const FORM_PURPOSE: &str = "contact-form";
+
+/// People need at least a few seconds to write anything
+pub const FORM_MIN_FILL_SECONDS: i64 = 3;
+pub const FORM_TOKEN_TTL_HOURS: i64 = 24;
+
+
+pub fn form_token(secret: &[u8], issued_at: DateTime<Utc>) -> String {
+    let timestamp = issued_at.timestamp().to_string();
+    format!("{}.{}", timestamp, sign_token(secret, FORM_PURPOSE, &timestamp))
+}
+
+/// Moment the form was issued, `None` for forged tokens
+pub fn form_issued_at(secret: &[u8], token: &str) -> Option<DateTime<Utc>> {
+    let (timestamp, signature) = token.split_once('.')?;
+    if !verify_token(secret, FORM_PURPOSE, timestamp, signature) {
+        return None;
+    }
+    DateTime::from_timestamp(timestamp.parse().ok()?, 0)
+}
+
+/// Key of a used token, the token itself is never stored
+pub fn form_token_hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Checks that `sha256("{challenge}:{nonce}")` starts with `difficulty` zero bits
+pub fn verify_proof_of_work(challenge: &str, nonce: &str, difficulty: u8) -> bool {
+    let hash = Sha256::digest(format!("{}:{}", challenge, nonce).as_bytes());
+    leading_zero_bits(&hash) >= difficulty as u32
+}
+
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in bytes {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Replies go straight to the sender
+pub fn contact_mail(to: &str, message: &ContactMessage) -> Mail {
+    Mail {
+        to: to.to_string(),
+        subject: match message.subject.is_empty() {
+            true => format!("Message from {}", message.name),
+            false => format!("[Contact] {}", message.subject)
+        },
+        text: format!(
+            "From: {} <{}>\nIP: {}\nSent: {}\n\n{}",
+            message.name,
+            message.email,
+            message.ip,
+            message.created_at.to_rfc2822(),
+            message.body
+        ),
+        html: None,
+        headers: vec![
+            ("Reply-To".to_string(), message.email.clone()),
+        ]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_token() {
+        let issued_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let token = form_token(b"secret", issued_at);
+
+        assert_eq!(form_issued_at(b"secret", &token), Some(issued_at));
+        assert_eq!(form_issued_at(b"other", &token), None);
+        assert_eq!(form_issued_at(b"secret", &token.replacen("17", "18", 1)), None);
+        assert_eq!(form_issued_at(b"secret", "garbage"), None);
+    }
+
+    #[test]
+    fn test_proof_of_work() {
+        let nonce = (0u64..)
+            .find(|nonce| verify_proof_of_work("challenge", &nonce.to_string(), 8))
+            .unwrap()
+            .to_string();
+        let hash = Sha256::digest(format!("challenge:{}", nonce).as_bytes());
+
+        assert_eq!(hash[0], 0);
+        assert!(verify_proof_of_work("challenge", "anything", 0));
+        assert_eq!(leading_zero_bits(&[0, 0b0001_0000]), 11);
+        assert_eq!(leading_zero_bits(&[0, 0]), 16);
+    }
+}
@@ -0,0 +1,328 @@
+//! Markdown files with front matter, as kept by static site generators.
+//! YAML goes between `---` lines, TOML between `+++` lines
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use slug::slugify;
+
+use crate::domain::models::note::{Note, NoteId};
+
+
+/// Note as described by a Markdown file, not validated against [`Note`](crate::domain::models::note::Note) yet
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkdownNote {
+    /// Stable identifier, when the file carries one
+    pub id: Option<String>,
+    pub slug: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    pub draft: bool,
+}
+
+/// Splits off the front matter, `None` when the file has none
+pub fn split_front_matter(content: &str) -> Result<(Option<Map<String, Value>>, &str), String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next().unwrap_or_default();
+    let fence = match first.trim_end() {
+        "---" => "---",
+        "+++" => "+++",
+        _ => return Ok((None, content)),
+    };
+
+    let start = first.len();
+    let mut end = start;
+    for line in lines {
+        let trimmed = line.trim_end();
+        if trimmed == fence || (fence == "---" && trimmed == "...") {
+            let raw = &content[start..end];
+            let fields = match fence {
+                "---" => parse_yaml(raw)?,
+                _ => parse_toml(raw)?,
+            };
+            return Ok((Some(fields), &content[end + line.len()..]));
+        }
+        end += line.len();
+    }
+    Err(format!("is not closed with {}", fence))
+}
+
+/// Reads `title`, `slug`, `date`, `updated`, `description`, `tags`, `draft` and `id`.
+///
+/// `path` fills in what the front matter leaves out the way Jekyll does:
+/// `2020-01-05-hello-world.md` gives the slug `hello-world` and the date 2020-01-05
+pub fn parse_markdown_note(path: &str, content: &str) -> Result<MarkdownNote, HashMap<String, String>> {
+    let error = |field: &str, message: String| HashMap::from([(field.to_string(), message)]);
+
+    let (fields, body) = split_front_matter(content).map_err(|e| error("front_matter", e))?;
+    let fields = fields.unwrap_or_default();
+    let (path_date, path_slug) = parse_file_name(path);
+
+    let string = |keys: &[&str]| keys.iter().find_map(|key| match fields.get(*key) {
+        Some(Value::String(value)) if !value.trim().is_empty() => Some(value.trim().to_string()),
+        Some(Value::Number(value)) => Some(value.to_string()),
+        _ => None
+    });
+    let date = |keys: &[&str]| -> Result<Option<DateTime<Utc>>, HashMap<String, String>> {
+        match string(keys) {
+            Some(value) => parse_date(&value)
+                .map(Some)
+                .ok_or_else(|| error(keys[0], format!("{:?} is not a date", value))),
+            None => Ok(None)
+        }
+    };
+
+    let title = string(&["title"]).ok_or_else(|| error("title", "is required".to_string()))?;
+    let created_at = date(&["date", "created", "published"])?
+        .or(path_date)
+        .ok_or_else(|| error("date", "is required".to_string()))?;
+    let updated_at = date(&["updated", "lastmod", "modified"])?;
+    let draft = match fields.get("draft") {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(draft)) => *draft,
+        Some(Value::String(draft)) if draft == "true" || draft == "false" => draft == "true",
+        Some(other) => return Err(error("draft", format!("{} is not true or false", other)))
+    };
+    let tags = match fields.get("tags") {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(tags)) => tags.iter()
+            .map(|tag| match tag {
+                Value::String(tag) => tag.trim().to_string(),
+                other => other.to_string()
+            })
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        Some(Value::String(tags)) => tags.split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        Some(other) => return Err(error("tags", format!("{} is not a list", other)))
+    };
+
+    Ok(MarkdownNote {
+        id: string(&["id"]),
+        slug: string(&["slug"]).or(path_slug).unwrap_or_else(|| slugify(title.chars().take(50).collect::<String>())),
+        title,
+        description: string(&["description", "summary"]),
+        body: body.trim_start_matches(['\r', '\n']).trim_end().to_string(),
+        created_at,
+        updated_at,
+        tags,
+        draft,
+    })
+}
+
+/// Front matter of exported notes. The keys are the ones
+/// [`parse_markdown_note`] reads, so an export can also be imported
+#[derive(Serialize, Deserialize)]
+struct NoteFrontMatter {
+    id: NoteId,
+    slug: String,
+    title: String,
+    description: String,
+    date: DateTime<Utc>,
+    updated: Option<DateTime<Utc>>,
+    version: i64,
+}
+
+/// The note as a Markdown file, [`parse_note`] reads it back unchanged
+pub fn render_note(note: &Note) -> String {
+    let front_matter = serde_yaml::to_string(&NoteFrontMatter {
+        id: note.id.clone(),
+        slug: note.slug.clone(),
+        title: note.title.clone(),
+        description: note.description.clone(),
+        date: note.created_at,
+        updated: note.updated_at,
+        version: note.version,
+    }).unwrap();
+    format!("---\n{}---\n{}", front_matter, note.body)
+}
+
+/// Unlike [`parse_markdown_note`] nothing is trimmed or filled in.
+/// The note is checked as [`Note::import`] checks imported ones
+pub fn parse_note(content: &str) -> Result<Note, String> {
+    let (fields, body) = split_front_matter(content)?;
+    let fields = fields.ok_or_else(|| "has no front matter".to_string())?;
+    let front_matter: NoteFrontMatter = serde_json::from_value(Value::Object(fields))
+        .map_err(|e| format!("has invalid front matter: {}", e))?;
+
+    let mut note = Note::import(
+        Some(front_matter.id),
+        front_matter.slug,
+        front_matter.title,
+        Some(front_matter.description),
+        body.to_string(),
+        front_matter.date,
+        front_matter.updated
+    ).map_err(|errors| {
+        let mut errors = errors.into_iter()
+            .map(|(field, message)| format!("{} {}", field, message))
+            .collect::<Vec<_>>();
+        errors.sort();
+        format!("is not a valid note: {}", errors.join(", "))
+    })?;
+    if front_matter.version < 1 {
+        return Err(format!("has version {}, versions start at 1", front_matter.version));
+    }
+    note.version = front_matter.version;
+    Ok(note)
+}
+
+/// Accepts RFC 3339, `2020-01-05 10:30:00` and `2020-01-05`, the last two taken as UTC
+pub fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(date.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(date.and_utc());
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
+
+fn parse_file_name(path: &str) -> (Option<DateTime<Utc>>, Option<String>) {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    // `index.md` in a directory of its own, as Hugo bundles are laid out
+    let stem = match stem {
+        "index" | "_index" => path.rsplit(['/', '\\']).nth(1).unwrap_or(stem),
+        stem => stem
+    };
+
+    let (date, rest) = match stem.get(..10).and_then(parse_date) {
+        Some(date) => (Some(date), stem[10..].trim_start_matches('-')),
+        None => (None, stem)
+    };
+    let slug = Some(slugify(rest)).filter(|slug| !slug.is_empty());
+    (date, slug)
+}
+
+fn parse_yaml(raw: &str) -> Result<Map<String, Value>, String> {
+    if raw.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    match serde_yaml::from_str::<Value>(raw) {
+        Ok(Value::Object(fields)) => Ok(fields),
+        Ok(_) => Err("is not a YAML mapping".to_string()),
+        Err(e) => Err(format!("is not valid YAML: {}", e))
+    }
+}
+
+fn parse_toml(raw: &str) -> Result<Map<String, Value>, String> {
+    let table = raw.parse::<toml::Table>().map_err(|e| format!("is not valid TOML: {}", e.message()))?;
+    Ok(table.into_iter().map(|(key, value)| (key, toml_to_json(value))).collect())
+}
+
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(value) => Value::String(value),
+        toml::Value::Integer(value) => Value::from(value),
+        toml::Value::Float(value) => Value::from(value),
+        toml::Value::Boolean(value) => Value::Bool(value),
+        toml::Value::Datetime(value) => Value::String(value.to_string()),
+        toml::Value::Array(values) => Value::Array(values.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table.into_iter().map(|(key, value)| (key, toml_to_json(value))).collect()
+        ),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::models::note::NOTE_DESCRIPTION_MAX;
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_yaml_front_matter() {
+        let note = parse_markdown_note(
+            "posts/hello.md",
+            "---\ntitle: Hello, world\nslug: hello-world\ndate: 2019-03-02 10:00:00 +0300\ntags: [rust, blog]\ndraft: false\n---\n\n# Hi\n\nText\n"
+        ).unwrap();
+
+        assert_eq!(note.title, "Hello, world");
+        assert_eq!(note.slug, "hello-world");
+        assert_eq!(note.created_at, at("2019-03-02T07:00:00Z"));
+        assert_eq!(note.tags, vec!["rust", "blog"]);
+        assert_eq!(note.body, "# Hi\n\nText");
+        assert!(!note.draft);
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let note = parse_markdown_note(
+            "content/posts/first/index.md",
+            "+++\ntitle = \"First\"\ndate = 2020-01-05T10:30:00Z\nlastmod = 2020-02-01\ndraft = true\nid = \"AbCdEfGh12345678\"\n+++\nBody"
+        ).unwrap();
+
+        assert_eq!(note.slug, "first");
+        assert_eq!(note.created_at, at("2020-01-05T10:30:00Z"));
+        assert_eq!(note.updated_at, Some(at("2020-02-01T00:00:00Z")));
+        assert_eq!(note.id.as_deref(), Some("AbCdEfGh12345678"));
+        assert!(note.draft);
+    }
+
+    #[test]
+    fn test_jekyll_file_name() {
+        let note = parse_markdown_note("_posts/2018-07-09-Old-Post.md", "---\ntitle: Old\n---\nBody").unwrap();
+        assert_eq!((note.slug.as_str(), note.created_at), ("old-post", at("2018-07-09T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_invalid_front_matter() {
+        let invalid = |path: &str, content: &str| parse_markdown_note(path, content).unwrap_err()
+            .into_keys()
+            .collect::<Vec<_>>();
+
+        assert_eq!(invalid("a.md", "---\ntitle: [unclosed\n---\n"), vec!["front_matter"]);
+        assert_eq!(invalid("a.md", "---\ntitle: A\n"), vec!["front_matter"]);
+        assert_eq!(invalid("a.md", "No front matter"), vec!["title"]);
+        assert_eq!(invalid("a.md", "---\ntitle: A\n---\n"), vec!["date"]);
+        assert_eq!(invalid("a.md", "---\ntitle: A\ndate: someday\n---\n"), vec!["date"]);
+    }
+
+    #[test]
+    fn test_note_round_trip() {
+        let mut note = Note::create("  123: [not yaml] ".to_string(), "\n---\nBody with a fence ---\n\n".to_string()).unwrap();
+        note.description = String::new();
+        note.updated_at = Some(at("2021-05-06T07:08:09.123456Z"));
+        note.version = 3;
+
+        let content = render_note(&note);
+        assert_eq!(parse_note(&content).unwrap(), note);
+        assert_eq!(parse_markdown_note("notes/a.md", &content).unwrap().id, Some(note.id));
+        assert!(parse_note("No front matter").is_err());
+
+        let mut bad_slug = note.clone();
+        bad_slug.slug = "Not a slug".to_string();
+        assert!(parse_note(&render_note(&bad_slug)).unwrap_err().contains("slug"));
+        let mut bad_id = note.clone();
+        bad_id.id = "short".to_string();
+        assert!(parse_note(&render_note(&bad_id)).unwrap_err().contains("id"));
+        let mut long_description = note.clone();
+        long_description.description = "д".repeat(NOTE_DESCRIPTION_MAX + 1);
+        assert!(parse_note(&render_note(&long_description)).unwrap_err().contains("description"));
+        let mut no_version = note;
+        no_version.version = 0;
+        assert!(parse_note(&render_note(&no_version)).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+use crate::domain::models::hash::Hash;
+use crate::domain::models::media::ResponsiveImage;
+
+const MEDIA_PREFIX: &str = "/media/";
+
+/// Width of the text column, `max-width: 38em` in `assets/css/sakura.css`
+const CONTENT_WIDTH: u32 = 608;
+
+const OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_FOOTNOTES)
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS);
+
+
+/// Hashes of our own media referenced as images in the markdown
+pub fn media_hashes(body: &str) -> Vec<Hash> {
+    let mut hashes = Vec::new();
+    for event in Parser::new_ext(body, OPTIONS) {
+        if let Event::Start(Tag::Image { dest_url, .. }) = event {
+            if let Some(hash) = media_hash(&dest_url) {
+                if !hashes.contains(&hash) {
+                    hashes.push(hash);
+                }
+            }
+        }
+    }
+    hashes
+}
+
+/// Renders markdown to HTML. Images from `images` become `<picture>` with
+/// WebP `srcset` and explicit dimensions, so the page does not jump while loading
+pub fn render_markdown(body: &str, images: &HashMap<Hash, ResponsiveImage>) -> String {
+    let mut events = Vec::new();
+    let mut parser = Parser::new_ext(body, OPTIONS);
+
+    while let Some(event) = parser.next() {
+        let image = match &event {
+            Event::Start(Tag::Image { dest_url, title, .. }) => media_hash(dest_url)
+                .and_then(|hash| images.get(&hash))
+                .map(|image| (image, title.clone())),
+            _ => None
+        };
+
+        match image {
+            None => events.push(event),
+            Some((image, title)) => {
+                // Alt text is everything up to the end of the image
+                let mut alt = String::new();
+                for inner in parser.by_ref() {
+                    match inner {
+                        Event::End(TagEnd::Image) => break,
+                        Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                        _ => {}
+                    }
+                }
+                events.push(Event::Html(CowStr::from(picture(image, &alt, &title))));
+            }
+        }
+    }
+
+    let mut result = String::new();
+    html::push_html(&mut result, events.into_iter());
+    result
+}
+
+/// Renders reader-submitted markdown: no raw HTML, images or headings,
+/// links only to `http`, `https` and `mailto` and marked as user content
+pub fn render_markdown_lite(body: &str) -> String {
+    let mut events = Vec::new();
+    // Whether each open link was emitted, so its end tag matches
+    let mut links = Vec::new();
+
+    for event in Parser::new_ext(body, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Html(text) | Event::InlineHtml(text) => events.push(Event::Text(text)),
+            Event::Start(Tag::Image { .. }) | Event::End(TagEnd::Image) => {}
+            Event::Start(Tag::Heading { .. }) => events.push(Event::Start(Tag::Paragraph)),
+            Event::End(TagEnd::Heading(_)) => events.push(Event::End(TagEnd::Paragraph)),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                let allowed = ["http://", "https://", "mailto:"].iter()
+                    .any(|scheme| dest_url.to_ascii_lowercase().starts_with(scheme));
+                if allowed {
+                    events.push(Event::Html(CowStr::from(format!(
+                        "<a href=\"{}\" rel=\"nofollow ugc noopener\">",
+                        escape(&dest_url)
+                    ))));
+                }
+                links.push(allowed);
+            }
+            Event::End(TagEnd::Link) => {
+                if links.pop().unwrap_or(false) {
+                    events.push(Event::Html(CowStr::from("</a>")));
+                }
+            }
+            event => events.push(event)
+        }
+    }
+
+    let mut result = String::new();
+    html::push_html(&mut result, events.into_iter());
+    result
+}
+
+fn media_hash(url: &str) -> Option<Hash> {
+    url.strip_prefix(MEDIA_PREFIX).and_then(|hash| Hash::from_hex(hash).ok())
+}
+
+fn picture(image: &ResponsiveImage, alt: &str, title: &str) -> String {
+    let original = &image.original;
+    let mut result = String::from("<picture>");
+
+    if !image.variants.is_empty() {
+        let srcset = image.variants.iter()
+            .filter_map(|v| v.width.map(|width| format!("{} {}w", v.url(), width)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        result.push_str(&format!(
+            "<source type=\"{}\" srcset=\"{}\" sizes=\"(max-width: {width}px) 100vw, {width}px\">",
+            escape(&image.variants[0].mime),
+            escape(&srcset),
+            width = CONTENT_WIDTH
+        ));
+    }
+
+    result.push_str(&format!("<img src=\"{}\" alt=\"{}\"", original.url(), escape(alt)));
+    if !title.is_empty() {
+        result.push_str(&format!(" title=\"{}\"", escape(title)));
+    }
+    if let (Some(width), Some(height)) = (original.width, original.height) {
+        result.push_str(&format!(" width=\"{}\" height=\"{}\"", width, height));
+    }
+    result.push_str(" loading=\"lazy\" decoding=\"async\"></picture>");
+    result
+}
+
+fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    pulldown_cmark_escape::escape_html(&mut result, value).unwrap();
+    result
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::media::Media;
+
+    fn image() -> ResponsiveImage {
+        let original = Media::create(
+            Hash::sha256(b"original"),
+            Some("image/png"),
+            8,
+            "pic.png".to_string(),
+            "test".to_string(),
+            Some((1200, 600))
+        ).unwrap();
+        let variants = vec![
+            original.variant(Hash::sha256(b"480"), "image/webp", 3, 480, 240),
+            original.variant(Hash::sha256(b"960"), "image/webp", 3, 960, 480),
+        ];
+        ResponsiveImage { original, variants }
+    }
+
+    #[test]
+    fn test_render_responsive_image() {
+        let image = image();
+        let body = format!("Text\n\n![A \"cat\"]({})", image.original.url());
+        let images = HashMap::from([(image.original.hash.clone(), image.clone())]);
+
+        let html = render_markdown(&body, &images);
+
+        assert!(html.contains("<p>Text</p>"));
+        assert!(html.contains(&format!("srcset=\"{} 480w, {} 960w\"", image.variants[0].url(), image.variants[1].url())));
+        assert!(html.contains("sizes=\"(max-width: 608px) 100vw, 608px\""));
+        assert!(html.contains("width=\"1200\" height=\"600\""));
+        assert!(html.contains("alt=\"A &quot;cat&quot;\""));
+    }
+
+    #[test]
+    fn test_render_foreign_image() {
+        let html = render_markdown("![logo](https://example.com/logo.png)", &HashMap::new());
+        assert!(html.contains("<img src=\"https://example.com/logo.png\" alt=\"logo\""));
+    }
+
+    #[test]
+    fn test_render_markdown_lite() {
+        let html = render_markdown_lite(
+            "# Hi\n\n**bold** <script>alert(1)</script> ![x](/a.png) \
+             [ok](https://example.com) [bad](javascript:alert(1))"
+        );
+
+        assert!(html.contains("<p>Hi</p>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("<a href=\"https://example.com\" rel=\"nofollow ugc noopener\">ok</a>"));
+        assert!(!html.contains("javascript:"));
+    }
+
+    #[test]
+    fn test_media_hashes() {
+        let image = image();
+        let body = format!(
+            "![a]({url}) ![b]({url}) ![c](/media/broken) [link]({url})",
+            url = image.original.url()
+        );
+        assert_eq!(media_hashes(&body), vec![image.original.hash]);
+    }
+}
@@ -1 +1,12 @@
 pub mod validator;
+pub mod mime;
+pub mod markdown;
+pub mod activitypub;
+pub mod signed_token;
+pub mod newsletter;
+pub mod contact;
+pub mod analytics;
+pub mod backup;
+pub mod webhook;
+pub mod recurrence;
+pub mod front_matter;
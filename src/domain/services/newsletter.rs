@@ -0,0 +1,83 @@
+use pulldown_cmark_escape::escape_html;
+
+use crate::domain::models::mail::Mail;
+use crate::domain::models::note::{note_url, NoteListItem};
+use crate::domain::models::subscriber::Subscriber;
+use crate::domain::services::signed_token::sign_token;
+
+pub const CONFIRM_PURPOSE: &str = "newsletter-confirm";
+pub const UNSUBSCRIBE_PURPOSE: &str = "newsletter-unsubscribe";
+
+
+pub fn confirm_url(site_url: &str, secret: &[u8], subscriber: &Subscriber) -> String {
+    format!(
+        "{}/api/newsletter/confirm?id={}&token={}",
+        site_url.trim_end_matches('/'),
+        subscriber.id,
+        sign_token(secret, CONFIRM_PURPOSE, &subscriber.id)
+    )
+}
+
+pub fn unsubscribe_url(site_url: &str, secret: &[u8], subscriber: &Subscriber) -> String {
+    format!(
+        "{}/api/newsletter/unsubscribe?id={}&token={}",
+        site_url.trim_end_matches('/'),
+        subscriber.id,
+        sign_token(secret, UNSUBSCRIBE_PURPOSE, &subscriber.id)
+    )
+}
+
+pub fn confirmation_mail(site_url: &str, secret: &[u8], subscriber: &Subscriber) -> Mail {
+    let url = confirm_url(site_url, secret, subscriber);
+    Mail {
+        to: subscriber.email.clone(),
+        subject: "Confirm your subscription".to_string(),
+        text: format!(
+            "Someone, hopefully you, subscribed this address to new notes on {site}.\n\n\
+             Confirm the subscription: {url}\n\n\
+             If it was not you, just ignore this mail.",
+            site = site_url,
+            url = url
+        ),
+        html: None,
+        headers: vec![]
+    }
+}
+
+/// One-click unsubscribe as described in RFC 8058
+pub fn digest_mail(site_url: &str, secret: &[u8], subscriber: &Subscriber, notes: &[NoteListItem]) -> Mail {
+    let unsubscribe = unsubscribe_url(site_url, secret, subscriber);
+
+    let mut text = String::from("New notes:\n\n");
+    let mut html = String::from("<p>New notes:</p>\n<ul>\n");
+    for note in notes {
+        let url = note_url(site_url, &note.slug);
+        text.push_str(&format!("{}\n{}\n{}\n\n", note.title, note.description, url));
+
+        html.push_str("<li><a href=\"");
+        escape_html(&mut html, &url).unwrap();
+        html.push_str("\">");
+        escape_html(&mut html, &note.title).unwrap();
+        html.push_str("</a><br>");
+        escape_html(&mut html, &note.description).unwrap();
+        html.push_str("</li>\n");
+    }
+    text.push_str(&format!("Unsubscribe: {}\n", unsubscribe));
+    html.push_str("</ul>\n<p><a href=\"");
+    escape_html(&mut html, &unsubscribe).unwrap();
+    html.push_str("\">Unsubscribe</a></p>\n");
+
+    Mail {
+        to: subscriber.email.clone(),
+        subject: match notes {
+            [note] => note.title.clone(),
+            _ => format!("{} new notes", notes.len())
+        },
+        text,
+        html: Some(html),
+        headers: vec![
+            ("List-Unsubscribe".to_string(), format!("<{}>", unsubscribe)),
+            ("List-Unsubscribe-Post".to_string(), "List-Unsubscribe=One-Click".to_string()),
+        ]
+    }
+}
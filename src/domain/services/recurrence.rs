@@ -0,0 +1,213 @@
+//! Recurring schedules of background jobs. Either `@every <n><s|m|h|d>`
+//! or a five field cron expression `minute hour day-of-month month day-of-week`
+//! with `*`, lists, ranges and steps, plus the usual `@hourly`, `@daily`,
+//! `@weekly`, `@monthly` and `@yearly`. Times are UTC
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+/// Five years, enough for `0 0 29 2 *`
+const SEARCH_DAYS_MAX: i64 = 366 * 5;
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Recurrence {
+    Every(Duration),
+    Cron(Cron),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day of month and day of week given as `*`. When both are restricted
+    /// a day matching either of them is due, as in Vixie cron
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Recurrence {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let expression = match value {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => match value.strip_prefix("@every ") {
+                Some(every) => return parse_every(every.trim()).map(Recurrence::Every),
+                None => value
+            }
+        };
+
+        let fields = expression.split_whitespace().collect::<Vec<_>>();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(format!("{:?} should have 5 fields", value));
+        };
+
+        Ok(Recurrence::Cron(Cron {
+            minutes: parse_field(minutes, 0, 59)?,
+            hours: parse_field(hours, 0, 23)?,
+            days: parse_field(days, 1, 31)?,
+            months: parse_field(months, 1, 12)?,
+            // 7 is Sunday as well
+            weekdays: match parse_field(weekdays, 0, 7)? {
+                bits if bits & (1 << 7) != 0 => (bits | 1) & !(1 << 7),
+                bits => bits
+            },
+            any_day: days.starts_with('*'),
+            any_weekday: weekdays.starts_with('*'),
+        }))
+    }
+
+    /// First due time strictly after `after`, `None` when there is none within five years
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Recurrence::Every(every) => Some(after + *every),
+            Recurrence::Cron(cron) => cron.next_after(after)
+        }
+    }
+}
+
+impl Cron {
+    fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after + Duration::days(SEARCH_DAYS_MAX);
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        while time <= limit {
+            if !has(self.months, time.month()) {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1)
+                };
+                time = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+                continue;
+            }
+            if !self.day_matches(time) {
+                time = Utc.with_ymd_and_hms(time.year(), time.month(), time.day(), 0, 0, 0).single()?
+                    + Duration::days(1);
+                continue;
+            }
+            if !has(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !has(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+            return Some(time);
+        }
+        None
+    }
+
+    fn day_matches(&self, time: DateTime<Utc>) -> bool {
+        let day = has(self.days, time.day());
+        let weekday = has(self.weekdays, time.weekday().num_days_from_sunday());
+        match self.any_day || self.any_weekday {
+            true => day && weekday,
+            false => day || weekday
+        }
+    }
+}
+
+fn has(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, parse_number(step, 1, max.max(1))?),
+            None => (part, 1)
+        };
+        let (from, to) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((from, to)) => (parse_number(from, min, max)?, parse_number(to, min, max)?),
+            // `5/15` means from 5 to the end
+            None if step > 1 => (parse_number(range, min, max)?, max),
+            None => {
+                let value = parse_number(range, min, max)?;
+                (value, value)
+            }
+        };
+        if from > to {
+            return Err(format!("{:?} is an empty range", part));
+        }
+        for value in (from..=to).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+fn parse_number(value: &str, min: u32, max: u32) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(number) if (min..=max).contains(&number) => Ok(number),
+        _ => Err(format!("{:?} is not a number from {} to {}", value, min, max))
+    }
+}
+
+fn parse_every(value: &str) -> Result<Duration, String> {
+    let error = || format!("{:?} is not a duration like 30s, 15m, 6h or 1d", value);
+    let unit = value.chars().last().ok_or_else(error)?;
+    let number = value[..value.len() - unit.len_utf8()].parse::<i64>().map_err(|_| error())?;
+    if number <= 0 {
+        return Err(error());
+    }
+    match unit {
+        's' => Ok(Duration::seconds(number)),
+        'm' => Ok(Duration::minutes(number)),
+        'h' => Ok(Duration::hours(number)),
+        'd' => Ok(Duration::days(number)),
+        _ => Err(error())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> Option<DateTime<Utc>> {
+        Recurrence::parse(expression).unwrap().next_after(at(after))
+    }
+
+    #[test]
+    fn test_every() {
+        assert_eq!(next("@every 15s", "2024-01-01T00:00:00Z"), Some(at("2024-01-01T00:00:15Z")));
+        assert_eq!(next("@every 6h", "2024-01-01T00:00:00Z"), Some(at("2024-01-01T06:00:00Z")));
+        assert!(Recurrence::parse("@every 0s").is_err());
+        assert!(Recurrence::parse("@every soon").is_err());
+        assert!(Recurrence::parse("@every 5µ").is_err());
+    }
+
+    #[test]
+    fn test_cron() {
+        assert_eq!(next("@hourly", "2024-01-01T10:30:12Z"), Some(at("2024-01-01T11:00:00Z")));
+        assert_eq!(next("*/15 * * * *", "2024-01-01T10:30:00Z"), Some(at("2024-01-01T10:45:00Z")));
+        assert_eq!(next("30 3 * * *", "2024-01-01T04:00:00Z"), Some(at("2024-01-02T03:30:00Z")));
+        // 2024-01-06 is a Saturday
+        assert_eq!(next("0 9 * * 1-5", "2024-01-05T10:00:00Z"), Some(at("2024-01-08T09:00:00Z")));
+        assert_eq!(next("0 0 * * 7", "2024-01-01T00:00:00Z"), Some(at("2024-01-07T00:00:00Z")));
+        // Either the 1st or a Monday
+        assert_eq!(next("0 0 1 * 1", "2024-01-01T12:00:00Z"), Some(at("2024-01-08T00:00:00Z")));
+        assert_eq!(next("0 0 29 2 *", "2024-03-01T00:00:00Z"), Some(at("2028-02-29T00:00:00Z")));
+        assert_eq!(next("0 0 31 2 *", "2024-03-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn test_invalid_cron() {
+        for expression in ["* * * *", "60 * * * *", "5-1 * * * *", "*/0 * * * *", "a * * * *"] {
+            assert!(Recurrence::parse(expression).is_err(), "{}", expression);
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Stateless tokens for links in mail. A token is bound to its purpose,
+//! so an unsubscribe token can not confirm a subscription and vice versa
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+
+pub fn sign_token(secret: &[u8], purpose: &str, subject: &str) -> String {
+    hex::encode(mac(secret, purpose, subject).finalize().into_bytes())
+}
+
+pub fn verify_token(secret: &[u8], purpose: &str, subject: &str, token: &str) -> bool {
+    match hex::decode(token) {
+        Ok(token) => mac(secret, purpose, subject).verify_slice(&token).is_ok(),
+        Err(_) => false
+    }
+}
+
+fn mac(secret: &[u8], purpose: &str, subject: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).unwrap();
+    mac.update(purpose.as_bytes());
+    mac.update(b"\0");
+    mac.update(subject.as_bytes());
+    mac
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token() {
+        let token = sign_token(b"secret", "unsubscribe", "id");
+
+        assert!(verify_token(b"secret", "unsubscribe", "id", &token));
+        assert!(!verify_token(b"secret", "confirm", "id", &token));
+        assert!(!verify_token(b"secret", "unsubscribe", "other", &token));
+        assert!(!verify_token(b"other", "unsubscribe", "id", &token));
+        assert!(!verify_token(b"secret", "unsubscribe", "id", "zz"));
+    }
+}
@@ -6,6 +6,15 @@ pub fn validate_page(page: &u64) -> Result<(), String> {
     Ok(())
 }
 
+/// Offset of the first row of `page`. Pages past what the database
+/// can skip are refused instead of overflowing
+pub fn page_offset(page: &u64, per_page: &u64) -> Result<u64, String> {
+    validate_page(page)?;
+    (page - 1).checked_mul(*per_page)
+        .filter(|offset| *offset <= i64::MAX as u64)
+        .ok_or_else(|| "Page number is too large".to_string())
+}
+
 pub fn validate_per_page(per_page: &u64) -> Result<(), String> {
     if *per_page == 0 {
         return Err("Number of elements per page should be greater than 0".to_string());
@@ -14,3 +23,27 @@ pub fn validate_per_page(per_page: &u64) -> Result<(), String> {
     }
     Ok(())
 }
+
+pub fn is_http_url(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+    (lower.starts_with("http://") || lower.starts_with("https://")) && !url.contains(char::is_whitespace)
+}
+
+/// Deliberately loose, the confirmation mail is the real check
+pub fn validate_email(email: &str, max: usize) -> Result<(), String> {
+    if email.len() > max {
+        return Err(format!("is too long: {} > {}", email.len(), max));
+    }
+    let valid = match email.rsplit_once('@') {
+        Some((local, domain)) => !local.is_empty()
+            && domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+            && !email.contains(|c: char| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | ',')),
+        None => false
+    };
+    match valid {
+        true => Ok(()),
+        false => Err("is not a valid email address".to_string())
+    }
+}
@@ -0,0 +1,68 @@
+//! Wire format of webhooks. The body is signed with the webhook secret,
+//! receivers recompute `HMAC-SHA256(secret, body)` and compare it
+//! with the signature header in constant time
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use crate::domain::id_generator::generate_id;
+use crate::domain::models::event::DomainEvent;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+const EVENT_ID_SIZE: usize = 16;
+
+
+/// `{"id", "event", "created_at", "data"}`. The `id` is new for every call and
+/// stays the same on redelivery, so receivers can deduplicate
+pub fn webhook_payload(event: &DomainEvent, created_at: DateTime<Utc>) -> String {
+    let data = match serde_json::to_value(event) {
+        Ok(mut value) => value["data"].take(),
+        Err(_) => serde_json::Value::Null
+    };
+
+    json!({
+        "id": generate_id(EVENT_ID_SIZE),
+        "event": event.kind().as_str(),
+        "created_at": created_at,
+        "data": data
+    }).to_string()
+}
+
+/// Value of the [`SIGNATURE_HEADER`]
+pub fn webhook_signature(secret: &str, payload: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(payload.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_payload() {
+        let event = DomainEvent::NoteDeleted { id: "abc".to_string() };
+        let payload: serde_json::Value = serde_json::from_str(
+            &webhook_payload(&event, Utc::now())
+        ).unwrap();
+
+        assert_eq!(payload["id"].as_str().unwrap().len(), EVENT_ID_SIZE);
+        assert_eq!(payload["event"], "note.deleted");
+        assert_eq!(payload["data"]["id"], "abc");
+    }
+
+    #[test]
+    fn test_webhook_signature() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            webhook_signature("Jefe", "what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
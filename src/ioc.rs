@@ -1,45 +1,285 @@
-use crate::adapters::argon2_password_hasher::Argon2PasswordHasher;
-use crate::adapters::database::note_db::NoteGateway;
-use crate::adapters::database::pool::DbPool;
-use crate::adapters::database::project_db::ProjectGateway;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::adapters::backup::sqlite_backup::SqliteBackupStorage;
+use crate::adapters::database::audit_db::AuditGateway;
+use crate::adapters::events::in_process_bus::InProcessEventBus;
+use crate::adapters::events::log_subscriber::LogSubscriber;
+use crate::adapters::database::comment_db::CommentGateway;
+use crate::adapters::database::contact_db::ContactMessageGateway;
+use crate::adapters::database::counter_db::CounterGateway;
+use crate::adapters::database::media_db::MediaGateway;
+use crate::adapters::database::backend::Backend;
+use crate::adapters::database::page_view_db::{DailyStatGateway, PageViewGateway};
+use crate::adapters::database::pool::DbPools;
+use crate::adapters::media::local_storage::LocalMediaStorage;
+use crate::adapters::memory_rate_limiter::MemoryRateLimiter;
+use crate::adapters::memory_visitor_salt::MemoryVisitorSalt;
+use crate::adapters::raster_image_processor::RasterImageProcessor;
+use crate::adapters::social_card::memory_cache::MemorySocialCardCache;
+use crate::adapters::social_card::resvg_renderer::ResvgCardRenderer;
+use crate::adapters::activitypub::http_client::HttpFederationClient;
+use crate::adapters::counter::asset_renderer::AssetCounterRenderer;
+use crate::adapters::database::actor_key_db::ActorKeyGateway;
+use crate::adapters::database::delivery_db::DeliveryGateway;
+use crate::adapters::database::follower_db::FollowerGateway;
+use crate::adapters::database::job_db::JobGateway;
+use crate::adapters::database::subscriber_db::SubscriberGateway;
+use crate::adapters::database::webhook_db::WebhookGateway;
+use crate::adapters::database::webmention_db::WebmentionGateway;
+use crate::adapters::webhook::http_client::HttpWebhookClient;
+use crate::adapters::webmention::http_client::HttpWebmentionClient;
+use crate::application::activitypub::actor::GetActor;
+use crate::application::activitypub::deliver::ProcessDeliveries;
+use crate::application::activitypub::followers::GetFollowers;
+use crate::application::activitypub::inbox::ReceiveActivity;
+use crate::application::activitypub::outbox::GetOutbox;
+use crate::application::activitypub::webfinger::GetWebfinger;
+use crate::application::audit::list::ListAudit;
+use crate::application::backup::create::CreateBackup;
+use crate::application::backup::get::GetBackup;
+use crate::application::backup::list::ListBackups;
+use crate::application::backup::trigger::TriggerBackup;
+use crate::application::comment::approve::ApproveComment;
+use crate::application::comment::delete::DeleteComment;
+use crate::application::comment::list::ListComments;
+use crate::application::comment::queue::GetModerationQueue;
+use crate::application::comment::reject::RejectComment;
+use crate::application::comment::submit::SubmitComment;
 use crate::application::common::id_provider::IdProvider;
+use crate::application::common::job_handler::{JobHandler, ScheduledJob};
+use crate::application::common::mailer::Mailer;
+use crate::application::common::note_gateway::NoteGateway;
+use crate::application::common::project_gateway::ProjectGateway;
+use crate::application::common::unit_of_work::UnitOfWork;
+use crate::application::common::user_gateway::UserGateway;
+use crate::application::contact::deliver::DeliverContactMessages;
+use crate::application::contact::form::GetContactForm;
+use crate::application::contact::list::ListContactMessages;
+use crate::application::contact::submit::SubmitContact;
+use crate::application::counter::hit::HitCounter;
 use crate::application::create_session::CreateSession;
 use crate::application::get_user_self::GetUserSelf;
-use crate::CredentialsProvider;
+use crate::application::job::get::GetJob;
+use crate::application::job::list::ListJobs;
+use crate::application::job::prune::PruneJobs;
+use crate::application::job::recover::RecoverJobs;
+use crate::application::job::retry::RetryJob;
+use crate::application::job::run_next::RunNextJob;
+use crate::application::job::schedule::EnqueueScheduledJobs;
+use crate::application::media::get::GetMedia;
+use crate::application::media::upload::UploadMedia;
+use crate::application::newsletter::confirm::ConfirmSubscription;
+use crate::application::newsletter::digest::SendDigest;
+use crate::application::newsletter::subscribe::Subscribe;
+use crate::application::newsletter::unsubscribe::Unsubscribe;
+use crate::application::note::create::CreateNote;
+use crate::application::note::get_by_slug::GetBySlugNote;
+use crate::application::note::get_card::GetNoteCard;
+use crate::application::note::list::GetNoteList;
+use crate::application::note::update::UpdateNote;
+use crate::application::note::delete::DeleteNote;
+use crate::application::note::import::ImportNotes;
+use crate::application::archive::export::ExportContent;
+use crate::application::archive::restore::RestoreContent;
+use crate::application::project::create::CreateProject;
+use crate::application::project::list::GetProjectList;
+use crate::application::project::update::UpdateProject;
+use crate::application::project::delete::DeleteProject;
+use crate::application::stats::get::GetStats;
+use crate::application::user::create::CreateUser;
+use crate::application::user::list::GetUserList;
+use crate::application::user::set_password::SetUserPassword;
+use crate::application::user::revoke_sessions::RevokeUserSessions;
+use crate::application::stats::record::RecordPageView;
+use crate::application::stats::rollup::RollupPageViews;
+use crate::application::trash::list::ListTrash;
+use crate::application::trash::purge::PurgeFromTrash;
+use crate::application::trash::purge_expired::PurgeExpiredTrash;
+use crate::application::trash::restore::RestoreFromTrash;
+use crate::application::webhook::create::CreateWebhook;
+use crate::application::webhook::delete::DeleteWebhook;
+use crate::application::webhook::deliveries::ListWebhookDeliveries;
+use crate::application::webhook::list::ListWebhooks;
+use crate::application::webhook::process::ProcessWebhookDeliveries;
+use crate::application::webhook::redeliver::RedeliverWebhook;
+use crate::application::webhook::subscriber::WebhookSubscriber;
+use crate::application::webmention::list::ListWebmentions;
+use crate::application::webmention::receive::ReceiveWebmention;
+use crate::config::{BackupConfig, ContactConfig};
+use crate::domain::models::actor::LocalActor;
+use crate::domain::models::backup::BackupRetention;
 use crate::domain::services::note::NoteService;
 use crate::domain::services::project::ProjectService;
-use crate::domain::services::validator::ValidatorService;
 use crate::presentation::interactor_factory::InteractorFactory;
 
 pub struct IoC {
-    note_gateway: NoteGateway,
+    note_gateway: Box<dyn NoteGateway>,
     note_service: NoteService,
 
-    project_gateway: ProjectGateway,
+    project_gateway: Box<dyn ProjectGateway>,
     project_service: ProjectService,
 
-    password_hasher: Argon2PasswordHasher,
-    validator: ValidatorService,
-    credential_provider: CredentialsProvider,
+    user_gateway: Box<dyn UserGateway>,
+    unit_of_work: Box<dyn UnitOfWork>,
+
+    media_gateway: MediaGateway,
+    media_storage: LocalMediaStorage,
+    image_processor: RasterImageProcessor,
+
+    card_renderer: ResvgCardRenderer,
+    card_cache: MemorySocialCardCache,
+
+    comment_gateway: CommentGateway,
+    comment_rate_limiter: MemoryRateLimiter,
+
+    webmention_gateway: WebmentionGateway,
+    webmention_client: Arc<HttpWebmentionClient>,
+    webmention_rate_limiter: MemoryRateLimiter,
+
+    actor: LocalActor,
+    actor_key_gateway: ActorKeyGateway,
+    follower_gateway: FollowerGateway,
+    delivery_gateway: DeliveryGateway,
+    federation_client: HttpFederationClient,
+
+    subscriber_gateway: SubscriberGateway,
+    newsletter_rate_limiter: MemoryRateLimiter,
+    mailer: Box<dyn Mailer>,
+
+    contact_gateway: ContactMessageGateway,
+    contact_rate_limiter: MemoryRateLimiter,
+    contact: ContactConfig,
+
+    page_view_gateway: PageViewGateway,
+    daily_stat_gateway: DailyStatGateway,
+    visitor_salt: MemoryVisitorSalt,
+    page_view_rate_limiter: MemoryRateLimiter,
+
+    counter_gateway: CounterGateway,
+    counter_renderer: AssetCounterRenderer,
+    counter_dedupe: MemoryRateLimiter,
+
+    backup_storage: SqliteBackupStorage,
+    backup_retention: BackupRetention,
+    backup_integrity_check: bool,
+
+    trash_retention: Duration,
+
+    audit_gateway: AuditGateway,
+    event_bus: InProcessEventBus,
+
+    webhook_gateway: WebhookGateway,
+    webhook_client: HttpWebhookClient,
+
+    job_gateway: JobGateway,
+    job_retention: Duration,
+
+    site_url: String,
+    secret_key: Vec<u8>,
 }
 
 impl IoC {
     pub fn new(
-        db_pool: DbPool,
-        credential_provider: CredentialsProvider,
+        db_pool: DbPools,
+        backend: Backend,
+        media_storage: LocalMediaStorage,
+        card_renderer: ResvgCardRenderer,
+        actor: LocalActor,
+        federation_client: HttpFederationClient,
+        webmention_client: Arc<HttpWebmentionClient>,
+        mailer: Box<dyn Mailer>,
+        secret_key: Vec<u8>,
+        contact: ContactConfig,
+        counter_renderer: AssetCounterRenderer,
+        counter_dedupe_window: Duration,
+        backup: BackupConfig,
+        trash_retention: Duration,
+        job_retention: Duration,
     ) -> Self {
         Self {
-            note_gateway: NoteGateway::new(db_pool.clone()),
+            note_gateway: backend.note_gateway(),
             note_service: NoteService { },
 
-            project_gateway: ProjectGateway::new(db_pool.clone()),
+            project_gateway: backend.project_gateway(),
             project_service: ProjectService { },
 
-            password_hasher: Argon2PasswordHasher::new(),
-            validator: ValidatorService::new(),
-            credential_provider,
+            user_gateway: backend.user_gateway(),
+            unit_of_work: backend.unit_of_work(),
+
+            media_gateway: MediaGateway::new(db_pool.clone()),
+            media_storage,
+            image_processor: RasterImageProcessor::new(),
+
+            card_renderer,
+            card_cache: MemorySocialCardCache::new(),
+
+            comment_gateway: CommentGateway::new(db_pool.clone()),
+            // A reader hardly writes more than a few comments in ten minutes
+            comment_rate_limiter: MemoryRateLimiter::new(5, Duration::from_secs(600)),
+
+            webmention_gateway: WebmentionGateway::new(db_pool.clone()),
+            webmention_client,
+            // Each mention costs an outgoing fetch of its source
+            webmention_rate_limiter: MemoryRateLimiter::new(30, Duration::from_secs(600)),
+
+            site_url: actor.site_url.clone(),
+            actor,
+            actor_key_gateway: ActorKeyGateway::new(db_pool.clone()),
+            follower_gateway: FollowerGateway::new(db_pool.clone()),
+            delivery_gateway: DeliveryGateway::new(db_pool.clone()),
+            federation_client,
+
+            subscriber_gateway: SubscriberGateway::new(db_pool.clone()),
+            // Every attempt sends a mail, keep it from becoming a mail bomb
+            newsletter_rate_limiter: MemoryRateLimiter::new(3, Duration::from_secs(3600)),
+            mailer,
+
+            contact_gateway: ContactMessageGateway::new(db_pool.clone()),
+            contact_rate_limiter: MemoryRateLimiter::new(5, Duration::from_secs(3600)),
+            contact,
+
+            page_view_gateway: PageViewGateway::new(db_pool.clone()),
+            daily_stat_gateway: DailyStatGateway::new(db_pool.clone()),
+            visitor_salt: MemoryVisitorSalt::new(),
+            // Far above anyone reading, keeps a script from filling the table
+            page_view_rate_limiter: MemoryRateLimiter::new(60, Duration::from_secs(60)),
+
+            counter_gateway: CounterGateway::new(db_pool.clone()),
+            counter_renderer,
+            counter_dedupe: MemoryRateLimiter::new(1, counter_dedupe_window),
+
+            backup_storage: SqliteBackupStorage::new(db_pool.reader.clone(), backup.dir),
+            backup_retention: BackupRetention {
+                daily: backup.keep_daily,
+                weekly: backup.keep_weekly,
+                monthly: backup.keep_monthly
+            },
+            backup_integrity_check: backup.integrity_check,
+
+            trash_retention,
+
+            audit_gateway: AuditGateway::new(db_pool.clone()),
+            event_bus: InProcessEventBus::new(vec![
+                Arc::new(LogSubscriber),
+                Arc::new(WebhookSubscriber {
+                    webhook_gateway: Box::new(WebhookGateway::new(db_pool.clone()))
+                }),
+            ]),
+
+            webhook_gateway: WebhookGateway::new(db_pool.clone()),
+            webhook_client: HttpWebhookClient::new(),
+
+            job_gateway: JobGateway::new(db_pool.clone()),
+            job_retention,
+
+            secret_key,
         }
     }
+
+    /// Waits for the subscribers to handle the events published so far
+    pub async fn flush_events(&self) {
+        self.event_bus.flush().await;
+    }
 }
 
 impl InteractorFactory for IoC {
@@ -49,12 +289,537 @@ impl InteractorFactory for IoC {
         }
     }
 
+    fn create_user(&self, id_provider: Box<dyn IdProvider>) -> CreateUser {
+        CreateUser {
+            id_provider,
+            unit_of_work: &*self.unit_of_work,
+            event_publisher: &self.event_bus
+        }
+    }
+
+    fn get_user_list(&self, id_provider: Box<dyn IdProvider>) -> GetUserList {
+        GetUserList {
+            id_provider,
+            user_reader: &*self.user_gateway
+        }
+    }
+
+    fn set_user_password(&self, id_provider: Box<dyn IdProvider>) -> SetUserPassword {
+        SetUserPassword {
+            id_provider,
+            unit_of_work: &*self.unit_of_work
+        }
+    }
+
+    fn revoke_user_sessions(&self, id_provider: Box<dyn IdProvider>) -> RevokeUserSessions {
+        RevokeUserSessions {
+            id_provider,
+            user_reader: &*self.user_gateway,
+            job_writer: &self.job_gateway,
+            audit_writer: &self.audit_gateway
+        }
+    }
+
     fn create_session(&self, id_provider: Box<dyn IdProvider>) -> CreateSession {
         CreateSession {
             id_provider,
-            password_hasher: &self.password_hasher,
-            validator: &self.validator,
-            credential_provider: &self.credential_provider
+            user_reader: &*self.user_gateway,
+            audit_writer: &self.audit_gateway,
+            event_publisher: &self.event_bus
+        }
+    }
+
+    fn create_note(&self, id_provider: Box<dyn IdProvider>) -> CreateNote {
+        CreateNote {
+            unit_of_work: &*self.unit_of_work,
+            webmention_client: &*self.webmention_client,
+            follower_reader: &self.follower_gateway,
+            delivery_writer: &self.delivery_gateway,
+            actor: &self.actor,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn get_note_by_slug(&self) -> GetBySlugNote {
+        GetBySlugNote {
+            note_reader: &*self.note_gateway,
+            media_reader: &self.media_gateway
+        }
+    }
+
+    fn get_note_card(&self) -> GetNoteCard {
+        GetNoteCard {
+            note_reader: &*self.note_gateway,
+            card_renderer: &self.card_renderer,
+            card_cache: &self.card_cache
+        }
+    }
+
+    fn get_note_list(&self) -> GetNoteList {
+        GetNoteList {
+            note_reader: &*self.note_gateway
+        }
+    }
+
+    fn update_note(&self, id_provider: Box<dyn IdProvider>) -> UpdateNote {
+        UpdateNote {
+            unit_of_work: &*self.unit_of_work,
+            webmention_client: &*self.webmention_client,
+            follower_reader: &self.follower_gateway,
+            delivery_writer: &self.delivery_gateway,
+            actor: &self.actor,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn delete_note(&self, id_provider: Box<dyn IdProvider>) -> DeleteNote {
+        DeleteNote {
+            unit_of_work: &*self.unit_of_work,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn import_notes(&self, id_provider: Box<dyn IdProvider>) -> ImportNotes {
+        ImportNotes {
+            note_reader: &*self.note_gateway,
+            note_writer: &*self.note_gateway,
+            audit_writer: &self.audit_gateway,
+            id_provider
+        }
+    }
+
+    fn export_content(&self, id_provider: Box<dyn IdProvider>) -> ExportContent {
+        ExportContent {
+            unit_of_work: &*self.unit_of_work,
+            media_reader: &self.media_gateway,
+            id_provider
+        }
+    }
+
+    fn restore_content(&self, id_provider: Box<dyn IdProvider>) -> RestoreContent {
+        RestoreContent {
+            unit_of_work: &*self.unit_of_work,
+            media_gateway: &self.media_gateway,
+            media_storage: &self.media_storage,
+            id_provider
+        }
+    }
+
+    fn get_project_list(&self) -> GetProjectList {
+        GetProjectList {
+            project_reader: &*self.project_gateway
+        }
+    }
+
+    fn create_project(&self, id_provider: Box<dyn IdProvider>) -> CreateProject {
+        CreateProject {
+            unit_of_work: &*self.unit_of_work,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn update_project(&self, id_provider: Box<dyn IdProvider>) -> UpdateProject {
+        UpdateProject {
+            unit_of_work: &*self.unit_of_work,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn delete_project(&self, id_provider: Box<dyn IdProvider>) -> DeleteProject {
+        DeleteProject {
+            unit_of_work: &*self.unit_of_work,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn list_trash(&self, id_provider: Box<dyn IdProvider>) -> ListTrash {
+        ListTrash {
+            note_reader: &*self.note_gateway,
+            project_reader: &*self.project_gateway,
+            id_provider
+        }
+    }
+
+    fn restore_from_trash(&self, id_provider: Box<dyn IdProvider>) -> RestoreFromTrash {
+        RestoreFromTrash {
+            note_remover: &*self.note_gateway,
+            project_remover: &*self.project_gateway,
+            audit_writer: &self.audit_gateway,
+            event_publisher: &self.event_bus,
+            id_provider
+        }
+    }
+
+    fn purge_from_trash(&self, id_provider: Box<dyn IdProvider>) -> PurgeFromTrash {
+        PurgeFromTrash {
+            note_remover: &*self.note_gateway,
+            project_remover: &*self.project_gateway,
+            comment_remover: &self.comment_gateway,
+            webmention_remover: &self.webmention_gateway,
+            audit_writer: &self.audit_gateway,
+            id_provider
+        }
+    }
+
+    fn purge_expired_trash(&self) -> PurgeExpiredTrash {
+        PurgeExpiredTrash {
+            note_remover: &*self.note_gateway,
+            project_remover: &*self.project_gateway,
+            comment_remover: &self.comment_gateway,
+            webmention_remover: &self.webmention_gateway,
+            retention: &self.trash_retention
+        }
+    }
+
+    fn list_audit(&self, id_provider: Box<dyn IdProvider>) -> ListAudit {
+        ListAudit {
+            audit_reader: &self.audit_gateway,
+            id_provider
+        }
+    }
+
+    fn create_webhook(&self, id_provider: Box<dyn IdProvider>) -> CreateWebhook {
+        CreateWebhook {
+            webhook_writer: &self.webhook_gateway,
+            id_provider
+        }
+    }
+
+    fn list_webhooks(&self, id_provider: Box<dyn IdProvider>) -> ListWebhooks {
+        ListWebhooks {
+            webhook_reader: &self.webhook_gateway,
+            id_provider
+        }
+    }
+
+    fn delete_webhook(&self, id_provider: Box<dyn IdProvider>) -> DeleteWebhook {
+        DeleteWebhook {
+            webhook_remover: &self.webhook_gateway,
+            id_provider
+        }
+    }
+
+    fn list_webhook_deliveries(&self, id_provider: Box<dyn IdProvider>) -> ListWebhookDeliveries {
+        ListWebhookDeliveries {
+            webhook_reader: &self.webhook_gateway,
+            delivery_reader: &self.webhook_gateway,
+            id_provider
+        }
+    }
+
+    fn redeliver_webhook(&self, id_provider: Box<dyn IdProvider>) -> RedeliverWebhook {
+        RedeliverWebhook {
+            delivery_reader: &self.webhook_gateway,
+            delivery_writer: &self.webhook_gateway,
+            id_provider
+        }
+    }
+
+    fn process_webhook_deliveries(&self) -> ProcessWebhookDeliveries {
+        ProcessWebhookDeliveries {
+            webhook_gateway: &self.webhook_gateway,
+            webhook_client: &self.webhook_client
+        }
+    }
+
+    fn run_next_job<'a>(&'a self, handlers: &'a [Arc<dyn JobHandler>]) -> RunNextJob<'a> {
+        RunNextJob {
+            job_writer: &self.job_gateway,
+            handlers
+        }
+    }
+
+    fn enqueue_scheduled_jobs<'a>(&'a self, schedules: &'a [ScheduledJob]) -> EnqueueScheduledJobs<'a> {
+        EnqueueScheduledJobs {
+            job_gateway: &self.job_gateway,
+            schedules
+        }
+    }
+
+    fn recover_jobs(&self) -> RecoverJobs {
+        RecoverJobs {
+            job_writer: &self.job_gateway
+        }
+    }
+
+    fn prune_jobs(&self) -> PruneJobs {
+        PruneJobs {
+            job_writer: &self.job_gateway,
+            retention: &self.job_retention
+        }
+    }
+
+    fn list_jobs(&self, id_provider: Box<dyn IdProvider>) -> ListJobs {
+        ListJobs {
+            job_reader: &self.job_gateway,
+            id_provider
+        }
+    }
+
+    fn get_job(&self, id_provider: Box<dyn IdProvider>) -> GetJob {
+        GetJob {
+            job_reader: &self.job_gateway,
+            id_provider
+        }
+    }
+
+    fn retry_job(&self, id_provider: Box<dyn IdProvider>) -> RetryJob {
+        RetryJob {
+            job_reader: &self.job_gateway,
+            job_writer: &self.job_gateway,
+            id_provider
+        }
+    }
+
+    fn upload_media(&self, id_provider: Box<dyn IdProvider>) -> UploadMedia {
+        UploadMedia {
+            id_provider,
+            media_gateway: &self.media_gateway,
+            media_storage: &self.media_storage,
+            image_processor: &self.image_processor
+        }
+    }
+
+    fn get_media(&self) -> GetMedia {
+        GetMedia {
+            media_reader: &self.media_gateway,
+            media_storage: &self.media_storage
+        }
+    }
+
+    fn submit_comment(&self) -> SubmitComment {
+        SubmitComment {
+            comment_gateway: &self.comment_gateway,
+            note_reader: &*self.note_gateway,
+            rate_limiter: &self.comment_rate_limiter
+        }
+    }
+
+    fn list_comments(&self) -> ListComments {
+        ListComments {
+            comment_reader: &self.comment_gateway
+        }
+    }
+
+    fn get_moderation_queue(&self, id_provider: Box<dyn IdProvider>) -> GetModerationQueue {
+        GetModerationQueue {
+            id_provider,
+            comment_reader: &self.comment_gateway
+        }
+    }
+
+    fn approve_comment(&self, id_provider: Box<dyn IdProvider>) -> ApproveComment {
+        ApproveComment {
+            id_provider,
+            comment_gateway: &self.comment_gateway
+        }
+    }
+
+    fn reject_comment(&self, id_provider: Box<dyn IdProvider>) -> RejectComment {
+        RejectComment {
+            id_provider,
+            comment_gateway: &self.comment_gateway
+        }
+    }
+
+    fn delete_comment(&self, id_provider: Box<dyn IdProvider>) -> DeleteComment {
+        DeleteComment {
+            id_provider,
+            comment_gateway: &self.comment_gateway
+        }
+    }
+
+    fn receive_webmention(&self) -> ReceiveWebmention {
+        ReceiveWebmention {
+            webmention_gateway: &self.webmention_gateway,
+            webmention_client: &*self.webmention_client,
+            note_reader: &*self.note_gateway,
+            rate_limiter: &self.webmention_rate_limiter,
+            site_url: &self.site_url
+        }
+    }
+
+    fn list_webmentions(&self) -> ListWebmentions {
+        ListWebmentions {
+            webmention_reader: &self.webmention_gateway
+        }
+    }
+
+    fn get_webfinger(&self) -> GetWebfinger {
+        GetWebfinger {
+            actor: &self.actor
+        }
+    }
+
+    fn get_actor(&self) -> GetActor {
+        GetActor {
+            actor: &self.actor,
+            actor_key_reader: &self.actor_key_gateway
+        }
+    }
+
+    fn receive_activity(&self) -> ReceiveActivity {
+        ReceiveActivity {
+            actor: &self.actor,
+            follower_gateway: &self.follower_gateway,
+            delivery_writer: &self.delivery_gateway,
+            federation_client: &self.federation_client
+        }
+    }
+
+    fn get_outbox(&self) -> GetOutbox {
+        GetOutbox {
+            actor: &self.actor,
+            note_reader: &*self.note_gateway
+        }
+    }
+
+    fn get_followers(&self) -> GetFollowers {
+        GetFollowers {
+            actor: &self.actor,
+            follower_reader: &self.follower_gateway
+        }
+    }
+
+    fn process_deliveries(&self) -> ProcessDeliveries {
+        ProcessDeliveries {
+            delivery_gateway: &self.delivery_gateway,
+            federation_client: &self.federation_client
+        }
+    }
+
+    fn subscribe(&self) -> Subscribe {
+        Subscribe {
+            subscriber_gateway: &self.subscriber_gateway,
+            mailer: self.mailer.as_ref(),
+            rate_limiter: &self.newsletter_rate_limiter,
+            site_url: &self.site_url,
+            secret: &self.secret_key
+        }
+    }
+
+    fn confirm_subscription(&self) -> ConfirmSubscription {
+        ConfirmSubscription {
+            subscriber_gateway: &self.subscriber_gateway,
+            secret: &self.secret_key
+        }
+    }
+
+    fn unsubscribe(&self) -> Unsubscribe {
+        Unsubscribe {
+            subscriber_gateway: &self.subscriber_gateway,
+            secret: &self.secret_key
+        }
+    }
+
+    fn send_digest(&self) -> SendDigest {
+        SendDigest {
+            subscriber_gateway: &self.subscriber_gateway,
+            note_reader: &*self.note_gateway,
+            mailer: self.mailer.as_ref(),
+            site_url: &self.site_url,
+            secret: &self.secret_key
+        }
+    }
+
+    fn get_contact_form(&self) -> GetContactForm {
+        GetContactForm {
+            secret: &self.secret_key,
+            pow_difficulty: self.contact.pow_difficulty
+        }
+    }
+
+    fn submit_contact(&self) -> SubmitContact {
+        SubmitContact {
+            contact_gateway: &self.contact_gateway,
+            mailer: self.mailer.as_ref(),
+            rate_limiter: &self.contact_rate_limiter,
+            recipient: &self.contact.recipient,
+            secret: &self.secret_key,
+            pow_difficulty: self.contact.pow_difficulty
+        }
+    }
+
+    fn deliver_contact_messages(&self) -> DeliverContactMessages {
+        DeliverContactMessages {
+            contact_gateway: &self.contact_gateway,
+            mailer: self.mailer.as_ref(),
+            recipient: &self.contact.recipient
+        }
+    }
+
+    fn list_contact_messages(&self, id_provider: Box<dyn IdProvider>) -> ListContactMessages {
+        ListContactMessages {
+            id_provider,
+            contact_reader: &self.contact_gateway
+        }
+    }
+
+    fn record_page_view(&self) -> RecordPageView {
+        RecordPageView {
+            page_view_writer: &self.page_view_gateway,
+            visitor_salt: &self.visitor_salt,
+            rate_limiter: &self.page_view_rate_limiter,
+            site_url: &self.site_url
+        }
+    }
+
+    fn rollup_page_views(&self) -> RollupPageViews {
+        RollupPageViews {
+            page_view_gateway: &self.page_view_gateway,
+            daily_stat_writer: &self.daily_stat_gateway
+        }
+    }
+
+    fn get_stats(&self, id_provider: Box<dyn IdProvider>) -> GetStats {
+        GetStats {
+            id_provider,
+            daily_stat_reader: &self.daily_stat_gateway
         }
     }
-}
\ No newline at end of file
+
+    fn hit_counter(&self) -> HitCounter {
+        HitCounter {
+            counter_gateway: &self.counter_gateway,
+            counter_renderer: &self.counter_renderer,
+            dedupe: &self.counter_dedupe
+        }
+    }
+
+    fn create_backup(&self) -> CreateBackup {
+        CreateBackup {
+            backup_storage: &self.backup_storage,
+            retention: &self.backup_retention,
+            integrity_check: self.backup_integrity_check
+        }
+    }
+
+    fn trigger_backup(&self, id_provider: Box<dyn IdProvider>) -> TriggerBackup {
+        TriggerBackup {
+            id_provider,
+            backup_storage: &self.backup_storage,
+            retention: &self.backup_retention,
+            integrity_check: self.backup_integrity_check
+        }
+    }
+
+    fn list_backups(&self, id_provider: Box<dyn IdProvider>) -> ListBackups {
+        ListBackups {
+            id_provider,
+            backup_storage: &self.backup_storage
+        }
+    }
+
+    fn get_backup(&self, id_provider: Box<dyn IdProvider>) -> GetBackup {
+        GetBackup {
+            id_provider,
+            backup_storage: &self.backup_storage
+        }
+    }
+}
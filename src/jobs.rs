@@ -0,0 +1,345 @@
+//! Periodic work of the blog, run as background jobs. Each handler wraps
+//! one of the scheduled interactors and logs what it did, the schedules
+//! come from the configuration. Session revocations queued by the command
+//! line are run here too, the sessions are in the memory of the server,
+//! and so are the webmentions queued as they are received or sent
+
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use serde_json::Value;
+use crate::adapters::auth::token::TokenProcessor;
+use crate::adapters::webmention::http_client::{HttpWebmentionClient, SEND_JOB, VERIFY_JOB};
+use crate::application::common::interactor::Interactor;
+use crate::application::common::job_handler::{JobHandler, ScheduledJob};
+use crate::application::user::revoke_sessions::REVOKE_SESSIONS_JOB;
+use crate::config::Config;
+use crate::domain::models::job::JobStatus;
+use crate::ioc::IoC;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+/// How often the schedules are checked for due jobs
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(5);
+
+const ACTIVITYPUB_DELIVERIES: &str = "activitypub.deliveries";
+const WEBHOOK_DELIVERIES: &str = "webhook.deliveries";
+const CONTACT_RETRY: &str = "contact.retry";
+const STATS_ROLLUP: &str = "stats.rollup";
+const BACKUP: &str = "backup";
+const TRASH_PURGE: &str = "trash.purge";
+const NEWSLETTER_DIGEST: &str = "newsletter.digest";
+const JOBS_PRUNE: &str = "jobs.prune";
+
+pub fn handlers(
+    ioc: &Arc<IoC>,
+    token_processor: &Arc<TokenProcessor>,
+    webmention_client: &Arc<HttpWebmentionClient>
+) -> Vec<Arc<dyn JobHandler>> {
+    vec![
+        Arc::new(ActivityPubDeliveries { ioc: ioc.clone() }),
+        Arc::new(WebhookDeliveries { ioc: ioc.clone() }),
+        Arc::new(ContactRetry { ioc: ioc.clone() }),
+        Arc::new(StatsRollup { ioc: ioc.clone() }),
+        Arc::new(Backup { ioc: ioc.clone() }),
+        Arc::new(TrashPurge { ioc: ioc.clone() }),
+        Arc::new(NewsletterDigest { ioc: ioc.clone() }),
+        Arc::new(JobsPrune { ioc: ioc.clone() }),
+        Arc::new(SessionsRevoke { token_processor: token_processor.clone() }),
+        Arc::new(WebmentionVerify { webmention_client: webmention_client.clone() }),
+        Arc::new(WebmentionSend { webmention_client: webmention_client.clone() }),
+    ]
+}
+
+/// Expressions are checked when the configuration is loaded
+pub fn schedules(config: &Config) -> Vec<ScheduledJob> {
+    let mut schedules = vec![
+        (ACTIVITYPUB_DELIVERIES, "@every 30s".to_string()),
+        (WEBHOOK_DELIVERIES, "@every 15s".to_string()),
+        (CONTACT_RETRY, "@every 10m".to_string()),
+        (STATS_ROLLUP, "@hourly".to_string()),
+        (NEWSLETTER_DIGEST, format!("@every {}h", config.mail.digest_interval_hours)),
+        (JOBS_PRUNE, "@hourly".to_string()),
+    ];
+    match (&config.backup.schedule, config.backup.interval_hours) {
+        (Some(schedule), _) => schedules.push((BACKUP, schedule.clone())),
+        (None, 0) => {}
+        (None, hours) => schedules.push((BACKUP, format!("@every {}h", hours))),
+    }
+    if config.trash_retention_days > 0 {
+        schedules.push((TRASH_PURGE, "@hourly".to_string()));
+    }
+
+    schedules.into_iter()
+        .map(|(kind, expression)| ScheduledJob::new(kind, &expression).unwrap())
+        .collect()
+}
+
+/// Queues jobs left running by the previous process again, then starts
+/// the scheduler and `workers` workers on the current runtime
+pub async fn spawn(
+    ioc: Arc<IoC>,
+    handlers: Vec<Arc<dyn JobHandler>>,
+    schedules: Vec<ScheduledJob>,
+    workers: u32,
+    poll_interval: Duration
+) {
+    match ioc.recover_jobs().execute(()).await {
+        Ok(0) => {}
+        Ok(count) => log::warn!("Jobs: {} interrupted jobs queued again", count),
+        Err(error) => log::error!("Failed to recover jobs: {}", error.to_string())
+    }
+
+    let scheduler_ioc = ioc.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(SCHEDULE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match scheduler_ioc.enqueue_scheduled_jobs(&schedules).execute(()).await {
+                Ok(result) if result.skipped > 0 => log::warn!(
+                    "Jobs: {} scheduled, {} skipped while the previous run is pending",
+                    result.enqueued, result.skipped
+                ),
+                Ok(_) => {}
+                Err(error) => log::error!("Failed to schedule jobs: {}", error.to_string())
+            }
+        }
+    });
+
+    let handlers: Arc<[Arc<dyn JobHandler>]> = handlers.into();
+    for _ in 0..workers {
+        let worker_ioc = ioc.clone();
+        let handlers = handlers.clone();
+        actix_web::rt::spawn(async move {
+            loop {
+                match worker_ioc.run_next_job(&handlers).execute(()).await {
+                    Ok(Some(job)) => {
+                        match job.status {
+                            JobStatus::Dead => log::error!(
+                                "Job {} ({}) gave up after {} attempts: {}",
+                                job.id, job.kind, job.attempts, job.last_error.unwrap_or_default()
+                            ),
+                            JobStatus::Queued => log::warn!(
+                                "Job {} ({}) failed, attempt {} of {}: {}",
+                                job.id, job.kind, job.attempts, job.max_attempts, job.last_error.unwrap_or_default()
+                            ),
+                            _ => log::debug!("Job {} ({}) done", job.id, job.kind)
+                        }
+                        // There may be more due, no need to wait
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(error) => log::error!("Failed to run a job: {}", error.to_string())
+                }
+                actix_web::rt::time::sleep(poll_interval).await;
+            }
+        });
+    }
+}
+
+struct ActivityPubDeliveries {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for ActivityPubDeliveries {
+    fn kind(&self) -> &'static str {
+        ACTIVITYPUB_DELIVERIES
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let result = self.ioc.process_deliveries().execute(()).await.map_err(|e| e.to_string())?;
+        // Failed deliveries keep their own backoff, the error only shows up in the job log
+        if result.retried + result.dropped > 0 {
+            return Err(format!(
+                "{} delivered, {} to retry, {} dropped",
+                result.delivered, result.retried, result.dropped
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct WebhookDeliveries {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for WebhookDeliveries {
+    fn kind(&self) -> &'static str {
+        WEBHOOK_DELIVERIES
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let result = self.ioc.process_webhook_deliveries().execute(()).await.map_err(|e| e.to_string())?;
+        // Same as ActivityPub deliveries, each delivery is retried on its own schedule
+        if result.retried + result.failed > 0 {
+            return Err(format!(
+                "{} delivered, {} to retry, {} failed",
+                result.delivered, result.retried, result.failed
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct ContactRetry {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for ContactRetry {
+    fn kind(&self) -> &'static str {
+        CONTACT_RETRY
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let result = self.ioc.deliver_contact_messages().execute(()).await.map_err(|e| e.to_string())?;
+        if result.failed > 0 {
+            log::warn!("Contact messages: {} delivered, {} still pending", result.delivered, result.failed);
+        }
+        Ok(())
+    }
+}
+
+struct StatsRollup {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for StatsRollup {
+    fn kind(&self) -> &'static str {
+        STATS_ROLLUP
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        self.ioc.rollup_page_views().execute(()).await.map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+struct Backup {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for Backup {
+    fn kind(&self) -> &'static str {
+        BACKUP
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let result = self.ioc.create_backup().execute(()).await.map_err(|e| e.to_string())?;
+        log::info!(
+            "Backup {} ({} bytes), {} rotated out",
+            result.backup.name, result.backup.size, result.removed.len()
+        );
+        Ok(())
+    }
+}
+
+struct TrashPurge {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for TrashPurge {
+    fn kind(&self) -> &'static str {
+        TRASH_PURGE
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let result = self.ioc.purge_expired_trash().execute(()).await.map_err(|e| e.to_string())?;
+        if result.notes + result.projects > 0 {
+            log::info!("Trash: {} notes and {} projects purged", result.notes, result.projects);
+        }
+        Ok(())
+    }
+}
+
+struct NewsletterDigest {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for NewsletterDigest {
+    fn kind(&self) -> &'static str {
+        NEWSLETTER_DIGEST
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let result = self.ioc.send_digest().execute(()).await.map_err(|e| e.to_string())?;
+        if result.sent + result.failed > 0 {
+            log::info!("Newsletter digest: {} sent, {} failed", result.sent, result.failed);
+        }
+        Ok(())
+    }
+}
+
+struct JobsPrune {
+    ioc: Arc<IoC>
+}
+
+#[async_trait]
+impl JobHandler for JobsPrune {
+    fn kind(&self) -> &'static str {
+        JOBS_PRUNE
+    }
+
+    async fn run(&self, _payload: &Value) -> Result<(), String> {
+        let removed = self.ioc.prune_jobs().execute(()).await.map_err(|e| e.to_string())?;
+        log::debug!("Jobs: {} done jobs removed", removed);
+        Ok(())
+    }
+}
+
+struct SessionsRevoke {
+    token_processor: Arc<TokenProcessor>
+}
+
+#[async_trait]
+impl JobHandler for SessionsRevoke {
+    fn kind(&self) -> &'static str {
+        REVOKE_SESSIONS_JOB
+    }
+
+    async fn run(&self, payload: &Value) -> Result<(), String> {
+        let username = payload["username"].as_str().ok_or("Payload has no username")?;
+        let revoked = self.token_processor.revoke_user_sessions(username);
+        log::info!("Sessions: {} sessions of {} revoked", revoked, username);
+        Ok(())
+    }
+}
+
+struct WebmentionVerify {
+    webmention_client: Arc<HttpWebmentionClient>
+}
+
+#[async_trait]
+impl JobHandler for WebmentionVerify {
+    fn kind(&self) -> &'static str {
+        VERIFY_JOB
+    }
+
+    async fn run(&self, payload: &Value) -> Result<(), String> {
+        let id = payload["id"].as_str().ok_or("Payload has no id")?;
+        self.webmention_client.verify(&id.to_string()).await
+    }
+}
+
+struct WebmentionSend {
+    webmention_client: Arc<HttpWebmentionClient>
+}
+
+#[async_trait]
+impl JobHandler for WebmentionSend {
+    fn kind(&self) -> &'static str {
+        SEND_JOB
+    }
+
+    async fn run(&self, payload: &Value) -> Result<(), String> {
+        let source = payload["source"].as_str().ok_or("Payload has no source")?;
+        let html = payload["html"].as_str().ok_or("Payload has no html")?;
+        self.webmention_client.send(source, html).await;
+        Ok(())
+    }
+}
@@ -2,13 +2,29 @@ use std::fs::File;
 use std::io::BufReader;
 use std::net::TcpListener;
 use std::sync::Arc;
+use std::time::Duration;
 use actix_web::{App, HttpServer, web};
 use actix_web::middleware::Logger;
-use sqlx::sqlite::SqlitePoolOptions;
+use clap::Parser;
+use crate::adapters::activitypub::http_client::HttpFederationClient;
+use crate::adapters::activitypub::keys::{load_actor_key, load_or_create_actor_key};
 use crate::adapters::argon2_password_hasher::Argon2PasswordHasher;
 use crate::adapters::auth::token::TokenProcessor;
+use crate::adapters::counter::asset_renderer::AssetCounterRenderer;
+use crate::adapters::database::actor_key_db::ActorKeyGateway;
+use crate::adapters::database::backend::Backend;
 use crate::adapters::database::initial::initial_models;
-use crate::application::common::hasher::Hasher;
+use crate::adapters::database::job_db::JobGateway;
+use crate::adapters::database::pool::{connect, DbOptions};
+use crate::adapters::database::webmention_db::WebmentionGateway;
+use crate::adapters::mailer::file::FileMailer;
+use crate::adapters::mailer::smtp::SmtpMailer;
+use crate::adapters::media::local_storage::LocalMediaStorage;
+use crate::adapters::social_card::resvg_renderer::ResvgCardRenderer;
+use crate::adapters::webmention::http_client::HttpWebmentionClient;
+use crate::application::common::mailer::Mailer;
+use crate::cli::{Cli, Command, ConfigCommand};
+use crate::domain::models::actor::LocalActor;
 use crate::ioc::IoC;
 use crate::presentation::interactor_factory::InteractorFactory;
 
@@ -17,45 +33,162 @@ mod application;
 mod presentation;
 mod domain;
 mod config;
+mod cli;
 mod ioc;
-
-pub struct CredentialsProvider {
-    pub username: String,
-    pub hashed_password: String,
-}
-
+mod jobs;
 
 #[actix_web::main]
 async fn main() {
     const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-    let config = config::Config::from_env();
+    let cli = Cli::parse();
+
+    // The logger is not set up yet, and these errors must be seen anyway
+    let (config, raw_config) = config::Config::load(cli.config.as_deref()).unwrap_or_else(|errors| {
+        eprintln!("Invalid configuration:");
+        for error in errors {
+            eprintln!("  {}", error);
+        }
+        std::process::exit(1);
+    });
+
+    if let Some(Command::Config { command: ConfigCommand::Check }) = cli.command {
+        print!("{}", raw_config.redacted());
+        return;
+    }
+
     pretty_env_logger::init_custom_env("LOG_LEVEL");
 
+    // Taken before the configuration is picked apart below
+    let job_schedules = jobs::schedules(&config);
+
     // Initial
-    let db_pool = SqlitePoolOptions::new()
-        .max_connections(100) // todo: test it
-        .connect(&"sqlite://database?mode=rwc".to_string()).await.map_err(
+    let db_pool = connect(&DbOptions {
+        url: &config.database.url,
+        writers: config.database.writers,
+        readers: config.database.readers,
+        busy_timeout: Duration::from_millis(config.database.busy_timeout_ms)
+    }).await.map_err(
         |error| {
             log::error!("Failed to connect to database: {}", error.to_string());
             std::process::exit(1);
         }
     ).unwrap();
 
-    initial_models(&db_pool).await.map_err(
+    initial_models(&db_pool.writer).await.map_err(
         |error| {
             log::error!("Failed to initial models: {}", error.to_string());
             std::process::exit(1);
         }
     ).unwrap();
 
-    let credentials_provider = CredentialsProvider {
-        username: config.credentials.username,
-        hashed_password: Argon2PasswordHasher::new().hash(&config.credentials.password).await,
+    let backend = Backend::connect(&db_pool, config.database.postgres_url.as_deref()).await.map_err(
+        |error| {
+            log::error!("Failed to connect to Postgres: {}", error);
+            std::process::exit(1);
+        }
+    ).unwrap();
+
+    let media_storage = LocalMediaStorage::new(&config.media_dir);
+    let logo = std::fs::read("assets/images/logo/overlord.webp").unwrap_or_else(|error| {
+        log::warn!("Failed to read logo for social cards: {}", error.to_string());
+        Vec::new()
+    });
+    let card_renderer = ResvgCardRenderer::new(&logo);
+    let actor = LocalActor::new(&config.site_url, &config.actor_username);
+    // Commands leave the key and the mail transport alone, they must work
+    // while the server is not set up yet or its SMTP settings are broken
+    let actor_key_gateway = ActorKeyGateway::new(db_pool.clone());
+    let actor_key = match cli.command {
+        Some(_) => load_actor_key(&actor_key_gateway).await,
+        None => load_or_create_actor_key(&actor_key_gateway).await
     };
-    let ioc = Arc::new(IoC::new(db_pool, credentials_provider));
+    let federation_client = HttpFederationClient::new(actor_key, actor.key_id());
+
+    let mail_from = config.mail.from.parse().map_err(
+        |error: lettre::address::AddressError| {
+            log::error!("Invalid mail.from: {}", error.to_string());
+            std::process::exit(1);
+        }
+    ).unwrap();
+    let mailer: Box<dyn Mailer> = match config.mail.smtp {
+        Some(_) if cli.command.is_some() => Box::new(FileMailer::new(&config.mail.dir, mail_from)),
+        Some(smtp) => Box::new(SmtpMailer::new(
+            &smtp.host,
+            smtp.port,
+            &smtp.username,
+            &smtp.password,
+            mail_from
+        ).map_err(
+            |error| {
+                log::error!("Failed to configure SMTP: {}", error);
+                std::process::exit(1);
+            }
+        ).unwrap()),
+        None => {
+            log::warn!("SMTP is not configured, mail is written to {}", config.mail.dir);
+            Box::new(FileMailer::new(&config.mail.dir, mail_from))
+        }
+    };
+    let secret_key = match config.secret_key {
+        Some(secret_key) => secret_key.into_bytes(),
+        None => {
+            log::warn!("secret_key is not set, links in sent mail will stop working after restart");
+            rand::random::<[u8; 32]>().to_vec()
+        }
+    };
+
+    let counter_renderer = AssetCounterRenderer::load("assets/counter");
+    // Shared with the jobs, which verify and send webmentions
+    let webmention_client = Arc::new(HttpWebmentionClient::new(
+        Box::new(WebmentionGateway::new(db_pool.clone())),
+        Box::new(JobGateway::new(db_pool.clone())),
+        &actor.site_url
+    ));
+
+    let ioc = Arc::new(IoC::new(
+        db_pool,
+        backend,
+        media_storage,
+        card_renderer,
+        actor,
+        federation_client,
+        webmention_client.clone(),
+        mailer,
+        secret_key,
+        config.contact,
+        counter_renderer,
+        Duration::from_secs(config.counter_dedupe_minutes * 60),
+        config.backup.clone(),
+        Duration::from_secs(config.trash_retention_days * 86_400),
+        Duration::from_secs(config.jobs.keep_done_hours * 3600)
+    ));
+
+    if let Some(command) = cli.command {
+        let result = presentation::cli::run(command, &*ioc, &Argon2PasswordHasher::new(), cli.json).await;
+        // Webhooks of what the command did are queued by the event subscribers
+        ioc.flush_events().await;
+        if let Err(error) = result {
+            presentation::cli::print_error(&error, cli.json);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Shared with the jobs, which revoke sessions
+    let token_processor = Arc::new(TokenProcessor::new());
+
+    jobs::spawn(
+        ioc.clone(),
+        jobs::handlers(&ioc, &token_processor, &webmention_client),
+        job_schedules,
+        config.jobs.workers,
+        Duration::from_millis(config.jobs.poll_interval_ms)
+    ).await;
 
-    let token_processor = web::Data::new(TokenProcessor::new());
+    let token_processor = web::Data::from(token_processor);
+    let trusted_proxies = web::Data::new(presentation::client_ip::TrustedProxies(config.trusted_proxies.clone()));
+    let site = web::Data::new(presentation::rest::page::Site { url: config.site_url.clone() });
 
     let app_builder = move || {
         let ioc_arc: Arc<dyn InteractorFactory> = ioc.clone();
@@ -65,8 +198,29 @@ async fn main() {
             .service(web::scope("/api")
                 .configure(presentation::rest::user::router)
                 .configure(presentation::rest::session::router)
+                .configure(presentation::rest::note::router)
+                .configure(presentation::rest::project::router)
+                .configure(presentation::rest::media::router)
+                .configure(presentation::rest::comment::router)
+                .configure(presentation::rest::webmention::router)
+                .configure(presentation::rest::newsletter::router)
+                .configure(presentation::rest::contact::router)
+                .configure(presentation::rest::stats::router)
+                .configure(presentation::rest::backup::router)
+                .configure(presentation::rest::trash::router)
+                .configure(presentation::rest::audit::router)
+                .configure(presentation::rest::webhook::router)
+                .configure(presentation::rest::job::router)
             )
+            .configure(presentation::rest::media::files_router)
+            .configure(presentation::rest::card::router)
+            .configure(presentation::rest::webmention::endpoint_router)
+            .configure(presentation::rest::activitypub::router)
+            .configure(presentation::rest::counter::router)
+            .configure(presentation::rest::page::router)
             .app_data(token_processor.clone())
+            .app_data(site.clone())
+            .app_data(trusted_proxies.clone())
             .app_data(ioc_data)
             .default_service(web::route().to(presentation::rest::exception::not_found))
             .wrap(Logger::default())
@@ -0,0 +1,32 @@
+use crate::application::common::id_provider::IdProvider;
+
+/// Whoever can run the binary against the database is an administrator.
+/// Audit entries name the operating system user that ran the command
+pub struct CliIdProvider {
+    username: Option<String>,
+    is_auth: bool
+}
+
+impl CliIdProvider {
+    pub fn new() -> Self {
+        let user = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+        Self {
+            username: Some(format!("cli:{}", user)),
+            is_auth: true
+        }
+    }
+}
+
+impl IdProvider for CliIdProvider {
+    fn session(&self) -> Option<&String> {
+        None
+    }
+
+    fn username(&self) -> Option<&String> {
+        self.username.as_ref()
+    }
+
+    fn is_auth(&self) -> &bool {
+        &self.is_auth
+    }
+}
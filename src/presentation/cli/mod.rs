@@ -0,0 +1,237 @@
+//! Administrative commands. They go through the same interactors as the
+//! REST API, print a short summary or, with `--json`, the result as JSON
+
+use std::collections::HashMap;
+use std::io::{BufRead, IsTerminal, Write};
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::hasher::Hasher;
+use crate::application::common::interactor::Interactor;
+use crate::adapters::archive::local_archive::{read_archive, write_archive};
+use crate::adapters::import::markdown_files::read_directory;
+use crate::application::archive::restore::{RestoreContentRequest, RestoreCounts};
+use crate::application::job::list::ListJobsRequest;
+use crate::application::job::retry::RetryJobRequest;
+use crate::application::note::import::{ImportNotesRequest, ImportOutcome};
+use crate::application::user::create::CreateUserRequest;
+use crate::application::user::revoke_sessions::RevokeUserSessionsRequest;
+use crate::application::user::set_password::SetUserPasswordRequest;
+use crate::cli::{Command, JobsCommand, UserCommand};
+use crate::domain::id_generator::generate_id;
+use crate::presentation::cli::id_provider::CliIdProvider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub mod id_provider;
+
+pub async fn run(
+    command: Command,
+    ioc: &dyn InteractorFactory,
+    hasher: &dyn Hasher,
+    json: bool
+) -> Result<(), ApplicationError> {
+    let id_provider = || Box::new(CliIdProvider::new());
+
+    match command {
+        Command::Config { .. } => unreachable!("handled before the database is opened"),
+        // Tables and migrations are brought up to date on every start
+        Command::Migrate => print(json, &json!({ "migrated": true }), "Database is up to date".to_string()),
+        Command::User { command: UserCommand::List } => {
+            let users = ioc.get_user_list(id_provider()).execute(()).await?;
+            let human = users.iter()
+                .map(|user| format!("{}  {}", user.id, user.username))
+                .collect::<Vec<_>>()
+                .join("\n");
+            print(json, &users, human);
+        }
+        Command::User { command: UserCommand::Create { username } } => {
+            let password_hash = hasher.hash(&read_password()?).await;
+            ioc.create_user(id_provider()).execute(CreateUserRequest {
+                username: username.clone(),
+                password_hash,
+                ip: None
+            }).await?;
+            print(json, &json!({ "username": username }), format!("User {} created", username));
+        }
+        Command::User { command: UserCommand::SetPassword { username } } => {
+            let password_hash = hasher.hash(&read_password()?).await;
+            ioc.set_user_password(id_provider()).execute(SetUserPasswordRequest {
+                username: username.clone(),
+                password_hash,
+                ip: None
+            }).await?;
+            // Whoever knew the old password must not stay signed in
+            ioc.revoke_user_sessions(id_provider()).execute(RevokeUserSessionsRequest {
+                username: username.clone(),
+                ip: None
+            }).await?;
+            print(
+                json,
+                &json!({ "username": username, "sessions_revoked": true }),
+                format!("Password of {} changed, sessions revoked", username)
+            );
+        }
+        Command::User { command: UserCommand::RevokeSessions { username } } => {
+            ioc.revoke_user_sessions(id_provider()).execute(RevokeUserSessionsRequest {
+                username: username.clone(),
+                ip: None
+            }).await?;
+            print(
+                json,
+                &json!({ "username": username, "sessions_revoked": true }),
+                format!("Sessions of {} revoked", username)
+            );
+        }
+        Command::Jobs { command: JobsCommand::List { status, kind, limit } } => {
+            let result = ioc.list_jobs(id_provider()).execute(ListJobsRequest {
+                status,
+                kind,
+                cursor: None,
+                per_page: limit
+            }).await?;
+            let human = result.items.iter()
+                .map(|job| format!(
+                    "{}  {:<24} {:<8} {}/{}  {}{}",
+                    job.id,
+                    job.kind,
+                    job.status.as_str(),
+                    job.attempts,
+                    job.max_attempts,
+                    job.run_at.to_rfc3339(),
+                    job.last_error.as_ref().map(|e| format!("  {}", e)).unwrap_or_default()
+                ))
+                .collect::<Vec<_>>()
+                .join("\n");
+            print(json, &result.items, human);
+        }
+        Command::Jobs { command: JobsCommand::Retry { id } } => {
+            let job = ioc.retry_job(id_provider()).execute(RetryJobRequest { id }).await?;
+            print(json, &job, format!("Job {} ({}) queued again", job.id, job.kind));
+        }
+        Command::Import { dir, dry_run } => {
+            let files = read_directory(&dir).map_err(|e| ApplicationError::ValidationError(
+                HashMap::from([("dir".to_string(), e)])
+            ))?;
+            let result = ioc.import_notes(id_provider()).execute(ImportNotesRequest {
+                files,
+                dry_run,
+                ip: None
+            }).await?;
+
+            let mut lines = Vec::new();
+            for file in &result.files {
+                if file.outcome != ImportOutcome::Unchanged {
+                    lines.push(format!("{:<9} {}", format!("{:?}", file.outcome).to_lowercase(), file.path));
+                }
+                let mut errors = file.errors.iter().collect::<Vec<_>>();
+                errors.sort();
+                lines.extend(errors.iter().map(|(field, message)| format!("          {}: {}", field, message)));
+                lines.extend(file.warnings.iter().map(|warning| format!("          {}", warning)));
+            }
+            lines.push(format!(
+                "{}{} created, {} updated, {} unchanged, {} skipped, {} failed",
+                if result.dry_run { "Dry run: " } else { "" },
+                result.created,
+                result.updated,
+                result.unchanged,
+                result.skipped,
+                result.failed
+            ));
+            print(json, &result, lines.join("\n"));
+        }
+        Command::Export { path } => {
+            let result = ioc.export_content(id_provider()).execute(()).await?;
+            write_archive(&path, &result.entries).map_err(ApplicationError::UnexpectedError)?;
+            print(json, &result, format!(
+                "Exported {} notes, {} projects, {} media records and {} users to {}",
+                result.notes,
+                result.projects,
+                result.media,
+                result.users,
+                path.display()
+            ));
+        }
+        Command::Restore { path, dry_run } => {
+            let entries = read_archive(&path).map_err(|e| ApplicationError::ValidationError(
+                HashMap::from([("archive".to_string(), e)])
+            ))?;
+            // Nobody knows it, restored users sign in after `user set-password`
+            let password_hash = hasher.hash(&generate_id(32)).await;
+            let result = ioc.restore_content(id_provider()).execute(RestoreContentRequest {
+                entries,
+                dry_run,
+                password_hash,
+                ip: None
+            }).await?;
+
+            let counts = |name: &str, counts: &RestoreCounts| format!(
+                "{:<9} {} created, {} updated, {} unchanged, {} skipped in the trash",
+                name,
+                counts.created,
+                counts.updated,
+                counts.unchanged,
+                counts.skipped
+            );
+            let mut lines = vec![
+                counts("Notes", &result.notes),
+                counts("Projects", &result.projects),
+                counts("Media", &result.media),
+                counts("Users", &result.users),
+            ];
+            if !result.users_without_password.is_empty() {
+                lines.push(format!("Set passwords of {}", result.users_without_password.join(", ")));
+            }
+            if !result.missing_files.is_empty() {
+                lines.push(format!(
+                    "{} media files are not in the media storage, copy them from the old host",
+                    result.missing_files.len()
+                ));
+            }
+            if result.dry_run {
+                lines.insert(0, "Dry run, nothing was written".to_string());
+            }
+            print(json, &result, lines.join("\n"));
+        }
+    }
+    Ok(())
+}
+
+/// Errors go to stderr, as `{"error": ...}` with `--json`
+pub fn print_error(error: &ApplicationError, json: bool) {
+    match json {
+        true => eprintln!("{}", json!({ "error": error.to_string() })),
+        false => eprintln!("Error: {}", error)
+    }
+}
+
+fn print<T: Serialize>(json: bool, value: &T, human: String) {
+    match json {
+        true => println!("{}", serde_json::to_string_pretty(value).unwrap()),
+        false if human.is_empty() => {}
+        false => println!("{}", human)
+    }
+}
+
+/// Not hidden when typed, pipe it in to keep it off the screen
+fn read_password() -> Result<String, ApplicationError> {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        eprint!("Password: ");
+        let _ = std::io::stderr().flush();
+    }
+
+    let mut password = String::new();
+    stdin.lock().read_line(&mut password)
+        .map_err(|e| ApplicationError::UnexpectedError(e.to_string()))?;
+    let password = password.trim_end_matches(['\r', '\n']).to_string();
+
+    match password.is_empty() {
+        true => Err(ApplicationError::ValidationError(HashMap::from([(
+            "password".to_string(),
+            "Password is empty".to_string()
+        )]))),
+        false => Ok(password)
+    }
+}
@@ -0,0 +1,61 @@
+use std::net::IpAddr;
+
+use actix_web::{web, HttpRequest};
+
+/// Peers allowed to tell the client address, registered as app data
+pub struct TrustedProxies(pub Vec<IpAddr>);
+
+/// Address of the client. `X-Forwarded-For` is only taken from a trusted
+/// proxy, anyone else could write any address there. The list is read from
+/// the right, the first address that is not a trusted proxy is the client
+pub fn client_ip(req: &HttpRequest) -> String {
+    let Some(peer) = req.peer_addr().map(|addr| addr.ip()) else {
+        return "unknown".to_string();
+    };
+    let trusted = req.app_data::<web::Data<TrustedProxies>>()
+        .map_or(&[][..], |proxies| proxies.0.as_slice());
+    if !trusted.contains(&peer) {
+        return peer.to_string();
+    }
+
+    let forwarded: Vec<&str> = req.headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+    for address in forwarded.into_iter().rev() {
+        match address.parse::<IpAddr>() {
+            Ok(address) if trusted.contains(&address) => continue,
+            Ok(address) => return address.to_string(),
+            Err(_) => break
+        }
+    }
+    peer.to_string()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+    use super::*;
+
+    fn request(peer: &str, forwarded: Option<&str>) -> HttpRequest {
+        let mut request = TestRequest::default()
+            .peer_addr(format!("{}:4000", peer).parse().unwrap())
+            .app_data(web::Data::new(TrustedProxies(vec!["127.0.0.1".parse().unwrap()])));
+        if let Some(forwarded) = forwarded {
+            request = request.insert_header(("X-Forwarded-For", forwarded));
+        }
+        request.to_http_request()
+    }
+
+    #[test]
+    fn test_client_ip() {
+        // Straight from the client, the header is its own claim
+        assert_eq!(client_ip(&request("203.0.113.5", Some("10.0.0.1"))), "203.0.113.5");
+        // Through the proxy, which appends the address it saw
+        assert_eq!(client_ip(&request("127.0.0.1", Some("10.0.0.1, 203.0.113.5"))), "203.0.113.5");
+        assert_eq!(client_ip(&request("127.0.0.1", None)), "127.0.0.1");
+    }
+}
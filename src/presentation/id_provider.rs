@@ -3,7 +3,6 @@ use actix_web::HttpRequest;
 use crate::adapters::auth::token::{IdTokenProvider, TokenProcessor};
 use crate::application::common::exceptions::{ApplicationError, ErrorContent};
 use crate::application::common::id_provider::IdProvider;
-use crate::CredentialsProvider;
 
 pub fn make_token_provider(
     req: &HttpRequest,
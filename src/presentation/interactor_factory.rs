@@ -1,8 +1,144 @@
+use std::sync::Arc;
+use crate::application::activitypub::actor::GetActor;
+use crate::application::activitypub::deliver::ProcessDeliveries;
+use crate::application::activitypub::followers::GetFollowers;
+use crate::application::activitypub::inbox::ReceiveActivity;
+use crate::application::activitypub::outbox::GetOutbox;
+use crate::application::activitypub::webfinger::GetWebfinger;
+use crate::application::audit::list::ListAudit;
+use crate::application::backup::create::CreateBackup;
+use crate::application::backup::get::GetBackup;
+use crate::application::backup::list::ListBackups;
+use crate::application::backup::trigger::TriggerBackup;
+use crate::application::comment::approve::ApproveComment;
+use crate::application::comment::delete::DeleteComment;
+use crate::application::comment::list::ListComments;
+use crate::application::comment::queue::GetModerationQueue;
+use crate::application::comment::reject::RejectComment;
+use crate::application::comment::submit::SubmitComment;
 use crate::application::common::id_provider::IdProvider;
+use crate::application::common::job_handler::{JobHandler, ScheduledJob};
+use crate::application::contact::deliver::DeliverContactMessages;
+use crate::application::contact::form::GetContactForm;
+use crate::application::contact::list::ListContactMessages;
+use crate::application::contact::submit::SubmitContact;
+use crate::application::counter::hit::HitCounter;
 use crate::application::create_session::CreateSession;
 use crate::application::get_user_self::GetUserSelf;
+use crate::application::job::get::GetJob;
+use crate::application::job::list::ListJobs;
+use crate::application::job::prune::PruneJobs;
+use crate::application::job::recover::RecoverJobs;
+use crate::application::job::retry::RetryJob;
+use crate::application::job::run_next::RunNextJob;
+use crate::application::job::schedule::EnqueueScheduledJobs;
+use crate::application::media::get::GetMedia;
+use crate::application::media::upload::UploadMedia;
+use crate::application::newsletter::confirm::ConfirmSubscription;
+use crate::application::newsletter::digest::SendDigest;
+use crate::application::newsletter::subscribe::Subscribe;
+use crate::application::newsletter::unsubscribe::Unsubscribe;
+use crate::application::note::create::CreateNote;
+use crate::application::note::delete::DeleteNote;
+use crate::application::note::import::ImportNotes;
+use crate::application::archive::export::ExportContent;
+use crate::application::archive::restore::RestoreContent;
+use crate::application::note::get_by_slug::GetBySlugNote;
+use crate::application::note::get_card::GetNoteCard;
+use crate::application::note::list::GetNoteList;
+use crate::application::note::update::UpdateNote;
+use crate::application::project::create::CreateProject;
+use crate::application::project::delete::DeleteProject;
+use crate::application::project::list::GetProjectList;
+use crate::application::project::update::UpdateProject;
+use crate::application::stats::get::GetStats;
+use crate::application::user::create::CreateUser;
+use crate::application::user::list::GetUserList;
+use crate::application::user::set_password::SetUserPassword;
+use crate::application::user::revoke_sessions::RevokeUserSessions;
+use crate::application::stats::record::RecordPageView;
+use crate::application::stats::rollup::RollupPageViews;
+use crate::application::trash::list::ListTrash;
+use crate::application::trash::purge::PurgeFromTrash;
+use crate::application::trash::purge_expired::PurgeExpiredTrash;
+use crate::application::trash::restore::RestoreFromTrash;
+use crate::application::webhook::create::CreateWebhook;
+use crate::application::webhook::delete::DeleteWebhook;
+use crate::application::webhook::deliveries::ListWebhookDeliveries;
+use crate::application::webhook::list::ListWebhooks;
+use crate::application::webhook::process::ProcessWebhookDeliveries;
+use crate::application::webhook::redeliver::RedeliverWebhook;
+use crate::application::webmention::list::ListWebmentions;
+use crate::application::webmention::receive::ReceiveWebmention;
 
 pub trait InteractorFactory {
     fn get_user_self(&self, id_provider: Box<dyn IdProvider>) -> GetUserSelf;
+    fn create_user(&self, id_provider: Box<dyn IdProvider>) -> CreateUser;
+    fn get_user_list(&self, id_provider: Box<dyn IdProvider>) -> GetUserList;
+    fn set_user_password(&self, id_provider: Box<dyn IdProvider>) -> SetUserPassword;
+    fn revoke_user_sessions(&self, id_provider: Box<dyn IdProvider>) -> RevokeUserSessions;
     fn create_session(&self, id_provider: Box<dyn IdProvider>) -> CreateSession;
+    fn create_note(&self, id_provider: Box<dyn IdProvider>) -> CreateNote;
+    fn get_note_by_slug(&self) -> GetBySlugNote;
+    fn get_note_card(&self) -> GetNoteCard;
+    fn get_note_list(&self) -> GetNoteList;
+    fn update_note(&self, id_provider: Box<dyn IdProvider>) -> UpdateNote;
+    fn delete_note(&self, id_provider: Box<dyn IdProvider>) -> DeleteNote;
+    fn import_notes(&self, id_provider: Box<dyn IdProvider>) -> ImportNotes;
+    fn export_content(&self, id_provider: Box<dyn IdProvider>) -> ExportContent;
+    fn restore_content(&self, id_provider: Box<dyn IdProvider>) -> RestoreContent;
+    fn get_project_list(&self) -> GetProjectList;
+    fn create_project(&self, id_provider: Box<dyn IdProvider>) -> CreateProject;
+    fn update_project(&self, id_provider: Box<dyn IdProvider>) -> UpdateProject;
+    fn delete_project(&self, id_provider: Box<dyn IdProvider>) -> DeleteProject;
+    fn list_trash(&self, id_provider: Box<dyn IdProvider>) -> ListTrash;
+    fn restore_from_trash(&self, id_provider: Box<dyn IdProvider>) -> RestoreFromTrash;
+    fn purge_from_trash(&self, id_provider: Box<dyn IdProvider>) -> PurgeFromTrash;
+    fn purge_expired_trash(&self) -> PurgeExpiredTrash;
+    fn list_audit(&self, id_provider: Box<dyn IdProvider>) -> ListAudit;
+    fn create_webhook(&self, id_provider: Box<dyn IdProvider>) -> CreateWebhook;
+    fn list_webhooks(&self, id_provider: Box<dyn IdProvider>) -> ListWebhooks;
+    fn delete_webhook(&self, id_provider: Box<dyn IdProvider>) -> DeleteWebhook;
+    fn list_webhook_deliveries(&self, id_provider: Box<dyn IdProvider>) -> ListWebhookDeliveries;
+    fn redeliver_webhook(&self, id_provider: Box<dyn IdProvider>) -> RedeliverWebhook;
+    fn process_webhook_deliveries(&self) -> ProcessWebhookDeliveries;
+    fn run_next_job<'a>(&'a self, handlers: &'a [Arc<dyn JobHandler>]) -> RunNextJob<'a>;
+    fn enqueue_scheduled_jobs<'a>(&'a self, schedules: &'a [ScheduledJob]) -> EnqueueScheduledJobs<'a>;
+    fn recover_jobs(&self) -> RecoverJobs;
+    fn prune_jobs(&self) -> PruneJobs;
+    fn list_jobs(&self, id_provider: Box<dyn IdProvider>) -> ListJobs;
+    fn get_job(&self, id_provider: Box<dyn IdProvider>) -> GetJob;
+    fn retry_job(&self, id_provider: Box<dyn IdProvider>) -> RetryJob;
+    fn upload_media(&self, id_provider: Box<dyn IdProvider>) -> UploadMedia;
+    fn get_media(&self) -> GetMedia;
+    fn submit_comment(&self) -> SubmitComment;
+    fn list_comments(&self) -> ListComments;
+    fn get_moderation_queue(&self, id_provider: Box<dyn IdProvider>) -> GetModerationQueue;
+    fn approve_comment(&self, id_provider: Box<dyn IdProvider>) -> ApproveComment;
+    fn reject_comment(&self, id_provider: Box<dyn IdProvider>) -> RejectComment;
+    fn delete_comment(&self, id_provider: Box<dyn IdProvider>) -> DeleteComment;
+    fn receive_webmention(&self) -> ReceiveWebmention;
+    fn list_webmentions(&self) -> ListWebmentions;
+    fn get_webfinger(&self) -> GetWebfinger;
+    fn get_actor(&self) -> GetActor;
+    fn receive_activity(&self) -> ReceiveActivity;
+    fn get_outbox(&self) -> GetOutbox;
+    fn get_followers(&self) -> GetFollowers;
+    fn process_deliveries(&self) -> ProcessDeliveries;
+    fn subscribe(&self) -> Subscribe;
+    fn confirm_subscription(&self) -> ConfirmSubscription;
+    fn unsubscribe(&self) -> Unsubscribe;
+    fn send_digest(&self) -> SendDigest;
+    fn get_contact_form(&self) -> GetContactForm;
+    fn submit_contact(&self) -> SubmitContact;
+    fn deliver_contact_messages(&self) -> DeliverContactMessages;
+    fn list_contact_messages(&self, id_provider: Box<dyn IdProvider>) -> ListContactMessages;
+    fn record_page_view(&self) -> RecordPageView;
+    fn rollup_page_views(&self) -> RollupPageViews;
+    fn get_stats(&self, id_provider: Box<dyn IdProvider>) -> GetStats;
+    fn hit_counter(&self) -> HitCounter;
+    fn create_backup(&self) -> CreateBackup;
+    fn trigger_backup(&self, id_provider: Box<dyn IdProvider>) -> TriggerBackup;
+    fn list_backups(&self, id_provider: Box<dyn IdProvider>) -> ListBackups;
+    fn get_backup(&self, id_provider: Box<dyn IdProvider>) -> GetBackup;
 }
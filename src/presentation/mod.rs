@@ -1,4 +1,6 @@
 pub mod interactor_factory;
 pub mod rest;
 pub mod id_provider;
-pub mod pages;
\ No newline at end of file
+pub mod pages;
+pub mod client_ip;
+pub mod cli;
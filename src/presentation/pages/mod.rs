@@ -0,0 +1,2 @@
+pub mod note_meta;
+pub mod note;
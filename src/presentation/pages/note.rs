@@ -0,0 +1,85 @@
+use futures_util::StreamExt;
+use leptos::prelude::*;
+use leptos_meta::{provide_meta_context, ServerMetaContext};
+use crate::application::note::get_by_slug::GetBySlugNoteResult;
+use crate::presentation::pages::note_meta::NoteMeta;
+
+#[component]
+pub fn NotePage(
+    #[prop(into)] site_url: String,
+    note: GetBySlugNoteResult,
+) -> impl IntoView {
+    let published = note.created_at.format("%Y-%m-%d").to_string();
+
+    view! {
+        <NoteMeta
+            site_url=site_url
+            slug=note.slug
+            title=note.title.clone()
+            description=note.description
+            revision=note.revision
+            created_at=note.created_at
+            updated_at=note.updated_at
+        />
+        <article class="note">
+            <h1>{note.title}</h1>
+            <time datetime=note.created_at.to_rfc3339()>{published}</time>
+            <div class="note-body" inner_html=note.html></div>
+        </article>
+    }
+}
+
+/// The whole document, with the tags registered through `leptos_meta`
+/// moved into `<head>`
+pub async fn render_note_page(site_url: String, note: GetBySlugNoteResult) -> String {
+    let owner = Owner::new();
+    let (meta_context, meta_output) = ServerMetaContext::new();
+    let body = owner.with(|| {
+        provide_meta_context();
+        provide_context(meta_context);
+        view! { <NotePage site_url=site_url note=note/> }.to_html()
+    });
+
+    let document = format!(
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"/>\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\"/>\
+         </head><body>{}</body></html>",
+        body
+    );
+    meta_output.inject_meta_context(futures_util::stream::once(async move { document }).boxed())
+        .await
+        .collect::<String>()
+        .await
+}
+
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_meta_tags_in_head() {
+        let now = Utc::now();
+        let html = render_note_page("https://jkearnsl.su/".to_string(), GetBySlugNoteResult {
+            id: "AbCdEfGh12345678".to_string(),
+            slug: "hello".to_string(),
+            title: "Hello <world>".to_string(),
+            description: "First note".to_string(),
+            body: "Text".to_string(),
+            html: "<p>Text</p>".to_string(),
+            revision: 7,
+            created_at: now,
+            updated_at: None,
+            version: 1
+        }).await;
+
+        let head = &html[..html.find("</head>").unwrap()];
+        assert!(head.contains("<title>Hello &lt;world&gt;</title>"));
+        assert!(head.contains(r#"property="og:title""#));
+        assert!(head.contains(r#"content="https://jkearnsl.su/cards/notes/hello.png?v=7""#));
+        assert!(head.contains(r#"rel="canonical" href="https://jkearnsl.su/notes/hello""#));
+        assert!(head.contains("application/ld+json"));
+        assert!(html.contains("<p>Text</p>"));
+    }
+}
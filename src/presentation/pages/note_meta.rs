@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Script, Title};
+use crate::domain::models::note::note_url;
+
+/// `<head>` tags of a note page: Open Graph, Twitter card, canonical URL
+/// and JSON-LD, so links to the note unfurl in chats and search results
+#[component]
+pub fn NoteMeta(
+    /// Public origin of the site, e.g. `https://jkearnsl.su`
+    #[prop(into)] site_url: String,
+    #[prop(into)] slug: String,
+    #[prop(into)] title: String,
+    #[prop(into)] description: String,
+    revision: i64,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+) -> impl IntoView {
+    let site_url = site_url.trim_end_matches('/').to_string();
+    let url = note_url(&site_url, &slug);
+    let webmention = format!("{}/webmention", site_url);
+    let image = format!("{}/cards/notes/{}.png?v={}", site_url, slug, revision);
+    let json_ld = blog_posting(&url, &image, &title, &description, created_at, updated_at);
+
+    view! {
+        <Title text=title.clone()/>
+        <Meta name="description" content=description.clone()/>
+        <Link rel="canonical" href=url.clone()/>
+        <Link rel="webmention" href=webmention/>
+
+        <Meta property="og:type" content="article"/>
+        <Meta property="og:url" content=url/>
+        <Meta property="og:title" content=title.clone()/>
+        <Meta property="og:description" content=description.clone()/>
+        <Meta property="og:image" content=image.clone()/>
+        <Meta property="og:image:width" content="1200"/>
+        <Meta property="og:image:height" content="630"/>
+        <Meta property="article:published_time" content=created_at.to_rfc3339()/>
+
+        <Meta name="twitter:card" content="summary_large_image"/>
+        <Meta name="twitter:title" content=title/>
+        <Meta name="twitter:description" content=description/>
+        <Meta name="twitter:image" content=image/>
+
+        <Script type_="application/ld+json">{json_ld}</Script>
+    }
+}
+
+fn blog_posting(
+    url: &str,
+    image: &str,
+    title: &str,
+    description: &str,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+) -> String {
+    let json = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "BlogPosting",
+        "mainEntityOfPage": url,
+        "url": url,
+        "headline": title,
+        "description": description,
+        "image": image,
+        "datePublished": created_at.to_rfc3339(),
+        "dateModified": updated_at.unwrap_or(created_at).to_rfc3339(),
+        "author": {
+            "@type": "Person",
+            "name": "JKearnsl"
+        }
+    });
+    // A title containing `</script>` must not close the tag
+    json.to_string().replace("</", "<\\/")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blog_posting_escapes_script() {
+        let now = Utc::now();
+        let json = blog_posting("https://a/notes/x", "https://a/c.png", "</script><b>", "d", now, None);
+
+        assert!(!json.contains("</script>"));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["headline"], "</script><b>");
+        assert_eq!(value["@type"], "BlogPosting");
+    }
+}
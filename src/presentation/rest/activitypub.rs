@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use serde_json::Value;
+
+use crate::application::activitypub::webfinger::GetWebfingerRequest;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::federation_client::SignedRequest;
+use crate::application::common::interactor::Interactor;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+const ACTIVITY_JSON: &str = "application/activity+json";
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(webfinger)
+        .service(
+            web::scope("/actor")
+                .service(actor)
+                .service(inbox)
+                .service(outbox)
+                .service(followers)
+        );
+}
+
+fn activity_response(document: Value) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(ACTIVITY_JSON)
+        .body(document.to_string())
+}
+
+#[get("/.well-known/webfinger")]
+async fn webfinger(
+    data: web::Query<GetWebfingerRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_webfinger().execute(data.into_inner()).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/jrd+json")
+        .insert_header(("Access-Control-Allow-Origin", "*"))
+        .body(result.to_string()))
+}
+
+#[get("")]
+async fn actor(
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_actor().execute(()).await?;
+    Ok(activity_response(result))
+}
+
+#[post("/inbox")]
+async fn inbox(
+    req: HttpRequest,
+    body: web::Bytes,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut headers: HashMap<String, String> = HashMap::new();
+    for (name, value) in req.headers() {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        headers.entry(name.as_str().to_string())
+            .and_modify(|joined| {
+                joined.push_str(", ");
+                joined.push_str(value);
+            })
+            .or_insert_with(|| value.to_string());
+    }
+
+    ioc.receive_activity().execute(SignedRequest {
+        method: req.method().to_string(),
+        path: req.uri().path_and_query().map(|p| p.as_str()).unwrap_or("/").to_string(),
+        headers,
+        body: body.to_vec()
+    }).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[get("/outbox")]
+async fn outbox(
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_outbox().execute(()).await?;
+    Ok(activity_response(result))
+}
+
+#[get("/followers")]
+async fn followers(
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_followers().execute(()).await?;
+    Ok(activity_response(result))
+}
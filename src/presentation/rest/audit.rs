@@ -0,0 +1,35 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::audit::list::ListAuditRequest;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+use crate::presentation::rest::links::page_links;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/audit")
+            .service(list)
+    );
+}
+
+/// Filters: `actor`, `action`, `target`, `target_id`, `since` and `until` (RFC 3339)
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    data: web::Query<ListAuditRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let per_page = data.per_page;
+    let result = ioc.list_audit(id_provider).execute(data.into_inner()).await?;
+
+    let mut response = HttpResponse::Ok();
+    if let Some(links) = page_links(&req, per_page, result.next.as_ref(), result.prev.as_ref()) {
+        response.insert_header(("Link", links));
+    }
+    Ok(response.json(result))
+}
@@ -0,0 +1,62 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::backup::get::GetBackupRequest;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/backups")
+            .service(trigger)
+            .service(list)
+            .service(download)
+    );
+}
+
+#[post("")]
+async fn trigger(
+    req: HttpRequest,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.trigger_backup(id_provider).execute(()).await?;
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.list_backups(id_provider).execute(()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[get("/{name}")]
+async fn download(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.get_backup(id_provider).execute(GetBackupRequest {
+        name: path.into_inner()
+    }).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/gzip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(result.name)],
+        })
+        .insert_header(("Cache-Control", "no-store"))
+        .body(result.data))
+}
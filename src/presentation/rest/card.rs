@@ -0,0 +1,32 @@
+use actix_web::http::header::{CacheControl, CacheDirective, ContentType, EntityTag, ETag};
+use actix_web::{get, web, HttpResponse};
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::note::get_card::GetNoteCardRequest;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+/// Social preview images, mounted at the root
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(note_card);
+}
+
+/// Pages link the card with `?v=<revision>`, so a new revision gets a new URL
+#[get("/cards/notes/{slug}.png")]
+async fn note_card(
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_note_card().execute(GetNoteCardRequest {
+        slug: path.into_inner()
+    }).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentType::png())
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(86_400),
+        ]))
+        .insert_header(ETag(EntityTag::new_strong(result.revision.to_string())))
+        .body(result.card))
+}
@@ -0,0 +1,100 @@
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::comment::approve::ApproveCommentRequest;
+use crate::application::comment::delete::DeleteCommentRequest;
+use crate::application::comment::list::ListCommentsRequest;
+use crate::application::comment::reject::RejectCommentRequest;
+use crate::application::comment::submit::SubmitCommentRequest;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/comments")
+            .service(list)
+            .service(submit)
+            .service(queue)
+            .service(approve)
+            .service(reject)
+            .service(remove)
+    );
+}
+
+#[get("")]
+async fn list(
+    data: web::Query<ListCommentsRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.list_comments().execute(data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("")]
+async fn submit(
+    req: HttpRequest,
+    data: web::Json<SubmitCommentRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut data = data.into_inner();
+    data.ip = client_ip(&req);
+
+    let result = ioc.submit_comment().execute(data).await?;
+    Ok(HttpResponse::Accepted().json(result))
+}
+
+#[get("/queue")]
+async fn queue(
+    req: HttpRequest,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.get_moderation_queue(id_provider).execute(()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/{id}/approve")]
+async fn approve(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    ioc.approve_comment(id_provider).execute(ApproveCommentRequest {
+        id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/{id}/reject")]
+async fn reject(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    ioc.reject_comment(id_provider).execute(RejectCommentRequest {
+        id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[delete("/{id}")]
+async fn remove(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    ioc.delete_comment(id_provider).execute(DeleteCommentRequest {
+        id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
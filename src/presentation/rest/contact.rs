@@ -0,0 +1,55 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::contact::list::ListContactMessagesRequest;
+use crate::application::contact::submit::SubmitContactRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/contact")
+            .service(form)
+            .service(submit)
+            .service(messages)
+    );
+}
+
+/// Fetched when the form is shown, the token is valid for a day
+#[get("/form")]
+async fn form(
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_contact_form().execute(()).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header(("Cache-Control", "no-store"))
+        .json(result))
+}
+
+#[post("")]
+async fn submit(
+    req: HttpRequest,
+    data: web::Json<SubmitContactRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut data = data.into_inner();
+    data.ip = client_ip(&req);
+
+    ioc.submit_contact().execute(data).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[get("/messages")]
+async fn messages(
+    req: HttpRequest,
+    data: web::Query<ListContactMessagesRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.list_contact_messages(id_provider).execute(data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
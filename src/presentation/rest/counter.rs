@@ -0,0 +1,33 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::counter::hit::HitCounterRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(hit);
+}
+
+/// Embedded in READMEs, so the image must never be cached: GitHub camo
+/// and other image proxies refetch it only with these headers
+#[get("/counter/{name}.svg")]
+async fn hit(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Query<HitCounterRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut data = data.into_inner();
+    data.name = path.into_inner();
+    data.ip = client_ip(&req);
+
+    let svg = ioc.hit_counter().execute(data).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("image/svg+xml; charset=utf-8")
+        .insert_header(("Cache-Control", "max-age=0, no-cache, no-store, must-revalidate"))
+        .insert_header(("Pragma", "no-cache"))
+        .insert_header(("Expires", "0"))
+        .body(svg))
+}
@@ -0,0 +1,68 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::job::get::GetJobRequest;
+use crate::application::job::list::ListJobsRequest;
+use crate::application::job::retry::RetryJobRequest;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+use crate::presentation::rest::links::page_links;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/jobs")
+            .service(list)
+            .service(get)
+            .service(retry)
+    );
+}
+
+/// Filters: `status` (`queued`, `running`, `done` or `dead`) and `kind`
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    data: web::Query<ListJobsRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let per_page = data.per_page;
+    let result = ioc.list_jobs(id_provider).execute(data.into_inner()).await?;
+
+    let mut response = HttpResponse::Ok();
+    if let Some(links) = page_links(&req, per_page, result.next.as_ref(), result.prev.as_ref()) {
+        response.insert_header(("Link", links));
+    }
+    Ok(response.json(result))
+}
+
+#[get("/{id}")]
+async fn get(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.get_job(id_provider).execute(GetJobRequest {
+        id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// Only dead jobs, they get a fresh set of attempts
+#[post("/{id}/retry")]
+async fn retry(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.retry_job(id_provider).execute(RetryJobRequest {
+        id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::Accepted().json(result))
+}
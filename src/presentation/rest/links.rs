@@ -0,0 +1,36 @@
+use actix_web::HttpRequest;
+
+/// Builds an RFC 8288 `Link` header value pointing to the neighbouring pages,
+/// other query parameters such as filters are carried over
+pub fn page_links(
+    req: &HttpRequest,
+    per_page: u64,
+    next: Option<&String>,
+    prev: Option<&String>,
+) -> Option<String> {
+    let filters: String = req.query_string()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| !matches!(pair.split('=').next(), Some("cursor") | Some("per_page")))
+        .map(|pair| format!("&{}", pair))
+        .collect();
+
+    let link = |cursor: &String, rel: &str| format!(
+        "<{}?cursor={}&per_page={}{}>; rel=\"{}\"",
+        req.path(),
+        cursor,
+        per_page,
+        filters,
+        rel
+    );
+
+    let links: Vec<String> = [next.map(|c| link(c, "next")), prev.map(|c| link(c, "prev"))]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    match links.is_empty() {
+        true => None,
+        false => Some(links.join(", "))
+    }
+}
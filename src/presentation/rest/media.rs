@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::http::header::{CacheControl, CacheDirective, ContentType, EntityTag, ETag};
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use futures_util::StreamExt;
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::media::get::GetMediaRequest;
+use crate::application::media::upload::UploadMediaRequest;
+use crate::domain::models::media::MEDIA_SIZE_MAX;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+/// Upload endpoint, mounted under `/api`
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/media")
+            .service(upload)
+    );
+}
+
+/// Public file serving, mounted at the root
+pub fn files_router(cfg: &mut web::ServiceConfig) {
+    cfg.service(serve);
+}
+
+#[post("")]
+async fn upload(
+    req: HttpRequest,
+    mut payload: Multipart,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    if !id_provider.is_auth() {
+        // Do not read the body of anonymous uploads at all
+        return Err(ApplicationError::Unauthorized);
+    }
+
+    let too_large = || ApplicationError::ValidationError(HashMap::from([(
+        "file".to_string(),
+        format!("is too large: > {}", MEDIA_SIZE_MAX)
+    )]));
+    let bad_request = |e: actix_multipart::MultipartError| ApplicationError::ValidationError(
+        HashMap::from([("file".to_string(), e.to_string())])
+    );
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(bad_request)?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let name = field.content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(bad_request)?;
+            if data.len() + chunk.len() > MEDIA_SIZE_MAX {
+                return Err(too_large());
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let result = ioc.upload_media(id_provider).execute(UploadMediaRequest {
+            name,
+            data
+        }).await?;
+        return Ok(HttpResponse::Created().json(result));
+    }
+
+    Err(ApplicationError::ValidationError(HashMap::from([(
+        "file".to_string(),
+        "is required".to_string()
+    )])))
+}
+
+#[get("/media/{hash}")]
+async fn serve(
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_media().execute(GetMediaRequest {
+        hash: path.into_inner()
+    }).await?;
+
+    let mime = result.mime.parse().map_err(|_| {
+        ApplicationError::UnexpectedError(format!("Stored mime {} is not valid", result.mime))
+    })?;
+
+    // Content never changes under the same address
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentType(mime))
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(31_536_000),
+            CacheDirective::Extension("immutable".to_string(), None),
+        ]))
+        .insert_header(ETag(EntityTag::new_strong(result.hash.to_string())))
+        .insert_header(("X-Content-Type-Options", "nosniff"))
+        .body(result.data))
+}
@@ -0,0 +1,22 @@
+pub mod exception;
+pub mod user;
+pub mod session;
+pub mod note;
+pub mod project;
+pub mod media;
+pub mod card;
+pub mod comment;
+pub mod webmention;
+pub mod activitypub;
+pub mod newsletter;
+pub mod contact;
+pub mod stats;
+pub mod counter;
+pub mod backup;
+pub mod trash;
+pub mod audit;
+pub mod webhook;
+pub mod job;
+pub mod page;
+mod links;
+mod version;
@@ -0,0 +1,71 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::newsletter::confirm::ConfirmSubscriptionRequest;
+use crate::application::newsletter::subscribe::SubscribeRequest;
+use crate::application::newsletter::unsubscribe::UnsubscribeRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/newsletter")
+            .service(subscribe)
+            .service(confirm)
+            .service(unsubscribe_form)
+            .service(unsubscribe)
+    );
+}
+
+/// Links from mail are opened in a browser, so they answer with a bare page
+fn page(message: &str) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(format!(
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Newsletter</title></head>\
+             <body><p>{}</p></body></html>",
+            message
+        ))
+}
+
+#[post("/subscribe")]
+async fn subscribe(
+    req: HttpRequest,
+    data: web::Json<SubscribeRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut data = data.into_inner();
+    data.ip = client_ip(&req);
+
+    ioc.subscribe().execute(data).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[get("/confirm")]
+async fn confirm(
+    data: web::Query<ConfirmSubscriptionRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    ioc.confirm_subscription().execute(data.into_inner()).await?;
+    Ok(page("Subscription confirmed, thank you!"))
+}
+
+/// Mail scanners follow links, so a plain GET only asks for confirmation
+#[get("/unsubscribe")]
+async fn unsubscribe_form(req: HttpRequest) -> HttpResponse {
+    page(&format!(
+        "<form method=\"post\" action=\"?{}\"><button type=\"submit\">Unsubscribe</button></form>",
+        req.query_string().replace('"', "&quot;")
+    ))
+}
+
+/// Also the RFC 8058 one-click endpoint, the body is `List-Unsubscribe=One-Click`
+#[post("/unsubscribe")]
+async fn unsubscribe(
+    data: web::Query<UnsubscribeRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    ioc.unsubscribe().execute(data.into_inner()).await?;
+    Ok(page("You are unsubscribed."))
+}
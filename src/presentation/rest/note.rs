@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use actix_multipart::Multipart;
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use futures_util::StreamExt;
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::adapters::import::markdown_files::{read_zip, ZIP_SIZE_MAX};
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::note::create::CreateNoteRequest;
+use crate::application::note::get_by_slug::GetBySlugNoteRequest;
+use crate::application::note::delete::DeleteNoteRequest;
+use crate::application::note::import::ImportNotesRequest;
+use crate::application::note::list::GetNoteListRequest;
+use crate::application::note::update::UpdateNoteRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+use crate::presentation::rest::links::page_links;
+use crate::presentation::rest::version::{conflict, if_match, version_etag};
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/notes")
+            .service(list)
+            .service(create)
+            .service(import)
+            .service(get_by_slug)
+            .service(update)
+            .service(remove)
+    );
+}
+
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    data: web::Query<GetNoteListRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let per_page = data.per_page;
+    let result = ioc.get_note_list().execute(data.into_inner()).await?;
+
+    let mut response = HttpResponse::Ok();
+    if let Some(links) = page_links(&req, per_page, result.next.as_ref(), result.prev.as_ref()) {
+        response.insert_header(("Link", links));
+    }
+    Ok(response.json(result))
+}
+
+#[get("/{slug}")]
+async fn get_by_slug(
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.get_note_by_slug().execute(GetBySlugNoteRequest {
+        slug: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::Ok()
+        .insert_header(version_etag(result.version))
+        .json(result))
+}
+
+#[post("")]
+async fn create(
+    req: HttpRequest,
+    data: web::Json<CreateNoteRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let mut data = data.into_inner();
+    data.ip = Some(client_ip(&req));
+    let result = ioc.create_note(id_provider).execute(data).await?;
+    Ok(HttpResponse::Created().json(result))
+}
+
+/// A zip of Markdown files with front matter in the `file` field,
+/// `?dry_run=true` only reports what would change
+#[post("/import")]
+async fn import(
+    req: HttpRequest,
+    data: web::Query<ImportNotesRequest>,
+    mut payload: Multipart,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    if !id_provider.is_auth() {
+        return Err(ApplicationError::Unauthorized);
+    }
+
+    let invalid = |message: String| ApplicationError::ValidationError(HashMap::from([(
+        "file".to_string(),
+        message
+    )]));
+
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| invalid(e.to_string()))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let mut archive = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| invalid(e.to_string()))?;
+            if archive.len() + chunk.len() > ZIP_SIZE_MAX {
+                return Err(invalid(format!("is too large: > {}", ZIP_SIZE_MAX)));
+            }
+            archive.extend_from_slice(&chunk);
+        }
+
+        let mut data = data.into_inner();
+        // Unpacking is blocking work, keep it off the worker thread
+        data.files = web::block(move || read_zip(archive)).await
+            .map_err(|e| ApplicationError::UnexpectedError(e.to_string()))?
+            .map_err(invalid)?;
+        data.ip = Some(client_ip(&req));
+        let result = ioc.import_notes(id_provider).execute(data).await?;
+        return Ok(HttpResponse::Ok().json(result));
+    }
+
+    Err(invalid("is required".to_string()))
+}
+
+/// The expected version comes from `If-Match` or the `version` field
+#[put("/{id}")]
+async fn update(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Json<UpdateNoteRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let mut data = data.into_inner();
+    data.id = path.into_inner();
+    data.version = if_match(&req).or(data.version);
+    data.ip = Some(client_ip(&req));
+
+    match ioc.update_note(id_provider).execute(data).await {
+        Ok(result) => Ok(HttpResponse::Ok()
+            .insert_header(version_etag(result.version))
+            .json(result)),
+        Err(ApplicationError::Conflict { current_version }) => Ok(conflict(current_version)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Moves it to the trash, see `/trash`
+#[delete("/{id}")]
+async fn remove(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    ioc.delete_note(id_provider).execute(DeleteNoteRequest {
+        id: path.into_inner(),
+        ip: Some(client_ip(&req))
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
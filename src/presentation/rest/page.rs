@@ -0,0 +1,50 @@
+use actix_web::http::header::{self, ContentType};
+use actix_web::{get, web, HttpRequest, HttpResponse};
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::note::get_by_slug::GetBySlugNoteRequest;
+use crate::application::stats::record::RecordPageViewRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::interactor_factory::InteractorFactory;
+use crate::presentation::pages::note::render_note_page;
+
+/// Settings of the rendered pages, registered as app data
+pub struct Site {
+    /// Public origin used in canonical and Open Graph URLs
+    pub url: String
+}
+
+/// Server-rendered pages, mounted at the root
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(note_page);
+}
+
+/// Views are counted here, the pages carry no script to report them
+#[get("/notes/{slug}")]
+async fn note_page(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    site: web::Data<Site>,
+) -> Result<HttpResponse, ApplicationError> {
+    let note = ioc.get_note_by_slug().execute(GetBySlugNoteRequest {
+        slug: path.into_inner()
+    }).await?;
+
+    // A view that is not counted, rate-limited or from a bot, still gets its page
+    let header_value = |name: header::HeaderName| req.headers().get(name)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    ioc.record_page_view().execute(RecordPageViewRequest {
+        path: req.path().to_string(),
+        referrer: header_value(header::REFERER),
+        ip: client_ip(&req),
+        user_agent: header_value(header::USER_AGENT)
+    }).await.ok();
+
+    Ok(HttpResponse::Ok()
+        .insert_header(ContentType::html())
+        .body(render_note_page(site.url.clone(), note).await))
+}
@@ -0,0 +1,97 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::project::create::CreateProjectRequest;
+use crate::application::project::delete::DeleteProjectRequest;
+use crate::application::project::list::GetProjectListRequest;
+use crate::application::project::update::UpdateProjectRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+use crate::presentation::rest::links::page_links;
+use crate::presentation::rest::version::{conflict, if_match, version_etag};
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/projects")
+            .service(list)
+            .service(create)
+            .service(update)
+            .service(remove)
+    );
+}
+
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    data: web::Query<GetProjectListRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let per_page = data.per_page;
+    let result = ioc.get_project_list().execute(data.into_inner()).await?;
+
+    let mut response = HttpResponse::Ok();
+    if let Some(links) = page_links(&req, per_page, result.next.as_ref(), result.prev.as_ref()) {
+        response.insert_header(("Link", links));
+    }
+    Ok(response.json(result))
+}
+
+#[post("")]
+async fn create(
+    req: HttpRequest,
+    data: web::Json<CreateProjectRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let mut data = data.into_inner();
+    data.ip = Some(client_ip(&req));
+    let result = ioc.create_project(id_provider).execute(data).await?;
+    Ok(HttpResponse::Created()
+        .insert_header(version_etag(result.version))
+        .json(result))
+}
+
+/// Same contract as `PUT /notes/{id}`
+#[put("/{id}")]
+async fn update(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Json<UpdateProjectRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let mut data = data.into_inner();
+    data.id = path.into_inner();
+    data.version = if_match(&req).or(data.version);
+    data.ip = Some(client_ip(&req));
+
+    match ioc.update_project(id_provider).execute(data).await {
+        Ok(result) => Ok(HttpResponse::Ok()
+            .insert_header(version_etag(result.version))
+            .json(result)),
+        Err(ApplicationError::Conflict { current_version }) => Ok(conflict(current_version)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Moves it to the trash, see `/trash`
+#[delete("/{id}")]
+async fn remove(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    ioc.delete_project(id_provider).execute(DeleteProjectRequest {
+        id: path.into_inner(),
+        ip: Some(client_ip(&req))
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,51 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web::http::header;
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::stats::get::GetStatsRequest;
+use crate::application::stats::record::RecordPageViewRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/stats")
+            .service(stats)
+            .service(record)
+    );
+}
+
+#[get("")]
+async fn stats(
+    req: HttpRequest,
+    data: web::Query<GetStatsRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.get_stats(id_provider).execute(data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// For pages rendered elsewhere, sent with `navigator.sendBeacon`, no cookies are involved.
+/// Note pages served here count their views themselves
+#[post("/views")]
+async fn record(
+    req: HttpRequest,
+    data: web::Json<RecordPageViewRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut data = data.into_inner();
+    data.ip = client_ip(&req);
+    data.user_agent = req.headers()
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    ioc.record_page_view().execute(data).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,66 @@
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::trash::purge::PurgeFromTrashRequest;
+use crate::application::trash::restore::RestoreFromTrashRequest;
+use crate::domain::models::trash::TrashKind;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/trash")
+            .service(list)
+            .service(restore)
+            .service(purge)
+    );
+}
+
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.list_trash(id_provider).execute(()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// `kind` is `note` or `project`
+#[post("/{kind}/{id}/restore")]
+async fn restore(
+    req: HttpRequest,
+    path: web::Path<(TrashKind, String)>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let (kind, id) = path.into_inner();
+    ioc.restore_from_trash(id_provider).execute(RestoreFromTrashRequest {
+        kind,
+        id,
+        ip: Some(client_ip(&req))
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[delete("/{kind}/{id}")]
+async fn purge(
+    req: HttpRequest,
+    path: web::Path<(TrashKind, String)>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let (kind, id) = path.into_inner();
+    ioc.purge_from_trash(id_provider).execute(PurgeFromTrashRequest {
+        kind,
+        id,
+        ip: Some(client_ip(&req))
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,27 @@
+use actix_web::http::header::{EntityTag, ETag, Header, IfMatch};
+use actix_web::{HttpRequest, HttpResponse};
+use serde_json::json;
+
+/// Versions travel as strong ETags, `"3"`
+pub fn version_etag(version: i64) -> ETag {
+    ETag(EntityTag::new_strong(version.to_string()))
+}
+
+/// Expected version from `If-Match`, `*` and foreign tags are ignored
+pub fn if_match(req: &HttpRequest) -> Option<i64> {
+    match IfMatch::parse(req).ok()? {
+        IfMatch::Any => None,
+        IfMatch::Items(tags) => tags.first().and_then(|tag| tag.tag().parse().ok())
+    }
+}
+
+/// The edit was made against an older version, the client is told
+/// which one is current so it can fetch it and merge
+pub fn conflict(current_version: i64) -> HttpResponse {
+    HttpResponse::Conflict()
+        .insert_header(version_etag(current_version))
+        .json(json!({
+            "error": "Conflict",
+            "current_version": current_version
+        }))
+}
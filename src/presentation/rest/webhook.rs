@@ -0,0 +1,89 @@
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+
+use crate::adapters::auth::token::TokenProcessor;
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::webhook::create::CreateWebhookRequest;
+use crate::application::webhook::delete::DeleteWebhookRequest;
+use crate::application::webhook::deliveries::ListWebhookDeliveriesRequest;
+use crate::application::webhook::redeliver::RedeliverWebhookRequest;
+use crate::presentation::id_provider::make_token_provider;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/webhooks")
+            .service(list)
+            .service(create)
+            .service(remove)
+            .service(deliveries)
+            .service(redeliver)
+    );
+}
+
+#[get("")]
+async fn list(
+    req: HttpRequest,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.list_webhooks(id_provider).execute(()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// The response holds the signing secret, it is not shown again
+#[post("")]
+async fn create(
+    req: HttpRequest,
+    data: web::Json<CreateWebhookRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.create_webhook(id_provider).execute(data.into_inner()).await?;
+    Ok(HttpResponse::Created().json(result))
+}
+
+#[delete("/{id}")]
+async fn remove(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    ioc.delete_webhook(id_provider).execute(DeleteWebhookRequest {
+        id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Delivery log, newest first
+#[get("/{id}/deliveries")]
+async fn deliveries(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.list_webhook_deliveries(id_provider).execute(ListWebhookDeliveriesRequest {
+        webhook_id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[post("/deliveries/{id}/redeliver")]
+async fn redeliver(
+    req: HttpRequest,
+    path: web::Path<String>,
+    ioc: web::Data<dyn InteractorFactory>,
+    token_processor: web::Data<TokenProcessor>,
+) -> Result<HttpResponse, ApplicationError> {
+    let id_provider = make_token_provider(&req, &token_processor)?;
+    let result = ioc.redeliver_webhook(id_provider).execute(RedeliverWebhookRequest {
+        delivery_id: path.into_inner()
+    }).await?;
+    Ok(HttpResponse::Accepted().json(result))
+}
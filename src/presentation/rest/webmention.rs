@@ -0,0 +1,42 @@
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+
+use crate::application::common::exceptions::ApplicationError;
+use crate::application::common::interactor::Interactor;
+use crate::application::webmention::list::ListWebmentionsRequest;
+use crate::application::webmention::receive::ReceiveWebmentionRequest;
+use crate::presentation::client_ip::client_ip;
+use crate::presentation::interactor_factory::InteractorFactory;
+
+/// Endpoint advertised in note pages, senders post form-encoded `source` and `target`
+pub fn endpoint_router(cfg: &mut web::ServiceConfig) {
+    cfg.service(receive);
+}
+
+pub fn router(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/webmentions")
+            .service(list)
+    );
+}
+
+#[post("/webmention")]
+async fn receive(
+    req: HttpRequest,
+    data: web::Form<ReceiveWebmentionRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let mut data = data.into_inner();
+    data.ip = client_ip(&req);
+
+    ioc.receive_webmention().execute(data).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+#[get("")]
+async fn list(
+    data: web::Query<ListWebmentionsRequest>,
+    ioc: web::Data<dyn InteractorFactory>,
+) -> Result<HttpResponse, ApplicationError> {
+    let result = ioc.list_webmentions().execute(data.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(result))
+}